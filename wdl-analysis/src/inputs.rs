@@ -0,0 +1,1012 @@
+//! Validation and templating of inputs to workflows and tasks.
+//!
+//! Inputs are specified as a JSON object where each key is the name of the
+//! workflow or task followed by a period and the name of the input (e.g.
+//! `workflow.input`).
+//!
+//! Inputs to the calls of a workflow (i.e. "nested inputs") are specified by
+//! including the name of the call in the key (e.g. `workflow.call.input`).
+
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use indexmap::IndexMap;
+use indexmap::IndexSet;
+use serde_json::Value as JsonValue;
+use wdl_ast::v1::DocumentItem;
+use wdl_ast::v1::MetadataValue;
+use wdl_ast::v1::ParameterMetadataSection;
+use wdl_ast::version::V1;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::SupportedVersion;
+
+use crate::scope::Call;
+use crate::scope::DocumentScope;
+use crate::types::CompoundTypeDef;
+use crate::types::Optional;
+use crate::types::PrimitiveTypeKind;
+use crate::types::Type;
+use crate::types::Types;
+use crate::AnalysisResult;
+
+/// Creates an "invalid input key" diagnostic.
+fn invalid_input_key(key: &str, kind: Kind, name: &str) -> Diagnostic {
+    Diagnostic::error(format!(
+        "invalid input key `{key}`: expected a key of the form `{name}.<input name>` for {kind} \
+         `{name}`"
+    ))
+}
+
+/// Creates an "unknown input" diagnostic.
+fn unknown_input(key: &str, kind: Kind, name: &str, input: &str) -> Diagnostic {
+    Diagnostic::error(format!(
+        "unknown input `{key}`: {kind} `{name}` does not have an input named `{input}`"
+    ))
+}
+
+/// Creates an "unknown call" diagnostic.
+fn unknown_call(key: &str, name: &str, call: &str) -> Diagnostic {
+    Diagnostic::error(format!(
+        "unknown input `{key}`: workflow `{name}` does not have a call named `{call}`"
+    ))
+}
+
+/// Creates a "nested inputs not allowed" diagnostic.
+fn nested_inputs_not_allowed(key: &str, name: &str) -> Diagnostic {
+    Diagnostic::error(format!(
+        "invalid input `{key}`: workflow `{name}` does not allow nested inputs"
+    ))
+}
+
+/// Creates an "input specified by call" diagnostic.
+fn input_specified_by_call(key: &str, call: &str) -> Diagnostic {
+    Diagnostic::error(format!(
+        "invalid input `{key}`: the input is already specified by call `{call}`"
+    ))
+}
+
+/// Creates a "missing input" diagnostic.
+fn missing_input(types: &Types, key: &str, ty: Option<Type>) -> Diagnostic {
+    match ty {
+        Some(ty) => Diagnostic::error(format!(
+            "missing required input `{key}` of type `{ty}`",
+            ty = ty.display(types)
+        )),
+        None => Diagnostic::error(format!("missing required input `{key}`")),
+    }
+}
+
+/// Creates a "type mismatch" diagnostic.
+fn type_mismatch(types: &Types, path: &str, expected: Type, value: &JsonValue) -> Diagnostic {
+    Diagnostic::error(format!(
+        "type mismatch for `{path}`: expected type `{expected}`, but found {actual}",
+        expected = expected.display(types),
+        actual = describe(value)
+    ))
+}
+
+/// Creates an "empty array" diagnostic.
+fn empty_array(types: &Types, path: &str, expected: Type) -> Diagnostic {
+    Diagnostic::error(format!(
+        "type mismatch for `{path}`: expected a non-empty array of type `{expected}`, but found \
+         an empty array",
+        expected = expected.display(types),
+    ))
+}
+
+/// Creates an "invalid map key" diagnostic.
+fn invalid_map_key(types: &Types, path: &str, expected: Type, key: &str) -> Diagnostic {
+    Diagnostic::error(format!(
+        "type mismatch for `{path}`: expected a key of type `{expected}`, but found key `{key}`",
+        expected = expected.display(types),
+    ))
+}
+
+/// Creates a "missing member" diagnostic.
+fn missing_member(path: &str, kind: &str, name: &str, member: &str) -> Diagnostic {
+    Diagnostic::error(format!(
+        "missing member `{member}` of {kind} `{name}` for `{path}`"
+    ))
+}
+
+/// Creates an "unknown member" diagnostic.
+fn unknown_member(path: &str, kind: &str, name: &str, member: &str) -> Diagnostic {
+    Diagnostic::error(format!(
+        "unknown member `{member}` for `{path}`: {kind} `{name}` does not have a member named \
+         `{member}`"
+    ))
+}
+
+/// Describes a JSON value for diagnostics.
+fn describe(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "a JSON null",
+        JsonValue::Bool(_) => "a JSON boolean",
+        JsonValue::Number(n) if n.is_f64() => "a JSON floating point number",
+        JsonValue::Number(_) => "a JSON integer",
+        JsonValue::String(_) => "a JSON string",
+        JsonValue::Array(_) => "a JSON array",
+        JsonValue::Object(_) => "a JSON object",
+    }
+}
+
+/// Represents the kind of a callable (i.e. a workflow or task).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// The callable is a workflow.
+    Workflow,
+    /// The callable is a task.
+    Task,
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Workflow => write!(f, "workflow"),
+            Self::Task => write!(f, "task"),
+        }
+    }
+}
+
+/// Determines if the workflow in the given document scope allows nested
+/// inputs.
+///
+/// Nested inputs are allowed in WDL 1.0; later versions require them to be
+/// explicitly allowed by the workflow.
+fn allows_nested_inputs(scope: &DocumentScope) -> bool {
+    scope.version() == Some(SupportedVersion::V1(V1::Zero)) || scope.allows_nested_inputs()
+}
+
+/// Validates the inputs to a workflow or task in a document.
+///
+/// If a task name is given, the inputs are validated against the inputs of
+/// the task with that name; otherwise, the inputs are validated against the
+/// inputs of the document's workflow.
+///
+/// Each input value is checked against the declared type of the input. Any
+/// unknown input keys and any missing required inputs are also reported.
+///
+/// The document scope is expected to be the result of analyzing a document
+/// without errors.
+///
+/// Returns the diagnostics describing the problems found with the inputs;
+/// diagnostics for inputs do not have source locations, but instead contain the
+/// path of the input value (e.g. `workflow.input[0].member`) in their message.
+pub fn validate_inputs(
+    scope: &DocumentScope,
+    task: Option<&str>,
+    inputs: &serde_json::Map<String, JsonValue>,
+) -> Result<(), Vec<Diagnostic>> {
+    let (kind, name) = match task {
+        Some(name) => {
+            if scope.task_inputs(name).is_none() {
+                return Err(vec![Diagnostic::error(format!(
+                    "document does not contain a task named `{name}`"
+                ))]);
+            }
+
+            (Kind::Task, name)
+        }
+        None => match scope.workflow() {
+            Some((name, _)) => (Kind::Workflow, name),
+            None => {
+                return Err(vec![Diagnostic::error(
+                    "document does not contain a workflow",
+                )]);
+            }
+        },
+    };
+
+    // Strip the workflow or task name from the keys
+    let mut diagnostics = Vec::new();
+    let mut provided = Vec::new();
+    for (key, value) in inputs {
+        match key.split_once('.') {
+            Some((prefix, rest)) if prefix == name => provided.push((rest, key.as_str(), value)),
+            _ => diagnostics.push(invalid_input_key(key, kind, name)),
+        }
+    }
+
+    validate(&mut diagnostics, scope, kind, name, name, provided, None);
+
+    if diagnostics.is_empty() {
+        Ok(())
+    } else {
+        Err(diagnostics)
+    }
+}
+
+/// Validates the inputs to a workflow or task.
+///
+/// The provided inputs are tuples of the key relative to the workflow or task,
+/// the full key, and the value.
+///
+/// If the workflow or task is the callee of a call, `call` contains the call
+/// name and the call.
+///
+/// Whether or not inputs to the calls of a workflow are allowed is determined
+/// by the workflow itself rather than by the workflow calling it.
+fn validate(
+    diagnostics: &mut Vec<Diagnostic>,
+    scope: &DocumentScope,
+    kind: Kind,
+    name: &str,
+    prefix: &str,
+    provided: Vec<(&str, &str, &JsonValue)>,
+    call: Option<(&str, &Call)>,
+) {
+    let nested = allows_nested_inputs(scope);
+    let inputs = match kind {
+        Kind::Workflow => scope.workflow_inputs(),
+        Kind::Task => scope.task_inputs(name),
+    }
+    .expect("workflow or task should exist");
+
+    // Check the provided inputs, collecting those that are for calls
+    let mut seen = IndexSet::new();
+    let mut calls: IndexMap<&str, Vec<(&str, &str, &JsonValue)>> = IndexMap::new();
+    for (rest, key, value) in provided {
+        if let Some((call, rest)) = rest.split_once('.') {
+            if kind == Kind::Task {
+                diagnostics.push(unknown_input(key, kind, name, call));
+                continue;
+            }
+
+            if !scope
+                .workflow_calls()
+                .map(|c| c.contains_key(call))
+                .unwrap_or(false)
+            {
+                diagnostics.push(unknown_call(key, name, call));
+                continue;
+            }
+
+            if !nested {
+                diagnostics.push(nested_inputs_not_allowed(key, name));
+                continue;
+            }
+
+            calls.entry(call).or_default().push((rest, key, value));
+            continue;
+        }
+
+        let input = match inputs.get(rest) {
+            Some(input) => input,
+            None => {
+                diagnostics.push(unknown_input(key, kind, name, rest));
+                continue;
+            }
+        };
+
+        if let Some((call_name, call)) = call {
+            if call.is_input_specified(rest) {
+                diagnostics.push(input_specified_by_call(key, call_name));
+                continue;
+            }
+        }
+
+        seen.insert(rest);
+        if let Some(ty) = input.ty() {
+            check_value(diagnostics, scope.types(), key, ty, value);
+        }
+    }
+
+    // Check for missing required inputs
+    for (input, i) in inputs {
+        if !i.required()
+            || seen.contains(input.as_str())
+            || call
+                .map(|(_, c)| c.is_input_specified(input))
+                .unwrap_or(false)
+        {
+            continue;
+        }
+
+        diagnostics.push(missing_input(
+            scope.types(),
+            &format!("{prefix}.{input}"),
+            i.ty(),
+        ));
+    }
+
+    // Recurse into the calls of a workflow when nested inputs are allowed
+    if kind != Kind::Workflow || !nested {
+        return;
+    }
+
+    for (call_name, c) in scope.workflow_calls().into_iter().flatten() {
+        let provided = calls.swap_remove(call_name.as_str()).unwrap_or_default();
+        let (callee_scope, callee_kind) = match c.namespace() {
+            Some(ns) => match scope.namespace(ns) {
+                Some(ns) => {
+                    let ns_scope = ns.scope();
+                    if ns_scope.task_inputs(c.target()).is_some() {
+                        (ns_scope, Kind::Task)
+                    } else if ns_scope.workflow().map(|(n, _)| n) == Some(c.target()) {
+                        (ns_scope, Kind::Workflow)
+                    } else {
+                        continue;
+                    }
+                }
+                None => continue,
+            },
+            None if scope.task_inputs(c.target()).is_some() => (scope, Kind::Task),
+            None => continue,
+        };
+
+        validate(
+            diagnostics,
+            callee_scope,
+            callee_kind,
+            c.target(),
+            &format!("{prefix}.{call_name}"),
+            provided,
+            Some((call_name, c)),
+        );
+    }
+}
+
+/// Checks a JSON value against the given type.
+///
+/// The path is the path to the value used in diagnostics.
+fn check_value(
+    diagnostics: &mut Vec<Diagnostic>,
+    types: &Types,
+    path: &str,
+    ty: Type,
+    value: &JsonValue,
+) {
+    if value.is_null() {
+        if !ty.is_optional() && !matches!(ty, Type::Union | Type::None) {
+            diagnostics.push(type_mismatch(types, path, ty, value));
+        }
+
+        return;
+    }
+
+    match ty {
+        Type::Primitive(primitive) => {
+            let matches = match primitive.kind() {
+                PrimitiveTypeKind::Boolean => value.is_boolean(),
+                PrimitiveTypeKind::Integer => value.is_i64(),
+                PrimitiveTypeKind::Float => value.is_number(),
+                PrimitiveTypeKind::String
+                | PrimitiveTypeKind::File
+                | PrimitiveTypeKind::Directory => value.is_string(),
+            };
+
+            if !matches {
+                diagnostics.push(type_mismatch(types, path, ty, value));
+            }
+        }
+        Type::Compound(compound) => match types.type_definition(compound.definition()) {
+            CompoundTypeDef::Array(array) => {
+                let elements = match value.as_array() {
+                    Some(elements) => elements,
+                    None => {
+                        diagnostics.push(type_mismatch(types, path, ty, value));
+                        return;
+                    }
+                };
+
+                if array.is_non_empty() && elements.is_empty() {
+                    diagnostics.push(empty_array(types, path, ty));
+                    return;
+                }
+
+                for (i, element) in elements.iter().enumerate() {
+                    check_value(
+                        diagnostics,
+                        types,
+                        &format!("{path}[{i}]"),
+                        array.element_type(),
+                        element,
+                    );
+                }
+            }
+            CompoundTypeDef::Pair(pair) => {
+                let object = match value.as_object() {
+                    Some(object) => object,
+                    None => {
+                        diagnostics.push(type_mismatch(types, path, ty, value));
+                        return;
+                    }
+                };
+
+                check_members(
+                    diagnostics,
+                    types,
+                    path,
+                    "type",
+                    &ty.display(types).to_string(),
+                    [("left", pair.first_type()), ("right", pair.second_type())],
+                    object,
+                );
+            }
+            CompoundTypeDef::Map(map) => {
+                let object = match value.as_object() {
+                    Some(object) => object,
+                    None => {
+                        diagnostics.push(type_mismatch(types, path, ty, value));
+                        return;
+                    }
+                };
+
+                for (key, value) in object {
+                    let valid = match map.key_type() {
+                        Type::Primitive(key_ty) => match key_ty.kind() {
+                            PrimitiveTypeKind::Boolean => key.parse::<bool>().is_ok(),
+                            PrimitiveTypeKind::Integer => key.parse::<i64>().is_ok(),
+                            PrimitiveTypeKind::Float => key.parse::<f64>().is_ok(),
+                            PrimitiveTypeKind::String
+                            | PrimitiveTypeKind::File
+                            | PrimitiveTypeKind::Directory => true,
+                        },
+                        _ => true,
+                    };
+
+                    if !valid {
+                        diagnostics.push(invalid_map_key(types, path, map.key_type(), key));
+                        continue;
+                    }
+
+                    check_value(
+                        diagnostics,
+                        types,
+                        &format!("{path}[{key:?}]"),
+                        map.value_type(),
+                        value,
+                    );
+                }
+            }
+            CompoundTypeDef::Struct(s) => {
+                let object = match value.as_object() {
+                    Some(object) => object,
+                    None => {
+                        diagnostics.push(type_mismatch(types, path, ty, value));
+                        return;
+                    }
+                };
+
+                check_members(
+                    diagnostics,
+                    types,
+                    path,
+                    "struct",
+                    s.name(),
+                    s.members().iter().map(|(n, ty)| (n.as_str(), *ty)),
+                    object,
+                );
+            }
+            CompoundTypeDef::Call(_) => {}
+        },
+        Type::Object | Type::OptionalObject => {
+            if !value.is_object() {
+                diagnostics.push(type_mismatch(types, path, ty, value));
+            }
+        }
+        Type::Union | Type::None | Type::Task | Type::Hints | Type::Input | Type::Output => {}
+    }
+}
+
+/// Checks the members of a JSON object against the given member types.
+///
+/// Members of an optional type may be omitted.
+fn check_members<'a>(
+    diagnostics: &mut Vec<Diagnostic>,
+    types: &Types,
+    path: &str,
+    kind: &str,
+    name: &str,
+    members: impl IntoIterator<Item = (&'a str, Type)>,
+    object: &serde_json::Map<String, JsonValue>,
+) {
+    let members: Vec<_> = members.into_iter().collect();
+    for (member, ty) in &members {
+        match object.get(*member) {
+            Some(value) => check_value(diagnostics, types, &format!("{path}.{member}"), *ty, value),
+            None if ty.is_optional() => {}
+            None => diagnostics.push(missing_member(path, kind, name, member)),
+        }
+    }
+
+    for key in object.keys() {
+        if !members.iter().any(|(m, _)| m == key) {
+            diagnostics.push(unknown_member(path, kind, name, key));
+        }
+    }
+}
+
+/// Generates a template of the inputs to a workflow or task in a document.
+///
+/// If a task name is given, the template is for the inputs of the task with
+/// that name; otherwise, the template is for the inputs of the document's
+/// workflow.
+///
+/// When the workflow allows nested inputs, the template also includes the
+/// inputs of the workflow's calls that are not specified by the call
+/// statements.
+///
+/// Each key of the returned map is an input key (e.g. `workflow.input`) and
+/// each value is an object describing the input with the following members:
+///
+/// * `type` - the declared type of the input.
+/// * `optional` - whether or not the input may be omitted.
+/// * `default` - the default expression of the input or `null` if the input has
+///   no default.
+/// * `description` - the description of the input from `parameter_meta`; this
+///   member is only present if the input has a description.
+///
+/// The analysis results are used to locate the documents of imported callees.
+pub fn inputs_template(
+    results: &[AnalysisResult],
+    document: &AnalysisResult,
+    task: Option<&str>,
+) -> Result<IndexMap<String, JsonValue>> {
+    let scope = document.scope();
+    let (kind, name) = match task {
+        Some(name) => {
+            if scope.task_inputs(name).is_none() {
+                bail!("document does not contain a task named `{name}`");
+            }
+
+            (Kind::Task, name)
+        }
+        None => match scope.workflow() {
+            Some((name, _)) => (Kind::Workflow, name),
+            None => bail!("document does not contain a workflow"),
+        },
+    };
+
+    let mut template = IndexMap::new();
+    add_template_entries(&mut template, results, document, kind, name, name, None)?;
+    Ok(template)
+}
+
+/// Adds template entries for the inputs of a workflow or task.
+///
+/// If the workflow or task is the callee of a call, inputs specified by the
+/// call statement are not included.
+///
+/// The inputs of the calls of a workflow are included when the workflow itself
+/// allows nested inputs.
+fn add_template_entries(
+    template: &mut IndexMap<String, JsonValue>,
+    results: &[AnalysisResult],
+    document: &AnalysisResult,
+    kind: Kind,
+    name: &str,
+    prefix: &str,
+    call: Option<&Call>,
+) -> Result<()> {
+    let ast = match document
+        .parse_result()
+        .document()
+        .and_then(|d| d.ast().into_v1())
+    {
+        Some(ast) => ast,
+        None => bail!(
+            "document `{uri}` is not a supported WDL document",
+            uri = document.uri()
+        ),
+    };
+
+    let (input, parameter_meta) = match kind {
+        Kind::Workflow => ast
+            .items()
+            .find_map(|i| match i {
+                DocumentItem::Workflow(w) if w.name().as_str() == name => {
+                    Some((w.input(), w.parameter_metadata()))
+                }
+                _ => None,
+            })
+            .with_context(|| format!("document does not contain a workflow named `{name}`"))?,
+        Kind::Task => ast
+            .items()
+            .find_map(|i| match i {
+                DocumentItem::Task(t) if t.name().as_str() == name => {
+                    Some((t.input(), t.parameter_metadata()))
+                }
+                _ => None,
+            })
+            .with_context(|| format!("document does not contain a task named `{name}`"))?,
+    };
+
+    let scope = document.scope();
+    let inputs = match kind {
+        Kind::Workflow => scope.workflow_inputs(),
+        Kind::Task => scope.task_inputs(name),
+    }
+    .expect("workflow or task should exist");
+
+    for decl in input.iter().flat_map(|i| i.declarations()) {
+        let input_name = decl.name();
+        if call
+            .map(|c| c.is_input_specified(input_name.as_str()))
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        let mut entry = serde_json::Map::new();
+        entry.insert("type".to_string(), decl.ty().to_string().into());
+        entry.insert(
+            "optional".to_string(),
+            inputs
+                .get(input_name.as_str())
+                .map(|i| !i.required())
+                .unwrap_or(true)
+                .into(),
+        );
+        entry.insert(
+            "default".to_string(),
+            decl.expr()
+                .map(|e| e.syntax().text().to_string().into())
+                .unwrap_or(JsonValue::Null),
+        );
+
+        if let Some(description) = parameter_meta
+            .as_ref()
+            .and_then(|s| input_description(s, input_name.as_str()))
+        {
+            entry.insert("description".to_string(), description.into());
+        }
+
+        template.insert(
+            format!("{prefix}.{input_name}", input_name = input_name.as_str()),
+            entry.into(),
+        );
+    }
+
+    if kind != Kind::Workflow || !allows_nested_inputs(scope) {
+        return Ok(());
+    }
+
+    for (call_name, c) in scope.workflow_calls().into_iter().flatten() {
+        let (callee, callee_kind) = match c.namespace() {
+            Some(ns) => {
+                let ns = match scope.namespace(ns) {
+                    Some(ns) => ns,
+                    None => continue,
+                };
+
+                let callee = match results.iter().find(|r| r.uri() == ns.source()) {
+                    Some(callee) => callee,
+                    None => continue,
+                };
+
+                let ns_scope = ns.scope();
+                if ns_scope.task_inputs(c.target()).is_some() {
+                    (callee, Kind::Task)
+                } else if ns_scope.workflow().map(|(n, _)| n) == Some(c.target()) {
+                    (callee, Kind::Workflow)
+                } else {
+                    continue;
+                }
+            }
+            None if scope.task_inputs(c.target()).is_some() => (document, Kind::Task),
+            None => continue,
+        };
+
+        add_template_entries(
+            template,
+            results,
+            callee,
+            callee_kind,
+            c.target(),
+            &format!("{prefix}.{call_name}"),
+            Some(c),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Gets the description of an input from a `parameter_meta` section.
+///
+/// The description is either a string value or the `description` or `help`
+/// member of an object value.
+pub fn input_description(section: &ParameterMetadataSection, name: &str) -> Option<String> {
+    let text = |value: MetadataValue| match value {
+        MetadataValue::String(s) => s.text().map(|t| t.as_str().to_string()),
+        _ => None,
+    };
+
+    let item = section.items().find(|i| i.name().as_str() == name)?;
+    match item.value() {
+        MetadataValue::Object(o) => o
+            .items()
+            .find(|i| matches!(i.name().as_str(), "description" | "help"))
+            .and_then(|i| text(i.value())),
+        value => text(value),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::Analyzer;
+
+    /// Analyzes the given files, returning the results of the first file.
+    ///
+    /// Each file is a tuple of the file name and source.
+    async fn analyze_files(files: &[(&str, &str)]) -> (Vec<AnalysisResult>, usize) {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let mut paths = Vec::new();
+        for (name, source) in files {
+            let path = dir.path().join(name);
+            fs::write(&path, source).expect("failed to create test file");
+            paths.push(path);
+        }
+
+        let analyzer = Analyzer::new(|_: (), _, _, _| async {});
+        analyzer
+            .add_documents(paths)
+            .await
+            .expect("should add documents");
+        let results = analyzer.analyze(()).await.expect("should analyze");
+        assert_eq!(results.len(), files.len());
+        assert!(
+            results.iter().all(|r| r.diagnostics().is_empty()),
+            "documents should have no diagnostics"
+        );
+
+        let index = results
+            .iter()
+            .position(|r| {
+                r.uri()
+                    .path()
+                    .ends_with(&format!("/{name}", name = files[0].0))
+            })
+            .expect("should have a result for the first file");
+        (results, index)
+    }
+
+    /// Analyzes the given source.
+    async fn analyze(source: &str) -> Vec<AnalysisResult> {
+        analyze_files(&[("source.wdl", source)]).await.0
+    }
+
+    /// Analyzes the given source and validates the given inputs against it.
+    async fn validate(source: &str, task: Option<&str>, inputs: JsonValue) -> Vec<String> {
+        let results = analyze(source).await;
+        match validate_inputs(
+            results[0].scope(),
+            task,
+            inputs.as_object().expect("inputs should be an object"),
+        ) {
+            Ok(()) => Vec::new(),
+            Err(diagnostics) => diagnostics
+                .iter()
+                .map(|d| d.message().to_string())
+                .collect(),
+        }
+    }
+
+    const SOURCE: &str = r#"version 1.1
+
+struct Person {
+    String name
+    Int? age
+}
+
+task greet {
+    input {
+        String name
+        String greeting = "hello"
+    }
+
+    parameter_meta {
+        name: "The name to greet"
+        greeting: {
+            help: "The greeting to use"
+        }
+    }
+
+    command <<<>>>
+}
+
+workflow test {
+    meta {
+        allowNestedInputs: true
+    }
+
+    input {
+        Array[Person]+ people
+        Map[Int, Float] scores = {}
+        Pair[Int, String]? pair
+        File? file
+    }
+
+    call greet
+}
+"#;
+
+    #[tokio::test]
+    async fn it_validates_workflow_inputs() {
+        let messages = validate(
+            SOURCE,
+            None,
+            json!({
+                "test.people": [{ "name": "Alice", "age": 30 }, { "name": "Bob" }],
+                "test.scores": { "1": 1.5, "2": 2 },
+                "test.pair": { "left": 1, "right": "one" },
+                "test.file": null,
+                "test.greet.name": "Alice",
+            }),
+        )
+        .await;
+        assert!(messages.is_empty(), "unexpected diagnostics: {messages:?}");
+    }
+
+    #[tokio::test]
+    async fn it_reports_invalid_workflow_inputs() {
+        let messages = validate(
+            SOURCE,
+            None,
+            json!({
+                "test.people": [{ "name": "Alice", "age": "30" }, { "nom": "Bob" }],
+                "test.scores": { "one": 1.5, "2": "2" },
+                "test.pair": [1, "one"],
+                "test.unknown": 1,
+                "test.greet.name": null,
+                "test.missing.name": "Alice",
+                "other.file": "foo.txt",
+            }),
+        )
+        .await;
+        assert_eq!(
+            messages,
+            [
+                "invalid input key `other.file`: expected a key of the form `test.<input name>` \
+                 for workflow `test`",
+                "unknown input `test.missing.name`: workflow `test` does not have a call named \
+                 `missing`",
+                "type mismatch for `test.pair`: expected type `Pair[Int, String]?`, but found a \
+                 JSON array",
+                "type mismatch for `test.people[0].age`: expected type `Int?`, but found a JSON \
+                 string",
+                "missing member `name` of struct `Person` for `test.people[1]`",
+                "unknown member `nom` for `test.people[1]`: struct `Person` does not have a \
+                 member named `nom`",
+                "type mismatch for `test.scores[\"2\"]`: expected type `Float`, but found a JSON \
+                 string",
+                "type mismatch for `test.scores`: expected a key of type `Int`, but found key \
+                 `one`",
+                "unknown input `test.unknown`: workflow `test` does not have an input named \
+                 `unknown`",
+                "type mismatch for `test.greet.name`: expected type `String`, but found a JSON \
+                 null",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_reports_missing_inputs() {
+        let messages = validate(SOURCE, None, json!({ "test.people": [] })).await;
+        assert_eq!(
+            messages,
+            [
+                "type mismatch for `test.people`: expected a non-empty array of type \
+                 `Array[Person]+`, but found an empty array",
+                "missing required input `test.greet.name` of type `String`",
+            ]
+        );
+
+        let messages = validate(SOURCE, Some("greet"), json!({})).await;
+        assert_eq!(
+            messages,
+            ["missing required input `greet.name` of type `String`"]
+        );
+    }
+
+    /// The source of a document whose workflow calls a workflow that does not
+    /// allow nested inputs.
+    const PARENT: &str = r#"version 1.1
+
+import "child.wdl"
+
+workflow test {
+    meta {
+        allowNestedInputs: true
+    }
+
+    call child.child
+}
+"#;
+
+    /// The source of the workflow called by [PARENT].
+    const CHILD: &str = r#"version 1.1
+
+task greet {
+    input {
+        String name
+        String greeting = "hello"
+    }
+
+    command <<<>>>
+}
+
+workflow child {
+    input {
+        Int count = 1
+    }
+
+    call greet { input: name = "world" }
+}
+"#;
+
+    #[tokio::test]
+    async fn it_uses_the_nested_inputs_setting_of_the_callee() {
+        let (results, index) = analyze_files(&[("parent.wdl", PARENT), ("child.wdl", CHILD)]).await;
+        let messages: Vec<_> = validate_inputs(
+            results[index].scope(),
+            None,
+            json!({ "test.child.count": 2, "test.child.greet.greeting": "hi" })
+                .as_object()
+                .expect("inputs should be an object"),
+        )
+        .expect_err("inputs should not be valid")
+        .iter()
+        .map(|d| d.message().to_string())
+        .collect();
+        assert_eq!(
+            messages,
+            [
+                "invalid input `test.child.greet.greeting`: workflow `child` does not allow \
+                 nested inputs"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_generates_a_template_with_the_nested_inputs_setting_of_the_callee() {
+        let (results, index) = analyze_files(&[("parent.wdl", PARENT), ("child.wdl", CHILD)]).await;
+        let template =
+            inputs_template(&results, &results[index], None).expect("should generate template");
+        assert_eq!(template.keys().collect::<Vec<_>>(), ["test.child.count"]);
+    }
+
+    #[tokio::test]
+    async fn it_generates_an_inputs_template() {
+        let results = analyze(SOURCE).await;
+        let template =
+            inputs_template(&results, &results[0], None).expect("should generate template");
+        assert_eq!(
+            serde_json::to_value(template).expect("should serialize"),
+            json!({
+                "test.people": { "type": "Array[Person]+", "optional": false, "default": null },
+                "test.scores": { "type": "Map[Int, Float]", "optional": true, "default": "{}" },
+                "test.pair": { "type": "Pair[Int, String]?", "optional": true, "default": null },
+                "test.file": { "type": "File?", "optional": true, "default": null },
+                "test.greet.name": {
+                    "type": "String",
+                    "optional": false,
+                    "default": null,
+                    "description": "The name to greet"
+                },
+                "test.greet.greeting": {
+                    "type": "String",
+                    "optional": true,
+                    "default": "\"hello\"",
+                    "description": "The greeting to use"
+                },
+            })
+        );
+
+        let template = inputs_template(&results, &results[0], Some("greet"))
+            .expect("should generate template");
+        assert_eq!(
+            template.keys().collect::<Vec<_>>(),
+            ["greet.name", "greet.greeting"]
+        );
+    }
+}
@@ -13,6 +13,7 @@ mod analyzer;
 mod graph;
 mod queue;
 mod rayon;
+pub mod inputs;
 pub mod scope;
 pub mod stdlib;
 pub mod types;
@@ -4,6 +4,7 @@ use std::fmt;
 use std::sync::Arc;
 
 use indexmap::IndexMap;
+use indexmap::IndexSet;
 use petgraph::graph::NodeIndex;
 use url::Url;
 use wdl_ast::support::token;
@@ -103,7 +104,7 @@ impl fmt::Display for Context {
 
 /// Represents the context of a name in a scope.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum NameContext {
+pub enum NameContext {
     /// The name was introduced by an task or workflow input.
     Input(Span),
     /// The name was introduced by an task or workflow output.
@@ -238,7 +239,7 @@ impl Name {
     }
 
     /// Gets the context of the name.
-    pub(crate) fn context(&self) -> NameContext {
+    pub fn context(&self) -> NameContext {
         self.context
     }
 
@@ -377,6 +378,74 @@ impl<'a> ScopeRefMut<'a> {
     }
 }
 
+/// Represents an input of a task or workflow.
+#[derive(Debug, Clone, Copy)]
+pub struct Input {
+    /// The type of the input.
+    ///
+    /// This is initially `None` until a type check occurs.
+    ty: Option<Type>,
+    /// Whether or not the input is required.
+    ///
+    /// An input is required if it is not optional and has no default value.
+    required: bool,
+}
+
+impl Input {
+    /// Gets the type of the input.
+    ///
+    /// Returns `None` if the type could not be determined; for example, if the
+    /// input's declared type is to an unknown struct.
+    pub fn ty(&self) -> Option<Type> {
+        self.ty
+    }
+
+    /// Determines if the input is required.
+    ///
+    /// An input is required if it is not optional and has no default value.
+    pub fn required(&self) -> bool {
+        self.required
+    }
+}
+
+/// Represents a call in a workflow.
+#[derive(Debug, Clone)]
+pub struct Call {
+    /// The namespace of the callee.
+    ///
+    /// This is `None` for a call to a task in the same document.
+    namespace: Option<String>,
+    /// The name of the callee.
+    target: String,
+    /// The names of the inputs specified in the call statement.
+    specified: IndexSet<String>,
+}
+
+impl Call {
+    /// Gets the namespace of the callee.
+    ///
+    /// Returns `None` for a call to a task in the same document.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Gets the name of the callee.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Gets the names of the inputs specified in the call statement.
+    pub fn specified_inputs(&self) -> impl Iterator<Item = &str> {
+        self.specified.iter().map(String::as_str)
+    }
+
+    /// Determines if an input of the callee is specified in the call
+    /// statement.
+    pub fn is_input_specified(&self, name: &str) -> bool {
+        self.specified.contains(name)
+    }
+}
+
 /// Represents a task in a document.
 #[derive(Debug)]
 struct Task {
@@ -384,6 +453,8 @@ struct Task {
     name_span: Span,
     /// The root scope index for the task.
     scope: ScopeIndex,
+    /// The inputs of the task.
+    inputs: IndexMap<String, Input>,
     /// The scope index for the outputs.
     outputs: Option<ScopeIndex>,
     /// The scope index for the command.
@@ -399,6 +470,17 @@ struct Workflow {
     name: String,
     /// The scope index of the workflow.
     scope: ScopeIndex,
+    /// The inputs of the workflow.
+    inputs: IndexMap<String, Input>,
+    /// The scope index for the outputs.
+    outputs: Option<ScopeIndex>,
+    /// The calls in the workflow, keyed by call name.
+    calls: IndexMap<String, Call>,
+    /// Whether or not the workflow allows nested inputs.
+    ///
+    /// When nested inputs are allowed, required inputs of calls may be
+    /// omitted and instead specified at runtime.
+    allows_nested_inputs: bool,
 }
 
 /// Represents the scope of a document.
@@ -511,6 +593,41 @@ impl DocumentScope {
         })
     }
 
+    /// Gets the inputs of a task in the document scope by name.
+    ///
+    /// Returns `None` if the document does not contain a task with the given
+    /// name.
+    pub fn task_inputs(&self, name: &str) -> Option<&IndexMap<String, Input>> {
+        self.tasks.get(name).map(|t| &t.inputs)
+    }
+
+    /// Gets the inputs of the workflow in the document scope.
+    ///
+    /// Returns `None` if the document does not contain a workflow.
+    pub fn workflow_inputs(&self) -> Option<&IndexMap<String, Input>> {
+        self.workflow.as_ref().map(|w| &w.inputs)
+    }
+
+    /// Gets the calls of the workflow in the document scope, keyed by call
+    /// name.
+    ///
+    /// This includes calls nested in scatter and conditional statements.
+    ///
+    /// Returns `None` if the document does not contain a workflow.
+    pub fn workflow_calls(&self) -> Option<&IndexMap<String, Call>> {
+        self.workflow.as_ref().map(|w| &w.calls)
+    }
+
+    /// Determines if the workflow in the document scope allows nested inputs.
+    ///
+    /// Returns `false` if the document does not contain a workflow.
+    pub fn allows_nested_inputs(&self) -> bool {
+        self.workflow
+            .as_ref()
+            .map(|w| w.allows_nested_inputs)
+            .unwrap_or(false)
+    }
+
     /// Gets the structs in the document scope.
     pub fn structs(&self) -> impl Iterator<Item = (&str, &Struct)> {
         self.structs.iter().map(|(n, s)| (n.as_str(), s))
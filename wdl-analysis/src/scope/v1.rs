@@ -1,5 +1,8 @@
 //! Conversion of a V1 AST to a document scope.
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::iter;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -12,13 +15,19 @@ use petgraph::prelude::DiGraphMap;
 use url::Url;
 use wdl_ast::v1::Ast;
 use wdl_ast::v1::BoundDecl;
+use wdl_ast::v1::CallStatement;
 use wdl_ast::v1::CommandPart;
 use wdl_ast::v1::CommandSection;
+use wdl_ast::v1::ConditionalStatement;
 use wdl_ast::v1::Decl;
 use wdl_ast::v1::DocumentItem;
 use wdl_ast::v1::Expr;
+use wdl_ast::v1::HintsSection;
 use wdl_ast::v1::ImportStatement;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::MetadataValue;
 use wdl_ast::v1::NameRef;
+use wdl_ast::v1::ScatterStatement;
 use wdl_ast::v1::StructDefinition;
 use wdl_ast::v1::TaskDefinition;
 use wdl_ast::v1::TaskItem;
@@ -40,8 +49,10 @@ use wdl_ast::Version;
 
 use super::braced_scope_span;
 use super::heredoc_scope_span;
+use super::Call;
 use super::Context;
 use super::DocumentScope;
+use super::Input;
 use super::Name;
 use super::NameContext;
 use super::Namespace;
@@ -54,16 +65,32 @@ use super::Workflow;
 use crate::graph::DocumentGraph;
 use crate::graph::ParseState;
 use crate::scope::ScopeRef;
+use crate::types::v1::cannot_access;
+use crate::types::v1::if_conditional_mismatch;
+use crate::types::v1::not_a_struct_member;
+use crate::types::v1::task_hint_types;
 use crate::types::v1::type_mismatch;
+use crate::types::v1::workflow_hint_types;
 use crate::types::v1::AstTypeConverter;
 use crate::types::v1::ExprTypeEvaluator;
+use crate::types::ArrayType;
+use crate::types::CallKind;
+use crate::types::CallType;
 use crate::types::Coercible;
+use crate::types::CompoundTypeDef;
+use crate::types::Optional;
+use crate::types::PrimitiveTypeKind;
 use crate::types::Type;
+use crate::types::Types;
 
 /// The `task` variable name available in task command sections and outputs in
 /// WDL 1.2.
 const TASK_VAR_NAME: &str = "task";
 
+/// The minimum similarity for a name to be suggested as a fix for an unknown
+/// name.
+const SIMILAR_NAME_THRESHOLD: f64 = 0.8;
+
 /// Creates a "name conflict" diagnostic
 fn name_conflict(name: &str, conflicting: Context, first: Context) -> Diagnostic {
     Diagnostic::error(format!("conflicting {conflicting} name `{name}`"))
@@ -185,7 +212,10 @@ fn duplicate_workflow(name: &Ident, first: Span) -> Diagnostic {
 }
 
 /// Creates a "call conflict" diagnostic
-fn call_conflict(name: &Ident, first: Context, suggest_fix: bool) -> Diagnostic {
+///
+/// If an alias is given, the fix adds an `as` clause with the alias to the
+/// call.
+fn call_conflict(name: &Ident, first: Context, alias: Option<&str>) -> Diagnostic {
     let diagnostic = Diagnostic::error(format!(
         "conflicting call name `{name}`",
         name = name.as_str()
@@ -199,10 +229,11 @@ fn call_conflict(name: &Ident, first: Context, suggest_fix: bool) -> Diagnostic
         first.span(),
     );
 
-    if suggest_fix {
-        diagnostic.with_fix("add an `as` clause to the call to specify a different name")
-    } else {
-        diagnostic
+    match alias {
+        Some(alias) => diagnostic
+            .with_fix("add an `as` clause to the call to specify a different name")
+            .with_edit(Span::new(name.span().end(), 0), format!(" as {alias}")),
+        None => diagnostic,
     }
 }
 
@@ -248,6 +279,221 @@ fn reference_cycle(from: &str, from_span: Span, to: &str, to_span: Span) -> Diag
         .with_label(format!("a reference back to `{to}` is here"), from_span)
 }
 
+/// Creates a "self-referential call" diagnostic.
+fn self_referential_call(name: &str, span: Span, reference: Span) -> Diagnostic {
+    Diagnostic::error(format!("call `{name}` is self-referential"))
+        .with_label("self-reference is here", reference)
+        .with_highlight(span)
+}
+
+/// Creates a "statement reference cycle" diagnostic.
+fn statement_reference_cycle(
+    kind: StatementKind,
+    to: &str,
+    from_span: Span,
+    to_span: Span,
+) -> Diagnostic {
+    Diagnostic::error("a name reference cycle was detected")
+        .with_label(
+            format!(
+                "ensure this expression does not directly or indirectly depend on the {kind} \
+                 statement"
+            ),
+            to_span,
+        )
+        .with_label(format!("a reference back to `{to}` is here"), from_span)
+}
+
+/// Creates an "unknown call" diagnostic.
+fn unknown_call(name: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("unknown call name `{name}`")).with_highlight(span)
+}
+
+/// Creates a "scatter not array" diagnostic.
+fn scatter_not_array(types: &Types, actual: Type, actual_span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "type mismatch: expected scatter expression to be type `Array`, but found type `{actual}`",
+        actual = actual.display(types)
+    ))
+    .with_label(
+        format!("this is type `{actual}`", actual = actual.display(types)),
+        actual_span,
+    )
+}
+
+/// Creates an "unknown namespace" diagnostic.
+fn unknown_namespace(ns: &Ident) -> Diagnostic {
+    Diagnostic::error(format!("unknown namespace `{ns}`", ns = ns.as_str()))
+        .with_highlight(ns.span())
+}
+
+/// Creates an "unknown task or workflow" diagnostic.
+fn unknown_task_or_workflow(namespace: Option<&str>, name: &Ident) -> Diagnostic {
+    let message = match namespace {
+        Some(ns) => format!(
+            "unknown task or workflow `{name}` in namespace `{ns}`",
+            name = name.as_str()
+        ),
+        None => format!("unknown task `{name}`", name = name.as_str()),
+    };
+
+    Diagnostic::error(message).with_highlight(name.span())
+}
+
+/// Creates an "unknown input" diagnostic.
+fn unknown_input(
+    kind: CallKind,
+    parent: &str,
+    name: &Ident,
+    suggestion: Option<&str>,
+) -> Diagnostic {
+    let diagnostic = Diagnostic::error(format!(
+        "{kind} `{parent}` does not have an input named `{name}`",
+        name = name.as_str()
+    ))
+    .with_highlight(name.span());
+
+    match suggestion {
+        Some(suggestion) => diagnostic
+            .with_fix(format!("did you mean `{suggestion}`?"))
+            .with_edit(name.span(), suggestion),
+        None => diagnostic,
+    }
+}
+
+/// Creates an "unknown output" diagnostic.
+fn unknown_output(
+    kind: CallKind,
+    parent: &str,
+    name: &Ident,
+    suggestion: Option<&str>,
+) -> Diagnostic {
+    let diagnostic = Diagnostic::error(format!(
+        "{kind} `{parent}` does not have an output named `{name}`",
+        name = name.as_str()
+    ))
+    .with_highlight(name.span());
+
+    match suggestion {
+        Some(suggestion) => diagnostic
+            .with_fix(format!("did you mean `{suggestion}`?"))
+            .with_edit(name.span(), suggestion),
+        None => diagnostic,
+    }
+}
+
+/// Creates a "hint type mismatch" diagnostic.
+///
+/// The expected types are the first allowed type of the hint followed by the
+/// rest of the allowed types.
+fn hint_type_mismatch(
+    types: &Types,
+    name: &Ident,
+    first: Type,
+    rest: &[Type],
+    actual: Type,
+    span: Span,
+) -> Diagnostic {
+    let expected = match rest.split_last() {
+        None => format!("`{first}`", first = first.display(types)),
+        Some((last, init)) => format!(
+            "`{first}`{init}{comma} or `{last}`",
+            first = first.display(types),
+            init = init
+                .iter()
+                .map(|ty| format!(", `{ty}`", ty = ty.display(types)))
+                .collect::<String>(),
+            comma = if init.is_empty() { "" } else { "," },
+            last = last.display(types)
+        ),
+    };
+
+    Diagnostic::error(format!(
+        "type mismatch: expected hint `{name}` to be type {expected}, but found type `{actual}`",
+        name = name.as_str(),
+        actual = actual.display(types)
+    ))
+    .with_label(
+        format!("this is type `{actual}`", actual = actual.display(types)),
+        span,
+    )
+}
+
+/// Creates a "not a hints literal" diagnostic.
+///
+/// The given kind is either `input` or `output`.
+fn not_a_hints_literal(
+    types: &Types,
+    kind: &str,
+    name: &Ident,
+    actual: Type,
+    span: Span,
+) -> Diagnostic {
+    Diagnostic::error(format!(
+        "type mismatch: expected a `hints` literal for {kind} `{name}`, but found type `{actual}`",
+        name = name.as_str(),
+        actual = actual.display(types)
+    ))
+    .with_label(
+        format!("this is type `{actual}`", actual = actual.display(types)),
+        span,
+    )
+}
+
+/// Creates a "missing call input" diagnostic.
+fn missing_call_input(kind: CallKind, callee: &Ident, input: &str) -> Diagnostic {
+    Diagnostic::error(format!(
+        "missing required call input `{input}` for {kind} `{callee}`",
+        callee = callee.as_str()
+    ))
+    .with_highlight(callee.span())
+}
+
+/// Represents the kind of a workflow statement that introduces a scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatementKind {
+    /// The statement is a scatter statement.
+    Scatter,
+    /// The statement is a conditional statement.
+    Conditional,
+}
+
+impl fmt::Display for StatementKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scatter => write!(f, "scatter"),
+            Self::Conditional => write!(f, "conditional"),
+        }
+    }
+}
+
+/// Represents the resolved target of a call statement.
+#[derive(Debug)]
+struct Callee {
+    /// The kind of the callee.
+    kind: CallKind,
+    /// The name of the callee from the call target.
+    name: Ident,
+    /// The inputs of the callee.
+    ///
+    /// The input types are local to the calling document.
+    inputs: IndexMap<String, Input>,
+    /// The type of the call.
+    ty: Type,
+}
+
+/// Gets the name of a call statement.
+///
+/// This is either the alias of the call or the last name of the call target.
+fn call_name(stmt: &CallStatement) -> Ident {
+    stmt.alias().map(|a| a.name()).unwrap_or_else(|| {
+        stmt.target()
+            .names()
+            .last()
+            .expect("expected a last call target name")
+    })
+}
+
 /// Creates a new document scope for a V1 AST.
 pub(crate) fn scope_from_ast(
     graph: &DocumentGraph,
@@ -463,7 +709,12 @@ fn add_struct(
 }
 
 /// Adds an input to a scope.
-fn add_input(mut scope: ScopeRefMut<'_>, decl: Decl, diagnostics: &mut Vec<Diagnostic>) {
+fn add_input(
+    mut scope: ScopeRefMut<'_>,
+    inputs: &mut IndexMap<String, Input>,
+    decl: Decl,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
     let name = decl.name();
     if let Some(prev) = scope.lookup(name.as_str()) {
         diagnostics.push(name_conflict(
@@ -478,6 +729,14 @@ fn add_input(mut scope: ScopeRefMut<'_>, decl: Decl, diagnostics: &mut Vec<Diagn
         name.as_str().to_string(),
         Name::new(NameContext::Input(name.span())),
     );
+
+    inputs.insert(
+        name.as_str().to_string(),
+        Input {
+            ty: None,
+            required: decl.expr().is_none() && !decl.ty().is_optional(),
+        },
+    );
 }
 
 /// Adds an output to a scope.
@@ -544,6 +803,7 @@ fn add_task(
 
     // Populate the task's scope and evaluation graph
     let scope = document.add_scope(Scope::new(None, braced_scope_span(task)));
+    let mut inputs = IndexMap::new();
     let mut saw_input = false;
     let mut outputs = None;
     let mut command = None;
@@ -552,7 +812,7 @@ fn add_task(
             TaskItem::Input(section) if !saw_input => {
                 saw_input = true;
                 for decl in section.declarations() {
-                    add_input(document.scope_mut(scope), decl, diagnostics)
+                    add_input(document.scope_mut(scope), &mut inputs, decl, diagnostics)
                 }
             }
             TaskItem::Output(section) if outputs.is_none() => {
@@ -612,6 +872,7 @@ fn add_task(
         Task {
             name_span: name.span(),
             scope,
+            inputs,
             outputs,
             command,
         },
@@ -639,24 +900,27 @@ fn add_workflow(
     }
 
     let scope = document.add_scope(Scope::new(None, braced_scope_span(workflow)));
+    let mut inputs = IndexMap::new();
+    let mut allows_nested_inputs = false;
     let mut saw_input = false;
-    let mut saw_output = false;
+    let mut outputs = None;
     for item in workflow.items() {
         match item {
             WorkflowItem::Input(section) if !saw_input => {
                 saw_input = true;
                 for decl in section.declarations() {
-                    add_input(document.scope_mut(scope), decl, diagnostics)
+                    add_input(document.scope_mut(scope), &mut inputs, decl, diagnostics)
                 }
             }
-            WorkflowItem::Output(section) if !saw_output => {
-                saw_output = true;
-                let outputs =
+            WorkflowItem::Output(section) if outputs.is_none() => {
+                let child =
                     document.add_scope(Scope::new(Some(scope), braced_scope_span(&section)));
-                document.scope_mut(scope).add_child(outputs);
+                document.scope_mut(scope).add_child(child);
                 for decl in section.declarations() {
-                    add_output(document.scope_mut(outputs), decl, diagnostics);
+                    add_output(document.scope_mut(child), decl, diagnostics);
                 }
+
+                outputs = Some(child);
             }
             WorkflowItem::Declaration(decl) => {
                 add_workflow_statement_decls(
@@ -690,18 +954,60 @@ fn add_workflow(
                     diagnostics,
                 );
             }
+            WorkflowItem::Metadata(section) => {
+                // WDL 1.1 uses the `allowNestedInputs` metadata key
+                for item in section.items() {
+                    if item.name().as_str() == "allowNestedInputs" {
+                        if let MetadataValue::Boolean(b) = item.value() {
+                            allows_nested_inputs = b.value();
+                        }
+                    }
+                }
+            }
+            WorkflowItem::Hints(section) => {
+                // WDL 1.2 uses the `allow_nested_inputs` hint
+                for item in section.items() {
+                    if item.name().as_str() == "allow_nested_inputs" {
+                        if let Expr::Literal(LiteralExpr::Boolean(b)) = item.expr() {
+                            allows_nested_inputs = b.value();
+                        }
+                    }
+                }
+            }
             WorkflowItem::Input(_)
             | WorkflowItem::Output(_)
-            | WorkflowItem::Metadata(_)
-            | WorkflowItem::ParameterMetadata(_)
-            | WorkflowItem::Hints(_) => continue,
+            | WorkflowItem::ParameterMetadata(_) => continue,
         }
     }
 
+    let mut calls = IndexMap::new();
+    for stmt in workflow
+        .syntax()
+        .descendants()
+        .filter_map(CallStatement::cast)
+    {
+        let mut names = stmt.target().names().collect::<Vec<_>>();
+        let target = names.pop().expect("expected a call target name");
+        calls
+            .entry(call_name(&stmt).as_str().to_string())
+            .or_insert_with(|| Call {
+                namespace: names.first().map(|n| n.as_str().to_string()),
+                target: target.as_str().to_string(),
+                specified: stmt
+                    .inputs()
+                    .map(|i| i.name().as_str().to_string())
+                    .collect(),
+            });
+    }
+
     document.workflow = Some(Workflow {
         name_span: name.span(),
         name: name.as_str().to_string(),
         scope,
+        inputs,
+        outputs,
+        calls,
+        allows_nested_inputs,
     });
 }
 
@@ -772,18 +1078,21 @@ fn add_workflow_statement_decls(
             }
         }
         WorkflowStatement::Call(stmt) => {
-            let name = stmt.alias().map(|a| a.name()).unwrap_or_else(|| {
-                stmt.target()
-                    .names()
-                    .last()
-                    .expect("expected a last call target name")
-            });
+            let name = call_name(stmt);
 
             if let Some(prev) = document.scope(parent).lookup(name.as_str()) {
+                // Suggest an alias for the call if it doesn't already have one
+                let alias = stmt.alias().is_none().then(|| {
+                    (2..)
+                        .map(|i| format!("{name}_{i}", name = name.as_str()))
+                        .find(|alias| document.scope(parent).lookup(alias).is_none())
+                        .expect("should find an unused alias")
+                });
+
                 diagnostics.push(call_conflict(
                     &name,
                     prev.context().into(),
-                    stmt.alias().is_none(),
+                    alias.as_deref(),
                 ));
 
                 // Define the name in this scope if it conflicted with a scatter variable
@@ -960,19 +1269,36 @@ fn add_struct_types(document: &mut DocumentScope, diagnostics: &mut Vec<Diagnost
     }
 }
 
+/// Looks up a struct type.
+fn lookup_type(
+    structs: &IndexMap<String, Struct>,
+    name: &str,
+    span: Span,
+) -> Result<Type, Diagnostic> {
+    structs
+        .get(name)
+        .map(|s| s.ty().expect("struct should have type"))
+        .ok_or_else(|| unknown_type(name, span))
+}
+
 /// Performs type checking on a document.
 fn type_check(document: &mut DocumentScope, ast: &Ast, diagnostics: &mut Vec<Diagnostic>) {
-    for item in ast.items() {
-        match item {
-            DocumentItem::Import(_) | DocumentItem::Struct(_) => continue,
-            DocumentItem::Task(definition) => {
-                if let Some(task) = document.tasks.get_index_of(definition.name().as_str()) {
-                    type_check_task(document, &definition, task, diagnostics);
-                }
-            }
-            DocumentItem::Workflow(_) => {
-                // TODO: implement
-            }
+    // Tasks are checked first as calls in the workflow depend on the task inputs
+    for definition in ast.tasks() {
+        if let Some(task) = document.tasks.get_index_of(definition.name().as_str()) {
+            type_check_task(document, &definition, task, diagnostics);
+        }
+    }
+
+    for definition in ast.workflows() {
+        // Only type check the workflow that was added to the document scope
+        if document
+            .workflow
+            .as_ref()
+            .map(|w| w.name_span == definition.name().span())
+            .unwrap_or(false)
+        {
+            type_check_workflow(document, &definition, diagnostics);
         }
     }
 }
@@ -1006,18 +1332,6 @@ fn type_check_task(
         },
     }
 
-    /// Looks up a struct type.
-    fn lookup_type(
-        structs: &IndexMap<String, Struct>,
-        name: &str,
-        span: Span,
-    ) -> Result<Type, Diagnostic> {
-        structs
-            .get(name)
-            .map(|s| s.ty().expect("struct should have type"))
-            .ok_or_else(|| unknown_type(name, span))
-    }
-
     /// Adds a decl node to the name reference graph.
     ///
     /// This also populates the declaration type for the name in the scope.
@@ -1288,4 +1602,1130 @@ fn type_check_task(
             }
         }
     }
+
+    // Type check the hints section
+    if document.version >= Some(SupportedVersion::V1(V1::Two)) {
+        if let Some(section) = definition.hints() {
+            let task = &document.tasks[task_index];
+            type_check_hints(
+                document,
+                &section,
+                task.scope,
+                task.outputs,
+                CallKind::Task,
+                definition.name().as_str(),
+                diagnostics,
+            );
+        }
+    }
+
+    // Populate the input types of the task now that the names are typed
+    let task = &mut document.tasks[task_index];
+    populate_input_types(&mut task.inputs, &document.scopes[task.scope.0]);
+}
+
+/// Populates the types of a task or workflow's inputs from its root scope.
+fn populate_input_types(inputs: &mut IndexMap<String, Input>, scope: &Scope) {
+    for (name, input) in inputs.iter_mut() {
+        input.ty = scope.names.get(name).and_then(|n| n.ty);
+    }
+}
+
+/// Performs a type check on a workflow.
+fn type_check_workflow(
+    document: &mut DocumentScope,
+    definition: &WorkflowDefinition,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    /// Represents a node in the name reference graph.
+    enum GraphNode {
+        /// The node is an input/output/decl node.
+        Decl {
+            /// The scope to use to evaluate the expression.
+            scope: ScopeIndex,
+            /// The expected type of the expression.
+            expected: Type,
+            /// The optional expression to evaluate.
+            expr: Option<Expr>,
+            /// The name of the declaration.
+            name: Ident,
+        },
+        /// The node is a call statement.
+        Call {
+            /// The scope to use to evaluate the call's input expressions.
+            scope: ScopeIndex,
+            /// The name of the call.
+            name: Ident,
+            /// The call statement.
+            statement: CallStatement,
+            /// The resolved callee of the call.
+            callee: Option<Callee>,
+        },
+        /// The node is a scatter statement.
+        Scatter {
+            /// The scope to use to evaluate the scatter expression.
+            scope: ScopeIndex,
+            /// The scope introduced by the scatter statement.
+            inner: ScopeIndex,
+            /// The scatter statement.
+            statement: ScatterStatement,
+        },
+        /// The node is a conditional statement.
+        Conditional {
+            /// The scope to use to evaluate the conditional expression.
+            scope: ScopeIndex,
+            /// The conditional statement.
+            statement: ConditionalStatement,
+        },
+    }
+
+    impl GraphNode {
+        /// Gets the scope used to look up names referenced by the node.
+        fn scope(&self) -> ScopeIndex {
+            match self {
+                Self::Decl { scope, .. }
+                | Self::Call { scope, .. }
+                | Self::Scatter { scope, .. }
+                | Self::Conditional { scope, .. } => *scope,
+            }
+        }
+
+        /// Gets the names referenced by the node.
+        fn references(&self) -> Vec<Ident> {
+            /// Gets the names referenced by an expression.
+            fn expr_references(expr: &Expr) -> impl Iterator<Item = Ident> {
+                expr.syntax()
+                    .descendants()
+                    .filter_map(NameRef::cast)
+                    .map(|r| r.name())
+            }
+
+            match self {
+                Self::Decl { expr, .. } => expr.iter().flat_map(expr_references).collect(),
+                Self::Call { statement, .. } => statement
+                    .inputs()
+                    .flat_map(|i| match i.expr() {
+                        Some(expr) => expr_references(&expr).collect::<Vec<_>>(),
+                        // An input without an expression implicitly references a name in scope
+                        None => vec![i.name()],
+                    })
+                    .collect(),
+                Self::Scatter { statement, .. } => expr_references(&statement.expr()).collect(),
+                Self::Conditional { statement, .. } => expr_references(&statement.expr()).collect(),
+            }
+        }
+
+        /// Gets the span to use when reporting a reference cycle to the node.
+        fn cycle_span(&self) -> Span {
+            match self {
+                Self::Decl { expr, name, .. } => expr
+                    .as_ref()
+                    .map(|e| e.span())
+                    .unwrap_or_else(|| name.span()),
+                Self::Call { name, .. } => name.span(),
+                Self::Scatter { statement, .. } => statement.expr().span(),
+                Self::Conditional { statement, .. } => statement.expr().span(),
+            }
+        }
+    }
+
+    /// Sets the type of a name in a scope.
+    ///
+    /// The type is promoted into each enclosing scope of a scatter (`Array[T]`)
+    /// or conditional (`T?`) statement.
+    ///
+    /// Returns `false` if the name in the scope was not introduced by the given
+    /// context (i.e. the name conflicted with another name).
+    fn set_name_type(
+        document: &mut DocumentScope,
+        scope: ScopeIndex,
+        name: &str,
+        context: NameContext,
+        ty: Type,
+        enclosing: &[(ScopeIndex, StatementKind)],
+    ) -> bool {
+        match document.scopes[scope.0].names.get_mut(name) {
+            Some(local) if local.context == context => local.ty = Some(ty),
+            _ => return false,
+        }
+
+        let mut ty = ty;
+        for (inner, kind) in enclosing.iter().rev() {
+            ty = promote_type(&mut document.types, ty, *kind);
+
+            let parent = document.scopes[inner.0]
+                .parent
+                .expect("statement scope should have a parent");
+            match document.scopes[parent.0].names.get_mut(name) {
+                Some(local) if local.context == context => local.ty = Some(ty),
+                _ => break,
+            }
+        }
+
+        true
+    }
+
+    /// Promotes a type out of a scatter (`Array[T]`) or conditional (`T?`)
+    /// statement.
+    ///
+    /// The outputs of a call type are promoted rather than the call itself.
+    fn promote_type(types: &mut Types, ty: Type, kind: StatementKind) -> Type {
+        // An indeterminate type remains indeterminate after promotion
+        if ty == Type::Union {
+            return ty;
+        }
+
+        if let Type::Compound(compound) = ty {
+            if let CompoundTypeDef::Call(call) = types.type_definition(compound.definition()) {
+                let call_kind = call.kind();
+                let name = call.name().to_string();
+                let outputs = call
+                    .outputs()
+                    .iter()
+                    .map(|(name, ty)| (name.clone(), *ty))
+                    .collect::<Vec<_>>();
+
+                let outputs = outputs
+                    .into_iter()
+                    .map(|(name, ty)| (name, promote_type(types, ty, kind)))
+                    .collect::<Vec<_>>();
+                return types.add_call(CallType::new(call_kind, name, outputs));
+            }
+        }
+
+        match kind {
+            StatementKind::Scatter => types.add_array(ArrayType::new(ty)),
+            StatementKind::Conditional => ty.optional(),
+        }
+    }
+
+    /// Gets the index of the scope that starts at the given position.
+    fn scope_index(document: &DocumentScope, start: usize) -> ScopeIndex {
+        ScopeIndex(
+            document
+                .scopes
+                .binary_search_by_key(&start, |s| s.span.start())
+                .expect("should have a scope at the position"),
+        )
+    }
+
+    /// Adds a decl node to the name reference graph.
+    ///
+    /// This also populates the declaration type for the name in the scope.
+    #[allow(clippy::too_many_arguments)]
+    fn add_decl_node(
+        document: &mut DocumentScope,
+        graph: &mut DiGraph<GraphNode, ()>,
+        names: &mut HashMap<Span, NodeIndex>,
+        scope: ScopeIndex,
+        decl: Decl,
+        enclosing: &[(ScopeIndex, StatementKind)],
+        parent: Option<NodeIndex>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let name = decl.name();
+        let context = match document.scopes[scope.0].names.get(name.as_str()) {
+            Some(local) if local.context.span() == name.span() && local.ty.is_none() => {
+                local.context
+            }
+            _ => return,
+        };
+
+        // Convert the AST type
+        let mut converter = AstTypeConverter::new(&mut document.types, |name, span| {
+            lookup_type(&document.structs, name, span)
+        });
+        let ty = match converter.convert_type(&decl.ty()) {
+            Ok(ty) => ty,
+            Err(diagnostic) => {
+                diagnostics.push(diagnostic);
+                Type::Union
+            }
+        };
+
+        set_name_type(document, scope, name.as_str(), context, ty, enclosing);
+
+        // Add a node to the graph for this declaration
+        let span = name.span();
+        let node = graph.add_node(GraphNode::Decl {
+            scope,
+            expected: ty,
+            expr: decl.expr(),
+            name,
+        });
+        names.insert(span, node);
+
+        if let Some(parent) = parent {
+            graph.add_edge(parent, node, ());
+        }
+    }
+
+    /// Adds the nodes for a workflow statement to the name reference graph.
+    #[allow(clippy::too_many_arguments)]
+    fn add_statement_nodes(
+        document: &mut DocumentScope,
+        graph: &mut DiGraph<GraphNode, ()>,
+        names: &mut HashMap<Span, NodeIndex>,
+        scope: ScopeIndex,
+        statement: WorkflowStatement,
+        enclosing: &mut Vec<(ScopeIndex, StatementKind)>,
+        parent: Option<NodeIndex>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let (node, statements) = match statement {
+            WorkflowStatement::Declaration(decl) => {
+                add_decl_node(
+                    document,
+                    graph,
+                    names,
+                    scope,
+                    Decl::Bound(decl),
+                    enclosing,
+                    parent,
+                    diagnostics,
+                );
+                return;
+            }
+            WorkflowStatement::Call(statement) => {
+                // The type of a call is indeterminate until the callee is resolved
+                let name = call_name(&statement);
+                let context = NameContext::Call(name.span());
+                if !set_name_type(
+                    document,
+                    scope,
+                    name.as_str(),
+                    context,
+                    Type::Union,
+                    enclosing,
+                ) {
+                    return;
+                }
+
+                let callee = resolve_callee(document, &statement, diagnostics);
+                if let Some(callee) = &callee {
+                    set_name_type(
+                        document,
+                        scope,
+                        name.as_str(),
+                        context,
+                        callee.ty,
+                        enclosing,
+                    );
+                }
+
+                let span = name.span();
+                let node = graph.add_node(GraphNode::Call {
+                    scope,
+                    name,
+                    statement,
+                    callee,
+                });
+                names.insert(span, node);
+                (node, None)
+            }
+            WorkflowStatement::Scatter(statement) => {
+                let inner = scope_index(document, braced_scope_span(&statement).start());
+                let variable = statement.variable();
+                let statements = statement.statements();
+                let node = graph.add_node(GraphNode::Scatter {
+                    scope,
+                    inner,
+                    statement,
+                });
+                names.insert(variable.span(), node);
+                (node, Some((inner, StatementKind::Scatter, statements)))
+            }
+            WorkflowStatement::Conditional(statement) => {
+                let inner = scope_index(document, braced_scope_span(&statement).start());
+                let statements = statement.statements();
+                let node = graph.add_node(GraphNode::Conditional { scope, statement });
+                (node, Some((inner, StatementKind::Conditional, statements)))
+            }
+        };
+
+        // Statements nested in a scatter or conditional depend on the enclosing
+        // statement
+        if let Some(parent) = parent {
+            graph.add_edge(parent, node, ());
+        }
+
+        if let Some((inner, kind, statements)) = statements {
+            enclosing.push((inner, kind));
+            for statement in statements {
+                add_statement_nodes(
+                    document,
+                    graph,
+                    names,
+                    inner,
+                    statement,
+                    enclosing,
+                    Some(node),
+                    diagnostics,
+                );
+            }
+            enclosing.pop();
+        }
+    }
+
+    /// Adds name reference edges to the graph.
+    fn add_reference_edges(
+        document: &DocumentScope,
+        graph: &mut DiGraph<GraphNode, ()>,
+        names: &HashMap<Span, NodeIndex>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let mut space = Default::default();
+
+        // Populate edges for any nodes that reference other nodes by name
+        for from in graph.node_indices() {
+            let scope = graph[from].scope();
+            let after = match &graph[from] {
+                GraphNode::Call { statement, .. } => statement.after().map(|a| a.name()).collect(),
+                _ => Vec::new(),
+            };
+
+            for name in graph[from].references() {
+                let to = match document.scope(scope).lookup(name.as_str()) {
+                    Some(n) => match names.get(&n.context().span()) {
+                        Some(to) => *to,
+                        None => continue,
+                    },
+                    None => {
+                        diagnostics.push(unknown_name(name.as_str(), name.span()));
+                        continue;
+                    }
+                };
+
+                // Check to see if the node is self-referential
+                if to == from {
+                    match &graph[from] {
+                        GraphNode::Decl { name: decl, .. } => diagnostics.push(self_referential(
+                            decl.as_str(),
+                            decl.span(),
+                            name.span(),
+                        )),
+                        GraphNode::Call { name: call, .. } => diagnostics.push(
+                            self_referential_call(call.as_str(), call.span(), name.span()),
+                        ),
+                        _ => panic!("expected a decl or call node"),
+                    }
+                    continue;
+                }
+
+                // Check to see if the edge would form a cycle
+                if has_path_connecting(&*graph, from, to, Some(&mut space)) {
+                    let to_span = graph[to].cycle_span();
+                    diagnostics.push(match &graph[from] {
+                        GraphNode::Decl { name: from, .. } | GraphNode::Call { name: from, .. } => {
+                            reference_cycle(from.as_str(), name.span(), name.as_str(), to_span)
+                        }
+                        GraphNode::Scatter { .. } => statement_reference_cycle(
+                            StatementKind::Scatter,
+                            name.as_str(),
+                            name.span(),
+                            to_span,
+                        ),
+                        GraphNode::Conditional { .. } => statement_reference_cycle(
+                            StatementKind::Conditional,
+                            name.as_str(),
+                            name.span(),
+                            to_span,
+                        ),
+                    });
+                    continue;
+                }
+
+                graph.update_edge(to, from, ());
+            }
+
+            // Add edges for any `after` clauses of a call
+            for name in after {
+                let to = match document.scope(scope).lookup(name.as_str()) {
+                    Some(n) if matches!(n.context(), NameContext::Call(_)) => {
+                        match names.get(&n.context().span()) {
+                            Some(to) => *to,
+                            None => continue,
+                        }
+                    }
+                    _ => {
+                        diagnostics.push(unknown_call(name.as_str(), name.span()));
+                        continue;
+                    }
+                };
+
+                if to == from {
+                    diagnostics.push(self_referential_call(
+                        name.as_str(),
+                        graph[from].cycle_span(),
+                        name.span(),
+                    ));
+                    continue;
+                }
+
+                if has_path_connecting(&*graph, from, to, Some(&mut space)) {
+                    let from_name = match &graph[from] {
+                        GraphNode::Call { name, .. } => name.as_str().to_string(),
+                        _ => panic!("expected a call node"),
+                    };
+                    diagnostics.push(reference_cycle(
+                        &from_name,
+                        name.span(),
+                        name.as_str(),
+                        graph[to].cycle_span(),
+                    ));
+                    continue;
+                }
+
+                graph.update_edge(to, from, ());
+            }
+        }
+    }
+
+    let root = document
+        .workflow
+        .as_ref()
+        .expect("should have a workflow")
+        .scope;
+
+    // Populate the declaration types and build a name reference graph
+    let mut saw_inputs = false;
+    let mut saw_outputs = false;
+    let mut graph = DiGraph::new();
+    let mut names = HashMap::new();
+    let mut enclosing = Vec::new();
+    for item in definition.items() {
+        match item {
+            WorkflowItem::Input(section) if !saw_inputs => {
+                saw_inputs = true;
+                for decl in section.declarations() {
+                    add_decl_node(
+                        document,
+                        &mut graph,
+                        &mut names,
+                        root,
+                        decl,
+                        &enclosing,
+                        None,
+                        diagnostics,
+                    );
+                }
+            }
+            WorkflowItem::Output(section) if !saw_outputs => {
+                saw_outputs = true;
+                let scope = scope_index(document, braced_scope_span(&section).start());
+                for decl in section.declarations() {
+                    add_decl_node(
+                        document,
+                        &mut graph,
+                        &mut names,
+                        scope,
+                        Decl::Bound(decl),
+                        &enclosing,
+                        None,
+                        diagnostics,
+                    );
+                }
+            }
+            WorkflowItem::Declaration(decl) => add_statement_nodes(
+                document,
+                &mut graph,
+                &mut names,
+                root,
+                WorkflowStatement::Declaration(decl),
+                &mut enclosing,
+                None,
+                diagnostics,
+            ),
+            WorkflowItem::Call(statement) => add_statement_nodes(
+                document,
+                &mut graph,
+                &mut names,
+                root,
+                WorkflowStatement::Call(statement),
+                &mut enclosing,
+                None,
+                diagnostics,
+            ),
+            WorkflowItem::Scatter(statement) => add_statement_nodes(
+                document,
+                &mut graph,
+                &mut names,
+                root,
+                WorkflowStatement::Scatter(statement),
+                &mut enclosing,
+                None,
+                diagnostics,
+            ),
+            WorkflowItem::Conditional(statement) => add_statement_nodes(
+                document,
+                &mut graph,
+                &mut names,
+                root,
+                WorkflowStatement::Conditional(statement),
+                &mut enclosing,
+                None,
+                diagnostics,
+            ),
+            _ => continue,
+        }
+    }
+
+    add_reference_edges(document, &mut graph, &names, diagnostics);
+
+    // Type check the nodes; nodes were added in document order, so scatter
+    // variables are typed before the statements of the scatter are checked
+    for index in graph.node_indices() {
+        match &graph[index] {
+            GraphNode::Decl {
+                scope,
+                expected,
+                expr,
+                name,
+            } => {
+                if let Some(expr) = expr {
+                    let scopes = &document.scopes;
+                    let mut evaluator = ExprTypeEvaluator::new(
+                        document.version.expect("document should be a 1.x version"),
+                        &mut document.types,
+                        diagnostics,
+                        |name, span| lookup_type(&document.structs, name, span),
+                    );
+                    let actual = evaluator
+                        .evaluate_expr(
+                            &ScopeRef {
+                                scopes,
+                                scope: *scope,
+                            },
+                            expr,
+                        )
+                        .unwrap_or(Type::Union);
+
+                    if *expected != Type::Union
+                        && !actual.is_coercible_to(&document.types, expected)
+                    {
+                        diagnostics.push(type_mismatch(
+                            &document.types,
+                            *expected,
+                            name.span(),
+                            actual,
+                            expr.span(),
+                        ));
+                    }
+                }
+            }
+            GraphNode::Call {
+                scope,
+                statement,
+                callee,
+                ..
+            } => type_check_call(document, *scope, statement, callee.as_ref(), diagnostics),
+            GraphNode::Scatter {
+                scope,
+                inner,
+                statement,
+            } => {
+                let expr = statement.expr();
+                let mut evaluator = ExprTypeEvaluator::new(
+                    document.version.expect("document should be a 1.x version"),
+                    &mut document.types,
+                    diagnostics,
+                    |name, span| lookup_type(&document.structs, name, span),
+                );
+                let actual = evaluator
+                    .evaluate_expr(
+                        &ScopeRef {
+                            scopes: &document.scopes,
+                            scope: *scope,
+                        },
+                        &expr,
+                    )
+                    .unwrap_or(Type::Union);
+
+                // The scatter variable is the element type of the array
+                let element_type = match actual {
+                    Type::Union => Type::Union,
+                    Type::Compound(ty) if !ty.is_optional() => {
+                        match document.types.type_definition(ty.definition()) {
+                            CompoundTypeDef::Array(ty) => ty.element_type(),
+                            _ => {
+                                diagnostics.push(scatter_not_array(
+                                    &document.types,
+                                    actual,
+                                    expr.span(),
+                                ));
+                                Type::Union
+                            }
+                        }
+                    }
+                    _ => {
+                        diagnostics.push(scatter_not_array(&document.types, actual, expr.span()));
+                        Type::Union
+                    }
+                };
+
+                let variable = statement.variable();
+                if let Some(local) = document.scopes[inner.0].names.get_mut(variable.as_str()) {
+                    if local.context == NameContext::ScatterVariable(variable.span()) {
+                        local.ty = Some(element_type);
+                    }
+                }
+            }
+            GraphNode::Conditional { scope, statement } => {
+                let expr = statement.expr();
+                let mut evaluator = ExprTypeEvaluator::new(
+                    document.version.expect("document should be a 1.x version"),
+                    &mut document.types,
+                    diagnostics,
+                    |name, span| lookup_type(&document.structs, name, span),
+                );
+                let actual = evaluator
+                    .evaluate_expr(
+                        &ScopeRef {
+                            scopes: &document.scopes,
+                            scope: *scope,
+                        },
+                        &expr,
+                    )
+                    .unwrap_or(Type::Union);
+
+                if !actual.is_coercible_to(&document.types, &PrimitiveTypeKind::Boolean.into()) {
+                    diagnostics.push(if_conditional_mismatch(
+                        &document.types,
+                        actual,
+                        expr.span(),
+                    ));
+                }
+            }
+        }
+    }
+
+    // Type check the hints section
+    if document.version >= Some(SupportedVersion::V1(V1::Two)) {
+        if let Some(section) = definition.hints() {
+            let workflow = document.workflow.as_ref().expect("should have a workflow");
+            type_check_hints(
+                document,
+                &section,
+                workflow.scope,
+                workflow.outputs,
+                CallKind::Workflow,
+                definition.name().as_str(),
+                diagnostics,
+            );
+        }
+    }
+
+    // Populate the input types of the workflow now that the names are typed
+    let workflow = document.workflow.as_mut().expect("should have a workflow");
+    populate_input_types(&mut workflow.inputs, &document.scopes[workflow.scope.0]);
+}
+
+/// Resolves the callee of a call statement.
+///
+/// The types of the callee's inputs and outputs are imported into the calling
+/// document.
+///
+/// Returns `None` if the callee could not be resolved.
+fn resolve_callee(
+    document: &mut DocumentScope,
+    statement: &CallStatement,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<Callee> {
+    let mut names = statement.target().names().collect::<Vec<_>>();
+    let name = names
+        .iter()
+        .map(|n| n.as_str())
+        .collect::<Vec<_>>()
+        .join(".");
+    let target = names.pop().expect("expected a call target name");
+
+    let ns = match names.as_slice() {
+        [] => {
+            // A call without a namespace refers to a task in this document
+            let task = match document.tasks.get(target.as_str()) {
+                Some(task) => task,
+                None => {
+                    diagnostics.push(unknown_task_or_workflow(None, &target));
+                    return None;
+                }
+            };
+
+            let inputs = task.inputs.clone();
+            let outputs = output_types(&document.scopes, task.outputs);
+            let ty = document
+                .types
+                .add_call(CallType::new(CallKind::Task, name, outputs));
+            return Some(Callee {
+                kind: CallKind::Task,
+                name: target,
+                inputs,
+                ty,
+            });
+        }
+        [ns] => ns,
+        [_, nested, ..] => {
+            // Namespaces cannot be nested
+            diagnostics.push(unknown_namespace(nested));
+            return None;
+        }
+    };
+
+    let scope = match document.namespaces.get(ns.as_str()) {
+        Some(namespace) => namespace.scope.clone(),
+        None => {
+            diagnostics.push(unknown_namespace(ns));
+            return None;
+        }
+    };
+
+    let (kind, inputs, outputs) = match scope.tasks.get(target.as_str()) {
+        Some(task) => (CallKind::Task, &task.inputs, task.outputs),
+        None => match &scope.workflow {
+            Some(workflow) if workflow.name == target.as_str() => {
+                (CallKind::Workflow, &workflow.inputs, workflow.outputs)
+            }
+            _ => {
+                diagnostics.push(unknown_task_or_workflow(Some(ns.as_str()), &target));
+                return None;
+            }
+        },
+    };
+
+    let inputs = inputs
+        .iter()
+        .map(|(name, input)| {
+            (
+                name.clone(),
+                Input {
+                    ty: input.ty.map(|ty| document.types.import(&scope.types, ty)),
+                    required: input.required,
+                },
+            )
+        })
+        .collect();
+    let outputs = output_types(&scope.scopes, outputs)
+        .into_iter()
+        .map(|(name, ty)| (name, document.types.import(&scope.types, ty)))
+        .collect::<Vec<_>>();
+    let ty = document.types.add_call(CallType::new(kind, name, outputs));
+
+    Some(Callee {
+        kind,
+        name: target,
+        inputs,
+        ty,
+    })
+}
+
+/// Gets the names and types of the outputs in an output section's scope.
+fn output_types(scopes: &[Scope], outputs: Option<ScopeIndex>) -> Vec<(String, Type)> {
+    outputs
+        .map(|outputs| {
+            scopes[outputs.0]
+                .names
+                .iter()
+                .filter(|(_, n)| matches!(n.context, NameContext::Output(_)))
+                .map(|(name, n)| (name.clone(), n.ty.unwrap_or(Type::Union)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Finds the candidate most similar to the given name.
+///
+/// Returns `None` if no candidate is sufficiently similar.
+fn find_similar_name<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|c| (c, strsim::jaro_winkler(name, c)))
+        .filter(|(_, similarity)| *similarity >= SIMILAR_NAME_THRESHOLD)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(c, _)| c)
+}
+
+/// Performs a type check on a call statement.
+///
+/// The call's inputs are checked against the inputs of the callee.
+fn type_check_call(
+    document: &mut DocumentScope,
+    scope: ScopeIndex,
+    statement: &CallStatement,
+    callee: Option<&Callee>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut seen = HashSet::new();
+    for input in statement.inputs() {
+        let name = input.name();
+        seen.insert(TokenStrHash::new(name.clone()));
+
+        // An input without an expression implicitly refers to a name in scope
+        let (actual, span) = match input.expr() {
+            Some(expr) => {
+                let mut evaluator = ExprTypeEvaluator::new(
+                    document.version.expect("document should be a 1.x version"),
+                    &mut document.types,
+                    diagnostics,
+                    |name, span| lookup_type(&document.structs, name, span),
+                );
+                let actual = evaluator.evaluate_expr(
+                    &ScopeRef {
+                        scopes: &document.scopes,
+                        scope,
+                    },
+                    &expr,
+                );
+                (actual, expr.span())
+            }
+            None => (
+                document
+                    .scope(scope)
+                    .lookup(name.as_str())
+                    .and_then(|n| n.ty()),
+                name.span(),
+            ),
+        };
+
+        let callee = match callee {
+            Some(callee) => callee,
+            None => continue,
+        };
+
+        let expected = match callee.inputs.get(name.as_str()) {
+            Some(input) => input.ty,
+            None => {
+                diagnostics.push(unknown_input(
+                    callee.kind,
+                    callee.name.as_str(),
+                    &name,
+                    find_similar_name(name.as_str(), callee.inputs.keys().map(String::as_str)),
+                ));
+                continue;
+            }
+        };
+
+        if let (Some(expected), Some(actual)) = (expected, actual) {
+            if expected != Type::Union && !actual.is_coercible_to(&document.types, &expected) {
+                diagnostics.push(type_mismatch(
+                    &document.types,
+                    expected,
+                    name.span(),
+                    actual,
+                    span,
+                ));
+            }
+        }
+    }
+
+    let callee = match callee {
+        Some(callee) => callee,
+        None => return,
+    };
+
+    // Required inputs may be omitted when the workflow allows nested inputs
+    if document
+        .workflow
+        .as_ref()
+        .map(|w| w.allows_nested_inputs)
+        .unwrap_or(false)
+    {
+        return;
+    }
+
+    for (name, input) in &callee.inputs {
+        if input.required && !seen.contains(name.as_str()) {
+            diagnostics.push(missing_call_input(callee.kind, &callee.name, name));
+        }
+    }
+}
+
+/// Evaluates the type of an expression in the given scope.
+fn evaluate_expr(
+    document: &mut DocumentScope,
+    scope: ScopeIndex,
+    expr: &Expr,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<Type> {
+    let mut evaluator = ExprTypeEvaluator::new(
+        document.version.expect("document should be a 1.x version"),
+        &mut document.types,
+        diagnostics,
+        |name, span| lookup_type(&document.structs, name, span),
+    );
+
+    evaluator.evaluate_expr(
+        &ScopeRef {
+            scopes: &document.scopes,
+            scope,
+        },
+        expr,
+    )
+}
+
+/// Performs a type check on the hints section of a task or workflow.
+///
+/// The items of `input` and `output` literals are checked against the inputs
+/// and outputs of the task or workflow.
+fn type_check_hints(
+    document: &mut DocumentScope,
+    section: &HintsSection,
+    scope: ScopeIndex,
+    outputs: Option<ScopeIndex>,
+    kind: CallKind,
+    name: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let inputs = document.scopes[scope.0]
+        .names
+        .iter()
+        .filter(|(_, n)| matches!(n.context, NameContext::Input(_)))
+        .map(|(name, n)| (name.clone(), n.ty.unwrap_or(Type::Union)))
+        .collect::<IndexMap<_, _>>();
+    let outputs = output_types(&document.scopes, outputs)
+        .into_iter()
+        .collect::<IndexMap<_, _>>();
+
+    for item in section.items() {
+        let key = item.name();
+        let expr = item.expr();
+        let actual = match &expr {
+            Expr::Literal(LiteralExpr::Input(literal)) => {
+                for item in literal.items() {
+                    let names = item.names().collect::<Vec<_>>();
+                    if let Some(ty) = inputs.get(names[0].as_str()) {
+                        check_hints_item_path(document, *ty, &names, diagnostics);
+                    } else {
+                        diagnostics.push(unknown_input(
+                            kind,
+                            name,
+                            &names[0],
+                            find_similar_name(names[0].as_str(), inputs.keys().map(String::as_str)),
+                        ));
+                    }
+
+                    check_hints_item_value(
+                        document,
+                        scope,
+                        "input",
+                        &names[0],
+                        &item.expr(),
+                        diagnostics,
+                    );
+                }
+
+                Type::Input
+            }
+            Expr::Literal(LiteralExpr::Output(literal)) => {
+                for item in literal.items() {
+                    let names = item.names().collect::<Vec<_>>();
+                    if let Some(ty) = outputs.get(names[0].as_str()) {
+                        check_hints_item_path(document, *ty, &names, diagnostics);
+                    } else {
+                        diagnostics.push(unknown_output(
+                            kind,
+                            name,
+                            &names[0],
+                            find_similar_name(
+                                names[0].as_str(),
+                                outputs.keys().map(String::as_str),
+                            ),
+                        ));
+                    }
+
+                    check_hints_item_value(
+                        document,
+                        scope,
+                        "output",
+                        &names[0],
+                        &item.expr(),
+                        diagnostics,
+                    );
+                }
+
+                Type::Output
+            }
+            _ => evaluate_expr(document, scope, &expr, diagnostics).unwrap_or(Type::Union),
+        };
+
+        // Check the type of any reserved hint
+        let expected = match kind {
+            CallKind::Task => task_hint_types(key.as_str()),
+            CallKind::Workflow => workflow_hint_types(key.as_str()),
+        };
+
+        if let Some((first, rest)) = expected.as_deref().and_then(<[Type]>::split_first) {
+            if !iter::once(first)
+                .chain(rest)
+                .any(|ty| actual.is_coercible_to(&document.types, ty))
+            {
+                diagnostics.push(hint_type_mismatch(
+                    &document.types,
+                    &key,
+                    *first,
+                    rest,
+                    actual,
+                    expr.span(),
+                ));
+            }
+        }
+    }
+}
+
+/// Checks the struct member path of an `input` or `output` literal item.
+///
+/// The first name of the path is the input or output of the given type.
+fn check_hints_item_path(
+    document: &DocumentScope,
+    ty: Type,
+    names: &[Ident],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut ty = ty;
+    for (i, member) in names.iter().enumerate().skip(1) {
+        // An indeterminate type cannot be checked further
+        if ty == Type::Union {
+            return;
+        }
+
+        match document.types.struct_type(ty) {
+            Some(s) => match s.members().get(member.as_str()) {
+                Some(member) => ty = *member,
+                None => {
+                    diagnostics.push(not_a_struct_member(s.name(), member));
+                    return;
+                }
+            },
+            None => {
+                let start = names[0].span().start();
+                diagnostics.push(cannot_access(
+                    &document.types,
+                    ty,
+                    Span::new(start, names[i - 1].span().end() - start),
+                ));
+                return;
+            }
+        }
+    }
+}
+
+/// Checks that the value of an `input` or `output` literal item is a `hints`
+/// literal.
+///
+/// The given kind is either `input` or `output`.
+fn check_hints_item_value(
+    document: &mut DocumentScope,
+    scope: ScopeIndex,
+    kind: &str,
+    name: &Ident,
+    expr: &Expr,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let actual = evaluate_expr(document, scope, expr, diagnostics).unwrap_or(Type::Union);
+    if !actual.is_coercible_to(&document.types, &Type::Hints) {
+        diagnostics.push(not_a_hints_literal(
+            &document.types,
+            kind,
+            name,
+            actual,
+            expr.span(),
+        ));
+    }
 }
@@ -548,7 +548,7 @@ impl<'a> TypeParameters<'a> {
     ///
     /// Panics if the count of the given type parameters exceeds the maximum
     /// allowed.
-    fn new(parameters: &'a [TypeParameter]) -> Self {
+    pub fn new(parameters: &'a [TypeParameter]) -> Self {
         assert!(
             parameters.len() < MAX_TYPE_PARAMETERS,
             "no more than {MAX_TYPE_PARAMETERS} type parameters is supported"
@@ -942,6 +942,51 @@ impl FunctionSignature {
             return Err(FunctionBindError::TooManyArguments(self.parameters.len()));
         }
 
+        let (type_parameters, coerced) = self.check_arguments(types, arguments)?;
+
+        // Finally, realize the return type; if it fails to realize, it means there was
+        // at least one uninferred type parameter; we return `Union` instead to indicate
+        // that the return value is indeterminate.
+        let ret = self
+            .ret()
+            .realize(types, &type_parameters)
+            .unwrap_or(Type::Union);
+
+        if coerced {
+            Ok(Binding::Coercion(ret))
+        } else {
+            Ok(Binding::Equivalence(ret))
+        }
+    }
+
+    /// Determines if the function signature may still be bound once the
+    /// arguments that follow the given arguments are supplied.
+    ///
+    /// Unlike binding, this does not require an argument for every required
+    /// parameter; it is used to determine which overloads of a function remain
+    /// possible for a partially written call.
+    pub fn bind_partial(
+        &self,
+        types: &mut Types,
+        arguments: &[Type],
+    ) -> Result<(), FunctionBindError> {
+        if arguments.len() > self.parameters.len() {
+            return Err(FunctionBindError::TooManyArguments(self.parameters.len()));
+        }
+
+        self.check_arguments(types, arguments).map(|_| ())
+    }
+
+    /// Checks the types of the given arguments against the parameters of the
+    /// function signature.
+    ///
+    /// Returns the inferred type parameters and whether or not an argument
+    /// required a coercion.
+    fn check_arguments(
+        &self,
+        types: &mut Types,
+        arguments: &[Type],
+    ) -> Result<(TypeParameters<'_>, bool), FunctionBindError> {
         // Ensure the argument types are correct for the function
         let mut coerced = false;
         let type_parameters = self.infer_type_parameters(types, arguments);
@@ -984,19 +1029,7 @@ impl FunctionSignature {
             }
         }
 
-        // Finally, realize the return type; if it fails to realize, it means there was
-        // at least one uninferred type parameter; we return `Union` instead to indicate
-        // that the return value is indeterminate.
-        let ret = self
-            .ret()
-            .realize(types, &type_parameters)
-            .unwrap_or(Type::Union);
-
-        if coerced {
-            Ok(Binding::Coercion(ret))
-        } else {
-            Ok(Binding::Equivalence(ret))
-        }
+        Ok((type_parameters, coerced))
     }
 }
 
@@ -1386,6 +1419,8 @@ pub struct StandardLibrary {
     functions: IndexMap<&'static str, Function>,
     /// The type for `Array[String]`.
     pub(crate) array_string: Type,
+    /// The type for `Map[String, String]`.
+    pub(crate) map_string_string: Type,
     /// The type for `Map[String, Int]`.
     pub(crate) map_string_int: Type,
 }
@@ -2631,6 +2666,7 @@ pub static STDLIB: LazyLock<StandardLibrary> = LazyLock::new(|| {
         types,
         functions,
         array_string,
+        map_string_string,
         map_string_int,
     }
 });
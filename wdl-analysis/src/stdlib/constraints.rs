@@ -71,6 +71,7 @@ impl Constraint for SizeableConstraint {
                 CompoundTypeDef::Struct(s) => {
                     s.members().values().any(|ty| type_is_sizable(types, *ty))
                 }
+                CompoundTypeDef::Call(_) => false,
             }
         }
 
@@ -85,7 +86,7 @@ impl Constraint for SizeableConstraint {
                 }
                 // Treat unions as sizable as they can only be checked at runtime
                 Type::Union | Type::None => true,
-                Type::Task => false,
+                Type::Task | Type::Hints | Type::Input | Type::Output => false,
             }
         }
 
@@ -137,6 +138,7 @@ impl Constraint for JsonSerializableConstraint {
                     .members()
                     .values()
                     .all(|ty| type_is_serializable(types, *ty)),
+                CompoundTypeDef::Call(_) => false,
             }
         }
 
@@ -151,6 +153,7 @@ impl Constraint for JsonSerializableConstraint {
                 | Type::None
                 | Type::Task => true,
                 Type::Compound(ty) => compound_type_is_serializable(types, ty),
+                Type::Hints | Type::Input | Type::Output => false,
             }
         }
 
@@ -172,9 +175,14 @@ impl Constraint for RequiredPrimitiveTypeConstraint {
             Type::Primitive(ty) => !ty.is_optional(),
             // Treat unions as primitive as they can only be checked at runtime
             Type::Union => true,
-            Type::Compound(_) | Type::Object | Type::OptionalObject | Type::None | Type::Task => {
-                false
-            }
+            Type::Compound(_)
+            | Type::Object
+            | Type::OptionalObject
+            | Type::None
+            | Type::Task
+            | Type::Hints
+            | Type::Input
+            | Type::Output => false,
         }
     }
 }
@@ -193,7 +201,13 @@ impl Constraint for AnyPrimitiveTypeConstraint {
             Type::Primitive(_) => true,
             // Treat unions as primitive as they can only be checked at runtime
             Type::Union | Type::None => true,
-            Type::Compound(_) | Type::Object | Type::OptionalObject | Type::Task => false,
+            Type::Compound(_)
+            | Type::Object
+            | Type::OptionalObject
+            | Type::Task
+            | Type::Hints
+            | Type::Input
+            | Type::Output => false,
         }
     }
 }
@@ -198,6 +198,12 @@ pub enum Type {
     /// A special hidden type for `task` that is available in command and task
     /// output sections in WDL 1.2.
     Task,
+    /// A special hidden type for a `hints` literal in WDL 1.2.
+    Hints,
+    /// A special hidden type for an `input` literal in WDL 1.2.
+    Input,
+    /// A special hidden type for an `output` literal in WDL 1.2.
+    Output,
 }
 
 impl Type {
@@ -239,6 +245,9 @@ impl Type {
                     Type::Union => write!(f, "Union"),
                     Type::None => write!(f, "None"),
                     Type::Task => write!(f, "Task"),
+                    Type::Hints => write!(f, "Hints"),
+                    Type::Input => write!(f, "Input"),
+                    Type::Output => write!(f, "Output"),
                 }
             }
         }
@@ -263,7 +272,10 @@ impl Type {
             | Self::OptionalObject
             | Self::Union
             | Self::None
-            | Self::Task => {}
+            | Self::Task
+            | Self::Hints
+            | Self::Input
+            | Self::Output => {}
         }
     }
 }
@@ -274,7 +286,9 @@ impl Optional for Type {
             Self::Primitive(ty) => ty.is_optional(),
             Self::Compound(ty) => ty.is_optional(),
             Self::OptionalObject | Self::None => true,
-            Self::Object | Self::Union | Self::Task => false,
+            Self::Object | Self::Union | Self::Task | Self::Hints | Self::Input | Self::Output => {
+                false
+            }
         }
     }
 
@@ -283,7 +297,9 @@ impl Optional for Type {
             Self::Primitive(ty) => Self::Primitive(ty.optional()),
             Self::Compound(ty) => Self::Compound(ty.optional()),
             Self::Object | Self::OptionalObject => Self::OptionalObject,
-            Self::Union | Self::None | Self::Task => Self::None,
+            Self::Union | Self::None | Self::Task | Self::Hints | Self::Input | Self::Output => {
+                Self::None
+            }
         }
     }
 
@@ -294,6 +310,9 @@ impl Optional for Type {
             Self::Object | Self::OptionalObject => Self::Object,
             Self::Union | Self::None => Self::Union,
             Self::Task => Self::Task,
+            Self::Hints => Self::Hints,
+            Self::Input => Self::Input,
+            Self::Output => Self::Output,
         }
     }
 }
@@ -431,6 +450,7 @@ impl CompoundType {
                     CompoundTypeDef::Pair(ty) => ty.display(self.types).fmt(f)?,
                     CompoundTypeDef::Map(ty) => ty.display(self.types).fmt(f)?,
                     CompoundTypeDef::Struct(ty) => ty.fmt(f)?,
+                    CompoundTypeDef::Call(ty) => ty.fmt(f)?,
                 }
 
                 if self.optional {
@@ -503,8 +523,9 @@ impl TypeEq for CompoundType {
             (CompoundTypeDef::Array(a), CompoundTypeDef::Array(b)) => a.type_eq(types, b),
             (CompoundTypeDef::Pair(a), CompoundTypeDef::Pair(b)) => a.type_eq(types, b),
             (CompoundTypeDef::Map(a), CompoundTypeDef::Map(b)) => a.type_eq(types, b),
-            (CompoundTypeDef::Struct(_), CompoundTypeDef::Struct(_)) => {
-                // Struct types are only equivalent if they're the same definition
+            (CompoundTypeDef::Struct(_), CompoundTypeDef::Struct(_))
+            | (CompoundTypeDef::Call(_), CompoundTypeDef::Call(_)) => {
+                // Struct and call types are only equivalent if they're the same definition
                 false
             }
             _ => false,
@@ -523,6 +544,8 @@ pub enum CompoundTypeDef {
     Map(MapType),
     /// The type is a struct (e.g. `Foo`).
     Struct(StructType),
+    /// The type is the result of a call statement.
+    Call(CallType),
 }
 
 impl CompoundTypeDef {
@@ -541,6 +564,9 @@ impl CompoundTypeDef {
             Self::Struct(ty) => {
                 ty.assert_valid(types);
             }
+            Self::Call(ty) => {
+                ty.assert_valid(types);
+            }
         }
     }
 
@@ -583,6 +609,16 @@ impl CompoundTypeDef {
             _ => None,
         }
     }
+
+    /// Converts the compound type to a call type.
+    ///
+    /// Returns `None` if the compound type is not a call type.
+    pub fn as_call(&self) -> Option<&CallType> {
+        match self {
+            Self::Call(ty) => Some(ty),
+            _ => None,
+        }
+    }
 }
 
 impl Coercible for CompoundTypeDef {
@@ -674,6 +710,12 @@ impl From<StructType> for CompoundTypeDef {
     }
 }
 
+impl From<CallType> for CompoundTypeDef {
+    fn from(value: CallType) -> Self {
+        Self::Call(value)
+    }
+}
+
 /// Represents the type of an `Array`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ArrayType {
@@ -962,6 +1004,89 @@ impl fmt::Display for StructType {
     }
 }
 
+/// Represents the kind of a call's target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CallKind {
+    /// The call is to a task.
+    Task,
+    /// The call is to a workflow.
+    Workflow,
+}
+
+impl fmt::Display for CallKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Task => write!(f, "task"),
+            Self::Workflow => write!(f, "workflow"),
+        }
+    }
+}
+
+/// Represents the type of a call statement.
+///
+/// The members of a call type are the outputs of the called task or workflow.
+#[derive(Debug)]
+pub struct CallType {
+    /// The kind of the call.
+    pub(crate) kind: CallKind,
+    /// The name of the called task or workflow.
+    ///
+    /// This includes the namespace for an imported task or workflow.
+    pub(crate) name: String,
+    /// The outputs of the call.
+    pub(crate) outputs: IndexMap<String, Type>,
+}
+
+impl CallType {
+    /// Constructs a new call type.
+    pub fn new<N, T>(
+        kind: CallKind,
+        name: impl Into<String>,
+        outputs: impl IntoIterator<Item = (N, T)>,
+    ) -> Self
+    where
+        N: Into<String>,
+        T: Into<Type>,
+    {
+        Self {
+            kind,
+            name: name.into(),
+            outputs: outputs
+                .into_iter()
+                .map(|(n, ty)| (n.into(), ty.into()))
+                .collect(),
+        }
+    }
+
+    /// Gets the kind of the call.
+    pub fn kind(&self) -> CallKind {
+        self.kind
+    }
+
+    /// Gets the name of the called task or workflow.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the outputs of the call.
+    pub fn outputs(&self) -> &IndexMap<String, Type> {
+        &self.outputs
+    }
+
+    /// Asserts that this type is valid.
+    fn assert_valid(&self, types: &Types) {
+        for v in self.outputs.values() {
+            v.assert_valid(types);
+        }
+    }
+}
+
+impl fmt::Display for CallType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "call to {kind} {name}", kind = self.kind, name = self.name)
+    }
+}
+
 /// Represents a collection of types.
 #[derive(Debug, Default)]
 pub struct Types(Arena<CompoundTypeDef>);
@@ -1028,6 +1153,20 @@ impl Types {
         })
     }
 
+    /// Adds a call type to the type collection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provided type contains a type definition identifier from a
+    /// different types collection.
+    pub fn add_call(&mut self, ty: CallType) -> Type {
+        ty.assert_valid(self);
+        Type::Compound(CompoundType {
+            definition: self.0.alloc(CompoundTypeDef::Call(ty)),
+            optional: false,
+        })
+    }
+
     /// Gets a compound type definition by identifier.
     ///
     /// # Panics
@@ -1060,47 +1199,72 @@ impl Types {
     pub fn import(&mut self, types: &Self, ty: Type) -> Type {
         match ty {
             Type::Primitive(ty) => Type::Primitive(ty),
-            Type::Compound(ty) => match &types.0[ty.definition] {
-                CompoundTypeDef::Array(ty) => {
-                    let element_type = self.import(types, ty.element_type);
-                    self.add_array(ArrayType {
-                        element_type,
-                        non_empty: ty.non_empty,
-                    })
-                }
-                CompoundTypeDef::Pair(ty) => {
-                    let first_type = self.import(types, ty.first_type);
-                    let second_type = self.import(types, ty.second_type);
-                    self.add_pair(PairType {
-                        first_type,
-                        second_type,
-                    })
-                }
-                CompoundTypeDef::Map(ty) => {
-                    let value_type = self.import(types, ty.value_type);
-                    self.add_map(MapType {
-                        key_type: ty.key_type,
-                        value_type,
-                    })
-                }
-                CompoundTypeDef::Struct(ty) => {
-                    let members = ty
-                        .members
-                        .iter()
-                        .map(|(k, v)| (k.clone(), self.import(types, *v)))
-                        .collect();
-
-                    self.add_struct(StructType {
-                        name: ty.name.clone(),
-                        members,
-                    })
+            Type::Compound(compound) => {
+                let imported = match &types.0[compound.definition] {
+                    CompoundTypeDef::Array(ty) => {
+                        let element_type = self.import(types, ty.element_type);
+                        self.add_array(ArrayType {
+                            element_type,
+                            non_empty: ty.non_empty,
+                        })
+                    }
+                    CompoundTypeDef::Pair(ty) => {
+                        let first_type = self.import(types, ty.first_type);
+                        let second_type = self.import(types, ty.second_type);
+                        self.add_pair(PairType {
+                            first_type,
+                            second_type,
+                        })
+                    }
+                    CompoundTypeDef::Map(ty) => {
+                        let value_type = self.import(types, ty.value_type);
+                        self.add_map(MapType {
+                            key_type: ty.key_type,
+                            value_type,
+                        })
+                    }
+                    CompoundTypeDef::Struct(ty) => {
+                        let members = ty
+                            .members
+                            .iter()
+                            .map(|(k, v)| (k.clone(), self.import(types, *v)))
+                            .collect();
+
+                        self.add_struct(StructType {
+                            name: ty.name.clone(),
+                            members,
+                        })
+                    }
+                    CompoundTypeDef::Call(ty) => {
+                        let outputs = ty
+                            .outputs
+                            .iter()
+                            .map(|(k, v)| (k.clone(), self.import(types, *v)))
+                            .collect();
+
+                        self.add_call(CallType {
+                            kind: ty.kind,
+                            name: ty.name.clone(),
+                            outputs,
+                        })
+                    }
+                };
+
+                // Preserve the optional qualifier of the foreign type
+                if compound.optional {
+                    imported.optional()
+                } else {
+                    imported
                 }
-            },
+            }
             Type::Object => Type::Object,
             Type::OptionalObject => Type::OptionalObject,
             Type::Union => Type::Union,
             Type::None => Type::None,
             Type::Task => Type::Task,
+            Type::Hints => Type::Hints,
+            Type::Input => Type::Input,
+            Type::Output => Type::Output,
         }
     }
 }
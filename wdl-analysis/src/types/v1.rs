@@ -30,6 +30,7 @@ use wdl_ast::Span;
 use wdl_ast::SupportedVersion;
 
 use super::ArrayType;
+use super::CallType;
 use super::CompoundTypeDef;
 use super::MapType;
 use super::Optional;
@@ -81,7 +82,7 @@ fn not_a_task_member(member: &Ident) -> Diagnostic {
 }
 
 /// Creates a "not a struct member" diagnostic.
-fn not_a_struct_member(name: &str, member: &Ident) -> Diagnostic {
+pub(crate) fn not_a_struct_member(name: &str, member: &Ident) -> Diagnostic {
     Diagnostic::error(format!(
         "struct `{name}` does not have a member named `{member}`",
         member = member.as_str()
@@ -89,6 +90,17 @@ fn not_a_struct_member(name: &str, member: &Ident) -> Diagnostic {
     .with_highlight(member.span())
 }
 
+/// Creates a "not a call output" diagnostic.
+fn not_a_call_output(ty: &CallType, output: &Ident) -> Diagnostic {
+    Diagnostic::error(format!(
+        "{kind} `{name}` does not have an output named `{output}`",
+        kind = ty.kind(),
+        name = ty.name(),
+        output = output.as_str()
+    ))
+    .with_highlight(output.span())
+}
+
 /// Creates a "not a pair accessor" diagnostic.
 fn not_a_pair_accessor(name: &Ident) -> Diagnostic {
     Diagnostic::error(format!(
@@ -120,7 +132,11 @@ fn map_key_not_primitive(types: &Types, span: Span, actual: Type, actual_span: S
 }
 
 /// Creates a "if conditional mismatch" diagnostic.
-fn if_conditional_mismatch(types: &Types, actual: Type, actual_span: Span) -> Diagnostic {
+pub(crate) fn if_conditional_mismatch(
+    types: &Types,
+    actual: Type,
+    actual_span: Span,
+) -> Diagnostic {
     Diagnostic::error(format!(
         "type mismatch: expected `if` conditional expression to be type `Boolean`, but found type \
          `{actual}`",
@@ -341,7 +357,7 @@ fn index_target_not_array(types: &Types, actual: Type, actual_span: Span) -> Dia
 }
 
 /// Constructs a "cannot access" diagnostic.
-fn cannot_access(types: &Types, actual: Type, actual_span: Span) -> Diagnostic {
+pub(crate) fn cannot_access(types: &Types, actual: Type, actual_span: Span) -> Diagnostic {
     Diagnostic::error(format!(
         "cannot access type `{actual}`",
         actual = actual.display(types)
@@ -382,6 +398,90 @@ pub fn task_member_type(name: &str) -> Option<Type> {
     }
 }
 
+/// Represents a function that gets the allowed types of a reserved hint.
+type HintTypes = fn() -> Vec<Type>;
+
+/// The reserved task hints and the functions that get their allowed types.
+///
+/// Task hints sections are supported in WDL 1.2.
+const TASK_HINTS: &[(&str, HintTypes)] = &[
+    ("disks", || {
+        vec![
+            PrimitiveTypeKind::Integer.into(),
+            PrimitiveTypeKind::String.into(),
+            STDLIB.map_string_string,
+        ]
+    }),
+    ("fpga", integer_or_string),
+    ("gpu", integer_or_string),
+    ("inputs", || vec![Type::Input]),
+    ("localization_optional", boolean),
+    ("max_cpu", || {
+        vec![
+            PrimitiveTypeKind::Integer.into(),
+            PrimitiveTypeKind::Float.into(),
+        ]
+    }),
+    ("max_memory", integer_or_string),
+    ("outputs", || vec![Type::Output]),
+    ("short_task", boolean),
+];
+
+/// The reserved workflow hints and the functions that get their allowed
+/// types.
+///
+/// Workflow hints sections are supported in WDL 1.2.
+const WORKFLOW_HINTS: &[(&str, HintTypes)] = &[("allow_nested_inputs", boolean)];
+
+/// Gets the allowed types of a hint that may be an `Int` or a `String`.
+fn integer_or_string() -> Vec<Type> {
+    vec![
+        PrimitiveTypeKind::Integer.into(),
+        PrimitiveTypeKind::String.into(),
+    ]
+}
+
+/// Gets the allowed types of a hint that must be a `Boolean`.
+fn boolean() -> Vec<Type> {
+    vec![PrimitiveTypeKind::Boolean.into()]
+}
+
+/// Gets the names of the reserved task hints.
+///
+/// Task hints sections are supported in WDL 1.2.
+pub fn task_hint_keys() -> impl Iterator<Item = &'static str> {
+    TASK_HINTS.iter().map(|(name, _)| *name)
+}
+
+/// Gets the names of the reserved workflow hints.
+///
+/// Workflow hints sections are supported in WDL 1.2.
+pub fn workflow_hint_keys() -> impl Iterator<Item = &'static str> {
+    WORKFLOW_HINTS.iter().map(|(name, _)| *name)
+}
+
+/// Gets the allowed types of a reserved task hint.
+///
+/// Task hints sections are supported in WDL 1.2.
+///
+/// Returns `None` if the given hint name is not reserved.
+pub fn task_hint_types(name: &str) -> Option<Vec<Type>> {
+    TASK_HINTS
+        .iter()
+        .find_map(|(n, types)| (*n == name).then(types))
+}
+
+/// Gets the allowed types of a reserved workflow hint.
+///
+/// Workflow hints sections are supported in WDL 1.2.
+///
+/// Returns `None` if the given hint name is not reserved.
+pub fn workflow_hint_types(name: &str) -> Option<Vec<Type>> {
+    WORKFLOW_HINTS
+        .iter()
+        .find_map(|(n, types)| (*n == name).then(types))
+}
+
 /// Represents a comparison operator.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ComparisonOperator {
@@ -498,7 +598,11 @@ where
             v1::Type::Primitive(ty) => Type::Primitive(ty.into()),
         };
 
-        if optional { Ok(ty.optional()) } else { Ok(ty) }
+        if optional {
+            Ok(ty.optional())
+        } else {
+            Ok(ty)
+        }
     }
 
     /// Converts an AST array type to a diagnostic array type.
@@ -741,9 +845,26 @@ where
             LiteralExpr::Object(_) => Some(Type::Object),
             LiteralExpr::Struct(expr) => self.evaluate_literal_struct(scope, expr),
             LiteralExpr::None(_) => Some(Type::None),
-            LiteralExpr::Input(_) | LiteralExpr::Output(_) | LiteralExpr::Hints(_) => {
-                // TODO: implement for full 1.2 support
-                None
+            LiteralExpr::Hints(expr) => {
+                for item in expr.items() {
+                    self.evaluate_expr(scope, &item.expr());
+                }
+
+                Some(Type::Hints)
+            }
+            LiteralExpr::Input(expr) => {
+                for item in expr.items() {
+                    self.evaluate_expr(scope, &item.expr());
+                }
+
+                Some(Type::Input)
+            }
+            LiteralExpr::Output(expr) => {
+                for item in expr.items() {
+                    self.evaluate_expr(scope, &item.expr());
+                }
+
+                Some(Type::Output)
             }
         }
     }
@@ -1457,6 +1578,16 @@ where
                 return None;
             }
 
+            // Check to see if it's a call
+            if let CompoundTypeDef::Call(ty) = definition {
+                if let Some(ty) = ty.outputs.get(name.as_str()) {
+                    return Some(*ty);
+                }
+
+                self.diagnostics.push(not_a_call_output(ty, &name));
+                return None;
+            }
+
             // Check to see if it's a `Pair`
             if let CompoundTypeDef::Pair(ty) = definition {
                 // Support `left` and `right` accessors for pairs
@@ -178,13 +178,8 @@ async fn main() {
         let base = clean(absolute(test).expect("should be made absolute"));
         let results = results
             .iter()
-            .filter_map(|r| {
-                r.uri()
-                    .to_file_path()
-                    .ok()?
-                    .starts_with(&base)
-                    .then(|| r.clone())
-            })
+            .filter(|r| r.uri().to_file_path().is_ok_and(|p| p.starts_with(&base)))
+            .cloned()
             .collect();
         match compare_results(test, results) {
             Ok(_) => {
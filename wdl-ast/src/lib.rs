@@ -44,8 +44,15 @@ pub use rowan::ast::AstNode;
 pub use rowan::Direction;
 pub use wdl_grammar::version;
 pub use wdl_grammar::Diagnostic;
+pub use wdl_grammar::Edit;
 pub use wdl_grammar::Label;
+#[cfg(feature = "serde")]
+pub use wdl_grammar::LocatedDiagnostic;
+#[cfg(feature = "serde")]
+pub use wdl_grammar::Location;
 pub use wdl_grammar::Severity;
+#[cfg(feature = "serde")]
+pub use wdl_grammar::SourceLines;
 pub use wdl_grammar::Span;
 pub use wdl_grammar::SupportedVersion;
 pub use wdl_grammar::SyntaxElement;
@@ -1379,7 +1379,7 @@ impl LiteralExpr {
     }
 
     /// Attempts to reference the expression as a literal `input`.
-
+    ///
     /// - If the value is a literal `input`, `Some()` is returned.
     /// - Else, `None` is returned.
     pub fn as_input(&self) -> Option<&LiteralInput> {
@@ -1391,7 +1391,7 @@ impl LiteralExpr {
 
     /// Consumes `self` and attempts to return the expression as a literal
     /// `input`.
-
+    ///
     /// - If the value is a literal `input`, `Some()` is returned.
     /// - Else, `None` is returned.
     pub fn into_input(self) -> Option<LiteralInput> {
@@ -1414,7 +1414,7 @@ impl LiteralExpr {
     }
 
     /// Attempts to reference the expression as a literal `output`.
-
+    ///
     /// - If the value is a literal `output`, `Some()` is returned.
     /// - Else, `None` is returned.
     pub fn as_output(&self) -> Option<&LiteralOutput> {
@@ -1426,7 +1426,7 @@ impl LiteralExpr {
 
     /// Consumes `self` and attempts to return the expression as a literal
     /// `output`.
-
+    ///
     /// - If the value is a literal `output`, `Some()` is returned.
     /// - Else, `None` is returned.
     pub fn into_output(self) -> Option<LiteralOutput> {
@@ -64,7 +64,7 @@ impl ImportStatement {
         let text = uri.text()?;
         let stem = match Url::parse(text.as_str()) {
             Ok(url) => Path::new(
-                urlencoding::decode(url.path_segments()?.last()?)
+                urlencoding::decode(url.path_segments()?.next_back()?)
                     .ok()?
                     .as_ref(),
             )
@@ -19,6 +19,9 @@ mod requirements;
 mod strings;
 mod version;
 
+pub use keys::REQUIREMENTS_KEY_ALIASES;
+pub use requirements::SUPPORTED_REQUIREMENTS_KEYS;
+
 /// Represents a collection of validation diagnostics.
 ///
 /// Validation visitors receive a diagnostics collection during
@@ -32,6 +35,17 @@ impl Diagnostics {
     pub fn add(&mut self, diagnostic: Diagnostic) {
         self.0.push(diagnostic);
     }
+
+    /// Gets a mutable iterator over the diagnostics in the collection.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Diagnostic> {
+        self.0.iter_mut()
+    }
+
+    /// Retains only the diagnostics for which the given predicate returns
+    /// `true`.
+    pub fn retain(&mut self, f: impl FnMut(&Diagnostic) -> bool) {
+        self.0.retain(f);
+    }
 }
 
 /// Implements an AST validator.
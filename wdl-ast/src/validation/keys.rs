@@ -58,6 +58,16 @@ impl fmt::Display for Context {
     }
 }
 
+/// The aliased keys of a requirements section.
+///
+/// The first key of each pair is the preferred key and the second key is its
+/// alias.
+pub const REQUIREMENTS_KEY_ALIASES: &[(&str, &str)] = &[
+    ("container", "docker"),
+    ("max_retries", "maxRetries"),
+    ("return_codes", "returnCodes"),
+];
+
 /// Creates a "duplicate key" diagnostic
 fn duplicate_key(context: Context, name: &Ident, first: Span) -> Diagnostic {
     Diagnostic::error(format!(
@@ -155,11 +165,7 @@ impl Visitor for UniqueKeysVisitor {
 
         check_duplicate_keys(
             &mut self.0,
-            &[
-                ("container", "docker"),
-                ("max_retries", "maxRetries"),
-                ("return_codes", "returnCodes"),
-            ],
+            REQUIREMENTS_KEY_ALIASES,
             section.items().map(|i| i.name()),
             Context::RequirementsSection,
             state,
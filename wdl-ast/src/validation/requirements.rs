@@ -10,6 +10,21 @@ use crate::SupportedVersion;
 use crate::VisitReason;
 use crate::Visitor;
 
+/// The supported set of requirements keys as of 1.2.
+pub const SUPPORTED_REQUIREMENTS_KEYS: &[&str] = &[
+    "container",
+    "docker", // alias of `container` to be removed in 2.0
+    "cpu",
+    "memory",
+    "gpu",
+    "fpga",
+    "disks",
+    "max_retries",
+    "maxRetries", // alias of `max_retries`
+    "return_codes",
+    "returnCodes", // alias of `return_codes`
+];
+
 /// Creates an "unsupported requirements key" diagnostic.
 fn unsupported_requirements_key(name: &Ident) -> Diagnostic {
     Diagnostic::error(format!(
@@ -48,28 +63,13 @@ impl Visitor for RequirementsVisitor {
         reason: VisitReason,
         section: &v1::RequirementsSection,
     ) {
-        /// The supported set of requirement keys as of 1.2
-        const SUPPORTED_KEYS: &[&str] = &[
-            "container",
-            "docker", // alias of `container` to be removed in 2.0
-            "cpu",
-            "memory",
-            "gpu",
-            "fpga",
-            "disks",
-            "max_retries",
-            "maxRetries", // alias of `max_retries`
-            "return_codes",
-            "returnCodes", // alias of `return_codes`
-        ];
-
         if reason == VisitReason::Exit {
             return;
         }
 
         for item in section.items() {
             let name = item.name();
-            if !SUPPORTED_KEYS.contains(&name.as_str()) {
+            if !SUPPORTED_REQUIREMENTS_KEYS.contains(&name.as_str()) {
                 state.add(unsupported_requirements_key(&name))
             }
         }
@@ -48,6 +48,7 @@ fn input_keyword_requirement(span: Span) -> Diagnostic {
     Diagnostic::error("omitting the `input` keyword in a call statement requires WDL version 1.2")
         .with_label("missing an `input` keyword before this input", span)
         .with_fix("add an `input` keyword followed by a colon before any call inputs")
+        .with_edit(Span::new(span.start(), 0), "input: ")
 }
 
 /// Creates a "struct metadata requirement" diagnostic.
@@ -0,0 +1,101 @@
+//! Module for task execution backends.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use indexmap::IndexMap;
+
+use crate::Value;
+
+mod local;
+
+pub use local::*;
+
+/// Represents a request to spawn a task's command.
+#[derive(Debug)]
+pub struct TaskSpawnRequest {
+    /// The root directory for the task's execution.
+    root: PathBuf,
+    /// The working directory for the task's command.
+    work_dir: PathBuf,
+    /// The evaluated command of the task.
+    command: String,
+    /// The evaluated requirements of the task.
+    requirements: Arc<IndexMap<String, Value>>,
+    /// The evaluated hints of the task.
+    hints: Arc<IndexMap<String, Value>>,
+}
+
+impl TaskSpawnRequest {
+    /// Constructs a new task spawn request.
+    pub fn new(
+        root: impl Into<PathBuf>,
+        work_dir: impl Into<PathBuf>,
+        command: String,
+        requirements: Arc<IndexMap<String, Value>>,
+        hints: Arc<IndexMap<String, Value>>,
+    ) -> Self {
+        Self {
+            root: root.into(),
+            work_dir: work_dir.into(),
+            command,
+            requirements,
+            hints,
+        }
+    }
+
+    /// Gets the root directory for the task's execution.
+    ///
+    /// Backends may create additional files (e.g. the command script and the
+    /// standard output and error files) in this directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Gets the working directory for the task's command.
+    ///
+    /// The working directory is created before the request is made.
+    pub fn work_dir(&self) -> &Path {
+        &self.work_dir
+    }
+
+    /// Gets the evaluated command of the task.
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Gets the evaluated requirements of the task.
+    ///
+    /// For WDL versions prior to 1.2, these are the items of the `runtime`
+    /// section.
+    pub fn requirements(&self) -> &IndexMap<String, Value> {
+        &self.requirements
+    }
+
+    /// Gets the evaluated hints of the task.
+    pub fn hints(&self) -> &IndexMap<String, Value> {
+        &self.hints
+    }
+}
+
+/// Represents the result of executing a task's command.
+#[derive(Debug, Clone)]
+pub struct TaskExecutionResult {
+    /// The exit code of the command.
+    pub exit_code: i32,
+    /// The path to the file containing the standard output of the command.
+    pub stdout: PathBuf,
+    /// The path to the file containing the standard error of the command.
+    pub stderr: PathBuf,
+}
+
+/// Represents a backend for executing the commands of tasks.
+pub trait TaskExecutionBackend: Send + Sync {
+    /// Spawns a task's command.
+    ///
+    /// The returned future completes when the command has exited.
+    fn spawn(&self, request: TaskSpawnRequest) -> BoxFuture<'static, Result<TaskExecutionResult>>;
+}
@@ -0,0 +1,93 @@
+//! Implementation of the local task execution backend.
+
+use std::fs;
+use std::fs::File;
+use std::process::Stdio;
+
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use tokio::process::Command;
+
+use super::TaskExecutionBackend;
+use super::TaskExecutionResult;
+use super::TaskSpawnRequest;
+
+/// The name of the file where the task's command is written.
+const COMMAND_FILE_NAME: &str = "command";
+
+/// The name of the file where the command's standard output is written.
+const STDOUT_FILE_NAME: &str = "stdout";
+
+/// The name of the file where the command's standard error is written.
+const STDERR_FILE_NAME: &str = "stderr";
+
+/// Represents a task execution backend that runs commands as local processes.
+///
+/// Commands are executed with `bash` in the task's working directory.
+///
+/// Container requirements of tasks are ignored by this backend.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalTaskExecutionBackend;
+
+impl LocalTaskExecutionBackend {
+    /// Constructs a new local task execution backend.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TaskExecutionBackend for LocalTaskExecutionBackend {
+    fn spawn(&self, request: TaskSpawnRequest) -> BoxFuture<'static, Result<TaskExecutionResult>> {
+        async move {
+            let command_path = request.root().join(COMMAND_FILE_NAME);
+            fs::write(&command_path, request.command()).with_context(|| {
+                format!(
+                    "failed to write command contents to `{path}`",
+                    path = command_path.display()
+                )
+            })?;
+
+            let stdout_path = request.root().join(STDOUT_FILE_NAME);
+            let stdout = File::create(&stdout_path).with_context(|| {
+                format!(
+                    "failed to create stdout file `{path}`",
+                    path = stdout_path.display()
+                )
+            })?;
+
+            let stderr_path = request.root().join(STDERR_FILE_NAME);
+            let stderr = File::create(&stderr_path).with_context(|| {
+                format!(
+                    "failed to create stderr file `{path}`",
+                    path = stderr_path.display()
+                )
+            })?;
+
+            let status = Command::new("bash")
+                .arg(&command_path)
+                .current_dir(request.work_dir())
+                .stdin(Stdio::null())
+                .stdout(stdout)
+                .stderr(stderr)
+                .kill_on_drop(true)
+                .status()
+                .await
+                .context("failed to spawn `bash`")?;
+
+            let exit_code = match status.code() {
+                Some(code) => code,
+                None => bail!("task process terminated without an exit code ({status})"),
+            };
+
+            Ok(TaskExecutionResult {
+                exit_code,
+                stdout: stdout_path,
+                stderr: stderr_path,
+            })
+        }
+        .boxed()
+    }
+}
@@ -0,0 +1,167 @@
+//! Implementation of the WDL evaluation engine.
+
+use std::fmt;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::thread::available_parallelism;
+
+use anyhow::Result;
+use tokio::sync::Semaphore;
+use wdl_analysis::types::Types;
+
+use crate::TaskExecutionBackend;
+use crate::TaskExecutionResult;
+use crate::TaskSpawnRequest;
+
+/// Represents a WDL evaluation engine.
+///
+/// The engine owns the collection of types for the values it evaluates and
+/// the backend used to execute task commands.
+///
+/// The number of task commands that are executed concurrently is limited; by
+/// default, the limit is the number of available CPUs.
+pub struct Engine {
+    /// The types collection for evaluation.
+    types: Types,
+    /// The task execution backend to use.
+    backend: Arc<dyn TaskExecutionBackend>,
+    /// The maximum number of task commands to execute concurrently.
+    max_concurrency: NonZeroUsize,
+    /// The permits for executing task commands.
+    permits: Arc<Semaphore>,
+}
+
+impl fmt::Debug for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Engine")
+            .field("types", &self.types)
+            .field("max_concurrency", &self.max_concurrency)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Engine {
+    /// Constructs a new engine with the given task execution backend.
+    pub fn new<B: TaskExecutionBackend + 'static>(backend: B) -> Self {
+        let max_concurrency = available_parallelism().unwrap_or(NonZeroUsize::MIN);
+        Self {
+            types: Default::default(),
+            backend: Arc::new(backend),
+            max_concurrency,
+            permits: Arc::new(Semaphore::new(max_concurrency.get())),
+        }
+    }
+
+    /// Sets the maximum number of task commands to execute concurrently.
+    pub fn with_max_concurrency(mut self, max_concurrency: NonZeroUsize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self.permits = Arc::new(Semaphore::new(max_concurrency.get()));
+        self
+    }
+
+    /// Gets the maximum number of task commands to execute concurrently.
+    pub fn max_concurrency(&self) -> NonZeroUsize {
+        self.max_concurrency
+    }
+
+    /// Gets the engine's type collection.
+    pub fn types(&self) -> &Types {
+        &self.types
+    }
+
+    /// Gets a mutable reference to the engine's type collection.
+    pub fn types_mut(&mut self) -> &mut Types {
+        &mut self.types
+    }
+
+    /// Gets the engine's task execution backend.
+    pub fn backend(&self) -> &Arc<dyn TaskExecutionBackend> {
+        &self.backend
+    }
+
+    /// Spawns a task's command with the engine's task execution backend.
+    ///
+    /// The command is not spawned until fewer than the maximum number of
+    /// concurrent task commands are executing.
+    pub(crate) fn spawn(
+        &self,
+        request: TaskSpawnRequest,
+    ) -> impl Future<Output = Result<TaskExecutionResult>> + 'static {
+        let backend = self.backend.clone();
+        let permits = self.permits.clone();
+        async move {
+            let _permit = permits
+                .acquire()
+                .await
+                .expect("semaphore should not be closed");
+            backend.spawn(request).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    use futures::future::try_join_all;
+    use futures::future::BoxFuture;
+    use futures::FutureExt;
+
+    use super::*;
+
+    /// A backend that records the maximum number of concurrently executing
+    /// task commands.
+    #[derive(Default)]
+    struct CountingBackend {
+        /// The number of executing task commands.
+        running: Arc<AtomicUsize>,
+        /// The maximum number of concurrently executing task commands.
+        max: Arc<AtomicUsize>,
+    }
+
+    impl TaskExecutionBackend for CountingBackend {
+        fn spawn(
+            &self,
+            request: TaskSpawnRequest,
+        ) -> BoxFuture<'static, Result<TaskExecutionResult>> {
+            let running = self.running.clone();
+            let max = self.max.clone();
+            async move {
+                let count = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max.fetch_max(count, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(TaskExecutionResult {
+                    exit_code: 0,
+                    stdout: request.root().join("stdout"),
+                    stderr: request.root().join("stderr"),
+                })
+            }
+            .boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn max_concurrency() {
+        let backend = CountingBackend::default();
+        let max = backend.max.clone();
+        let engine = Engine::new(backend).with_max_concurrency(NonZeroUsize::new(2).unwrap());
+
+        try_join_all((0..8).map(|_| {
+            engine.spawn(TaskSpawnRequest::new(
+                "root",
+                "root/work",
+                String::new(),
+                Default::default(),
+                Default::default(),
+            ))
+        }))
+        .await
+        .expect("tasks should execute");
+
+        assert_eq!(max.load(Ordering::SeqCst), 2);
+    }
+}
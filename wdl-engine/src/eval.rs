@@ -0,0 +1,92 @@
+//! Module for evaluation.
+
+use std::path::Path;
+
+use wdl_analysis::types::Type;
+use wdl_analysis::types::Types;
+use wdl_ast::Diagnostic;
+use wdl_ast::Ident;
+use wdl_ast::SupportedVersion;
+
+use crate::Value;
+
+pub mod v1;
+
+/// Represents an error that may occur when evaluating a workflow or task.
+#[derive(Debug)]
+pub enum EvaluationError {
+    /// The error came from WDL source evaluation.
+    Source(Diagnostic),
+    /// The error came from another source.
+    Other(anyhow::Error),
+}
+
+impl From<Diagnostic> for EvaluationError {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self::Source(diagnostic)
+    }
+}
+
+impl From<anyhow::Error> for EvaluationError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Other(e)
+    }
+}
+
+/// Represents a result from evaluating a workflow or task.
+pub type EvaluationResult<T> = Result<T, EvaluationError>;
+
+/// Represents context to an expression evaluator.
+pub trait EvaluationContext {
+    /// Gets the supported version of the document being evaluated.
+    fn version(&self) -> SupportedVersion;
+
+    /// Gets the types collection associated with the evaluation.
+    fn types(&self) -> &Types;
+
+    /// Gets the mutable types collection associated with the evaluation.
+    fn types_mut(&mut self) -> &mut Types;
+
+    /// Gets the value of the given name in scope.
+    fn resolve_name(&self, name: &Ident) -> Result<Value, Diagnostic>;
+
+    /// Resolves a type name to a type.
+    ///
+    /// The returned type must be defined in the context's types collection.
+    fn resolve_type_name(&mut self, name: &Ident) -> Result<Type, Diagnostic>;
+
+    /// Gets the working directory for the evaluation.
+    ///
+    /// Standard library functions that access the file system are only
+    /// available when a working directory is present.
+    ///
+    /// Defaults to `None`.
+    fn work_dir(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Gets the temporary directory for the evaluation.
+    ///
+    /// Standard library functions that write files (e.g. `write_lines`) create
+    /// their files in the temporary directory and are only available when a
+    /// temporary directory is present.
+    ///
+    /// Defaults to `None`.
+    fn temp_dir(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Gets the value to return for a call to the `stdout` function.
+    ///
+    /// Defaults to `None`, which means `stdout` may not be called.
+    fn stdout(&self) -> Option<&Value> {
+        None
+    }
+
+    /// Gets the value to return for a call to the `stderr` function.
+    ///
+    /// Defaults to `None`, which means `stderr` may not be called.
+    fn stderr(&self) -> Option<&Value> {
+        None
+    }
+}
@@ -0,0 +1,1637 @@
+//! Implementation of evaluation for V1 documents.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+use wdl_analysis::stdlib::FunctionBindError;
+use wdl_analysis::stdlib::STDLIB;
+use wdl_analysis::types::ArrayType;
+use wdl_analysis::types::Coercible;
+use wdl_analysis::types::MapType;
+use wdl_analysis::types::Optional;
+use wdl_analysis::types::PairType;
+use wdl_analysis::types::PrimitiveTypeKind;
+use wdl_analysis::types::Type;
+use wdl_analysis::types::Types;
+use wdl_ast::v1::AccessExpr;
+use wdl_ast::v1::CallExpr;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::IfExpr;
+use wdl_ast::v1::IndexExpr;
+use wdl_ast::v1::LiteralArray;
+use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::LiteralMap;
+use wdl_ast::v1::LiteralObject;
+use wdl_ast::v1::LiteralPair;
+use wdl_ast::v1::LiteralString;
+use wdl_ast::v1::LiteralStringKind;
+use wdl_ast::v1::LiteralStruct;
+use wdl_ast::v1::NegationExpr;
+use wdl_ast::v1::Placeholder;
+use wdl_ast::v1::PlaceholderOption;
+use wdl_ast::v1::StringPart;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Ident;
+use wdl_ast::Span;
+
+use super::EvaluationContext;
+use crate::stdlib;
+use crate::stdlib::CallArgument;
+use crate::stdlib::CallContext;
+use crate::Array;
+use crate::Map;
+use crate::Object;
+use crate::Pair;
+use crate::PrimitiveValue;
+use crate::Struct;
+use crate::Value;
+
+mod task;
+mod workflow;
+
+pub use task::*;
+pub use workflow::*;
+
+/// Represents a comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ComparisonOperator {
+    /// The `==` operator.
+    Equality,
+    /// The `!=` operator.
+    Inequality,
+    /// The `<` operator.
+    Less,
+    /// The `<=` operator.
+    LessEqual,
+    /// The `>` operator.
+    Greater,
+    /// The `>=` operator.
+    GreaterEqual,
+}
+
+impl fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Equality => "==",
+                Self::Inequality => "!=",
+                Self::Less => "<",
+                Self::LessEqual => "<=",
+                Self::Greater => ">",
+                Self::GreaterEqual => ">=",
+            }
+        )
+    }
+}
+
+/// Represents a numeric operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumericOperator {
+    /// The `+` operator.
+    Addition,
+    /// The `-` operator.
+    Subtraction,
+    /// The `*` operator.
+    Multiplication,
+    /// The `/` operator.
+    Division,
+    /// The `%` operator.
+    Modulo,
+    /// The `**` operator.
+    Exponentiation,
+}
+
+impl fmt::Display for NumericOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Addition => "addition",
+                Self::Subtraction => "subtraction",
+                Self::Multiplication => "multiplication",
+                Self::Division => "division",
+                Self::Modulo => "remainder",
+                Self::Exponentiation => "exponentiation",
+            }
+        )
+    }
+}
+
+/// Creates a "type mismatch" diagnostic.
+pub(crate) fn type_mismatch(types: &Types, expected: Type, actual: Type, span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "type mismatch: expected type `{expected}`, but found type `{actual}`",
+        expected = expected.display(types),
+        actual = actual.display(types)
+    ))
+    .with_label(
+        format!("this is type `{actual}`", actual = actual.display(types)),
+        span,
+    )
+}
+
+/// Creates a "coercion failed" diagnostic.
+fn coercion_failed(error: anyhow::Error, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("{error:#}")).with_highlight(span)
+}
+
+/// Creates an "integer not in range" diagnostic.
+fn integer_not_in_range(span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "literal integer exceeds the range for a 64-bit signed integer ({min}..={max})",
+        min = i64::MIN,
+        max = i64::MAX,
+    ))
+    .with_highlight(span)
+}
+
+/// Creates a "float not in range" diagnostic.
+fn float_not_in_range(span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "literal float exceeds the range for a 64-bit float ({min:+e}..={max:+e})",
+        min = f64::MIN,
+        max = f64::MAX,
+    ))
+    .with_highlight(span)
+}
+
+/// Creates a "numeric overflow" diagnostic.
+fn numeric_overflow(span: Span) -> Diagnostic {
+    Diagnostic::error("evaluation of arithmetic expression resulted in overflow")
+        .with_highlight(span)
+}
+
+/// Creates a "division by zero" diagnostic.
+fn division_by_zero(span: Span, divisor_span: Span) -> Diagnostic {
+    Diagnostic::error("attempt to divide by zero")
+        .with_highlight(span)
+        .with_label("this expression evaluated to zero", divisor_span)
+}
+
+/// Creates an "exponent not in range" diagnostic.
+fn exponent_not_in_range(span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "exponent exceeds acceptable range (0..={max})",
+        max = u32::MAX
+    ))
+    .with_highlight(span)
+}
+
+/// Creates a "numeric mismatch" diagnostic.
+fn numeric_mismatch(
+    types: &Types,
+    op: NumericOperator,
+    span: Span,
+    lhs: Type,
+    lhs_span: Span,
+    rhs: Type,
+    rhs_span: Span,
+) -> Diagnostic {
+    Diagnostic::error(format!(
+        "{op} operator is not supported for type `{lhs}` and type `{rhs}`",
+        lhs = lhs.display(types),
+        rhs = rhs.display(types)
+    ))
+    .with_highlight(span)
+    .with_label(
+        format!("this is type `{lhs}`", lhs = lhs.display(types)),
+        lhs_span,
+    )
+    .with_label(
+        format!("this is type `{rhs}`", rhs = rhs.display(types)),
+        rhs_span,
+    )
+}
+
+/// Creates a "comparison mismatch" diagnostic.
+fn comparison_mismatch(
+    types: &Types,
+    op: ComparisonOperator,
+    span: Span,
+    lhs: Type,
+    lhs_span: Span,
+    rhs: Type,
+    rhs_span: Span,
+) -> Diagnostic {
+    Diagnostic::error(format!(
+        "type mismatch: operator `{op}` cannot compare type `{lhs}` to type `{rhs}`",
+        lhs = lhs.display(types),
+        rhs = rhs.display(types)
+    ))
+    .with_highlight(span)
+    .with_label(
+        format!("this is type `{lhs}`", lhs = lhs.display(types)),
+        lhs_span,
+    )
+    .with_label(
+        format!("this is type `{rhs}`", rhs = rhs.display(types)),
+        rhs_span,
+    )
+}
+
+/// Creates a "cannot coerce to string" diagnostic.
+fn cannot_coerce_to_string(types: &Types, actual: Type, span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "cannot coerce type `{actual}` to `String`",
+        actual = actual.display(types)
+    ))
+    .with_label(
+        format!("this is type `{actual}`", actual = actual.display(types)),
+        span,
+    )
+}
+
+/// Creates an "array index out of range" diagnostic.
+fn array_index_out_of_range(index: i64, count: usize, span: Span, target_span: Span) -> Diagnostic {
+    Diagnostic::error(format!("array index {index} is out of range"))
+        .with_highlight(span)
+        .with_label(
+            if count == 0 {
+                "this array is empty".to_string()
+            } else {
+                format!(
+                    "this array has only {count} element{s}",
+                    s = if count == 1 { "" } else { "s" }
+                )
+            },
+            target_span,
+        )
+}
+
+/// Creates a "map key not found" diagnostic.
+fn map_key_not_found(key: &PrimitiveValue, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("the map does not contain an entry for key {key}"))
+        .with_highlight(span)
+}
+
+/// Creates a "cannot index" diagnostic.
+fn cannot_index(types: &Types, actual: Type, span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "indexing is only allowed on `Array` and `Map` types, but found type `{actual}`",
+        actual = actual.display(types)
+    ))
+    .with_label(
+        format!("this is type `{actual}`", actual = actual.display(types)),
+        span,
+    )
+}
+
+/// Creates a "not a pair accessor" diagnostic.
+fn not_a_pair_accessor(name: &Ident) -> Diagnostic {
+    Diagnostic::error(format!(
+        "cannot access a pair with name `{name}`",
+        name = name.as_str()
+    ))
+    .with_highlight(name.span())
+    .with_fix("use `left` or `right` to access a pair")
+}
+
+/// Creates a "not a struct member" diagnostic.
+fn not_a_struct_member(name: &str, member: &Ident) -> Diagnostic {
+    Diagnostic::error(format!(
+        "struct `{name}` does not have a member named `{member}`",
+        member = member.as_str()
+    ))
+    .with_highlight(member.span())
+}
+
+/// Creates a "not an object member" diagnostic.
+fn not_an_object_member(member: &Ident) -> Diagnostic {
+    Diagnostic::error(format!(
+        "object does not have a member named `{member}`",
+        member = member.as_str()
+    ))
+    .with_highlight(member.span())
+}
+
+/// Creates a "cannot access" diagnostic.
+fn cannot_access(types: &Types, actual: Type, span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "cannot access type `{actual}`",
+        actual = actual.display(types)
+    ))
+    .with_label(
+        format!("this is type `{actual}`", actual = actual.display(types)),
+        span,
+    )
+}
+
+/// Creates an "unknown function" diagnostic.
+fn unknown_function(name: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("unknown function `{name}`")).with_label(
+        "the WDL standard library does not have a function with this name",
+        span,
+    )
+}
+
+/// Creates an "unsupported function" diagnostic.
+fn unsupported_function(name: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!(
+        "this use of function `{name}` requires a newer WDL version"
+    ))
+    .with_highlight(span)
+}
+
+/// Creates a "function binding failed" diagnostic.
+fn function_bind_failed(
+    name: &Ident,
+    error: FunctionBindError,
+    arguments: &[CallArgument],
+    span: Span,
+    types: &Types,
+) -> Diagnostic {
+    match error {
+        FunctionBindError::TooFewArguments(minimum) => Diagnostic::error(format!(
+            "function `{name}` requires at least {minimum} argument{s} but {count} {v} supplied",
+            name = name.as_str(),
+            s = if minimum == 1 { "" } else { "s" },
+            count = arguments.len(),
+            v = if arguments.len() == 1 { "was" } else { "were" },
+        ))
+        .with_highlight(span),
+        FunctionBindError::TooManyArguments(maximum) => Diagnostic::error(format!(
+            "function `{name}` requires no more than {maximum} argument{s} but {count} {v} \
+             supplied",
+            name = name.as_str(),
+            s = if maximum == 1 { "" } else { "s" },
+            count = arguments.len(),
+            v = if arguments.len() == 1 { "was" } else { "were" },
+        ))
+        .with_highlight(span),
+        FunctionBindError::ArgumentTypeMismatch { index, expected } => {
+            let actual = arguments[index].value.ty();
+            Diagnostic::error(format!(
+                "type mismatch: expected type {expected}, but found type `{actual}`",
+                actual = actual.display(types)
+            ))
+            .with_label(
+                format!("this is type `{actual}`", actual = actual.display(types)),
+                arguments[index].span,
+            )
+        }
+        FunctionBindError::Ambiguous { first, second } => Diagnostic::error(format!(
+            "ambiguous call to function `{name}` with conflicting signatures `{first}` and \
+             `{second}`",
+            name = name.as_str(),
+        ))
+        .with_highlight(span),
+    }
+}
+
+/// Unescapes the text of a literal string, appending it to the given buffer.
+fn unescape(text: &str, buffer: &mut String) {
+    /// Reads a number of digits of the given radix from the iterator.
+    fn read_digits(
+        chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+        radix: u32,
+        count: usize,
+    ) -> Option<char> {
+        let mut value = 0;
+        for _ in 0..count {
+            value = value * radix + chars.next()?.to_digit(radix)?;
+        }
+
+        char::from_u32(value)
+    }
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            buffer.push(c);
+            continue;
+        }
+
+        let escaped = match chars.peek().copied() {
+            Some('\\') | Some('\'') | Some('"') | Some('~') | Some('$') => chars.next(),
+            Some('n') => {
+                chars.next();
+                Some('\n')
+            }
+            Some('t') => {
+                chars.next();
+                Some('\t')
+            }
+            Some('x') => {
+                chars.next();
+                read_digits(&mut chars, 16, 2)
+            }
+            Some('u') => {
+                chars.next();
+                read_digits(&mut chars, 16, 4)
+            }
+            Some('U') => {
+                chars.next();
+                read_digits(&mut chars, 16, 8)
+            }
+            Some('0'..='7') => read_digits(&mut chars, 8, 3),
+            _ => {
+                // Not a recognized escape sequence; the text is kept as-is
+                buffer.push(c);
+                continue;
+            }
+        };
+
+        // Validation of the document ensures escape sequences are valid
+        if let Some(escaped) = escaped {
+            buffer.push(escaped);
+        }
+    }
+}
+
+/// Represents a part of a multi-line string.
+enum MultilinePart {
+    /// The part is (still escaped) text.
+    Text(String),
+    /// The part is a placeholder.
+    Placeholder(Placeholder),
+}
+
+/// Strips the whitespace of a multi-line string according to the rules of
+/// WDL 1.2.
+///
+/// Line continuations are removed, the first line is removed if it is
+/// blank, the last line is removed if it contains only whitespace, and the
+/// common leading whitespace of every line is removed.
+fn strip_multiline(parts: &mut [MultilinePart]) {
+    // Remove line continuations along with the leading whitespace of the next
+    // line
+    for part in parts.iter_mut() {
+        if let MultilinePart::Text(text) = part {
+            let mut result = String::with_capacity(text.len());
+            let mut chars = text.chars().peekable();
+            while let Some(c) = chars.next() {
+                match (c, chars.peek()) {
+                    ('\\', Some('\\')) => {
+                        result.push(c);
+                        result.push(chars.next().unwrap());
+                    }
+                    ('\\', Some('\n')) => {
+                        chars.next();
+                        while matches!(chars.peek(), Some(' ') | Some('\t')) {
+                            chars.next();
+                        }
+                    }
+                    _ => result.push(c),
+                }
+            }
+
+            *text = result;
+        }
+    }
+
+    // Remove a blank first line
+    if let Some(MultilinePart::Text(text)) = parts.first_mut() {
+        if let Some(index) = text.find('\n') {
+            if text[..index].trim().is_empty() {
+                text.replace_range(..=index, "");
+            }
+        }
+    }
+
+    // Remove a whitespace-only last line
+    if let Some(MultilinePart::Text(text)) = parts.last_mut() {
+        if let Some(index) = text.rfind('\n') {
+            if text[index + 1..].trim().is_empty() {
+                text.truncate(index);
+            }
+        }
+    }
+
+    // Determine the common leading whitespace of every line that isn't blank;
+    // a line starts at the beginning of the string and after every newline
+    let mut min = usize::MAX;
+    let mut at_line_start = true;
+    for (i, part) in parts.iter().enumerate() {
+        match part {
+            MultilinePart::Text(text) => {
+                let mut lines = text.split('\n').peekable();
+                let mut first = true;
+                while let Some(line) = lines.next() {
+                    if at_line_start || !first {
+                        let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+                        let blank = indent == line.len()
+                            && (lines.peek().is_some()
+                                || !matches!(
+                                    parts.get(i + 1),
+                                    Some(MultilinePart::Placeholder(_))
+                                ));
+                        if !blank {
+                            min = min.min(indent);
+                        }
+                    }
+
+                    first = false;
+                }
+
+                at_line_start = text.ends_with('\n');
+            }
+            MultilinePart::Placeholder(_) => {
+                if at_line_start {
+                    min = 0;
+                }
+
+                at_line_start = false;
+            }
+        }
+    }
+
+    if min == 0 || min == usize::MAX {
+        return;
+    }
+
+    // Remove the common leading whitespace from every line
+    let mut at_line_start = true;
+    for part in parts.iter_mut() {
+        match part {
+            MultilinePart::Text(text) => {
+                let ends_with_newline = text.ends_with('\n');
+                let mut result = String::with_capacity(text.len());
+                for (i, line) in text.split('\n').enumerate() {
+                    if i > 0 {
+                        result.push('\n');
+                    }
+
+                    if at_line_start || i > 0 {
+                        let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+                        result.push_str(&line[indent.min(min)..]);
+                    } else {
+                        result.push_str(line);
+                    }
+                }
+
+                *text = result;
+                at_line_start = ends_with_newline;
+            }
+            MultilinePart::Placeholder(_) => at_line_start = false,
+        }
+    }
+}
+
+/// Determines the common type of two types.
+///
+/// Returns `None` if the types have no common type.
+pub(crate) fn common_type(types: &Types, a: Type, b: Type) -> Option<Type> {
+    if a.is_none() {
+        return Some(b.optional());
+    }
+
+    if b.is_none() {
+        return Some(a.optional());
+    }
+
+    if b.is_coercible_to(types, &a) {
+        return Some(a);
+    }
+
+    if a.is_coercible_to(types, &b) {
+        return Some(b);
+    }
+
+    // Check for one side being the optional form of the other
+    if b.optional().is_coercible_to(types, &a.optional()) {
+        return Some(a.optional());
+    }
+
+    None
+}
+
+/// Gets the type of a value to use when binding a function call.
+///
+/// A non-empty array value is bound as a non-empty array type so that it may
+/// be passed to parameters requiring a non-empty array.
+///
+/// If `optional` is `true`, the type (or the element type of an array) is made
+/// optional.
+fn binding_type(types: &mut Types, value: &Value, optional: bool) -> Type {
+    let ty = value.ty();
+    match value.as_array() {
+        Some(array) => {
+            let element_type = types
+                .type_definition(match ty {
+                    Type::Compound(ty) => ty.definition(),
+                    _ => unreachable!("array value should have a compound type"),
+                })
+                .as_array()
+                .expect("type should be an array")
+                .element_type();
+            let element_type = if optional {
+                element_type.optional()
+            } else {
+                element_type
+            };
+
+            if array.is_empty() {
+                types.add_array(ArrayType::new(element_type))
+            } else {
+                types.add_array(ArrayType::non_empty(element_type))
+            }
+        }
+        None if optional => ty.optional(),
+        None => ty,
+    }
+}
+
+/// Represents a WDL V1 expression evaluator.
+#[derive(Debug)]
+pub struct ExprEvaluator<C> {
+    /// The evaluation context.
+    context: C,
+    /// The nested count of placeholder evaluation.
+    ///
+    /// This is incremented immediately before a placeholder expression is
+    /// evaluated and decremented immediately after.
+    ///
+    /// If the count is non-zero, special evaluation behavior is enabled for
+    /// string interpolation.
+    placeholders: usize,
+}
+
+impl<C: EvaluationContext> ExprEvaluator<C> {
+    /// Creates a new expression evaluator with the given context.
+    pub fn new(context: C) -> Self {
+        Self {
+            context,
+            placeholders: 0,
+        }
+    }
+
+    /// Gets the evaluation context of the evaluator.
+    pub fn context(&self) -> &C {
+        &self.context
+    }
+
+    /// Consumes the evaluator and returns the evaluation context.
+    pub fn into_context(self) -> C {
+        self.context
+    }
+
+    /// Evaluates the given expression.
+    pub fn evaluate_expr(&mut self, expr: &Expr) -> Result<Value, Diagnostic> {
+        match expr {
+            Expr::Literal(expr) => self.evaluate_literal_expr(expr),
+            Expr::Name(r) => self.context.resolve_name(&r.name()),
+            Expr::Parenthesized(expr) => self.evaluate_expr(&expr.inner()),
+            Expr::If(expr) => self.evaluate_if_expr(expr),
+            Expr::LogicalNot(expr) => {
+                let operand = expr.operand();
+                let value = self.evaluate_boolean(&operand)?;
+                Ok((!value).into())
+            }
+            Expr::Negation(expr) => self.evaluate_negation_expr(expr),
+            Expr::LogicalOr(expr) => {
+                let (lhs, rhs) = expr.operands();
+                // Short-circuit if the left-hand side is `true`
+                if self.evaluate_boolean(&lhs)? {
+                    return Ok(true.into());
+                }
+
+                self.evaluate_boolean(&rhs).map(Into::into)
+            }
+            Expr::LogicalAnd(expr) => {
+                let (lhs, rhs) = expr.operands();
+                // Short-circuit if the left-hand side is `false`
+                if !self.evaluate_boolean(&lhs)? {
+                    return Ok(false.into());
+                }
+
+                self.evaluate_boolean(&rhs).map(Into::into)
+            }
+            Expr::Equality(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.comparison_expr(ComparisonOperator::Equality, &lhs, &rhs, expr.span())
+            }
+            Expr::Inequality(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.comparison_expr(ComparisonOperator::Inequality, &lhs, &rhs, expr.span())
+            }
+            Expr::Less(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.comparison_expr(ComparisonOperator::Less, &lhs, &rhs, expr.span())
+            }
+            Expr::LessEqual(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.comparison_expr(ComparisonOperator::LessEqual, &lhs, &rhs, expr.span())
+            }
+            Expr::Greater(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.comparison_expr(ComparisonOperator::Greater, &lhs, &rhs, expr.span())
+            }
+            Expr::GreaterEqual(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.comparison_expr(ComparisonOperator::GreaterEqual, &lhs, &rhs, expr.span())
+            }
+            Expr::Addition(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.numeric_expr(NumericOperator::Addition, expr.span(), &lhs, &rhs)
+            }
+            Expr::Subtraction(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.numeric_expr(NumericOperator::Subtraction, expr.span(), &lhs, &rhs)
+            }
+            Expr::Multiplication(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.numeric_expr(NumericOperator::Multiplication, expr.span(), &lhs, &rhs)
+            }
+            Expr::Division(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.numeric_expr(NumericOperator::Division, expr.span(), &lhs, &rhs)
+            }
+            Expr::Modulo(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.numeric_expr(NumericOperator::Modulo, expr.span(), &lhs, &rhs)
+            }
+            Expr::Exponentiation(expr) => {
+                let (lhs, rhs) = expr.operands();
+                self.numeric_expr(NumericOperator::Exponentiation, expr.span(), &lhs, &rhs)
+            }
+            Expr::Call(expr) => self.evaluate_call_expr(expr),
+            Expr::Index(expr) => self.evaluate_index_expr(expr),
+            Expr::Access(expr) => self.evaluate_access_expr(expr),
+        }
+    }
+
+    /// Evaluates a literal string and returns the resulting string.
+    pub fn evaluate_literal_string(&mut self, expr: &LiteralString) -> Result<String, Diagnostic> {
+        let mut result = String::new();
+        if expr.kind() == LiteralStringKind::Multiline {
+            let mut parts = expr
+                .parts()
+                .map(|p| match p {
+                    StringPart::Text(t) => MultilinePart::Text(t.as_str().to_string()),
+                    StringPart::Placeholder(p) => MultilinePart::Placeholder(p),
+                })
+                .collect::<Vec<_>>();
+
+            strip_multiline(&mut parts);
+
+            for part in parts {
+                match part {
+                    MultilinePart::Text(t) => unescape(&t, &mut result),
+                    MultilinePart::Placeholder(p) => self.evaluate_placeholder(&p, &mut result)?,
+                }
+            }
+        } else {
+            for part in expr.parts() {
+                match part {
+                    StringPart::Text(t) => unescape(t.as_str(), &mut result),
+                    StringPart::Placeholder(p) => self.evaluate_placeholder(&p, &mut result)?,
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Evaluates a placeholder, appending the result to the given buffer.
+    pub fn evaluate_placeholder(
+        &mut self,
+        placeholder: &Placeholder,
+        buffer: &mut String,
+    ) -> Result<(), Diagnostic> {
+        let expr = placeholder.expr();
+
+        self.placeholders += 1;
+        let value = self.evaluate_expr(&expr);
+        self.placeholders -= 1;
+        let value = value?;
+
+        match placeholder.option() {
+            Some(PlaceholderOption::Sep(option)) => {
+                let separator = self.evaluate_literal_string(&option.separator())?;
+                match &value {
+                    Value::None => {}
+                    Value::Compound(_) => {
+                        let array = value.as_array().ok_or_else(|| {
+                            cannot_coerce_to_string(self.context.types(), value.ty(), expr.span())
+                        })?;
+
+                        for (i, element) in array.elements().iter().enumerate() {
+                            if i > 0 {
+                                buffer.push_str(&separator);
+                            }
+
+                            let raw = element.raw().ok_or_else(|| {
+                                cannot_coerce_to_string(
+                                    self.context.types(),
+                                    element.ty(),
+                                    expr.span(),
+                                )
+                            })?;
+                            write!(buffer, "{raw}").unwrap();
+                        }
+                    }
+                    Value::Primitive(_) => {
+                        let expected = self
+                            .context
+                            .types_mut()
+                            .add_array(ArrayType::new(Type::Union));
+                        return Err(type_mismatch(
+                            self.context.types(),
+                            expected,
+                            value.ty(),
+                            expr.span(),
+                        ));
+                    }
+                }
+
+                return Ok(());
+            }
+            Some(PlaceholderOption::Default(option)) if value.is_none() => {
+                let default = self.evaluate_literal_string(&option.value())?;
+                buffer.push_str(&default);
+                return Ok(());
+            }
+            Some(PlaceholderOption::TrueFalse(option)) => {
+                if let Some(b) = value.as_boolean() {
+                    let (t, f) = option.values();
+                    let s = self.evaluate_literal_string(if b { &t } else { &f })?;
+                    buffer.push_str(&s);
+                    return Ok(());
+                }
+
+                if !value.is_none() {
+                    return Err(type_mismatch(
+                        self.context.types(),
+                        PrimitiveTypeKind::Boolean.into(),
+                        value.ty(),
+                        expr.span(),
+                    ));
+                }
+            }
+            Some(PlaceholderOption::Default(_)) | None => {}
+        }
+
+        let raw = value.raw().ok_or_else(|| {
+            cannot_coerce_to_string(self.context.types(), value.ty(), expr.span())
+        })?;
+        write!(buffer, "{raw}").unwrap();
+        Ok(())
+    }
+
+    /// Evaluates an expression that must evaluate to a `Boolean`.
+    fn evaluate_boolean(&mut self, expr: &Expr) -> Result<bool, Diagnostic> {
+        let value = self.evaluate_expr(expr)?;
+        value.as_boolean().ok_or_else(|| {
+            type_mismatch(
+                self.context.types(),
+                PrimitiveTypeKind::Boolean.into(),
+                value.ty(),
+                expr.span(),
+            )
+        })
+    }
+
+    /// Evaluates a literal expression.
+    fn evaluate_literal_expr(&mut self, expr: &LiteralExpr) -> Result<Value, Diagnostic> {
+        match expr {
+            LiteralExpr::Boolean(lit) => Ok(lit.value().into()),
+            LiteralExpr::Integer(lit) => lit
+                .value()
+                .map(Into::into)
+                .ok_or_else(|| integer_not_in_range(lit.span())),
+            LiteralExpr::Float(lit) => lit
+                .value()
+                .map(Into::into)
+                .ok_or_else(|| float_not_in_range(lit.span())),
+            LiteralExpr::String(lit) => self.evaluate_literal_string(lit).map(Into::into),
+            LiteralExpr::Array(lit) => self.evaluate_literal_array(lit),
+            LiteralExpr::Pair(lit) => self.evaluate_literal_pair(lit),
+            LiteralExpr::Map(lit) => self.evaluate_literal_map(lit),
+            LiteralExpr::Object(lit) => self.evaluate_literal_object(lit),
+            LiteralExpr::Struct(lit) => self.evaluate_literal_struct(lit),
+            LiteralExpr::None(_) => Ok(Value::None),
+            LiteralExpr::Hints(lit) => {
+                // Hints are represented as an object of the hint values
+                let members = lit
+                    .items()
+                    .map(|item| {
+                        Ok((
+                            item.name().as_str().to_string(),
+                            self.evaluate_expr(&item.expr())?,
+                        ))
+                    })
+                    .collect::<Result<IndexMap<_, _>, Diagnostic>>()?;
+                Ok(Object::new(members).into())
+            }
+            LiteralExpr::Input(lit) => {
+                // Inputs are represented as an object keyed by the dotted input path
+                let members = lit
+                    .items()
+                    .map(|item| {
+                        Ok((
+                            item.names()
+                                .map(|n| n.as_str().to_string())
+                                .collect::<Vec<_>>()
+                                .join("."),
+                            self.evaluate_expr(&item.expr())?,
+                        ))
+                    })
+                    .collect::<Result<IndexMap<_, _>, Diagnostic>>()?;
+                Ok(Object::new(members).into())
+            }
+            LiteralExpr::Output(lit) => {
+                // Outputs are represented as an object keyed by the dotted output path
+                let members = lit
+                    .items()
+                    .map(|item| {
+                        Ok((
+                            item.names()
+                                .map(|n| n.as_str().to_string())
+                                .collect::<Vec<_>>()
+                                .join("."),
+                            self.evaluate_expr(&item.expr())?,
+                        ))
+                    })
+                    .collect::<Result<IndexMap<_, _>, Diagnostic>>()?;
+                Ok(Object::new(members).into())
+            }
+        }
+    }
+
+    /// Evaluates a literal array expression.
+    fn evaluate_literal_array(&mut self, expr: &LiteralArray) -> Result<Value, Diagnostic> {
+        let mut element_ty: Option<(Type, Span)> = None;
+        let mut elements = Vec::new();
+        for expr in expr.elements() {
+            let value = self.evaluate_expr(&expr)?;
+            element_ty = match element_ty {
+                Some((expected, span)) => {
+                    let ty = common_type(self.context.types(), expected, value.ty()).ok_or_else(
+                        || {
+                            type_mismatch(self.context.types(), expected, value.ty(), expr.span())
+                                .with_label(
+                                    format!(
+                                        "this is type `{expected}`",
+                                        expected = expected.display(self.context.types())
+                                    ),
+                                    span,
+                                )
+                        },
+                    )?;
+                    Some((ty, span))
+                }
+                None => Some((value.ty(), expr.span())),
+            };
+
+            elements.push(value);
+        }
+
+        // An empty array is treated as `Array[Union]`
+        let element_ty = element_ty.map(|(ty, _)| ty).unwrap_or(Type::Union);
+        let ty = self
+            .context
+            .types_mut()
+            .add_array(ArrayType::new(element_ty));
+        Array::new(self.context.types(), ty, elements)
+            .map(Into::into)
+            .map_err(|e| coercion_failed(e, expr.span()))
+    }
+
+    /// Evaluates a literal pair expression.
+    fn evaluate_literal_pair(&mut self, expr: &LiteralPair) -> Result<Value, Diagnostic> {
+        let (left, right) = expr.exprs();
+        let left = self.evaluate_expr(&left)?;
+        let right = self.evaluate_expr(&right)?;
+        let ty = self
+            .context
+            .types_mut()
+            .add_pair(PairType::new(left.ty(), right.ty()));
+        Ok(Pair::new_unchecked(ty, left, right).into())
+    }
+
+    /// Evaluates a literal map expression.
+    fn evaluate_literal_map(&mut self, expr: &LiteralMap) -> Result<Value, Diagnostic> {
+        let mut key_ty: Option<(Type, Span)> = None;
+        let mut value_ty: Option<(Type, Span)> = None;
+        let mut elements = Vec::new();
+        for item in expr.items() {
+            let (key_expr, value_expr) = item.key_value();
+            let key = self.evaluate_expr(&key_expr)?;
+            let key = match key {
+                Value::Primitive(key) => key,
+                _ => {
+                    return Err(Diagnostic::error(format!(
+                        "expected map key to be a non-optional primitive type, but found type \
+                         `{ty}`",
+                        ty = key.ty().display(self.context.types())
+                    ))
+                    .with_highlight(key_expr.span()));
+                }
+            };
+
+            let value = self.evaluate_expr(&value_expr)?;
+            for (expected, actual, span) in [
+                (&mut key_ty, key.ty(), key_expr.span()),
+                (&mut value_ty, value.ty(), value_expr.span()),
+            ] {
+                *expected = match *expected {
+                    Some((ty, expected_span)) => Some((
+                        common_type(self.context.types(), ty, actual).ok_or_else(|| {
+                            type_mismatch(self.context.types(), ty, actual, span).with_label(
+                                format!(
+                                    "this is type `{ty}`",
+                                    ty = ty.display(self.context.types())
+                                ),
+                                expected_span,
+                            )
+                        })?,
+                        expected_span,
+                    )),
+                    None => Some((actual, span)),
+                };
+            }
+
+            elements.push((key, value));
+        }
+
+        // An empty map is treated as `Map[Union, Union]`
+        let ty = self.context.types_mut().add_map(MapType::new(
+            key_ty.map(|(ty, _)| ty).unwrap_or(Type::Union),
+            value_ty.map(|(ty, _)| ty).unwrap_or(Type::Union),
+        ));
+        Map::new(self.context.types(), ty, elements)
+            .map(Into::into)
+            .map_err(|e| coercion_failed(e, expr.span()))
+    }
+
+    /// Evaluates a literal object expression.
+    fn evaluate_literal_object(&mut self, expr: &LiteralObject) -> Result<Value, Diagnostic> {
+        let members = expr
+            .items()
+            .map(|item| {
+                let (name, value) = item.name_value();
+                Ok((name.as_str().to_string(), self.evaluate_expr(&value)?))
+            })
+            .collect::<Result<IndexMap<_, _>, Diagnostic>>()?;
+        Ok(Object::new(members).into())
+    }
+
+    /// Evaluates a literal struct expression.
+    fn evaluate_literal_struct(&mut self, expr: &LiteralStruct) -> Result<Value, Diagnostic> {
+        let ty = self.context.resolve_type_name(&expr.name())?;
+        let members = expr
+            .items()
+            .map(|item| {
+                let (name, value) = item.name_value();
+                Ok((name.as_str().to_string(), self.evaluate_expr(&value)?))
+            })
+            .collect::<Result<IndexMap<_, _>, Diagnostic>>()?;
+        Struct::new(self.context.types(), ty, members)
+            .map(Into::into)
+            .map_err(|e| coercion_failed(e, expr.span()))
+    }
+
+    /// Evaluates an `if` expression.
+    fn evaluate_if_expr(&mut self, expr: &IfExpr) -> Result<Value, Diagnostic> {
+        let (conditional, true_expr, false_expr) = expr.exprs();
+        if self.evaluate_boolean(&conditional)? {
+            self.evaluate_expr(&true_expr)
+        } else {
+            self.evaluate_expr(&false_expr)
+        }
+    }
+
+    /// Evaluates a negation expression.
+    fn evaluate_negation_expr(&mut self, expr: &NegationExpr) -> Result<Value, Diagnostic> {
+        let operand = expr.operand();
+
+        // Handle negation of a literal integer directly so that the minimum
+        // integer value may be expressed
+        if let Expr::Literal(LiteralExpr::Integer(lit)) = &operand {
+            return lit
+                .negate()
+                .map(Into::into)
+                .ok_or_else(|| integer_not_in_range(expr.span()));
+        }
+
+        let value = self.evaluate_expr(&operand)?;
+        match value {
+            Value::Primitive(PrimitiveValue::Integer(v)) => v
+                .checked_neg()
+                .map(Into::into)
+                .ok_or_else(|| numeric_overflow(expr.span())),
+            Value::Primitive(PrimitiveValue::Float(v)) => Ok((-v.0).into()),
+            _ => Err(type_mismatch(
+                self.context.types(),
+                PrimitiveTypeKind::Float.into(),
+                value.ty(),
+                operand.span(),
+            )),
+        }
+    }
+
+    /// Evaluates a comparison expression.
+    fn comparison_expr(
+        &mut self,
+        op: ComparisonOperator,
+        lhs: &Expr,
+        rhs: &Expr,
+        span: Span,
+    ) -> Result<Value, Diagnostic> {
+        let left = self.evaluate_expr(lhs)?;
+        let right = self.evaluate_expr(rhs)?;
+
+        let result = match op {
+            ComparisonOperator::Equality => Value::equals(&left, &right),
+            ComparisonOperator::Inequality => Value::equals(&left, &right).map(|v| !v),
+            _ => {
+                let ordering = match (&left, &right) {
+                    (
+                        Value::Primitive(PrimitiveValue::Boolean(l)),
+                        Value::Primitive(PrimitiveValue::Boolean(r)),
+                    ) => Some(l.cmp(r)),
+                    (
+                        Value::Primitive(PrimitiveValue::Integer(l)),
+                        Value::Primitive(PrimitiveValue::Integer(r)),
+                    ) => Some(l.cmp(r)),
+                    (
+                        Value::Primitive(PrimitiveValue::Integer(l)),
+                        Value::Primitive(PrimitiveValue::Float(r)),
+                    ) => (*l as f64).partial_cmp(&r.0),
+                    (
+                        Value::Primitive(PrimitiveValue::Float(l)),
+                        Value::Primitive(PrimitiveValue::Integer(r)),
+                    ) => l.0.partial_cmp(&(*r as f64)),
+                    (
+                        Value::Primitive(PrimitiveValue::Float(l)),
+                        Value::Primitive(PrimitiveValue::Float(r)),
+                    ) => l.0.partial_cmp(&r.0),
+                    (
+                        Value::Primitive(PrimitiveValue::String(l)),
+                        Value::Primitive(PrimitiveValue::String(r)),
+                    ) => Some(l.cmp(r)),
+                    _ => None,
+                };
+
+                ordering.map(|o| match op {
+                    ComparisonOperator::Less => o == Ordering::Less,
+                    ComparisonOperator::LessEqual => o != Ordering::Greater,
+                    ComparisonOperator::Greater => o == Ordering::Greater,
+                    ComparisonOperator::GreaterEqual => o != Ordering::Less,
+                    _ => unreachable!(),
+                })
+            }
+        };
+
+        result.map(Into::into).ok_or_else(|| {
+            comparison_mismatch(
+                self.context.types(),
+                op,
+                span,
+                left.ty(),
+                lhs.span(),
+                right.ty(),
+                rhs.span(),
+            )
+        })
+    }
+
+    /// Evaluates a numeric expression.
+    fn numeric_expr(
+        &mut self,
+        op: NumericOperator,
+        span: Span,
+        lhs: &Expr,
+        rhs: &Expr,
+    ) -> Result<Value, Diagnostic> {
+        let left = self.evaluate_expr(lhs)?;
+        let right = self.evaluate_expr(rhs)?;
+
+        match (&left, &right) {
+            (
+                Value::Primitive(PrimitiveValue::Integer(l)),
+                Value::Primitive(PrimitiveValue::Integer(r)),
+            ) => {
+                let (l, r) = (*l, *r);
+                if r == 0 && matches!(op, NumericOperator::Division | NumericOperator::Modulo) {
+                    return Err(division_by_zero(span, rhs.span()));
+                }
+
+                let result = match op {
+                    NumericOperator::Addition => l.checked_add(r),
+                    NumericOperator::Subtraction => l.checked_sub(r),
+                    NumericOperator::Multiplication => l.checked_mul(r),
+                    NumericOperator::Division => l.checked_div(r),
+                    NumericOperator::Modulo => l.checked_rem(r),
+                    NumericOperator::Exponentiation => {
+                        let exponent =
+                            u32::try_from(r).map_err(|_| exponent_not_in_range(rhs.span()))?;
+                        l.checked_pow(exponent)
+                    }
+                };
+
+                return result.map(Into::into).ok_or_else(|| numeric_overflow(span));
+            }
+            (
+                Value::Primitive(PrimitiveValue::Integer(_) | PrimitiveValue::Float(_)),
+                Value::Primitive(PrimitiveValue::Integer(_) | PrimitiveValue::Float(_)),
+            ) => {
+                let l = left
+                    .as_float()
+                    .unwrap_or_else(|| left.as_integer().unwrap() as f64);
+                let r = right
+                    .as_float()
+                    .unwrap_or_else(|| right.as_integer().unwrap() as f64);
+                if r == 0.0 && matches!(op, NumericOperator::Division | NumericOperator::Modulo) {
+                    return Err(division_by_zero(span, rhs.span()));
+                }
+
+                return Ok(match op {
+                    NumericOperator::Addition => l + r,
+                    NumericOperator::Subtraction => l - r,
+                    NumericOperator::Multiplication => l * r,
+                    NumericOperator::Division => l / r,
+                    NumericOperator::Modulo => l % r,
+                    NumericOperator::Exponentiation => l.powf(r),
+                }
+                .into());
+            }
+            _ => {}
+        }
+
+        // For addition, also support string concatenation with a `String` on one
+        // or both sides of any primitive that isn't `Boolean`
+        if op == NumericOperator::Addition {
+            let is_string = |v: &Value| matches!(v, Value::Primitive(PrimitiveValue::String(_)));
+            let is_concatenable = |v: &Value| match v {
+                Value::Primitive(PrimitiveValue::Boolean(_)) => false,
+                Value::Primitive(_) => true,
+                _ => false,
+            };
+
+            if (is_string(&left) && is_concatenable(&right))
+                || (is_concatenable(&left) && is_string(&right))
+            {
+                return Ok(format!(
+                    "{left}{right}",
+                    left = left.raw().unwrap(),
+                    right = right.raw().unwrap()
+                )
+                .into());
+            }
+
+            // In placeholders, concatenation with `None` evaluates to `None`
+            if self.placeholders > 0
+                && ((is_string(&left) && right.is_none()) || (left.is_none() && is_string(&right)))
+            {
+                return Ok(Value::None);
+            }
+        }
+
+        Err(numeric_mismatch(
+            self.context.types(),
+            op,
+            span,
+            left.ty(),
+            lhs.span(),
+            right.ty(),
+            rhs.span(),
+        ))
+    }
+
+    /// Evaluates a call expression.
+    fn evaluate_call_expr(&mut self, expr: &CallExpr) -> Result<Value, Diagnostic> {
+        let target = expr.target();
+        let f = STDLIB
+            .function(target.as_str())
+            .ok_or_else(|| unknown_function(target.as_str(), target.span()))?;
+
+        if f.minimum_version() > self.context.version() {
+            return Err(unsupported_function(target.as_str(), target.span()));
+        }
+
+        let arguments = expr
+            .arguments()
+            .map(|expr| {
+                Ok(CallArgument {
+                    value: self.evaluate_expr(&expr)?,
+                    span: expr.span(),
+                })
+            })
+            .collect::<Result<Vec<_>, Diagnostic>>()?;
+
+        let argument_types = arguments
+            .iter()
+            .map(|a| binding_type(self.context.types_mut(), &a.value, false))
+            .collect::<Vec<_>>();
+        let bound = f
+            .bind(self.context.types_mut(), &argument_types)
+            .or_else(|e| {
+                // Runtime values do not retain the optional qualifiers of their declared
+                // types, so retry the binding as if the arguments were optional
+                let argument_types = arguments
+                    .iter()
+                    .map(|a| binding_type(self.context.types_mut(), &a.value, true))
+                    .collect::<Vec<_>>();
+                f.bind(self.context.types_mut(), &argument_types)
+                    .map_err(|_| e)
+            });
+
+        match bound {
+            Ok(return_type) => stdlib::call(
+                target.as_str(),
+                CallContext::new(&mut self.context, expr.span(), &arguments, return_type),
+            ),
+            Err(e) => Err(function_bind_failed(
+                &target,
+                e,
+                &arguments,
+                expr.span(),
+                self.context.types(),
+            )),
+        }
+    }
+
+    /// Evaluates an index expression.
+    fn evaluate_index_expr(&mut self, expr: &IndexExpr) -> Result<Value, Diagnostic> {
+        let (target_expr, index_expr) = expr.operands();
+        let target = self.evaluate_expr(&target_expr)?;
+        let index = self.evaluate_expr(&index_expr)?;
+
+        match &target {
+            Value::Compound(crate::CompoundValue::Array(array)) => {
+                let i = index.as_integer().ok_or_else(|| {
+                    type_mismatch(
+                        self.context.types(),
+                        PrimitiveTypeKind::Integer.into(),
+                        index.ty(),
+                        index_expr.span(),
+                    )
+                })?;
+
+                usize::try_from(i)
+                    .ok()
+                    .and_then(|i| array.elements().get(i))
+                    .cloned()
+                    .ok_or_else(|| {
+                        array_index_out_of_range(
+                            i,
+                            array.len(),
+                            index_expr.span(),
+                            target_expr.span(),
+                        )
+                    })
+            }
+            Value::Compound(crate::CompoundValue::Map(map)) => {
+                let key_ty = self
+                    .context
+                    .types()
+                    .type_definition(match map.ty() {
+                        Type::Compound(ty) => ty.definition(),
+                        _ => unreachable!("map should have a compound type"),
+                    })
+                    .as_map()
+                    .expect("type should be a map")
+                    .key_type();
+
+                let key = match index.coerce(self.context.types(), key_ty) {
+                    Ok(Value::Primitive(key)) => key,
+                    _ => {
+                        return Err(type_mismatch(
+                            self.context.types(),
+                            key_ty,
+                            index.ty(),
+                            index_expr.span(),
+                        ));
+                    }
+                };
+
+                map.get(&key)
+                    .cloned()
+                    .ok_or_else(|| map_key_not_found(&key, index_expr.span()))
+            }
+            _ => Err(cannot_index(
+                self.context.types(),
+                target.ty(),
+                target_expr.span(),
+            )),
+        }
+    }
+
+    /// Evaluates an access expression.
+    fn evaluate_access_expr(&mut self, expr: &AccessExpr) -> Result<Value, Diagnostic> {
+        let (target_expr, name) = expr.operands();
+        let target = self.evaluate_expr(&target_expr)?;
+
+        match &target {
+            Value::Compound(crate::CompoundValue::Pair(pair)) => match name.as_str() {
+                "left" => Ok(pair.left().clone()),
+                "right" => Ok(pair.right().clone()),
+                _ => Err(not_a_pair_accessor(&name)),
+            },
+            Value::Compound(crate::CompoundValue::Struct(s)) => s
+                .members()
+                .get(name.as_str())
+                .cloned()
+                .ok_or_else(|| not_a_struct_member(s.name(), &name)),
+            Value::Compound(crate::CompoundValue::Object(object)) => object
+                .members()
+                .get(name.as_str())
+                .cloned()
+                .ok_or_else(|| not_an_object_member(&name)),
+            _ => Err(cannot_access(
+                self.context.types(),
+                target.ty(),
+                target_expr.span(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_analysis::types::StructType;
+    use wdl_ast::v1::Decl;
+    use wdl_ast::v1::DocumentItem;
+    use wdl_ast::version::V1;
+    use wdl_ast::Document;
+    use wdl_ast::SupportedVersion;
+
+    use super::*;
+
+    /// Represents a test evaluation context.
+    #[derive(Debug)]
+    pub(crate) struct TestContext {
+        /// The types collection of the context.
+        types: Types,
+        /// The names in scope.
+        names: IndexMap<String, Value>,
+        /// The `Foo` struct type.
+        foo: Type,
+    }
+
+    impl TestContext {
+        /// Creates a new test context.
+        pub(crate) fn new() -> Self {
+            let mut types = Types::new();
+            let foo = types.add_struct(StructType::new(
+                "Foo",
+                [
+                    ("a", Type::from(PrimitiveTypeKind::Integer)),
+                    ("b", Type::from(PrimitiveTypeKind::String).optional()),
+                ],
+            ));
+
+            let mut names = IndexMap::new();
+            names.insert("x".to_string(), 10.into());
+            names.insert("s".to_string(), "hello".to_string().into());
+            names.insert("n".to_string(), Value::None);
+
+            Self { types, names, foo }
+        }
+    }
+
+    impl EvaluationContext for TestContext {
+        fn version(&self) -> SupportedVersion {
+            SupportedVersion::V1(V1::Two)
+        }
+
+        fn types(&self) -> &Types {
+            &self.types
+        }
+
+        fn types_mut(&mut self) -> &mut Types {
+            &mut self.types
+        }
+
+        fn resolve_name(&self, name: &Ident) -> Result<Value, Diagnostic> {
+            self.names.get(name.as_str()).cloned().ok_or_else(|| {
+                Diagnostic::error(format!("unknown name `{name}`", name = name.as_str()))
+            })
+        }
+
+        fn resolve_type_name(&mut self, name: &Ident) -> Result<Type, Diagnostic> {
+            match name.as_str() {
+                "Foo" => Ok(self.foo),
+                _ => Err(Diagnostic::error(format!(
+                    "unknown type `{name}`",
+                    name = name.as_str()
+                ))),
+            }
+        }
+    }
+
+    /// Evaluates the given expression source in a test context.
+    pub(crate) fn eval(source: &str) -> Result<Value, Diagnostic> {
+        let source = format!("version 1.2\nworkflow test {{ Int x = {source} }}");
+        let (document, diagnostics) = Document::parse(&source);
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+        let workflow = match document.ast().into_v1().unwrap().items().next() {
+            Some(DocumentItem::Workflow(w)) => w,
+            _ => panic!("expected a workflow"),
+        };
+
+        let decl = workflow
+            .statements()
+            .next()
+            .expect("should have a statement")
+            .unwrap_bound_decl();
+        let expr = Decl::Bound(decl).expr().expect("should have an expression");
+
+        ExprEvaluator::new(TestContext::new()).evaluate_expr(&expr)
+    }
+
+    /// Evaluates the given expression source and formats the result.
+    pub(crate) fn eval_str(source: &str) -> String {
+        match eval(source) {
+            Ok(v) => v.to_string(),
+            Err(e) => format!("error: {message}", message = e.message()),
+        }
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(eval_str("true"), "true");
+        assert_eq!(eval_str("12345"), "12345");
+        assert_eq!(eval_str("-9223372036854775808"), "-9223372036854775808");
+        assert_eq!(eval_str("1.5"), "1.500000");
+        assert_eq!(eval_str(r#""foo\tbar\x41é""#), r#""foo\tbarAé""#);
+        assert_eq!(eval_str("[1, 2.5, None]"), "[1.000000, 2.500000, None]");
+        assert_eq!(eval_str("(1, \"a\")"), "(1, \"a\")");
+        assert_eq!(eval_str("{\"a\": 1, \"b\": 2}"), "{\"a\": 1, \"b\": 2}");
+        assert_eq!(eval_str("object { a: 1 }"), "object {a: 1}");
+        assert_eq!(eval_str("Foo { a: 1 }"), "Foo {a: 1, b: None}");
+        assert_eq!(
+            eval_str("Foo { b: \"x\" }"),
+            "error: struct `Foo` requires a value for member `a`"
+        );
+        assert_eq!(
+            eval_str("[1, \"a\"]"),
+            "error: type mismatch: expected type `Int`, but found type `String`"
+        );
+    }
+
+    #[test]
+    fn interpolation() {
+        assert_eq!(eval_str(r#""~{s} world ~{x + 1}""#), r#""hello world 11""#);
+        assert_eq!(eval_str(r#""[~{n}]""#), r#""[]""#);
+        assert_eq!(eval_str(r#""~{"-f " + n}""#), r#""""#);
+        assert_eq!(eval_str(r#""~{default="d" n}""#), r#""d""#);
+        assert_eq!(eval_str(r#""~{true="y" false="n" x > 1}""#), r#""y""#);
+        assert_eq!(eval_str(r#""~{sep=", " [1, 2, 3]}""#), r#""1, 2, 3""#);
+        assert_eq!(
+            eval_str(r#""~{[1]}""#),
+            "error: cannot coerce type `Array[Int]` to `String`"
+        );
+        assert_eq!(
+            eval_str("<<<\n    foo \\\n      bar\n      ~{s}\n  >>>"),
+            r#""foo bar\n  hello""#
+        );
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!(eval_str("1 + 2 * 3"), "7");
+        assert_eq!(eval_str("7 / 2"), "3");
+        assert_eq!(eval_str("7 % 2"), "1");
+        assert_eq!(eval_str("7.0 / 2"), "3.500000");
+        assert_eq!(eval_str("2 ** 10"), "1024");
+        assert_eq!(eval_str("-x"), "-10");
+        assert_eq!(eval_str("\"a\" + 1"), "\"a1\"");
+        assert_eq!(eval_str("1 / 0"), "error: attempt to divide by zero");
+        assert_eq!(
+            eval_str("9223372036854775807 + 1"),
+            "error: evaluation of arithmetic expression resulted in overflow"
+        );
+        assert_eq!(eval_str("1 == 1.0"), "true");
+        assert_eq!(eval_str("\"a\" < \"b\""), "true");
+        assert_eq!(eval_str("[1, 2] == [1, 2]"), "true");
+        assert_eq!(eval_str("n == None"), "true");
+        assert_eq!(eval_str("!(x > 1) || x == 10"), "true");
+        assert_eq!(eval_str("false && (1 / 0 == 0)"), "false");
+        assert_eq!(eval_str("if x > 5 then \"big\" else \"small\""), "\"big\"");
+        assert_eq!(
+            eval_str("true + 1"),
+            "error: addition operator is not supported for type `Boolean` and type `Int`"
+        );
+    }
+
+    #[test]
+    fn index_and_access() {
+        assert_eq!(eval_str("[1, 2, 3][1]"), "2");
+        assert_eq!(
+            eval_str("[1, 2, 3][3]"),
+            "error: array index 3 is out of range"
+        );
+        assert_eq!(eval_str("{\"a\": 1}[\"a\"]"), "1");
+        assert_eq!(
+            eval_str("{\"a\": 1}[\"b\"]"),
+            "error: the map does not contain an entry for key \"b\""
+        );
+        assert_eq!(eval_str("(1, 2).right"), "2");
+        assert_eq!(eval_str("Foo { a: 1 }.a"), "1");
+        assert_eq!(eval_str("object { a: 1 }.a"), "1");
+        assert_eq!(
+            eval_str("Foo { a: 1 }.c"),
+            "error: struct `Foo` does not have a member named `c`"
+        );
+    }
+}
@@ -0,0 +1,868 @@
+//! Implementation of evaluation for V1 tasks.
+
+use std::fs;
+use std::path;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Context;
+use indexmap::IndexMap;
+use petgraph::algo::toposort;
+use petgraph::graph::DiGraph;
+use wdl_analysis::scope::DocumentScope;
+use wdl_analysis::types::v1::AstTypeConverter;
+use wdl_analysis::types::Optional;
+use wdl_analysis::types::PrimitiveTypeKind;
+use wdl_analysis::types::Type;
+use wdl_analysis::types::Types;
+use wdl_ast::v1::CommandPart;
+use wdl_ast::v1::CommandSection;
+use wdl_ast::v1::Decl;
+use wdl_ast::v1::NameRef;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Ident;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+
+use super::ExprEvaluator;
+use crate::stdlib::unit_multiplier;
+use crate::Array;
+use crate::CompoundValue;
+use crate::Engine;
+use crate::EvaluationContext;
+use crate::EvaluationResult;
+use crate::Map;
+use crate::Object;
+use crate::Pair;
+use crate::PrimitiveValue;
+use crate::Struct;
+use crate::TaskExecutionResult;
+use crate::TaskInputs;
+use crate::TaskSpawnRequest;
+use crate::Value;
+
+/// The name of the task variable available in WDL 1.2 documents.
+const TASK_VAR_NAME: &str = "task";
+
+/// The name of the directory, relative to the task's root, used as the
+/// working directory of the task's command.
+const WORK_DIR_NAME: &str = "work";
+
+/// The name of the directory, relative to the task's root, used for temporary
+/// files created during evaluation.
+const TEMP_DIR_NAME: &str = "tmp";
+
+/// The default amount of memory, in bytes, for a task.
+const DEFAULT_MEMORY: i64 = 2 * 1024 * 1024 * 1024;
+
+/// Creates an "unknown name" diagnostic.
+fn unknown_name(name: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("unknown name `{name}`")).with_highlight(span)
+}
+
+/// Creates an "unknown type" diagnostic.
+fn unknown_type(name: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("unknown type name `{name}`")).with_highlight(span)
+}
+
+/// Creates a "missing input" diagnostic.
+fn missing_input(task: &str, name: &Ident) -> Diagnostic {
+    Diagnostic::error(format!(
+        "missing a value for required input `{name}` of task `{task}`",
+        name = name.as_str()
+    ))
+    .with_label("this input is required", name.span())
+}
+
+/// Creates a "declaration coercion failed" diagnostic.
+fn decl_coercion_failed(name: &Ident, error: anyhow::Error) -> Diagnostic {
+    Diagnostic::error(format!(
+        "failed to assign a value to `{name}`: {error:#}",
+        name = name.as_str()
+    ))
+    .with_highlight(name.span())
+}
+
+/// Represents the evaluation context of a task.
+struct TaskEvaluationContext<'a> {
+    /// The version of the document being evaluated.
+    version: SupportedVersion,
+    /// The types collection of the engine.
+    types: &'a mut Types,
+    /// The struct types of the document.
+    structs: &'a IndexMap<String, Type>,
+    /// The names in scope.
+    names: &'a IndexMap<String, Value>,
+    /// The working directory of the task, if available.
+    work_dir: Option<&'a Path>,
+    /// The temporary directory of the task.
+    temp_dir: &'a Path,
+    /// The value of the task's standard output, if available.
+    stdout: Option<&'a Value>,
+    /// The value of the task's standard error, if available.
+    stderr: Option<&'a Value>,
+}
+
+impl EvaluationContext for TaskEvaluationContext<'_> {
+    fn version(&self) -> SupportedVersion {
+        self.version
+    }
+
+    fn types(&self) -> &Types {
+        self.types
+    }
+
+    fn types_mut(&mut self) -> &mut Types {
+        self.types
+    }
+
+    fn resolve_name(&self, name: &Ident) -> Result<Value, Diagnostic> {
+        self.names
+            .get(name.as_str())
+            .cloned()
+            .ok_or_else(|| unknown_name(name.as_str(), name.span()))
+    }
+
+    fn resolve_type_name(&mut self, name: &Ident) -> Result<Type, Diagnostic> {
+        self.structs
+            .get(name.as_str())
+            .copied()
+            .ok_or_else(|| unknown_type(name.as_str(), name.span()))
+    }
+
+    fn work_dir(&self) -> Option<&Path> {
+        self.work_dir
+    }
+
+    fn temp_dir(&self) -> Option<&Path> {
+        Some(self.temp_dir)
+    }
+
+    fn stdout(&self) -> Option<&Value> {
+        self.stdout
+    }
+
+    fn stderr(&self) -> Option<&Value> {
+        self.stderr
+    }
+}
+
+/// Represents a task that has been evaluated.
+#[derive(Debug)]
+pub struct EvaluatedTask {
+    /// The exit code of the task's command.
+    exit_code: i32,
+    /// The working directory of the task's command.
+    work_dir: PathBuf,
+    /// The path to the standard output of the task's command.
+    stdout: PathBuf,
+    /// The path to the standard error of the task's command.
+    stderr: PathBuf,
+    /// The evaluated outputs of the task.
+    outputs: IndexMap<String, Value>,
+}
+
+impl EvaluatedTask {
+    /// Gets the exit code of the task's command.
+    pub fn exit_code(&self) -> i32 {
+        self.exit_code
+    }
+
+    /// Gets the working directory of the task's command.
+    pub fn work_dir(&self) -> &Path {
+        &self.work_dir
+    }
+
+    /// Gets the path to the standard output of the task's command.
+    pub fn stdout(&self) -> &Path {
+        &self.stdout
+    }
+
+    /// Gets the path to the standard error of the task's command.
+    pub fn stderr(&self) -> &Path {
+        &self.stderr
+    }
+
+    /// Gets the evaluated outputs of the task.
+    pub fn outputs(&self) -> &IndexMap<String, Value> {
+        &self.outputs
+    }
+}
+
+/// Represents a task that has been prepared for execution.
+///
+/// This is the state of a task's evaluation between the evaluation of its
+/// command and the evaluation of its outputs.
+pub(crate) struct PreparedTask {
+    /// The version of the document containing the task.
+    version: SupportedVersion,
+    /// The name of the task.
+    name: String,
+    /// The task being evaluated.
+    task: TaskDefinition,
+    /// The struct types of the document containing the task.
+    structs: IndexMap<String, Type>,
+    /// The evaluated names of the task.
+    names: IndexMap<String, Value>,
+    /// The evaluated requirements of the task.
+    requirements: Arc<IndexMap<String, Value>>,
+    /// The root directory of the task.
+    root: PathBuf,
+    /// The working directory of the task's command.
+    work_dir: PathBuf,
+    /// The temporary directory of the task.
+    temp_dir: PathBuf,
+}
+
+/// Represents a WDL V1 task evaluator.
+#[derive(Debug)]
+pub struct TaskEvaluator<'a> {
+    /// The engine used for evaluation.
+    engine: &'a mut Engine,
+}
+
+impl<'a> TaskEvaluator<'a> {
+    /// Constructs a new task evaluator with the given engine.
+    pub fn new(engine: &'a mut Engine) -> Self {
+        Self { engine }
+    }
+
+    /// Evaluates the given task.
+    ///
+    /// The document scope must be the result of analyzing the document
+    /// containing the task; the document is expected to have no errors.
+    ///
+    /// Relative `File` and `Directory` input paths are resolved against the
+    /// current directory.
+    ///
+    /// The task's files, including the working directory of its command, are
+    /// created in the given root directory.
+    pub async fn evaluate(
+        &mut self,
+        scope: &DocumentScope,
+        task: &TaskDefinition,
+        inputs: &TaskInputs,
+        root: &Path,
+    ) -> EvaluationResult<EvaluatedTask> {
+        let (prepared, request) = self.prepare(scope, task, inputs, root)?;
+        let result = self.engine.spawn(request).await;
+        self.finish(prepared, result)
+    }
+
+    /// Prepares the given task for execution.
+    ///
+    /// This evaluates the task's inputs, private declarations, requirements,
+    /// hints, and command.
+    ///
+    /// Returns the prepared task and the request to send to the task execution
+    /// backend.
+    pub(crate) fn prepare(
+        &mut self,
+        scope: &DocumentScope,
+        task: &TaskDefinition,
+        inputs: &TaskInputs,
+        root: &Path,
+    ) -> EvaluationResult<(PreparedTask, TaskSpawnRequest)> {
+        let version = scope
+            .version()
+            .context("the document's version is not supported")?;
+        let name = task.name();
+        let task_name = name.as_str();
+
+        let structs = import_structs(self.engine.types_mut(), scope);
+
+        // Check for unknown inputs
+        let input_decls: Vec<Decl> = task
+            .input()
+            .map(|s| s.declarations().collect())
+            .unwrap_or_default();
+        for (name, _) in inputs.iter() {
+            if !input_decls.iter().any(|d| d.name().as_str() == name) {
+                return Err(
+                    anyhow!("task `{task_name}` does not have an input named `{name}`").into(),
+                );
+            }
+        }
+
+        let root = &path::absolute(root).with_context(|| {
+            format!(
+                "failed to make path `{root}` absolute",
+                root = root.display()
+            )
+        })?;
+        let work_dir = root.join(WORK_DIR_NAME);
+        let temp_dir = root.join(TEMP_DIR_NAME);
+        for dir in [&work_dir, &temp_dir] {
+            fs::create_dir_all(dir).with_context(|| {
+                format!("failed to create directory `{dir}`", dir = dir.display())
+            })?;
+        }
+
+        let cwd = std::env::current_dir().context("failed to determine the current directory")?;
+
+        // Evaluate the inputs and private declarations in dependency order
+        let mut names = IndexMap::new();
+        let decls: Vec<(Decl, bool)> = input_decls
+            .into_iter()
+            .map(|d| (d, true))
+            .chain(task.declarations().map(|d| (Decl::Bound(d), false)))
+            .collect();
+        for index in dependency_order(decls.iter().map(|(d, _)| d))? {
+            let (decl, is_input) = &decls[index];
+            let name = decl.name();
+            let ty = convert_type(self.engine.types_mut(), &structs, decl)?;
+
+            let value = match (is_input, inputs.get(name.as_str())) {
+                (true, Some(value)) => {
+                    let value = value
+                        .coerce(self.engine.types(), ty)
+                        .map_err(|e| decl_coercion_failed(&name, e))?;
+                    resolve_paths(&value, &mut |path| Ok(join_path(&cwd, path)))?
+                }
+                _ => match decl.expr() {
+                    Some(expr) => {
+                        let mut evaluator = ExprEvaluator::new(TaskEvaluationContext {
+                            version,
+                            types: self.engine.types_mut(),
+                            structs: &structs,
+                            names: &names,
+                            work_dir: Some(&cwd),
+                            temp_dir: &temp_dir,
+                            stdout: None,
+                            stderr: None,
+                        });
+                        evaluator
+                            .evaluate_expr(&expr)?
+                            .coerce(self.engine.types(), ty)
+                            .map_err(|e| decl_coercion_failed(&name, e))?
+                    }
+                    None if ty.is_optional() => Value::None,
+                    None => return Err(missing_input(task_name, &name).into()),
+                },
+            };
+
+            names.insert(name.as_str().to_string(), value);
+        }
+
+        // Evaluate the requirements and hints of the task
+        let mut requirements = IndexMap::new();
+        let mut hints = IndexMap::new();
+        {
+            let mut evaluator = ExprEvaluator::new(TaskEvaluationContext {
+                version,
+                types: self.engine.types_mut(),
+                structs: &structs,
+                names: &names,
+                work_dir: Some(&cwd),
+                temp_dir: &temp_dir,
+                stdout: None,
+                stderr: None,
+            });
+
+            for item in task.runtime().into_iter().flat_map(|s| s.items()) {
+                requirements.insert(
+                    item.name().as_str().to_string(),
+                    evaluator.evaluate_expr(&item.expr())?,
+                );
+            }
+
+            for item in task.requirements().into_iter().flat_map(|s| s.items()) {
+                requirements.insert(
+                    item.name().as_str().to_string(),
+                    evaluator.evaluate_expr(&item.expr())?,
+                );
+            }
+
+            for item in task.hints().into_iter().flat_map(|s| s.items()) {
+                hints.insert(
+                    item.name().as_str().to_string(),
+                    evaluator.evaluate_expr(&item.expr())?,
+                );
+            }
+        }
+
+        // Add the task variable for WDL 1.2 documents
+        if has_task_variable(version) {
+            names.insert(
+                TASK_VAR_NAME.to_string(),
+                task_variable(task_name, root, &requirements, None)?,
+            );
+        }
+
+        // Evaluate the command
+        let command = match task.command() {
+            Some(section) => {
+                let mut evaluator = ExprEvaluator::new(TaskEvaluationContext {
+                    version,
+                    types: self.engine.types_mut(),
+                    structs: &structs,
+                    names: &names,
+                    work_dir: Some(&work_dir),
+                    temp_dir: &temp_dir,
+                    stdout: None,
+                    stderr: None,
+                });
+                evaluate_command(&mut evaluator, &section)?
+            }
+            None => String::new(),
+        };
+
+        let requirements = Arc::new(requirements);
+        let request = TaskSpawnRequest::new(
+            root,
+            &work_dir,
+            command,
+            requirements.clone(),
+            Arc::new(hints),
+        );
+
+        Ok((
+            PreparedTask {
+                version,
+                name: task_name.to_string(),
+                task: task.clone(),
+                structs,
+                names,
+                requirements,
+                root: root.clone(),
+                work_dir,
+                temp_dir,
+            },
+            request,
+        ))
+    }
+
+    /// Finishes the evaluation of a prepared task given the result of its
+    /// execution.
+    ///
+    /// This evaluates the task's outputs.
+    pub(crate) fn finish(
+        &mut self,
+        prepared: PreparedTask,
+        result: anyhow::Result<TaskExecutionResult>,
+    ) -> EvaluationResult<EvaluatedTask> {
+        let PreparedTask {
+            version,
+            name: task_name,
+            task,
+            structs,
+            mut names,
+            requirements,
+            root,
+            work_dir,
+            temp_dir,
+        } = prepared;
+
+        let result = result.with_context(|| format!("failed to execute task `{task_name}`"))?;
+        if !is_allowed_exit_code(&requirements, result.exit_code) {
+            return Err(anyhow!(
+                "task `{task_name}` failed: command exited with status code {code} (see \
+                 `{stderr}` for the standard error of the command)",
+                code = result.exit_code,
+                stderr = result.stderr.display()
+            )
+            .into());
+        }
+
+        if has_task_variable(version) {
+            names.insert(
+                TASK_VAR_NAME.to_string(),
+                task_variable(&task_name, &root, &requirements, Some(result.exit_code))?,
+            );
+        }
+
+        // Evaluate the outputs in dependency order
+        let stdout = PrimitiveValue::new_file(result.stdout.to_string_lossy()).into();
+        let stderr = PrimitiveValue::new_file(result.stderr.to_string_lossy()).into();
+        let output_decls: Vec<Decl> = task
+            .output()
+            .map(|s| s.declarations().map(Decl::Bound).collect())
+            .unwrap_or_default();
+        let mut outputs = IndexMap::new();
+        for index in dependency_order(output_decls.iter())? {
+            let decl = &output_decls[index];
+            let name = decl.name();
+            let ty = convert_type(self.engine.types_mut(), &structs, decl)?;
+
+            let mut evaluator = ExprEvaluator::new(TaskEvaluationContext {
+                version,
+                types: self.engine.types_mut(),
+                structs: &structs,
+                names: &names,
+                work_dir: Some(&work_dir),
+                temp_dir: &temp_dir,
+                stdout: Some(&stdout),
+                stderr: Some(&stderr),
+            });
+
+            let value = evaluator
+                .evaluate_expr(&decl.expr().expect("output should have an expression"))?
+                .coerce(self.engine.types(), ty)
+                .map_err(|e| decl_coercion_failed(&name, e))?;
+            let value = resolve_output_paths(&value, ty, &work_dir)
+                .map_err(|e| decl_coercion_failed(&name, e))?;
+
+            names.insert(name.as_str().to_string(), value.clone());
+            outputs.insert(name.as_str().to_string(), value);
+        }
+
+        // Sort the outputs by their declaration order
+        outputs.sort_by_cached_key(|name, _| {
+            output_decls
+                .iter()
+                .position(|d| d.name().as_str() == name)
+                .expect("output should exist")
+        });
+
+        Ok(EvaluatedTask {
+            exit_code: result.exit_code,
+            work_dir,
+            stdout: result.stdout,
+            stderr: result.stderr,
+            outputs,
+        })
+    }
+}
+
+/// Determines if the `task` variable is available to tasks in the given
+/// version.
+fn has_task_variable(version: SupportedVersion) -> bool {
+    version >= SupportedVersion::V1(wdl_ast::version::V1::Two)
+}
+
+/// Imports the struct types of a document into the given types collection.
+///
+/// Returns a map of struct name to imported type.
+pub(super) fn import_structs(types: &mut Types, scope: &DocumentScope) -> IndexMap<String, Type> {
+    scope
+        .structs()
+        .filter_map(|(name, s)| Some((name.to_string(), types.import(scope.types(), s.ty()?))))
+        .collect()
+}
+
+/// Converts the declared type of a declaration to a type in the given types
+/// collection.
+pub(super) fn convert_type(
+    types: &mut Types,
+    structs: &IndexMap<String, Type>,
+    decl: &Decl,
+) -> Result<Type, Diagnostic> {
+    AstTypeConverter::new(types, |name, span| {
+        structs
+            .get(name)
+            .copied()
+            .ok_or_else(|| unknown_type(name, span))
+    })
+    .convert_type(&decl.ty())
+}
+
+/// Determines the evaluation order of the given declarations based on the
+/// names referenced by their expressions.
+///
+/// Returns the indexes of the declarations in the order they should be
+/// evaluated.
+pub(super) fn dependency_order<'a>(
+    decls: impl Iterator<Item = &'a Decl>,
+) -> anyhow::Result<Vec<usize>> {
+    let decls: Vec<_> = decls.collect();
+    let mut graph = DiGraph::<usize, ()>::new();
+    let nodes: Vec<_> = (0..decls.len()).map(|i| graph.add_node(i)).collect();
+
+    for (from, decl) in decls.iter().enumerate() {
+        for r in decl
+            .expr()
+            .into_iter()
+            .flat_map(|e| e.syntax().descendants().filter_map(NameRef::cast))
+        {
+            let name = r.name();
+            if let Some(to) = decls
+                .iter()
+                .position(|d| d.name().as_str() == name.as_str())
+            {
+                graph.update_edge(nodes[to], nodes[from], ());
+            }
+        }
+    }
+
+    match toposort(&graph, None) {
+        Ok(order) => Ok(order.into_iter().map(|n| graph[n]).collect()),
+        Err(cycle) => bail!(
+            "declaration `{name}` is part of a reference cycle",
+            name = decls[graph[cycle.node_id()]].name().as_str()
+        ),
+    }
+}
+
+/// Evaluates the command section of a task.
+///
+/// Common leading whitespace is removed from the lines of the command before
+/// any placeholders are evaluated; placeholders are treated as non-whitespace
+/// when determining the indentation of a line.
+fn evaluate_command<C: EvaluationContext>(
+    evaluator: &mut ExprEvaluator<C>,
+    section: &CommandSection,
+) -> Result<String, Diagnostic> {
+    /// Represents a segment of a line of the command.
+    enum Segment {
+        /// The segment is literal text.
+        Text(String),
+        /// The segment is a placeholder.
+        Placeholder(wdl_ast::v1::Placeholder),
+    }
+
+    // Split the command into lines of segments
+    let mut lines: Vec<Vec<Segment>> = vec![Vec::new()];
+    for part in section.parts() {
+        match part {
+            CommandPart::Text(text) => {
+                for (i, line) in text.as_str().split('\n').enumerate() {
+                    if i > 0 {
+                        lines.push(Vec::new());
+                    }
+
+                    if !line.is_empty() {
+                        lines
+                            .last_mut()
+                            .unwrap()
+                            .push(Segment::Text(line.to_string()));
+                    }
+                }
+            }
+            CommandPart::Placeholder(p) => lines.last_mut().unwrap().push(Segment::Placeholder(p)),
+        }
+    }
+
+    /// Determines if a line consists only of whitespace.
+    fn is_blank(line: &[Segment]) -> bool {
+        line.iter().all(|s| match s {
+            Segment::Text(t) => t.trim().is_empty(),
+            Segment::Placeholder(_) => false,
+        })
+    }
+
+    // Remove a blank first line (i.e. the remainder of the opening line) and a
+    // blank last line (i.e. the line containing the closing delimiter)
+    if lines.len() > 1 && is_blank(&lines[0]) {
+        lines.remove(0);
+    }
+
+    if lines.len() > 1 && is_blank(lines.last().unwrap()) {
+        lines.pop();
+    }
+
+    // Determine the common indentation of the non-blank lines
+    let indent = lines
+        .iter()
+        .filter(|l| !is_blank(l))
+        .map(|l| match l.first() {
+            Some(Segment::Text(t)) => t.len() - t.trim_start().len(),
+            _ => 0,
+        })
+        .min()
+        .unwrap_or(0);
+
+    let mut command = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            command.push('\n');
+        }
+
+        for (j, segment) in line.iter().enumerate() {
+            match segment {
+                Segment::Text(t) if j == 0 => {
+                    let whitespace = t.len() - t.trim_start().len();
+                    command.push_str(&t[indent.min(whitespace)..]);
+                }
+                Segment::Text(t) => command.push_str(t),
+                Segment::Placeholder(p) => evaluator.evaluate_placeholder(p, &mut command)?,
+            }
+        }
+    }
+
+    command.push('\n');
+    Ok(command)
+}
+
+/// Determines if the given exit code is allowed by the task's requirements.
+///
+/// The allowed exit codes are specified by the `return_codes` requirement (or
+/// `returnCodes` in a `runtime` section); by default, only `0` is allowed.
+fn is_allowed_exit_code(requirements: &IndexMap<String, Value>, code: i32) -> bool {
+    let allowed = requirements
+        .get("return_codes")
+        .or_else(|| requirements.get("returnCodes"));
+
+    match allowed {
+        Some(Value::Primitive(PrimitiveValue::Integer(allowed))) => *allowed == code as i64,
+        Some(Value::Primitive(PrimitiveValue::String(s))) if s.as_str() == "*" => true,
+        Some(Value::Compound(CompoundValue::Array(array))) => array
+            .elements()
+            .iter()
+            .any(|v| v.as_integer() == Some(code as i64)),
+        _ => code == 0,
+    }
+}
+
+/// Creates the value of the `task` variable available to WDL 1.2 tasks.
+fn task_variable(
+    name: &str,
+    root: &Path,
+    requirements: &IndexMap<String, Value>,
+    return_code: Option<i32>,
+) -> anyhow::Result<Value> {
+    let container = requirements
+        .get("container")
+        .or_else(|| requirements.get("docker"))
+        .cloned()
+        .unwrap_or(Value::None);
+    let cpu = requirements
+        .get("cpu")
+        .and_then(|v| v.as_float().or_else(|| v.as_integer().map(|i| i as f64)))
+        .unwrap_or(1.0);
+    let memory = match requirements.get("memory") {
+        Some(Value::Primitive(PrimitiveValue::Integer(v))) => *v,
+        Some(Value::Primitive(PrimitiveValue::String(s))) => parse_memory(s)?,
+        _ => DEFAULT_MEMORY,
+    };
+
+    let mut members = IndexMap::new();
+    members.insert("name".to_string(), name.to_string().into());
+    members.insert("id".to_string(), root.to_string_lossy().to_string().into());
+    members.insert("container".to_string(), container);
+    members.insert("cpu".to_string(), cpu.into());
+    members.insert("memory".to_string(), memory.into());
+    members.insert("attempt".to_string(), 0.into());
+    members.insert("end_time".to_string(), Value::None);
+    members.insert(
+        "return_code".to_string(),
+        return_code
+            .map(|c| (c as i64).into())
+            .unwrap_or(Value::None),
+    );
+    members.insert("meta".to_string(), Object::new(IndexMap::new()).into());
+    members.insert(
+        "parameter_meta".to_string(),
+        Object::new(IndexMap::new()).into(),
+    );
+    members.insert("ext".to_string(), Object::new(IndexMap::new()).into());
+    Ok(Object::new(members).into())
+}
+
+/// Parses a memory string (e.g. `2 GiB`) into a number of bytes.
+fn parse_memory(s: &str) -> anyhow::Result<i64> {
+    let s = s.trim();
+    let (number, unit) = s.split_at(
+        s.find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(s.len()),
+    );
+
+    let number: f64 = number
+        .parse()
+        .with_context(|| format!("invalid memory specification `{s}`"))?;
+    let unit = unit.trim();
+    let multiplier = if unit.is_empty() {
+        1.0
+    } else {
+        unit_multiplier(unit).with_context(|| format!("invalid memory specification `{s}`"))?
+    };
+
+    Ok((number * multiplier) as i64)
+}
+
+/// Joins a path to a base directory if the path is relative.
+pub(super) fn join_path(base: &Path, path: &str) -> String {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return path.to_string_lossy().into_owned();
+    }
+
+    base.join(path).to_string_lossy().into_owned()
+}
+
+/// Resolves the `File` and `Directory` paths of a value with the given
+/// callback.
+pub(super) fn resolve_paths(
+    value: &Value,
+    resolve: &mut impl FnMut(&str) -> anyhow::Result<String>,
+) -> anyhow::Result<Value> {
+    match value {
+        Value::None => Ok(Value::None),
+        Value::Primitive(PrimitiveValue::File(path)) => {
+            Ok(PrimitiveValue::new_file(resolve(path)?).into())
+        }
+        Value::Primitive(PrimitiveValue::Directory(path)) => {
+            Ok(PrimitiveValue::new_directory(resolve(path)?).into())
+        }
+        Value::Primitive(_) => Ok(value.clone()),
+        Value::Compound(v) => Ok(match v {
+            CompoundValue::Pair(v) => Pair::new_unchecked(
+                v.ty(),
+                resolve_paths(v.left(), resolve)?,
+                resolve_paths(v.right(), resolve)?,
+            )
+            .into(),
+            CompoundValue::Array(v) => Array::new_unchecked(
+                v.ty(),
+                v.elements()
+                    .iter()
+                    .map(|e| resolve_paths(e, resolve))
+                    .collect::<anyhow::Result<_>>()?,
+            )
+            .into(),
+            CompoundValue::Map(v) => Map::new_unchecked(
+                v.ty(),
+                v.elements()
+                    .iter()
+                    .map(|(k, v)| Ok((k.clone(), resolve_paths(v, resolve)?)))
+                    .collect::<anyhow::Result<_>>()?,
+            )
+            .into(),
+            CompoundValue::Object(v) => Object::new(
+                v.members()
+                    .iter()
+                    .map(|(k, v)| Ok((k.clone(), resolve_paths(v, resolve)?)))
+                    .collect::<anyhow::Result<_>>()?,
+            )
+            .into(),
+            CompoundValue::Struct(v) => Struct::new_unchecked(
+                v.ty(),
+                v.name().to_string(),
+                v.members()
+                    .iter()
+                    .map(|(k, v)| Ok((k.clone(), resolve_paths(v, resolve)?)))
+                    .collect::<anyhow::Result<_>>()?,
+            )
+            .into(),
+        }),
+    }
+}
+
+/// Resolves the `File` and `Directory` paths of an output value relative to
+/// the task's working directory.
+///
+/// An output of an optional `File` or `Directory` type that does not exist is
+/// `None`; otherwise, every path in the value must exist.
+fn resolve_output_paths(value: &Value, ty: Type, work_dir: &Path) -> anyhow::Result<Value> {
+    if let Value::Primitive(PrimitiveValue::File(path) | PrimitiveValue::Directory(path)) = value {
+        let optional = ty.is_optional()
+            && matches!(ty, Type::Primitive(ty) if matches!(ty.kind(), PrimitiveTypeKind::File | PrimitiveTypeKind::Directory));
+        if optional && !Path::new(&join_path(work_dir, path)).exists() {
+            return Ok(Value::None);
+        }
+    }
+
+    resolve_paths(value, &mut |path| {
+        let resolved = join_path(work_dir, path);
+        if !Path::new(&resolved).exists() {
+            bail!("path `{path}` does not exist");
+        }
+
+        Ok(resolved)
+    })
+}
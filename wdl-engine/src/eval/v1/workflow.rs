@@ -0,0 +1,967 @@
+//! Implementation of evaluation for V1 workflows.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fs;
+use std::path;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::Context;
+use futures::future::try_join_all;
+use futures::future::LocalBoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::FutureExt;
+use futures::StreamExt;
+use indexmap::IndexMap;
+use wdl_analysis::types::CallType;
+use wdl_analysis::types::CompoundTypeDef;
+use wdl_analysis::types::Optional;
+use wdl_analysis::types::Type;
+use wdl_analysis::types::Types;
+use wdl_analysis::AnalysisResult;
+use wdl_ast::v1::BoundDecl;
+use wdl_ast::v1::CallInputItem;
+use wdl_ast::v1::CallStatement;
+use wdl_ast::v1::ConditionalStatement;
+use wdl_ast::v1::Decl;
+use wdl_ast::v1::DocumentItem;
+use wdl_ast::v1::Expr;
+use wdl_ast::v1::NameRef;
+use wdl_ast::v1::ScatterStatement;
+use wdl_ast::v1::WorkflowDefinition;
+use wdl_ast::v1::WorkflowStatement;
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Diagnostic;
+use wdl_ast::Ident;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxNode;
+
+use super::task::convert_type;
+use super::task::dependency_order;
+use super::task::import_structs;
+use super::task::join_path;
+use super::task::resolve_paths;
+use super::ExprEvaluator;
+use super::TaskEvaluator;
+use crate::Array;
+use crate::Engine;
+use crate::EvaluationContext;
+use crate::EvaluationError;
+use crate::EvaluationResult;
+use crate::Object;
+use crate::TaskInputs;
+use crate::Value;
+use crate::WorkflowInputs;
+
+/// The name of the directory, relative to a workflow's root, in which the
+/// workflow's calls are executed.
+const CALLS_DIR_NAME: &str = "calls";
+
+/// The name of the directory, relative to a workflow's root, used for
+/// temporary files created during evaluation.
+const TEMP_DIR_NAME: &str = "tmp";
+
+/// Creates an "unknown name" diagnostic.
+fn unknown_name(name: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("unknown name `{name}`")).with_highlight(span)
+}
+
+/// Creates an "unknown type" diagnostic.
+fn unknown_type(name: &str, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("unknown type name `{name}`")).with_highlight(span)
+}
+
+/// Creates a "missing input" diagnostic.
+fn missing_input(workflow: &str, name: &Ident) -> Diagnostic {
+    Diagnostic::error(format!(
+        "missing a value for required input `{name}` of workflow `{workflow}`",
+        name = name.as_str()
+    ))
+    .with_label("this input is required", name.span())
+}
+
+/// Creates a "declaration coercion failed" diagnostic.
+fn decl_coercion_failed(name: &Ident, error: anyhow::Error) -> Diagnostic {
+    Diagnostic::error(format!(
+        "failed to assign a value to `{name}`: {error:#}",
+        name = name.as_str()
+    ))
+    .with_highlight(name.span())
+}
+
+/// Creates a "scatter not an array" diagnostic.
+fn scatter_not_array(span: Span) -> Diagnostic {
+    Diagnostic::error("the scatter expression did not evaluate to an array").with_highlight(span)
+}
+
+/// Creates a "conditional not a boolean" diagnostic.
+fn conditional_not_boolean(span: Span) -> Diagnostic {
+    Diagnostic::error("the conditional expression did not evaluate to a boolean")
+        .with_highlight(span)
+}
+
+/// Represents the evaluation context of a workflow.
+struct WorkflowEvaluationContext<'a> {
+    /// The version of the document being evaluated.
+    version: SupportedVersion,
+    /// The types collection of the engine.
+    types: &'a mut Types,
+    /// The struct types of the document.
+    structs: &'a IndexMap<String, Type>,
+    /// The names in scope.
+    names: &'a IndexMap<String, Value>,
+    /// The current working directory.
+    work_dir: &'a Path,
+    /// The temporary directory of the workflow.
+    temp_dir: &'a Path,
+}
+
+impl EvaluationContext for WorkflowEvaluationContext<'_> {
+    fn version(&self) -> SupportedVersion {
+        self.version
+    }
+
+    fn types(&self) -> &Types {
+        self.types
+    }
+
+    fn types_mut(&mut self) -> &mut Types {
+        self.types
+    }
+
+    fn resolve_name(&self, name: &Ident) -> Result<Value, Diagnostic> {
+        self.names
+            .get(name.as_str())
+            .cloned()
+            .ok_or_else(|| unknown_name(name.as_str(), name.span()))
+    }
+
+    fn resolve_type_name(&mut self, name: &Ident) -> Result<Type, Diagnostic> {
+        self.structs
+            .get(name.as_str())
+            .copied()
+            .ok_or_else(|| unknown_type(name.as_str(), name.span()))
+    }
+
+    fn work_dir(&self) -> Option<&Path> {
+        Some(self.work_dir)
+    }
+
+    fn temp_dir(&self) -> Option<&Path> {
+        Some(self.temp_dir)
+    }
+}
+
+/// Represents a workflow that has been evaluated.
+#[derive(Debug)]
+pub struct EvaluatedWorkflow {
+    /// The evaluated outputs of the workflow.
+    outputs: IndexMap<String, Value>,
+}
+
+impl EvaluatedWorkflow {
+    /// Gets the evaluated outputs of the workflow.
+    pub fn outputs(&self) -> &IndexMap<String, Value> {
+        &self.outputs
+    }
+}
+
+/// Represents a WDL V1 workflow evaluator.
+#[derive(Debug)]
+pub struct WorkflowEvaluator<'a> {
+    /// The engine used for evaluation.
+    engine: &'a mut Engine,
+}
+
+impl<'a> WorkflowEvaluator<'a> {
+    /// Constructs a new workflow evaluator with the given engine.
+    pub fn new(engine: &'a mut Engine) -> Self {
+        Self { engine }
+    }
+
+    /// Evaluates the workflow of the given document.
+    ///
+    /// The analysis results must contain the results of analyzing the document
+    /// and every document it imports; the documents are expected to have no
+    /// errors.
+    ///
+    /// Calls that do not depend on one another are executed concurrently and
+    /// the shards of a scatter statement are executed concurrently.
+    ///
+    /// Relative `File` and `Directory` input paths are resolved against the
+    /// current directory.
+    ///
+    /// The files of the workflow's calls are created in the given root
+    /// directory.
+    pub async fn evaluate(
+        &mut self,
+        results: &[AnalysisResult],
+        document: &AnalysisResult,
+        inputs: &WorkflowInputs,
+        root: &Path,
+    ) -> EvaluationResult<EvaluatedWorkflow> {
+        let workflow = document
+            .parse_result()
+            .document()
+            .and_then(|d| d.ast().into_v1())
+            .and_then(|ast| {
+                ast.items().find_map(|i| match i {
+                    DocumentItem::Workflow(w) => Some(w),
+                    _ => None,
+                })
+            })
+            .context("the document does not contain a workflow")?;
+
+        let root = path::absolute(root).with_context(|| {
+            format!(
+                "failed to make path `{root}` absolute",
+                root = root.display()
+            )
+        })?;
+
+        let state = State {
+            engine: RefCell::new(self.engine),
+            results,
+            cwd: std::env::current_dir().context("failed to determine the current directory")?,
+        };
+
+        let outputs = state
+            .evaluate_workflow(document, &workflow, inputs, &root)
+            .await?;
+        Ok(EvaluatedWorkflow { outputs })
+    }
+}
+
+/// Represents the state of a workflow evaluation.
+struct State<'a, 'b> {
+    /// The engine used for evaluation.
+    ///
+    /// The engine is borrowed only while evaluating expressions and never
+    /// across an await point.
+    engine: RefCell<&'b mut Engine>,
+    /// The analysis results of the documents being evaluated.
+    results: &'a [AnalysisResult],
+    /// The current working directory.
+    cwd: PathBuf,
+}
+
+/// Represents a workflow being evaluated.
+struct Workflow<'a> {
+    /// The analysis result of the document containing the workflow.
+    document: &'a AnalysisResult,
+    /// The version of the document containing the workflow.
+    version: SupportedVersion,
+    /// The name of the workflow.
+    name: String,
+    /// The struct types of the document.
+    structs: IndexMap<String, Type>,
+    /// The inputs to the workflow.
+    inputs: &'a WorkflowInputs,
+    /// The root directory of the workflow.
+    root: &'a Path,
+    /// The temporary directory of the workflow.
+    temp_dir: PathBuf,
+}
+
+/// Represents a node in a workflow's dependency graph.
+enum Node {
+    /// The node is a workflow input.
+    Input(Decl),
+    /// The node is a private declaration.
+    Decl(BoundDecl),
+    /// The node is a call statement.
+    Call(CallStatement),
+    /// The node is a scatter statement.
+    Scatter(ScatterStatement),
+    /// The node is a conditional statement.
+    Conditional(ConditionalStatement),
+}
+
+impl Node {
+    /// Gets the syntax node of the graph node.
+    fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Self::Input(decl) => decl.syntax(),
+            Self::Decl(decl) => decl.syntax(),
+            Self::Call(stmt) => stmt.syntax(),
+            Self::Scatter(stmt) => stmt.syntax(),
+            Self::Conditional(stmt) => stmt.syntax(),
+        }
+    }
+
+    /// Gets the names introduced by the node into its enclosing scope.
+    ///
+    /// For scatter and conditional statements, these are the names introduced
+    /// by the statements they contain.
+    fn names(&self) -> Vec<String> {
+        /// Adds the names introduced by a workflow statement.
+        fn add_names(stmt: &WorkflowStatement, names: &mut Vec<String>) {
+            match stmt {
+                WorkflowStatement::Conditional(stmt) => {
+                    for stmt in stmt.statements() {
+                        add_names(&stmt, names);
+                    }
+                }
+                WorkflowStatement::Scatter(stmt) => {
+                    for stmt in stmt.statements() {
+                        add_names(&stmt, names);
+                    }
+                }
+                WorkflowStatement::Call(stmt) => names.push(call_name(stmt).as_str().to_string()),
+                WorkflowStatement::Declaration(decl) => {
+                    names.push(decl.name().as_str().to_string())
+                }
+            }
+        }
+
+        let mut names = Vec::new();
+        match self {
+            Self::Input(decl) => names.push(decl.name().as_str().to_string()),
+            Self::Decl(decl) => names.push(decl.name().as_str().to_string()),
+            Self::Call(stmt) => names.push(call_name(stmt).as_str().to_string()),
+            Self::Scatter(stmt) => add_names(&WorkflowStatement::Scatter(stmt.clone()), &mut names),
+            Self::Conditional(stmt) => {
+                add_names(&WorkflowStatement::Conditional(stmt.clone()), &mut names)
+            }
+        }
+
+        names
+    }
+
+    /// Gets the names referenced by the node.
+    ///
+    /// This includes names referenced by any statements nested within the node.
+    fn references(&self) -> HashSet<String> {
+        let mut references = HashSet::new();
+        for node in self.syntax().descendants() {
+            if let Some(r) = NameRef::cast(node.clone()) {
+                references.insert(r.name().as_str().to_string());
+            } else if let Some(item) = CallInputItem::cast(node.clone()) {
+                // An input without an expression implicitly references a name in scope
+                if item.expr().is_none() {
+                    references.insert(item.name().as_str().to_string());
+                }
+            } else if let Some(stmt) = CallStatement::cast(node) {
+                references.extend(stmt.after().map(|a| a.name().as_str().to_string()));
+            }
+        }
+
+        references
+    }
+}
+
+impl From<WorkflowStatement> for Node {
+    fn from(stmt: WorkflowStatement) -> Self {
+        match stmt {
+            WorkflowStatement::Conditional(stmt) => Self::Conditional(stmt),
+            WorkflowStatement::Scatter(stmt) => Self::Scatter(stmt),
+            WorkflowStatement::Call(stmt) => Self::Call(stmt),
+            WorkflowStatement::Declaration(decl) => Self::Decl(decl),
+        }
+    }
+}
+
+impl<'a> State<'a, '_> {
+    /// Evaluates a workflow.
+    ///
+    /// Returns the evaluated outputs of the workflow.
+    fn evaluate_workflow<'b>(
+        &'b self,
+        document: &'a AnalysisResult,
+        definition: &'b WorkflowDefinition,
+        inputs: &'b WorkflowInputs,
+        root: &'b Path,
+    ) -> LocalBoxFuture<'b, EvaluationResult<IndexMap<String, Value>>> {
+        async move {
+            let scope = document.scope();
+            let version = scope
+                .version()
+                .context("the document's version is not supported")?;
+            let name = definition.name();
+            let workflow_name = name.as_str();
+
+            // Check for unknown inputs
+            let input_decls: Vec<Decl> = definition
+                .input()
+                .map(|s| s.declarations().collect())
+                .unwrap_or_default();
+            for (name, _) in inputs.iter() {
+                if !input_decls.iter().any(|d| d.name().as_str() == name) {
+                    return Err(anyhow!(
+                        "workflow `{workflow_name}` does not have an input named `{name}`"
+                    )
+                    .into());
+                }
+            }
+
+            let calls: HashSet<String> = definition
+                .syntax()
+                .descendants()
+                .filter_map(CallStatement::cast)
+                .map(|s| call_name(&s).as_str().to_string())
+                .collect();
+            for (name, _) in inputs.calls() {
+                if !calls.contains(name) {
+                    return Err(anyhow!(
+                        "workflow `{workflow_name}` does not have a call named `{name}`"
+                    )
+                    .into());
+                }
+            }
+
+            let temp_dir = root.join(TEMP_DIR_NAME);
+            fs::create_dir_all(&temp_dir).with_context(|| {
+                format!(
+                    "failed to create directory `{dir}`",
+                    dir = temp_dir.display()
+                )
+            })?;
+
+            let workflow = Workflow {
+                document,
+                version,
+                name: workflow_name.to_string(),
+                structs: import_structs(self.engine.borrow_mut().types_mut(), scope),
+                inputs,
+                root,
+                temp_dir,
+            };
+
+            // Evaluate the inputs and the statements of the workflow
+            let nodes: Vec<Node> = input_decls
+                .into_iter()
+                .map(Node::Input)
+                .chain(definition.statements().map(Node::from))
+                .collect();
+            let mut names = self
+                .evaluate_nodes(&workflow, &nodes, IndexMap::new(), &[])
+                .await?;
+
+            // Evaluate the outputs in dependency order
+            let output_decls: Vec<Decl> = definition
+                .output()
+                .map(|s| s.declarations().map(Decl::Bound).collect())
+                .unwrap_or_default();
+            let mut outputs = IndexMap::new();
+            for index in dependency_order(output_decls.iter())? {
+                let decl = &output_decls[index];
+                let name = decl.name();
+                let ty = convert_type(
+                    self.engine.borrow_mut().types_mut(),
+                    &workflow.structs,
+                    decl,
+                )?;
+                let value = self
+                    .evaluate_expr(
+                        &workflow,
+                        &names,
+                        &decl.expr().expect("output should have an expression"),
+                    )?
+                    .coerce(self.engine.borrow().types(), ty)
+                    .map_err(|e| decl_coercion_failed(&name, e))?;
+
+                names.insert(name.as_str().to_string(), value.clone());
+                outputs.insert(name.as_str().to_string(), value);
+            }
+
+            // Sort the outputs by their declaration order
+            outputs.sort_by_cached_key(|name, _| {
+                output_decls
+                    .iter()
+                    .position(|d| d.name().as_str() == name)
+                    .expect("output should exist")
+            });
+
+            Ok(outputs)
+        }
+        .boxed_local()
+    }
+
+    /// Evaluates the given graph nodes of a workflow.
+    ///
+    /// The nodes are evaluated in dependency order; nodes that do not depend on
+    /// one another are evaluated concurrently.
+    ///
+    /// The given shard indexes are those of the enclosing scatter statements.
+    ///
+    /// Returns the names in scope after evaluating the nodes.
+    fn evaluate_nodes<'b>(
+        &'b self,
+        workflow: &'b Workflow<'a>,
+        nodes: &'b [Node],
+        mut names: IndexMap<String, Value>,
+        shards: &'b [usize],
+    ) -> LocalBoxFuture<'b, EvaluationResult<IndexMap<String, Value>>> {
+        async move {
+            // Build the dependencies between the nodes
+            let introduced: Vec<Vec<String>> = nodes.iter().map(Node::names).collect();
+            let mut remaining = vec![0; nodes.len()];
+            let mut dependents = vec![Vec::new(); nodes.len()];
+            for (index, node) in nodes.iter().enumerate() {
+                let references = node.references();
+                for (other, names) in introduced.iter().enumerate() {
+                    if other != index && names.iter().any(|n| references.contains(n)) {
+                        remaining[index] += 1;
+                        dependents[other].push(index);
+                    }
+                }
+            }
+
+            let mut ready: Vec<usize> = (0..nodes.len()).filter(|i| remaining[*i] == 0).collect();
+            let mut pending = FuturesUnordered::new();
+            let mut completed = 0;
+            loop {
+                for index in ready.drain(..) {
+                    let names = names.clone();
+                    pending.push(
+                        async move {
+                            (
+                                index,
+                                self.evaluate_node(workflow, &nodes[index], names, shards)
+                                    .await,
+                            )
+                        }
+                        .boxed_local(),
+                    );
+                }
+
+                let Some((index, result)) = pending.next().await else {
+                    break;
+                };
+
+                names.extend(result?);
+                completed += 1;
+                for dependent in &dependents[index] {
+                    remaining[*dependent] -= 1;
+                    if remaining[*dependent] == 0 {
+                        ready.push(*dependent);
+                    }
+                }
+            }
+
+            if completed != nodes.len() {
+                return Err(anyhow!(
+                    "workflow `{workflow}` contains a reference cycle",
+                    workflow = workflow.name
+                )
+                .into());
+            }
+
+            Ok(names)
+        }
+        .boxed_local()
+    }
+
+    /// Evaluates a graph node of a workflow.
+    ///
+    /// Returns the names introduced by the node.
+    async fn evaluate_node(
+        &self,
+        workflow: &Workflow<'a>,
+        node: &Node,
+        names: IndexMap<String, Value>,
+        shards: &[usize],
+    ) -> EvaluationResult<Vec<(String, Value)>> {
+        match node {
+            Node::Input(decl) => {
+                let name = decl.name();
+                let ty = convert_type(
+                    self.engine.borrow_mut().types_mut(),
+                    &workflow.structs,
+                    decl,
+                )?;
+                let value = match workflow.inputs.get(name.as_str()) {
+                    Some(value) => {
+                        let value = value
+                            .coerce(self.engine.borrow().types(), ty)
+                            .map_err(|e| decl_coercion_failed(&name, e))?;
+                        resolve_paths(&value, &mut |path| Ok(join_path(&self.cwd, path)))?
+                    }
+                    None => match decl.expr() {
+                        Some(expr) => self
+                            .evaluate_expr(workflow, &names, &expr)?
+                            .coerce(self.engine.borrow().types(), ty)
+                            .map_err(|e| decl_coercion_failed(&name, e))?,
+                        None if ty.is_optional() => Value::None,
+                        None => return Err(missing_input(&workflow.name, &name).into()),
+                    },
+                };
+
+                Ok(vec![(name.as_str().to_string(), value)])
+            }
+            Node::Decl(decl) => {
+                let decl = Decl::Bound(decl.clone());
+                let name = decl.name();
+                let ty = convert_type(
+                    self.engine.borrow_mut().types_mut(),
+                    &workflow.structs,
+                    &decl,
+                )?;
+                let value = self
+                    .evaluate_expr(
+                        workflow,
+                        &names,
+                        &decl.expr().expect("declaration should have an expression"),
+                    )?
+                    .coerce(self.engine.borrow().types(), ty)
+                    .map_err(|e| decl_coercion_failed(&name, e))?;
+                Ok(vec![(name.as_str().to_string(), value)])
+            }
+            Node::Call(stmt) => {
+                let value = self.evaluate_call(workflow, stmt, &names, shards).await?;
+                Ok(vec![(call_name(stmt).as_str().to_string(), value)])
+            }
+            Node::Scatter(stmt) => self.evaluate_scatter(workflow, stmt, names, shards).await,
+            Node::Conditional(stmt) => {
+                self.evaluate_conditional(workflow, stmt, names, shards)
+                    .await
+            }
+        }
+    }
+
+    /// Evaluates a call statement.
+    ///
+    /// Returns the value of the call, which is an object containing the
+    /// outputs of the callee.
+    async fn evaluate_call(
+        &self,
+        workflow: &Workflow<'a>,
+        stmt: &CallStatement,
+        names: &IndexMap<String, Value>,
+        shards: &[usize],
+    ) -> EvaluationResult<Value> {
+        let call = call_name(stmt);
+        let call = call.as_str();
+
+        // Resolve the document containing the callee
+        let mut targets: Vec<Ident> = stmt.target().names().collect();
+        let callee = targets.pop().expect("call should have a target");
+        let document = match targets.first() {
+            Some(namespace) => {
+                let source = workflow
+                    .document
+                    .scope()
+                    .namespace(namespace.as_str())
+                    .with_context(|| {
+                        format!(
+                            "unknown namespace `{namespace}`",
+                            namespace = namespace.as_str()
+                        )
+                    })?
+                    .source();
+                self.results
+                    .iter()
+                    .find(|r| r.uri() == source)
+                    .with_context(|| format!("failed to find the analysis result for `{source}`"))?
+            }
+            None => workflow.document,
+        };
+
+        let ast = document
+            .parse_result()
+            .document()
+            .and_then(|d| d.ast().into_v1())
+            .with_context(|| {
+                format!(
+                    "document `{uri}` is not a supported WDL document",
+                    uri = document.uri()
+                )
+            })?;
+
+        // Evaluate the inputs to the call; inputs specified in the call statement take
+        // precedence over nested inputs
+        let mut inputs = workflow.inputs.call(call).cloned().unwrap_or_default();
+        for item in stmt.inputs() {
+            let name = item.name();
+            let value = match item.expr() {
+                Some(expr) => self.evaluate_expr(workflow, names, &expr)?,
+                None => names
+                    .get(name.as_str())
+                    .cloned()
+                    .ok_or_else(|| unknown_name(name.as_str(), name.span()))?,
+            };
+
+            inputs.set(name.as_str(), value);
+        }
+
+        let mut root = workflow.root.join(CALLS_DIR_NAME).join(call);
+        for shard in shards {
+            root.push(shard.to_string());
+        }
+
+        let outputs = match ast.items().find_map(|i| match i {
+            DocumentItem::Task(t) if t.name().as_str() == callee.as_str() => Some(t),
+            _ => None,
+        }) {
+            Some(task) => {
+                if let Some((name, _)) = inputs.calls().next() {
+                    return Err(anyhow!(
+                        "call `{call}` is to a task, which does not have a call named `{name}`"
+                    )
+                    .into());
+                }
+
+                let mut task_inputs = TaskInputs::default();
+                for (name, value) in inputs.iter() {
+                    task_inputs.set(name, value.clone());
+                }
+
+                let result = async {
+                    let (prepared, spawn) = {
+                        let mut engine = self.engine.borrow_mut();
+                        let (prepared, request) = TaskEvaluator::new(&mut engine).prepare(
+                            document.scope(),
+                            &task,
+                            &task_inputs,
+                            &root,
+                        )?;
+                        (prepared, engine.spawn(request))
+                    };
+
+                    let result = spawn.await;
+                    TaskEvaluator::new(&mut self.engine.borrow_mut()).finish(prepared, result)
+                }
+                .await;
+
+                result.map(|t| t.outputs().clone())
+            }
+            None => {
+                let definition = ast
+                    .items()
+                    .find_map(|i| match i {
+                        DocumentItem::Workflow(w) if w.name().as_str() == callee.as_str() => {
+                            Some(w)
+                        }
+                        _ => None,
+                    })
+                    .with_context(|| {
+                        format!(
+                            "unknown task or workflow `{callee}`",
+                            callee = callee.as_str()
+                        )
+                    })?;
+
+                self.evaluate_workflow(document, &definition, &inputs, &root)
+                    .await
+            }
+        };
+
+        // Diagnostics from other documents cannot be reported against this document
+        let outputs = match outputs {
+            Err(EvaluationError::Source(diagnostic))
+                if document.uri() != workflow.document.uri() =>
+            {
+                return Err(anyhow!(
+                    "{message} (in `{uri}`)",
+                    message = diagnostic.message(),
+                    uri = document.uri()
+                )
+                .context(format!("failed to evaluate call `{call}`"))
+                .into());
+            }
+            outputs => outputs?,
+        };
+
+        Ok(Object::new(outputs).into())
+    }
+
+    /// Evaluates a scatter statement.
+    ///
+    /// Returns the names introduced by the statement; each value is gathered
+    /// into an array of the values from each shard of the scatter.
+    async fn evaluate_scatter(
+        &self,
+        workflow: &Workflow<'a>,
+        stmt: &ScatterStatement,
+        names: IndexMap<String, Value>,
+        shards: &[usize],
+    ) -> EvaluationResult<Vec<(String, Value)>> {
+        let expr = stmt.expr();
+        let value = self.evaluate_expr(workflow, &names, &expr)?;
+        let elements = value
+            .as_array()
+            .ok_or_else(|| scatter_not_array(expr.span()))?
+            .elements()
+            .to_vec();
+
+        let variable = stmt.variable();
+        let nodes: Vec<Node> = stmt.statements().map(Node::from).collect();
+        let results = try_join_all(elements.into_iter().enumerate().map(|(index, element)| {
+            let mut names = names.clone();
+            names.insert(variable.as_str().to_string(), element);
+            let mut shards = shards.to_vec();
+            shards.push(index);
+            let nodes = &nodes;
+            async move { self.evaluate_nodes(workflow, nodes, names, &shards).await }
+        }))
+        .await?;
+
+        // Gather the values from each shard
+        let node = Node::Scatter(stmt.clone());
+        let mut gathered = Vec::new();
+        for name in node.names() {
+            let ty = self.promoted_type(workflow, node.syntax(), &name)?;
+            let values = results.iter().map(|names| {
+                names
+                    .get(&name)
+                    .cloned()
+                    .expect("shard should have evaluated the name")
+            });
+
+            let mut engine = self.engine.borrow_mut();
+            let types = engine.types_mut();
+            let value = match call_type(types, ty) {
+                Some(call) => {
+                    let outputs: Vec<_> = call
+                        .outputs()
+                        .iter()
+                        .map(|(name, ty)| (name.clone(), *ty))
+                        .collect();
+                    let values: Vec<_> = values.collect();
+                    let mut members = IndexMap::new();
+                    for (output, ty) in outputs {
+                        let elements: Vec<_> = values
+                            .iter()
+                            .map(|v| {
+                                v.as_object()
+                                    .and_then(|o| o.members().get(&output))
+                                    .cloned()
+                                    .expect("call value should have the output")
+                            })
+                            .collect();
+                        members.insert(output, Array::new(types, ty, elements)?.into());
+                    }
+
+                    Object::new(members).into()
+                }
+                _ => Array::new(types, ty, values)?.into(),
+            };
+
+            gathered.push((name, value));
+        }
+
+        Ok(gathered)
+    }
+
+    /// Evaluates a conditional statement.
+    ///
+    /// Returns the names introduced by the statement; if the condition is
+    /// `false`, the value of each name is `None`.
+    async fn evaluate_conditional(
+        &self,
+        workflow: &Workflow<'a>,
+        stmt: &ConditionalStatement,
+        names: IndexMap<String, Value>,
+        shards: &[usize],
+    ) -> EvaluationResult<Vec<(String, Value)>> {
+        let expr = stmt.expr();
+        let condition = self
+            .evaluate_expr(workflow, &names, &expr)?
+            .as_boolean()
+            .ok_or_else(|| conditional_not_boolean(expr.span()))?;
+
+        let node = Node::Conditional(stmt.clone());
+        if condition {
+            let nodes: Vec<Node> = stmt.statements().map(Node::from).collect();
+            let mut results = self.evaluate_nodes(workflow, &nodes, names, shards).await?;
+            return Ok(node
+                .names()
+                .into_iter()
+                .map(|name| {
+                    let value = results
+                        .swap_remove(&name)
+                        .expect("statement should have evaluated the name");
+                    (name, value)
+                })
+                .collect());
+        }
+
+        let mut values = Vec::new();
+        for name in node.names() {
+            let ty = self.promoted_type(workflow, node.syntax(), &name)?;
+            let value = match call_type(self.engine.borrow().types(), ty) {
+                Some(call) => Object::new(
+                    call.outputs()
+                        .keys()
+                        .map(|name| (name.clone(), Value::None))
+                        .collect(),
+                )
+                .into(),
+                _ => Value::None,
+            };
+
+            values.push((name, value));
+        }
+
+        Ok(values)
+    }
+
+    /// Gets the type of a name introduced by a scatter or conditional
+    /// statement, as seen from the scope enclosing the statement.
+    ///
+    /// The type is imported into the engine's types collection.
+    fn promoted_type(
+        &self,
+        workflow: &Workflow<'a>,
+        statement: &SyntaxNode,
+        name: &str,
+    ) -> EvaluationResult<Type> {
+        let scope = workflow.document.scope();
+        let ty = scope
+            .find_scope_by_position(statement.text_range().start().into())
+            .and_then(|s| s.lookup(name))
+            .and_then(|n| n.ty())
+            .with_context(|| format!("failed to determine the type of `{name}`"))?;
+        Ok(self
+            .engine
+            .borrow_mut()
+            .types_mut()
+            .import(scope.types(), ty))
+    }
+
+    /// Evaluates an expression with the given names in scope.
+    fn evaluate_expr(
+        &self,
+        workflow: &Workflow<'a>,
+        names: &IndexMap<String, Value>,
+        expr: &Expr,
+    ) -> Result<Value, Diagnostic> {
+        let mut engine = self.engine.borrow_mut();
+        let mut evaluator = ExprEvaluator::new(WorkflowEvaluationContext {
+            version: workflow.version,
+            types: engine.types_mut(),
+            structs: &workflow.structs,
+            names,
+            work_dir: &self.cwd,
+            temp_dir: &workflow.temp_dir,
+        });
+        evaluator.evaluate_expr(expr)
+    }
+}
+
+/// Gets the call type definition of the given type.
+///
+/// Returns `None` if the type is not a call type.
+fn call_type(types: &Types, ty: Type) -> Option<&CallType> {
+    match ty {
+        Type::Compound(ty) => match types.type_definition(ty.definition()) {
+            CompoundTypeDef::Call(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Gets the name of a call statement.
+///
+/// This is the alias of the call, if present, or the name of the callee.
+fn call_name(stmt: &CallStatement) -> Ident {
+    stmt.alias()
+        .map(|a| a.name())
+        .unwrap_or_else(|| stmt.target().names().last().expect("should have a name"))
+}
@@ -0,0 +1,152 @@
+//! Implementation of inputs to tasks and workflows.
+
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde_json::Value as JsonValue;
+use wdl_analysis::types::Types;
+
+use crate::Value;
+
+/// Represents inputs to a task.
+#[derive(Default, Debug, Clone)]
+pub struct TaskInputs {
+    /// The task input values.
+    inputs: IndexMap<String, Value>,
+}
+
+impl TaskInputs {
+    /// Parses task inputs from a JSON object.
+    ///
+    /// Each key of the object must be the name of the task followed by a
+    /// period and the name of the input (e.g. `task.input`).
+    ///
+    /// The values are inferred from the JSON values; the values are coerced to
+    /// the types of the task's inputs upon evaluation.
+    pub fn parse_json(
+        types: &mut Types,
+        task: &str,
+        object: serde_json::Map<String, JsonValue>,
+    ) -> Result<Self> {
+        let mut inputs = IndexMap::new();
+        for (key, value) in object {
+            let name = match key.split_once('.') {
+                Some((prefix, name)) if prefix == task && !name.contains('.') => name,
+                _ => bail!(
+                    "invalid input key `{key}`: expected a key of the form `{task}.<input name>`"
+                ),
+            };
+
+            let value = Value::from_json(types, value)
+                .with_context(|| format!("invalid value for input `{key}`"))?;
+            inputs.insert(name.to_string(), value);
+        }
+
+        Ok(Self { inputs })
+    }
+
+    /// Iterates the inputs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.inputs.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Gets an input by name.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.inputs.get(name)
+    }
+
+    /// Sets an input value.
+    ///
+    /// Returns the previous value of the input.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<Value>) -> Option<Value> {
+        self.inputs.insert(name.into(), value.into())
+    }
+}
+
+/// Represents inputs to a workflow.
+///
+/// Workflow inputs may also contain inputs to the workflow's calls; these are
+/// known as "nested inputs".
+#[derive(Default, Debug, Clone)]
+pub struct WorkflowInputs {
+    /// The workflow input values.
+    inputs: IndexMap<String, Value>,
+    /// The nested inputs to the workflow's calls, keyed by call name.
+    calls: IndexMap<String, WorkflowInputs>,
+}
+
+impl WorkflowInputs {
+    /// Parses workflow inputs from a JSON object.
+    ///
+    /// Each key of the object must be the name of the workflow followed by a
+    /// period and the name of the input (e.g. `workflow.input`).
+    ///
+    /// Inputs to calls are specified by including the name of the call in the
+    /// key (e.g. `workflow.call.input`).
+    ///
+    /// The values are inferred from the JSON values; the values are coerced to
+    /// the types of the inputs upon evaluation.
+    pub fn parse_json(
+        types: &mut Types,
+        workflow: &str,
+        object: serde_json::Map<String, JsonValue>,
+    ) -> Result<Self> {
+        let mut inputs = Self::default();
+        for (key, value) in object {
+            let name = match key.split_once('.') {
+                Some((prefix, name)) if prefix == workflow => name,
+                _ => bail!(
+                    "invalid input key `{key}`: expected a key of the form `{workflow}.<input \
+                     name>`"
+                ),
+            };
+
+            let value = Value::from_json(types, value)
+                .with_context(|| format!("invalid value for input `{key}`"))?;
+            inputs.set(name, value);
+        }
+
+        Ok(inputs)
+    }
+
+    /// Iterates the inputs.
+    ///
+    /// Nested inputs to calls are not included.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.inputs.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Gets an input by name.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.inputs.get(name)
+    }
+
+    /// Sets an input value.
+    ///
+    /// If the name contains a period, the portion of the name before the first
+    /// period is treated as the name of a call and the value is set as a nested
+    /// input of the call.
+    ///
+    /// Returns the previous value of the input.
+    pub fn set(&mut self, name: &str, value: impl Into<Value>) -> Option<Value> {
+        match name.split_once('.') {
+            Some((call, name)) => self
+                .calls
+                .entry(call.to_string())
+                .or_default()
+                .set(name, value),
+            None => self.inputs.insert(name.to_string(), value.into()),
+        }
+    }
+
+    /// Iterates the nested inputs of the workflow's calls.
+    pub fn calls(&self) -> impl Iterator<Item = (&str, &WorkflowInputs)> {
+        self.calls.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Gets the nested inputs of a call by name.
+    pub fn call(&self, name: &str) -> Option<&WorkflowInputs> {
+        self.calls.get(name)
+    }
+}
@@ -0,0 +1,27 @@
+//! Execution engine for Workflow Description Language (WDL) documents.
+//!
+//! The engine provides a representation of WDL runtime values, an evaluator of
+//! WDL expressions, and evaluators of WDL tasks and workflows.
+//!
+//! Task commands are executed by a [`TaskExecutionBackend`]; the
+//! [`LocalTaskExecutionBackend`] executes commands as local processes.
+
+#![warn(missing_docs)]
+#![warn(rust_2018_idioms)]
+#![warn(rust_2021_compatibility)]
+#![warn(missing_debug_implementations)]
+#![warn(clippy::missing_docs_in_private_items)]
+#![warn(rustdoc::broken_intra_doc_links)]
+
+mod backend;
+mod engine;
+mod eval;
+mod inputs;
+mod stdlib;
+mod value;
+
+pub use backend::*;
+pub use engine::*;
+pub use eval::*;
+pub use inputs::*;
+pub use value::*;
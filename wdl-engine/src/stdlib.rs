@@ -0,0 +1,350 @@
+//! Module for the WDL standard library implementation.
+
+use std::fmt;
+
+use wdl_analysis::types::CompoundTypeDef;
+use wdl_analysis::types::Type;
+use wdl_analysis::types::Types;
+use wdl_ast::Diagnostic;
+use wdl_ast::Span;
+
+use crate::Array;
+use crate::EvaluationContext;
+use crate::Value;
+
+mod arrays;
+mod io;
+mod maps;
+mod math;
+mod misc;
+mod paths;
+mod strings;
+
+/// Creates a "function call failed" diagnostic.
+pub(crate) fn function_call_failed(name: &str, error: impl fmt::Display, span: Span) -> Diagnostic {
+    Diagnostic::error(format!("call to function `{name}` failed: {error}")).with_highlight(span)
+}
+
+/// Gets the number of bytes represented by the given size unit.
+///
+/// Both decimal (e.g. `KB`) and binary (e.g. `KiB`) units are supported.
+///
+/// Returns `None` if the unit is not recognized.
+pub(crate) fn unit_multiplier(unit: &str) -> Option<f64> {
+    match unit {
+        "B" => Some(1.0),
+        "KB" | "K" => Some(1000.0),
+        "MB" | "M" => Some(1000.0 * 1000.0),
+        "GB" | "G" => Some(1000.0 * 1000.0 * 1000.0),
+        "TB" | "T" => Some(1000.0 * 1000.0 * 1000.0 * 1000.0),
+        "KiB" | "Ki" => Some(1024.0),
+        "MiB" | "Mi" => Some(1024.0 * 1024.0),
+        "GiB" | "Gi" => Some(1024.0 * 1024.0 * 1024.0),
+        "TiB" | "Ti" => Some(1024.0 * 1024.0 * 1024.0 * 1024.0),
+        _ => None,
+    }
+}
+
+/// Represents an argument to a standard library function call.
+#[derive(Debug)]
+pub(crate) struct CallArgument {
+    /// The value of the argument.
+    pub(crate) value: Value,
+    /// The span of the argument expression.
+    pub(crate) span: Span,
+}
+
+/// Represents the context of a call to a standard library function.
+pub(crate) struct CallContext<'a> {
+    /// The evaluation context of the call.
+    context: &'a mut dyn EvaluationContext,
+    /// The span of the call expression.
+    call_site: Span,
+    /// The arguments of the call.
+    arguments: &'a [CallArgument],
+    /// The return type of the bound function signature.
+    return_type: Type,
+}
+
+impl<'a> CallContext<'a> {
+    /// Constructs a new call context.
+    pub(crate) fn new(
+        context: &'a mut dyn EvaluationContext,
+        call_site: Span,
+        arguments: &'a [CallArgument],
+        return_type: Type,
+    ) -> Self {
+        Self {
+            context,
+            call_site,
+            arguments,
+            return_type,
+        }
+    }
+
+    /// Gets the types collection of the call.
+    fn types(&self) -> &Types {
+        self.context.types()
+    }
+
+    /// Gets the mutable types collection of the call.
+    fn types_mut(&mut self) -> &mut Types {
+        self.context.types_mut()
+    }
+
+    /// Coerces the argument at the given index to the given type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument cannot be coerced; the arguments of the call are
+    /// expected to have been bound to the function's signature.
+    fn coerce_argument(&self, index: usize, ty: impl Into<Type>) -> Value {
+        self.arguments[index]
+            .value
+            .coerce(self.types(), ty.into())
+            .expect("value should coerce")
+    }
+
+    /// Creates a new array value of the return type with the given elements.
+    fn new_array(&self, elements: Vec<Value>) -> Value {
+        Array::new_unchecked(self.return_type, elements).into()
+    }
+
+    /// Gets the element type of the given array type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given type is not an array type.
+    fn element_type(&self, ty: Type) -> Type {
+        self.compound_definition(ty)
+            .as_array()
+            .expect("type should be an array")
+            .element_type()
+    }
+
+    /// Gets the compound type definition of the given type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given type is not a compound type.
+    fn compound_definition(&self, ty: Type) -> &CompoundTypeDef {
+        match ty {
+            Type::Compound(ty) => self.types().type_definition(ty.definition()),
+            _ => panic!("type should be compound"),
+        }
+    }
+}
+
+/// Calls a standard library function with the given context.
+///
+/// The arguments of the call must have been bound to the signature of the
+/// function.
+pub(crate) fn call(name: &str, context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    match name {
+        // Numeric functions
+        "floor" => math::floor(context),
+        "ceil" => math::ceil(context),
+        "round" => math::round(context),
+        "min" => math::min(context),
+        "max" => math::max(context),
+        // String functions
+        "find" => strings::find(context),
+        "matches" => strings::matches(context),
+        "sub" => strings::sub(context),
+        // File functions
+        "basename" => paths::basename(context),
+        "join_paths" => paths::join_paths(context),
+        "glob" => io::glob(context),
+        "size" => io::size(context),
+        "read_string" => io::read_string(context),
+        "read_int" => io::read_int(context),
+        "read_float" => io::read_float(context),
+        "read_boolean" => io::read_boolean(context),
+        "read_lines" => io::read_lines(context),
+        "write_lines" => io::write_lines(context),
+        "read_tsv" => io::read_tsv(context),
+        "write_tsv" => io::write_tsv(context),
+        "read_map" => io::read_map(context),
+        "write_map" => io::write_map(context),
+        "read_json" => io::read_json(context),
+        "write_json" => io::write_json(context),
+        "read_object" => io::read_object(context),
+        "read_objects" => io::read_objects(context),
+        "write_object" => io::write_object(context),
+        "write_objects" => io::write_objects(context),
+        "stdout" => io::stdout(context),
+        "stderr" => io::stderr(context),
+        // String array functions
+        "prefix" => arrays::prefix(context),
+        "suffix" => arrays::suffix(context),
+        "quote" => arrays::quote(context),
+        "squote" => arrays::squote(context),
+        "sep" => arrays::sep(context),
+        // Generic array functions
+        "range" => arrays::range(context),
+        "transpose" => arrays::transpose(context),
+        "cross" => arrays::cross(context),
+        "zip" => arrays::zip(context),
+        "unzip" => arrays::unzip(context),
+        "contains" => arrays::contains(context),
+        "chunk" => arrays::chunk(context),
+        "flatten" => arrays::flatten(context),
+        "select_first" => arrays::select_first(context),
+        "select_all" => arrays::select_all(context),
+        // Map functions
+        "as_pairs" => maps::as_pairs(context),
+        "as_map" => maps::as_map(context),
+        "keys" => maps::keys(context),
+        "contains_key" => maps::contains_key(context),
+        "values" => maps::values(context),
+        "collect_by_key" => maps::collect_by_key(context),
+        // Other functions
+        "defined" => misc::defined(context),
+        "length" => misc::length(context),
+        _ => Err(function_call_failed(
+            name,
+            "the function is not implemented",
+            context.call_site,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::eval::v1::test::eval_str;
+
+    #[test]
+    fn numeric_functions() {
+        assert_eq!(eval_str("floor(1.9)"), "1");
+        assert_eq!(eval_str("ceil(1.1)"), "2");
+        assert_eq!(eval_str("round(2.5)"), "3");
+        assert_eq!(eval_str("min(1, 2)"), "1");
+        assert_eq!(eval_str("max(1, 2.5)"), "2.500000");
+    }
+
+    #[test]
+    fn string_functions() {
+        assert_eq!(eval_str(r#"find("hello world", "o\\s+w")"#), r#""o w""#);
+        assert_eq!(eval_str(r#"find("hello", "z")"#), "None");
+        assert_eq!(eval_str(r#"matches("sample.bam", "\\.bam$")"#), "true");
+        assert_eq!(
+            eval_str(r#"sub("sample.bam", "\\.bam$", ".bai")"#),
+            r#""sample.bai""#
+        );
+        assert_eq!(
+            eval_str(r#"sub("a", "(", "b")"#),
+            "error: call to function `sub` failed: regex parse error:\n    (\n    ^\nerror: \
+             unclosed group"
+        );
+    }
+
+    #[test]
+    fn file_functions() {
+        assert_eq!(eval_str(r#"basename("/a/b/c.txt")"#), r#""c.txt""#);
+        assert_eq!(eval_str(r#"basename("/a/b/c.txt", ".txt")"#), r#""c""#);
+        assert_eq!(eval_str(r#"join_paths("/a", "b/c")"#), r#""/a/b/c""#);
+        assert_eq!(eval_str(r#"join_paths(["/a", "b", "c"])"#), r#""/a/b/c""#);
+        assert_eq!(
+            eval_str(r#"join_paths("/a", "/b")"#),
+            "error: call to function `join_paths` failed: path `/b` is absolute"
+        );
+        assert_eq!(
+            eval_str(r#"read_lines("foo.txt")"#),
+            "error: call to function `read_lines` failed: the function requires file system \
+             access, which is not available in this context"
+        );
+        assert_eq!(
+            eval_str("stdout()"),
+            "error: call to function `stdout` failed: the standard output of a task is not \
+             available in this context"
+        );
+    }
+
+    #[test]
+    fn array_functions() {
+        assert_eq!(eval_str("prefix(\"-i \", [1, 2])"), r#"["-i 1", "-i 2"]"#);
+        assert_eq!(eval_str("suffix(\".txt\", [\"a\"])"), r#"["a.txt"]"#);
+        assert_eq!(eval_str("quote([1, 2])"), r#"["\"1\"", "\"2\""]"#);
+        assert_eq!(eval_str("squote([1, 2])"), r#"["'1'", "'2'"]"#);
+        assert_eq!(eval_str("sep(\",\", [1, 2])"), r#""1,2""#);
+        assert_eq!(eval_str("range(3)"), "[0, 1, 2]");
+        assert_eq!(
+            eval_str("range(-1)"),
+            "error: call to function `range` failed: array length cannot be negative"
+        );
+        assert_eq!(eval_str("transpose([[1, 2], [3, 4]])"), "[[1, 3], [2, 4]]");
+        assert_eq!(
+            eval_str("transpose([[1, 2], [3]])"),
+            "error: call to function `transpose` failed: expected array at index 1 to have a \
+             length of 2, but found 1"
+        );
+        assert_eq!(
+            eval_str("cross([1, 2], [\"a\"])"),
+            r#"[(1, "a"), (2, "a")]"#
+        );
+        assert_eq!(
+            eval_str("zip([1, 2], [\"a\", \"b\"])"),
+            r#"[(1, "a"), (2, "b")]"#
+        );
+        assert_eq!(
+            eval_str("zip([1, 2], [\"a\"])"),
+            "error: call to function `zip` failed: expected an array of length 2, but found an \
+             array of length 1"
+        );
+        assert_eq!(
+            eval_str("unzip([(1, \"a\"), (2, \"b\")])"),
+            r#"([1, 2], ["a", "b"])"#
+        );
+        assert_eq!(eval_str("contains([1, 2], 2)"), "true");
+        assert_eq!(eval_str("chunk([1, 2, 3], 2)"), "[[1, 2], [3]]");
+        assert_eq!(eval_str("flatten([[1], [], [2, 3]])"), "[1, 2, 3]");
+        assert_eq!(eval_str("select_first([None, 2, 3])"), "2");
+        assert_eq!(eval_str("select_first([None, 2], 5)"), "2");
+        assert_eq!(
+            eval_str("select_first([n])"),
+            "error: call to function `select_first` failed: array is empty or contains only \
+             `None` values"
+        );
+        assert_eq!(eval_str("select_all([None, 2, None, 3])"), "[2, 3]");
+    }
+
+    #[test]
+    fn map_functions() {
+        assert_eq!(
+            eval_str("as_pairs({\"a\": 1, \"b\": 2})"),
+            r#"[("a", 1), ("b", 2)]"#
+        );
+        assert_eq!(
+            eval_str("as_map([(\"a\", 1), (\"b\", 2)])"),
+            r#"{"a": 1, "b": 2}"#
+        );
+        assert_eq!(
+            eval_str("as_map([(\"a\", 1), (\"a\", 2)])"),
+            "error: call to function `as_map` failed: array contains a duplicate entry for map \
+             key \"a\""
+        );
+        assert_eq!(eval_str("keys({\"a\": 1, \"b\": 2})"), r#"["a", "b"]"#);
+        assert_eq!(eval_str("keys(Foo { a: 1 })"), r#"["a", "b"]"#);
+        assert_eq!(eval_str("values({\"a\": 1, \"b\": 2})"), "[1, 2]");
+        assert_eq!(eval_str("contains_key({\"a\": 1}, \"a\")"), "true");
+        assert_eq!(
+            eval_str("contains_key(object { a: { \"b\": 1 } }, [\"a\", \"b\"])"),
+            "true"
+        );
+        assert_eq!(
+            eval_str("collect_by_key([(\"a\", 1), (\"b\", 2), (\"a\", 3)])"),
+            r#"{"a": [1, 3], "b": [2]}"#
+        );
+    }
+
+    #[test]
+    fn other_functions() {
+        assert_eq!(eval_str("defined(n)"), "false");
+        assert_eq!(eval_str("defined(x)"), "true");
+        assert_eq!(eval_str("length([1, 2])"), "2");
+        assert_eq!(eval_str("length(\"abc\")"), "3");
+        assert_eq!(eval_str("length({\"a\": 1})"), "1");
+    }
+}
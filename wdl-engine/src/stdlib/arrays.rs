@@ -0,0 +1,335 @@
+//! Implementation of the array functions of the standard library.
+
+use wdl_ast::Diagnostic;
+
+use super::function_call_failed;
+use super::CallContext;
+use crate::Array;
+use crate::Pair;
+use crate::Value;
+
+impl CallContext<'_> {
+    /// Gets the argument at the given index as an array.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is not an array.
+    pub(super) fn array_argument(&self, index: usize) -> &Array {
+        self.arguments[index]
+            .value
+            .as_array()
+            .expect("value should be an array")
+    }
+
+    /// Gets the raw string representation of the primitive elements of the
+    /// array argument at the given index.
+    pub(super) fn raw_elements(&self, index: usize) -> impl Iterator<Item = String> + '_ {
+        self.array_argument(index).elements().iter().map(|e| {
+            e.raw()
+                .expect("array element should be primitive")
+                .to_string()
+        })
+    }
+}
+
+/// Adds a prefix to each element of the input array of primitive values.
+///
+/// `Array[String] prefix(String, Array[P])`
+pub(super) fn prefix(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let prefix = context.arguments[0].value.raw().unwrap().to_string();
+    let elements = context
+        .raw_elements(1)
+        .map(|e| format!("{prefix}{e}").into())
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Adds a suffix to each element of the input array of primitive values.
+///
+/// `Array[String] suffix(String, Array[P])`
+pub(super) fn suffix(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let suffix = context.arguments[0].value.raw().unwrap().to_string();
+    let elements = context
+        .raw_elements(1)
+        .map(|e| format!("{e}{suffix}").into())
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Adds double-quotes around each element of the input array of primitive
+/// values.
+///
+/// `Array[String] quote(Array[P])`
+pub(super) fn quote(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let elements = context
+        .raw_elements(0)
+        .map(|e| format!("\"{e}\"").into())
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Adds single-quotes around each element of the input array of primitive
+/// values.
+///
+/// `Array[String] squote(Array[P])`
+pub(super) fn squote(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let elements = context
+        .raw_elements(0)
+        .map(|e| format!("'{e}'").into())
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Concatenates the elements of an array together into a string with the
+/// given separator between consecutive elements.
+///
+/// `String sep(String, Array[P])`
+pub(super) fn sep(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let separator = context.arguments[0].value.raw().unwrap().to_string();
+    Ok(context
+        .raw_elements(1)
+        .collect::<Vec<_>>()
+        .join(&separator)
+        .into())
+}
+
+/// Creates an array of the given length containing sequential integers
+/// starting from 0.
+///
+/// `Array[Int] range(Int)`
+pub(super) fn range(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let n = context.arguments[0]
+        .value
+        .as_integer()
+        .expect("value should be an integer");
+    if n < 0 {
+        return Err(function_call_failed(
+            "range",
+            "array length cannot be negative",
+            context.arguments[0].span,
+        ));
+    }
+
+    Ok(context.new_array((0..n).map(Into::into).collect()))
+}
+
+/// Transposes a two-dimensional array according to the standard matrix
+/// transposition rules.
+///
+/// `Array[Array[X]] transpose(Array[Array[X]])`
+pub(super) fn transpose(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let rows = context.array_argument(0);
+    let columns = rows
+        .elements()
+        .first()
+        .map(|r| r.as_array().expect("element should be an array").len())
+        .unwrap_or(0);
+
+    for (i, row) in rows.elements().iter().enumerate() {
+        let len = row.as_array().expect("element should be an array").len();
+        if len != columns {
+            return Err(function_call_failed(
+                "transpose",
+                format!(
+                    "expected array at index {i} to have a length of {columns}, but found {len}"
+                ),
+                context.arguments[0].span,
+            ));
+        }
+    }
+
+    let inner = context.element_type(context.return_type);
+    let elements = (0..columns)
+        .map(|c| {
+            Array::new_unchecked(
+                inner,
+                rows.elements()
+                    .iter()
+                    .map(|r| r.as_array().unwrap().elements()[c].clone())
+                    .collect(),
+            )
+            .into()
+        })
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Creates an array of pairs containing the cross product of two input arrays.
+///
+/// `Array[Pair[X,Y]] cross(Array[X], Array[Y])`
+pub(super) fn cross(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let left = context.array_argument(0);
+    let right = context.array_argument(1);
+    let pair_ty = context.element_type(context.return_type);
+    let elements = left
+        .elements()
+        .iter()
+        .flat_map(|l| {
+            right
+                .elements()
+                .iter()
+                .map(move |r| Pair::new_unchecked(pair_ty, l.clone(), r.clone()).into())
+        })
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Creates an array of pairs containing the dot product of two input arrays.
+///
+/// `Array[Pair[X,Y]] zip(Array[X], Array[Y])`
+pub(super) fn zip(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let left = context.array_argument(0);
+    let right = context.array_argument(1);
+    if left.len() != right.len() {
+        return Err(function_call_failed(
+            "zip",
+            format!(
+                "expected an array of length {expected}, but found an array of length {actual}",
+                expected = left.len(),
+                actual = right.len()
+            ),
+            context.arguments[1].span,
+        ));
+    }
+
+    let pair_ty = context.element_type(context.return_type);
+    let elements = left
+        .elements()
+        .iter()
+        .zip(right.elements())
+        .map(|(l, r)| Pair::new_unchecked(pair_ty, l.clone(), r.clone()).into())
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Creates a pair of arrays, the first containing the elements from the left
+/// members of an array of pairs, and the second containing the right members.
+///
+/// `Pair[Array[X], Array[Y]] unzip(Array[Pair[X, Y]])`
+pub(super) fn unzip(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let pairs = context.array_argument(0);
+    let pair_ty = context
+        .compound_definition(context.return_type)
+        .as_pair()
+        .expect("type should be a pair");
+    let (left_ty, right_ty) = (pair_ty.first_type(), pair_ty.second_type());
+
+    let (left, right) = pairs
+        .elements()
+        .iter()
+        .map(|p| {
+            let p = p.as_pair().expect("element should be a pair");
+            (p.left().clone(), p.right().clone())
+        })
+        .unzip();
+
+    Ok(Pair::new_unchecked(
+        context.return_type,
+        Array::new_unchecked(left_ty, left).into(),
+        Array::new_unchecked(right_ty, right).into(),
+    )
+    .into())
+}
+
+/// Tests whether the given array contains at least one occurrence of the
+/// given value.
+///
+/// `Boolean contains(Array[P], P)`
+pub(super) fn contains(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let value = &context.arguments[1].value;
+    Ok(context
+        .array_argument(0)
+        .elements()
+        .iter()
+        .any(|e| Value::equals(e, value).unwrap_or(false))
+        .into())
+}
+
+/// Given an array and a length `n`, splits the array into consecutive,
+/// non-overlapping arrays of `n` elements.
+///
+/// `Array[Array[X]] chunk(Array[X], Int)`
+pub(super) fn chunk(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let array = context.array_argument(0);
+    let size = context.arguments[1]
+        .value
+        .as_integer()
+        .expect("value should be an integer");
+    let size = usize::try_from(size)
+        .ok()
+        .filter(|s| *s > 0)
+        .ok_or_else(|| {
+            function_call_failed(
+                "chunk",
+                "chunk size must be greater than zero",
+                context.arguments[1].span,
+            )
+        })?;
+
+    let inner = context.element_type(context.return_type);
+    let elements = array
+        .elements()
+        .chunks(size)
+        .map(|c| Array::new_unchecked(inner, c.to_vec()).into())
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Flattens a nested array by concatenating all of the element arrays, in
+/// order, into a single array.
+///
+/// `Array[X] flatten(Array[Array[X]])`
+pub(super) fn flatten(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let elements = context
+        .array_argument(0)
+        .elements()
+        .iter()
+        .flat_map(|a| {
+            a.as_array()
+                .expect("element should be an array")
+                .elements()
+                .iter()
+                .cloned()
+        })
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Selects the first (i.e., left-most) non-`None` value from an array of
+/// optional values; the optional second parameter is returned if all values
+/// are `None`.
+///
+/// `X select_first(Array[X?]+, [X])`
+pub(super) fn select_first(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    if let Some(value) = context
+        .array_argument(0)
+        .elements()
+        .iter()
+        .find(|e| !e.is_none())
+    {
+        return Ok(value.clone());
+    }
+
+    match context.arguments.get(1) {
+        Some(default) if !default.value.is_none() => Ok(default.value.clone()),
+        _ => Err(function_call_failed(
+            "select_first",
+            "array is empty or contains only `None` values",
+            context.arguments[0].span,
+        )),
+    }
+}
+
+/// Filters the input array of optional values by removing all `None` values.
+///
+/// `Array[X] select_all(Array[X?])`
+pub(super) fn select_all(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let elements = context
+        .array_argument(0)
+        .elements()
+        .iter()
+        .filter(|e| !e.is_none())
+        .cloned()
+        .collect();
+    Ok(context.new_array(elements))
+}
@@ -0,0 +1,748 @@
+//! Implementation of the file system functions of the standard library.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use indexmap::IndexMap;
+use wdl_ast::Diagnostic;
+
+use super::function_call_failed;
+use super::unit_multiplier;
+use super::CallContext;
+use crate::Array;
+use crate::CompoundValue;
+use crate::Map;
+use crate::Object;
+use crate::PrimitiveValue;
+use crate::Value;
+
+impl CallContext<'_> {
+    /// Gets the working directory used to resolve relative paths.
+    fn work_dir(&self, name: &str) -> Result<&Path, Diagnostic> {
+        self.context.work_dir().ok_or_else(|| {
+            function_call_failed(
+                name,
+                "the function requires file system access, which is not available in this context",
+                self.call_site,
+            )
+        })
+    }
+
+    /// Gets the path argument at the given index, resolved against the
+    /// working directory.
+    fn path_argument(&self, name: &str, index: usize) -> Result<PathBuf, Diagnostic> {
+        let work_dir = self.work_dir(name)?;
+        let path = self.arguments[index]
+            .value
+            .as_primitive()
+            .and_then(PrimitiveValue::as_str)
+            .expect("value should be a string");
+        Ok(work_dir.join(path))
+    }
+
+    /// Reads the contents of the file specified by the argument at the given
+    /// index.
+    fn read_argument(&self, name: &str, index: usize) -> Result<String, Diagnostic> {
+        let path = self.path_argument(name, index)?;
+        fs::read_to_string(&path).map_err(|e| {
+            function_call_failed(
+                name,
+                format!("failed to read file `{path}`: {e}", path = path.display()),
+                self.arguments[index].span,
+            )
+        })
+    }
+
+    /// Writes the given contents to a new file in the temporary directory.
+    ///
+    /// Returns the `File` value of the new file.
+    fn write_file(&self, name: &str, contents: &str) -> Result<Value, Diagnostic> {
+        let temp_dir = self.context.temp_dir().ok_or_else(|| {
+            function_call_failed(
+                name,
+                "the function requires a temporary directory, which is not available in this \
+                 context",
+                self.call_site,
+            )
+        })?;
+
+        let write = || -> std::io::Result<PathBuf> {
+            let mut file = tempfile::Builder::new()
+                .prefix(&format!("{name}-"))
+                .tempfile_in(temp_dir)?;
+            file.write_all(contents.as_bytes())?;
+            let (_, path) = file.keep().map_err(|e| e.error)?;
+            Ok(path)
+        };
+
+        let path = write().map_err(|e| {
+            function_call_failed(
+                name,
+                format!(
+                    "failed to create file in directory `{dir}`: {e}",
+                    dir = temp_dir.display()
+                ),
+                self.call_site,
+            )
+        })?;
+
+        Ok(PrimitiveValue::new_file(path.to_string_lossy()).into())
+    }
+}
+
+/// Splits the given file contents into lines.
+///
+/// A trailing newline does not produce an empty last line.
+fn lines(contents: &str) -> impl Iterator<Item = &str> {
+    contents.lines()
+}
+
+/// Gets the raw representation of a primitive value for writing to a TSV
+/// file.
+///
+/// Returns an error message if the value is not primitive or contains a tab.
+fn tsv_field(value: &Value) -> Result<String, String> {
+    let field = value
+        .raw()
+        .ok_or_else(|| {
+            format!(
+                "value `{value}` cannot be written to a TSV file as it is not a primitive value"
+            )
+        })?
+        .to_string();
+
+    if field.contains('\t') {
+        return Err(format!(
+            "value `{value}` cannot be written to a TSV file as it contains a tab character"
+        ));
+    }
+
+    Ok(field)
+}
+
+/// Returns an array of files that match the given glob pattern.
+///
+/// The pattern is evaluated relative to the working directory.
+///
+/// `Array[File] glob(String)`
+pub(super) fn glob(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let work_dir = context.work_dir("glob")?;
+    let pattern = context.arguments[0]
+        .value
+        .as_string()
+        .expect("value should be a string");
+
+    let full_pattern = work_dir.join(pattern.as_str());
+    let paths = glob::glob(&full_pattern.to_string_lossy()).map_err(|e| {
+        function_call_failed(
+            "glob",
+            format!("invalid glob pattern: {e}"),
+            context.arguments[0].span,
+        )
+    })?;
+
+    let mut elements = Vec::new();
+    for path in paths {
+        let path = path.map_err(|e| function_call_failed("glob", e, context.call_site))?;
+        if !path.is_file() {
+            continue;
+        }
+
+        let path = path.strip_prefix(work_dir).unwrap_or(&path);
+        elements.push(PrimitiveValue::new_file(path.to_string_lossy()).into());
+    }
+
+    Ok(context.new_array(elements))
+}
+
+/// Determines the size of a file, directory, or the sum total sizes of the
+/// files and directories contained within a compound value.
+///
+/// The optional second parameter specifies the unit of the returned value; the
+/// default unit is bytes.
+///
+/// `Float size(File?|String?|Directory?, [String])`
+/// `Float size(X, [String])`
+pub(super) fn size(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    /// Calculates the size of the given path in bytes.
+    fn path_size(path: &Path) -> std::io::Result<u64> {
+        let metadata = path.metadata()?;
+        if !metadata.is_dir() {
+            return Ok(metadata.len());
+        }
+
+        let mut size = 0;
+        for entry in fs::read_dir(path)? {
+            size += path_size(&entry?.path())?;
+        }
+
+        Ok(size)
+    }
+
+    /// Calculates the size of the given value in bytes.
+    fn value_size(work_dir: &Path, value: &Value) -> Result<u64, String> {
+        match value {
+            Value::None => Ok(0),
+            Value::Primitive(PrimitiveValue::File(path) | PrimitiveValue::Directory(path)) => {
+                let path = work_dir.join(path.as_str());
+                path_size(&path).map_err(|e| {
+                    format!(
+                        "failed to calculate size of `{path}`: {e}",
+                        path = path.display()
+                    )
+                })
+            }
+            Value::Primitive(_) => Ok(0),
+            Value::Compound(v) => {
+                let mut size = 0;
+                match v {
+                    CompoundValue::Pair(v) => {
+                        size += value_size(work_dir, v.left())?;
+                        size += value_size(work_dir, v.right())?;
+                    }
+                    CompoundValue::Array(v) => {
+                        for e in v.elements() {
+                            size += value_size(work_dir, e)?;
+                        }
+                    }
+                    CompoundValue::Map(v) => {
+                        for (k, v) in v.elements() {
+                            size += value_size(work_dir, &k.clone().into())?;
+                            size += value_size(work_dir, v)?;
+                        }
+                    }
+                    CompoundValue::Object(v) => {
+                        for v in v.members().values() {
+                            size += value_size(work_dir, v)?;
+                        }
+                    }
+                    CompoundValue::Struct(v) => {
+                        for v in v.members().values() {
+                            size += value_size(work_dir, v)?;
+                        }
+                    }
+                }
+
+                Ok(size)
+            }
+        }
+    }
+
+    let work_dir = context.work_dir("size")?;
+    let multiplier = match context.arguments.get(1) {
+        Some(unit) => {
+            let s = unit.value.as_string().expect("value should be a string");
+            unit_multiplier(s).ok_or_else(|| {
+                function_call_failed("size", format!("invalid size unit `{s}`"), unit.span)
+            })?
+        }
+        None => 1.0,
+    };
+
+    // A `String` argument is treated as a path
+    let value = match &context.arguments[0].value {
+        Value::Primitive(PrimitiveValue::String(path)) => PrimitiveValue::File(path.clone()).into(),
+        value => value.clone(),
+    };
+
+    let size = value_size(work_dir, &value)
+        .map_err(|e| function_call_failed("size", e, context.arguments[0].span))?;
+    Ok((size as f64 / multiplier).into())
+}
+
+/// Reads an entire file as a string, with any trailing end-of-line characters
+/// removed.
+///
+/// `String read_string(File)`
+pub(super) fn read_string(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let contents = context.read_argument("read_string", 0)?;
+    Ok(contents.trim_end_matches(['\r', '\n']).to_string().into())
+}
+
+/// Reads a file that contains a single line containing only an integer and
+/// (optional) whitespace.
+///
+/// `Int read_int(File)`
+pub(super) fn read_int(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let contents = context.read_argument("read_int", 0)?;
+    contents.trim().parse::<i64>().map(Into::into).map_err(|_| {
+        function_call_failed(
+            "read_int",
+            "file does not contain a single integer value",
+            context.arguments[0].span,
+        )
+    })
+}
+
+/// Reads a file that contains only a numeric value and (optional)
+/// whitespace.
+///
+/// `Float read_float(File)`
+pub(super) fn read_float(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let contents = context.read_argument("read_float", 0)?;
+    contents.trim().parse::<f64>().map(Into::into).map_err(|_| {
+        function_call_failed(
+            "read_float",
+            "file does not contain a single float value",
+            context.arguments[0].span,
+        )
+    })
+}
+
+/// Reads a file that contains a single line containing only a boolean value
+/// and (optional) whitespace.
+///
+/// `Boolean read_boolean(File)`
+pub(super) fn read_boolean(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let contents = context.read_argument("read_boolean", 0)?;
+    match contents.trim().to_lowercase().as_str() {
+        "true" => Ok(true.into()),
+        "false" => Ok(false.into()),
+        _ => Err(function_call_failed(
+            "read_boolean",
+            "file does not contain a single boolean value",
+            context.arguments[0].span,
+        )),
+    }
+}
+
+/// Reads each line of a file as a `String`, and returns all lines in the file
+/// as an `Array[String]`.
+///
+/// `Array[String] read_lines(File)`
+pub(super) fn read_lines(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let contents = context.read_argument("read_lines", 0)?;
+    let elements = lines(&contents).map(|l| l.to_string().into()).collect();
+    Ok(context.new_array(elements))
+}
+
+/// Writes a file with one line for each element in an `Array[String]`.
+///
+/// `File write_lines(Array[String])`
+pub(super) fn write_lines(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let mut contents = String::new();
+    for line in context.raw_elements(0) {
+        contents.push_str(&line);
+        contents.push('\n');
+    }
+
+    context.write_file("write_lines", &contents)
+}
+
+/// Reads a tab-separated value (TSV) file.
+///
+/// `Array[Array[String]] read_tsv(File)`
+/// `Array[Object] read_tsv(File, Boolean)`
+/// `Array[Object] read_tsv(File, Boolean, Array[String])`
+pub(super) fn read_tsv(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let contents = context.read_argument("read_tsv", 0)?;
+    let mut lines = lines(&contents);
+
+    // Without a header, return an array of arrays
+    let header = match context.arguments.get(1) {
+        Some(header) => header
+            .value
+            .as_boolean()
+            .expect("value should be a boolean"),
+        None => {
+            let elements = lines
+                .map(|l| {
+                    let row = l.split('\t').map(|f| f.to_string().into()).collect();
+                    Array::new_unchecked(context.element_type(context.return_type), row).into()
+                })
+                .collect();
+            return Ok(context.new_array(elements));
+        }
+    };
+
+    let names: Vec<String> = match context.arguments.get(2) {
+        Some(_) => {
+            if header {
+                lines.next();
+            }
+
+            context.raw_elements(2).collect()
+        }
+        None if header => match lines.next() {
+            Some(line) => line.split('\t').map(str::to_string).collect(),
+            None => {
+                return Err(function_call_failed(
+                    "read_tsv",
+                    "file is empty and does not contain a header",
+                    context.arguments[0].span,
+                ));
+            }
+        },
+        None => {
+            return Err(function_call_failed(
+                "read_tsv",
+                "column names must be specified when the file does not have a header",
+                context.call_site,
+            ));
+        }
+    };
+
+    let mut elements = Vec::new();
+    for (i, line) in lines.enumerate() {
+        let fields: Vec<_> = line.split('\t').collect();
+        if fields.len() != names.len() {
+            return Err(function_call_failed(
+                "read_tsv",
+                format!(
+                    "expected {expected} column{s} in row {row}, but found {actual}",
+                    expected = names.len(),
+                    s = if names.len() == 1 { "" } else { "s" },
+                    row = i + 1,
+                    actual = fields.len()
+                ),
+                context.arguments[0].span,
+            ));
+        }
+
+        elements.push(
+            Object::new(
+                names
+                    .iter()
+                    .zip(fields)
+                    .map(|(n, f)| (n.clone(), f.to_string().into()))
+                    .collect(),
+            )
+            .into(),
+        );
+    }
+
+    Ok(context.new_array(elements))
+}
+
+/// Writes a tab-separated value (TSV) file.
+///
+/// `File write_tsv(Array[Array[String]])`
+/// `File write_tsv(Array[S])`
+/// `File write_tsv(Array[Array[String]], Boolean, Array[String])`
+/// `File write_tsv(Array[S], Boolean, Array[String])`
+pub(super) fn write_tsv(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let rows = context.array_argument(0).elements();
+    let write_header = context
+        .arguments
+        .get(1)
+        .map(|a| a.value.as_boolean().expect("value should be a boolean"))
+        .unwrap_or(false);
+
+    let mut contents = String::new();
+    let mut write_row = |fields: &mut dyn Iterator<Item = Result<String, String>>| {
+        for (i, field) in fields.enumerate() {
+            if i > 0 {
+                contents.push('\t');
+            }
+
+            contents.push_str(&field?);
+        }
+
+        contents.push('\n');
+        Ok::<_, String>(())
+    };
+
+    let result = (|| {
+        if write_header {
+            let names: Vec<_> = context.raw_elements(2).collect();
+            write_row(&mut names.into_iter().map(|n| {
+                if n.contains('\t') {
+                    return Err(format!(
+                        "column name `{n}` cannot be written to a TSV file as it contains a tab \
+                         character"
+                    ));
+                }
+
+                Ok(n)
+            }))?;
+        }
+
+        for row in rows {
+            match row {
+                Value::Compound(CompoundValue::Array(a)) => {
+                    write_row(&mut a.elements().iter().map(tsv_field))?
+                }
+                Value::Compound(CompoundValue::Struct(s)) => {
+                    write_row(&mut s.members().values().map(tsv_field))?
+                }
+                _ => unreachable!("value should be an array or struct"),
+            }
+        }
+
+        Ok::<_, String>(())
+    })();
+
+    result.map_err(|e| function_call_failed("write_tsv", e, context.arguments[0].span))?;
+    context.write_file("write_tsv", &contents)
+}
+
+/// Reads a tab-separated value (TSV) file representing a set of pairs.
+///
+/// Each row must have exactly two columns.
+///
+/// `Map[String, String] read_map(File)`
+pub(super) fn read_map(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let contents = context.read_argument("read_map", 0)?;
+    let mut elements = IndexMap::new();
+    for (i, line) in lines(&contents).enumerate() {
+        let (key, value) = match line.split_once('\t') {
+            Some((key, value)) if !value.contains('\t') => (key, value),
+            _ => {
+                return Err(function_call_failed(
+                    "read_map",
+                    format!("expected 2 columns in row {row}", row = i + 1),
+                    context.arguments[0].span,
+                ));
+            }
+        };
+
+        if elements
+            .insert(PrimitiveValue::new_string(key), value.to_string().into())
+            .is_some()
+        {
+            return Err(function_call_failed(
+                "read_map",
+                format!("file contains duplicate key `{key}`"),
+                context.arguments[0].span,
+            ));
+        }
+    }
+
+    Ok(Map::new_unchecked(context.return_type, elements).into())
+}
+
+/// Writes a tab-separated value (TSV) file with one line for each element in
+/// a `Map[String, String]`.
+///
+/// `File write_map(Map[String, String])`
+pub(super) fn write_map(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let map = context.arguments[0]
+        .value
+        .as_map()
+        .expect("value should be a map");
+
+    let mut contents = String::new();
+    for (key, value) in map.elements() {
+        let key = tsv_field(&key.clone().into())
+            .map_err(|e| function_call_failed("write_map", e, context.arguments[0].span))?;
+        let value = tsv_field(value)
+            .map_err(|e| function_call_failed("write_map", e, context.arguments[0].span))?;
+        writeln!(contents, "{key}\t{value}").unwrap();
+    }
+
+    context.write_file("write_map", &contents)
+}
+
+/// Reads a JSON file into a WDL value whose type depends on the file's
+/// contents.
+///
+/// `Union read_json(File)`
+pub(super) fn read_json(mut context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let contents = context.read_argument("read_json", 0)?;
+    let json = serde_json::from_str(&contents).map_err(|e| {
+        function_call_failed(
+            "read_json",
+            format!("invalid JSON: {e}"),
+            context.arguments[0].span,
+        )
+    })?;
+
+    let span = context.arguments[0].span;
+    Value::from_json(context.types_mut(), json)
+        .map_err(|e| function_call_failed("read_json", format!("{e:#}"), span))
+}
+
+/// Writes a JSON file with the serialized form of a WDL value.
+///
+/// `File write_json(X)`
+pub(super) fn write_json(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let json = context.arguments[0]
+        .value
+        .to_json()
+        .map_err(|e| function_call_failed("write_json", format!("{e:#}"), context.call_site))?;
+    context.write_file(
+        "write_json",
+        &serde_json::to_string(&json).expect("JSON should serialize"),
+    )
+}
+
+/// Reads the objects of a tab-separated value (TSV) file with a header row.
+fn read_objects_from(
+    context: &CallContext<'_>,
+    name: &str,
+    contents: &str,
+) -> Result<Vec<Value>, Diagnostic> {
+    let mut lines = lines(contents);
+    let names: Vec<_> = match lines.next() {
+        Some(line) => line.split('\t').collect(),
+        None => return Ok(Vec::new()),
+    };
+
+    lines
+        .enumerate()
+        .map(|(i, line)| {
+            let fields: Vec<_> = line.split('\t').collect();
+            if fields.len() != names.len() {
+                return Err(function_call_failed(
+                    name,
+                    format!(
+                        "expected {expected} column{s} in row {row}, but found {actual}",
+                        expected = names.len(),
+                        s = if names.len() == 1 { "" } else { "s" },
+                        row = i + 1,
+                        actual = fields.len()
+                    ),
+                    context.arguments[0].span,
+                ));
+            }
+
+            Ok(Object::new(
+                names
+                    .iter()
+                    .zip(fields)
+                    .map(|(n, f)| (n.to_string(), f.to_string().into()))
+                    .collect(),
+            )
+            .into())
+        })
+        .collect()
+}
+
+/// Reads a tab-separated value (TSV) file representing the names and values
+/// of the members of an `Object`.
+///
+/// `Object read_object(File)`
+pub(super) fn read_object(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let contents = context.read_argument("read_object", 0)?;
+    let mut objects = read_objects_from(&context, "read_object", &contents)?;
+    if objects.len() != 1 {
+        return Err(function_call_failed(
+            "read_object",
+            format!(
+                "expected a file with exactly two rows, but found {count}",
+                count = lines(&contents).count()
+            ),
+            context.arguments[0].span,
+        ));
+    }
+
+    Ok(objects.pop().unwrap())
+}
+
+/// Reads a tab-separated value (TSV) file representing the names and values
+/// of the members of any number of `Object`s.
+///
+/// `Array[Object] read_objects(File)`
+pub(super) fn read_objects(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let contents = context.read_argument("read_objects", 0)?;
+    let objects = read_objects_from(&context, "read_objects", &contents)?;
+    Ok(context.new_array(objects))
+}
+
+/// Gets the members of an object or struct value.
+fn members(value: &Value) -> &IndexMap<String, Value> {
+    match value {
+        Value::Compound(CompoundValue::Object(o)) => o.members(),
+        Value::Compound(CompoundValue::Struct(s)) => s.members(),
+        _ => unreachable!("value should be an object or struct"),
+    }
+}
+
+/// Writes a tab-separated value (TSV) file with the contents of a `Object` or
+/// struct.
+///
+/// `File write_object(Object)`
+/// `File write_object(S)`
+pub(super) fn write_object(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    write_objects_to(context, "write_object", |c| {
+        vec![c.arguments[0].value.clone()]
+    })
+}
+
+/// Writes a tab-separated value (TSV) file with the contents of a
+/// `Array[Object]` or `Array[S]`.
+///
+/// `File write_objects(Array[Object])`
+/// `File write_objects(Array[S])`
+pub(super) fn write_objects(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    write_objects_to(context, "write_objects", |c| {
+        c.array_argument(0).elements().to_vec()
+    })
+}
+
+/// Writes the given objects or structs to a TSV file with a header row.
+fn write_objects_to(
+    context: CallContext<'_>,
+    name: &str,
+    values: impl FnOnce(&CallContext<'_>) -> Vec<Value>,
+) -> Result<Value, Diagnostic> {
+    let values = values(&context);
+    let mut contents = String::new();
+    let result = (|| {
+        let Some(first) = values.first() else {
+            return Ok(());
+        };
+
+        let names: Vec<_> = members(first).keys().collect();
+        for (i, n) in names.iter().enumerate() {
+            if i > 0 {
+                contents.push('\t');
+            }
+
+            contents.push_str(n);
+        }
+
+        contents.push('\n');
+
+        for value in &values {
+            let members = members(value);
+            if members.len() != names.len() || !members.keys().zip(&names).all(|(a, b)| a == *b) {
+                return Err("objects must have the same member names".to_string());
+            }
+
+            for (i, v) in members.values().enumerate() {
+                if i > 0 {
+                    contents.push('\t');
+                }
+
+                contents.push_str(&tsv_field(v)?);
+            }
+
+            contents.push('\n');
+        }
+
+        Ok(())
+    })();
+
+    result.map_err(|e| function_call_failed(name, e, context.arguments[0].span))?;
+    context.write_file(name, &contents)
+}
+
+/// Returns the value of the executed command's standard output.
+///
+/// `File stdout()`
+pub(super) fn stdout(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    context.context.stdout().cloned().ok_or_else(|| {
+        function_call_failed(
+            "stdout",
+            "the standard output of a task is not available in this context",
+            context.call_site,
+        )
+    })
+}
+
+/// Returns the value of the executed command's standard error.
+///
+/// `File stderr()`
+pub(super) fn stderr(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    context.context.stderr().cloned().ok_or_else(|| {
+        function_call_failed(
+            "stderr",
+            "the standard error of a task is not available in this context",
+            context.call_site,
+        )
+    })
+}
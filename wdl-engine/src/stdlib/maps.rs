@@ -0,0 +1,197 @@
+//! Implementation of the map functions of the standard library.
+
+use indexmap::IndexMap;
+use wdl_ast::Diagnostic;
+
+use super::function_call_failed;
+use super::CallContext;
+use crate::Array;
+use crate::CompoundValue;
+use crate::Map;
+use crate::Pair;
+use crate::PrimitiveValue;
+use crate::Value;
+
+impl CallContext<'_> {
+    /// Gets the argument at the given index as a map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is not a map.
+    fn map_argument(&self, index: usize) -> &Map {
+        self.arguments[index]
+            .value
+            .as_map()
+            .expect("value should be a map")
+    }
+
+    /// Gets the pairs of the array argument at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the argument is not an array of pairs with primitive left
+    /// values.
+    fn pairs_argument(&self, index: usize) -> impl Iterator<Item = (&PrimitiveValue, &Value)> {
+        self.arguments[index]
+            .value
+            .as_array()
+            .expect("value should be an array")
+            .elements()
+            .iter()
+            .map(|e| {
+                let pair = e.as_pair().expect("element should be a pair");
+                (
+                    pair.left()
+                        .as_primitive()
+                        .expect("left value should be primitive"),
+                    pair.right(),
+                )
+            })
+    }
+}
+
+/// Converts a `Map` into an `Array` of `Pair`s.
+///
+/// `Array[Pair[K, V]] as_pairs(Map[K, V])`
+pub(super) fn as_pairs(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let pair_ty = context.element_type(context.return_type);
+    let elements = context
+        .map_argument(0)
+        .elements()
+        .iter()
+        .map(|(k, v)| Pair::new_unchecked(pair_ty, k.clone().into(), v.clone()).into())
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Converts an `Array` of `Pair`s into a `Map` in which the left elements of
+/// the `Pair`s are the keys and the right elements the values.
+///
+/// `Map[K, V] as_map(Array[Pair[K, V]])`
+pub(super) fn as_map(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let mut elements = IndexMap::new();
+    for (k, v) in context.pairs_argument(0) {
+        if elements.insert(k.clone(), v.clone()).is_some() {
+            return Err(function_call_failed(
+                "as_map",
+                format!("array contains a duplicate entry for map key {k}"),
+                context.arguments[0].span,
+            ));
+        }
+    }
+
+    Ok(Map::new_unchecked(context.return_type, elements).into())
+}
+
+/// Returns an `Array` of the keys of the given `Map`, or the member names of
+/// the given struct or `Object`.
+///
+/// `Array[K] keys(Map[K, V])`
+/// `Array[String] keys(S)`
+/// `Array[String] keys(Object)`
+pub(super) fn keys(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let elements = match &context.arguments[0].value {
+        Value::Compound(CompoundValue::Map(map)) => {
+            map.elements().keys().map(|k| k.clone().into()).collect()
+        }
+        Value::Compound(CompoundValue::Struct(s)) => s
+            .members()
+            .keys()
+            .map(|k| PrimitiveValue::new_string(k.clone()).into())
+            .collect(),
+        Value::Compound(CompoundValue::Object(o)) => o
+            .members()
+            .keys()
+            .map(|k| PrimitiveValue::new_string(k.clone()).into())
+            .collect(),
+        _ => unreachable!("value should be a map, struct, or object"),
+    };
+
+    Ok(context.new_array(elements))
+}
+
+/// Given a key-value type collection and a key, tests whether the collection
+/// contains an entry with the given key.
+///
+/// When given an array of keys, the keys are treated as a path into nested
+/// collections.
+///
+/// `Boolean contains_key(Map[K, V], K)`
+/// `Boolean contains_key(Object, String)`
+/// `Boolean contains_key(Map[String, V] | S | Object, Array[String])`
+pub(super) fn contains_key(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    /// Gets the value of a key in a collection value.
+    fn get<'a>(value: &'a Value, key: &PrimitiveValue) -> Option<&'a Value> {
+        match value {
+            Value::Compound(CompoundValue::Map(map)) => map.get(key).or_else(|| {
+                // Allow the key to match a `File` or `Directory` key by string
+                map.elements()
+                    .iter()
+                    .find(|(k, _)| PrimitiveValue::equals(k, key).unwrap_or(false))
+                    .map(|(_, v)| v)
+            }),
+            Value::Compound(CompoundValue::Struct(s)) => s.members().get(key.as_str()?),
+            Value::Compound(CompoundValue::Object(o)) => o.members().get(key.as_str()?),
+            _ => None,
+        }
+    }
+
+    let collection = &context.arguments[0].value;
+    let key = &context.arguments[1].value;
+    match key {
+        Value::Primitive(key) => Ok(get(collection, key).is_some().into()),
+        Value::Compound(_) => {
+            let path = key.as_array().expect("value should be an array");
+            let mut current = collection;
+            for key in path.elements() {
+                let key = key.as_primitive().expect("key should be primitive");
+                match get(current, key) {
+                    Some(value) => current = value,
+                    None => return Ok(false.into()),
+                }
+            }
+
+            Ok(true.into())
+        }
+        Value::None => Ok(false.into()),
+    }
+}
+
+/// Returns an `Array` of the values of the given `Map`.
+///
+/// `Array[V] values(Map[K, V])`
+pub(super) fn values(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let elements = context
+        .map_argument(0)
+        .elements()
+        .values()
+        .cloned()
+        .collect();
+    Ok(context.new_array(elements))
+}
+
+/// Given an `Array` of `Pair`s, creates a `Map` in which the right elements of
+/// the `Pair`s are grouped by the left elements.
+///
+/// `Map[K, Array[V]] collect_by_key(Array[Pair[K, V]])`
+pub(super) fn collect_by_key(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let mut groups: IndexMap<PrimitiveValue, Vec<Value>> = IndexMap::new();
+    for (k, v) in context.pairs_argument(0) {
+        groups.entry(k.clone()).or_default().push(v.clone());
+    }
+
+    let array_ty = context
+        .compound_definition(context.return_type)
+        .as_map()
+        .expect("type should be a map")
+        .value_type();
+
+    Ok(Map::new_unchecked(
+        context.return_type,
+        groups
+            .into_iter()
+            .map(|(k, v)| (k, Array::new_unchecked(array_ty, v).into()))
+            .collect(),
+    )
+    .into())
+}
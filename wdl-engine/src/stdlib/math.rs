@@ -0,0 +1,87 @@
+//! Implementation of the numeric functions of the standard library.
+
+use wdl_analysis::types::PrimitiveTypeKind;
+use wdl_ast::Diagnostic;
+
+use super::CallContext;
+use crate::Value;
+
+/// Rounds a floating point number down to the next lower integer.
+///
+/// `Int floor(Float)`
+pub(super) fn floor(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let value = context
+        .coerce_argument(0, PrimitiveTypeKind::Float)
+        .as_float()
+        .expect("value should be a float");
+    Ok((value.floor() as i64).into())
+}
+
+/// Rounds a floating point number up to the next higher integer.
+///
+/// `Int ceil(Float)`
+pub(super) fn ceil(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let value = context
+        .coerce_argument(0, PrimitiveTypeKind::Float)
+        .as_float()
+        .expect("value should be a float");
+    Ok((value.ceil() as i64).into())
+}
+
+/// Rounds a floating point number to the nearest integer based on standard
+/// rounding rules ("round half up").
+///
+/// `Int round(Float)`
+pub(super) fn round(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let value = context
+        .coerce_argument(0, PrimitiveTypeKind::Float)
+        .as_float()
+        .expect("value should be a float");
+    Ok(((value + 0.5).floor() as i64).into())
+}
+
+/// Returns the smaller of two values.
+///
+/// If both values are `Int`s, the return value is an `Int`, otherwise it is a
+/// `Float`.
+pub(super) fn min(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    if let (Some(a), Some(b)) = (
+        context.arguments[0].value.as_integer(),
+        context.arguments[1].value.as_integer(),
+    ) {
+        return Ok(a.min(b).into());
+    }
+
+    let a = context
+        .coerce_argument(0, PrimitiveTypeKind::Float)
+        .as_float()
+        .expect("value should be a float");
+    let b = context
+        .coerce_argument(1, PrimitiveTypeKind::Float)
+        .as_float()
+        .expect("value should be a float");
+    Ok(a.min(b).into())
+}
+
+/// Returns the larger of two values.
+///
+/// If both values are `Int`s, the return value is an `Int`, otherwise it is a
+/// `Float`.
+pub(super) fn max(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    if let (Some(a), Some(b)) = (
+        context.arguments[0].value.as_integer(),
+        context.arguments[1].value.as_integer(),
+    ) {
+        return Ok(a.max(b).into());
+    }
+
+    let a = context
+        .coerce_argument(0, PrimitiveTypeKind::Float)
+        .as_float()
+        .expect("value should be a float");
+    let b = context
+        .coerce_argument(1, PrimitiveTypeKind::Float)
+        .as_float()
+        .expect("value should be a float");
+    Ok(a.max(b).into())
+}
@@ -0,0 +1,33 @@
+//! Implementation of the remaining functions of the standard library.
+
+use wdl_ast::Diagnostic;
+
+use super::CallContext;
+use crate::CompoundValue;
+use crate::PrimitiveValue;
+use crate::Value;
+
+/// Tests whether the given optional value is defined, i.e., has a non-`None`
+/// value.
+///
+/// `Boolean defined(X?)`
+pub(super) fn defined(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    Ok((!context.arguments[0].value.is_none()).into())
+}
+
+/// Returns the length of the given array, map, object, or string.
+///
+/// `Int length(Array[X] | Map[K, V] | Object | String)`
+pub(super) fn length(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let len = match &context.arguments[0].value {
+        Value::Compound(CompoundValue::Array(v)) => v.len(),
+        Value::Compound(CompoundValue::Map(v)) => v.len(),
+        Value::Compound(CompoundValue::Object(v)) => v.members().len(),
+        Value::Primitive(PrimitiveValue::String(s)) => s.chars().count(),
+        _ => unreachable!("value should be an array, map, object, or string"),
+    };
+
+    Ok(i64::try_from(len)
+        .expect("length should fit in an integer")
+        .into())
+}
@@ -0,0 +1,89 @@
+//! Implementation of the path functions of the standard library.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use wdl_ast::Diagnostic;
+
+use super::function_call_failed;
+use super::CallContext;
+use crate::PrimitiveValue;
+use crate::Value;
+
+/// Returns the "basename" of a file or directory - the name after the last
+/// directory separator in the path.
+///
+/// If the optional `suffix` parameter is specified and the basename ends with
+/// the suffix, the suffix is removed.
+///
+/// `String basename(File|String|Directory, [String])`
+pub(super) fn basename(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let path = context.arguments[0]
+        .value
+        .as_primitive()
+        .and_then(PrimitiveValue::as_str)
+        .expect("value should be a string");
+
+    let trimmed = path.trim_end_matches('/');
+    let mut base = match trimmed.rfind('/') {
+        Some(index) => &trimmed[index + 1..],
+        None if trimmed.is_empty() && !path.is_empty() => "/",
+        None => trimmed,
+    };
+
+    if let Some(suffix) = context.arguments.get(1) {
+        let suffix = suffix.value.as_string().expect("value should be a string");
+        if base.len() > suffix.len() {
+            base = base.strip_suffix(suffix.as_str()).unwrap_or(base);
+        }
+    }
+
+    Ok(base.to_string().into())
+}
+
+/// Joins together two or more paths into an absolute path in the execution
+/// filesystem.
+///
+/// `File join_paths(File, String)`
+/// `File join_paths(File, Array[String]+)`
+/// `File join_paths(Array[String]+)`
+pub(super) fn join_paths(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let mut paths = Vec::new();
+    for argument in context.arguments {
+        match &argument.value {
+            Value::Primitive(v) => paths.push((
+                v.as_str().expect("value should be a string").to_string(),
+                argument.span,
+            )),
+            Value::Compound(_) => {
+                let array = argument.value.as_array().expect("value should be an array");
+                for element in array.elements() {
+                    paths.push((
+                        element
+                            .as_primitive()
+                            .and_then(PrimitiveValue::as_str)
+                            .expect("value should be a string")
+                            .to_string(),
+                        argument.span,
+                    ));
+                }
+            }
+            Value::None => unreachable!("argument should not be `None`"),
+        }
+    }
+
+    let mut result = PathBuf::new();
+    for (i, (path, span)) in paths.iter().enumerate() {
+        if i > 0 && Path::new(path).is_absolute() {
+            return Err(function_call_failed(
+                "join_paths",
+                format!("path `{path}` is absolute"),
+                *span,
+            ));
+        }
+
+        result.push(path);
+    }
+
+    Ok(PrimitiveValue::new_file(result.to_string_lossy()).into())
+}
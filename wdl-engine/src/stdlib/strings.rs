@@ -0,0 +1,63 @@
+//! Implementation of the string functions of the standard library.
+
+use regex::NoExpand;
+use regex::Regex;
+use wdl_analysis::types::PrimitiveTypeKind;
+use wdl_ast::Diagnostic;
+
+use super::function_call_failed;
+use super::CallContext;
+use crate::Value;
+
+/// Compiles the regular expression pattern of the argument at the given
+/// index.
+fn pattern(context: &CallContext<'_>, name: &str, index: usize) -> Result<Regex, Diagnostic> {
+    let pattern = context.coerce_argument(index, PrimitiveTypeKind::String);
+    Regex::new(pattern.as_string().expect("value should be a string"))
+        .map_err(|e| function_call_failed(name, e, context.arguments[index].span))
+}
+
+/// Given two `String` parameters `input` and `pattern`, searches for the
+/// occurrence of `pattern` within `input` and returns the first match or
+/// `None` if there are no matches.
+///
+/// `String? find(String, String)`
+pub(super) fn find(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let input = context.coerce_argument(0, PrimitiveTypeKind::String);
+    let regex = pattern(&context, "find", 1)?;
+    Ok(
+        match regex.find(input.as_string().expect("value should be a string")) {
+            Some(m) => m.as_str().to_string().into(),
+            None => Value::None,
+        },
+    )
+}
+
+/// Given two `String` parameters `input` and `pattern`, tests whether
+/// `pattern` matches `input` at least once.
+///
+/// `Boolean matches(String, String)`
+pub(super) fn matches(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let input = context.coerce_argument(0, PrimitiveTypeKind::String);
+    let regex = pattern(&context, "matches", 1)?;
+    Ok(regex
+        .is_match(input.as_string().expect("value should be a string"))
+        .into())
+}
+
+/// Given three `String` parameters `input`, `pattern`, and `replace`, replaces
+/// all non-overlapping occurrences of `pattern` in `input` by `replace`.
+///
+/// `String sub(String, String, String)`
+pub(super) fn sub(context: CallContext<'_>) -> Result<Value, Diagnostic> {
+    let input = context.coerce_argument(0, PrimitiveTypeKind::String);
+    let regex = pattern(&context, "sub", 1)?;
+    let replacement = context.coerce_argument(2, PrimitiveTypeKind::String);
+    Ok(regex
+        .replace_all(
+            input.as_string().expect("value should be a string"),
+            NoExpand(replacement.as_string().expect("value should be a string")),
+        )
+        .into_owned()
+        .into())
+}
@@ -0,0 +1,1293 @@
+//! Implementation of the WDL runtime and values.
+
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use indexmap::IndexMap;
+use ordered_float::OrderedFloat;
+use serde_json::Value as JsonValue;
+use wdl_analysis::types::ArrayType;
+use wdl_analysis::types::CompoundTypeDef;
+use wdl_analysis::types::Optional;
+use wdl_analysis::types::PrimitiveType;
+use wdl_analysis::types::PrimitiveTypeKind;
+use wdl_analysis::types::Type;
+use wdl_analysis::types::Types;
+
+use crate::eval::v1::common_type;
+
+/// Represents a WDL runtime value.
+///
+/// Values are cheap to clone.
+#[derive(Debug, Clone)]
+pub enum Value {
+    /// The value is a literal `None` value.
+    None,
+    /// The value is a primitive value.
+    Primitive(PrimitiveValue),
+    /// The value is a compound value.
+    Compound(CompoundValue),
+}
+
+impl Value {
+    /// Gets the type of the value.
+    pub fn ty(&self) -> Type {
+        match self {
+            Self::None => Type::None,
+            Self::Primitive(v) => v.ty(),
+            Self::Compound(v) => v.ty(),
+        }
+    }
+
+    /// Determines if the value is `None`.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// Gets the value as a primitive value.
+    ///
+    /// Returns `None` if the value is not a primitive value.
+    pub fn as_primitive(&self) -> Option<&PrimitiveValue> {
+        match self {
+            Self::Primitive(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a compound value.
+    ///
+    /// Returns `None` if the value is not a compound value.
+    pub fn as_compound(&self) -> Option<&CompoundValue> {
+        match self {
+            Self::Compound(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a `Boolean`.
+    ///
+    /// Returns `None` if the value is not a `Boolean`.
+    pub fn as_boolean(&self) -> Option<bool> {
+        match self {
+            Self::Primitive(PrimitiveValue::Boolean(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as an `Int`.
+    ///
+    /// Returns `None` if the value is not an `Int`.
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Self::Primitive(PrimitiveValue::Integer(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a `Float`.
+    ///
+    /// Returns `None` if the value is not a `Float`.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Primitive(PrimitiveValue::Float(v)) => Some(v.0),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a `String`.
+    ///
+    /// Returns `None` if the value is not a `String`.
+    pub fn as_string(&self) -> Option<&Arc<String>> {
+        match self {
+            Self::Primitive(PrimitiveValue::String(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a `File`.
+    ///
+    /// Returns `None` if the value is not a `File`.
+    pub fn as_file(&self) -> Option<&Arc<String>> {
+        match self {
+            Self::Primitive(PrimitiveValue::File(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a `Directory`.
+    ///
+    /// Returns `None` if the value is not a `Directory`.
+    pub fn as_directory(&self) -> Option<&Arc<String>> {
+        match self {
+            Self::Primitive(PrimitiveValue::Directory(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a `Pair`.
+    ///
+    /// Returns `None` if the value is not a `Pair`.
+    pub fn as_pair(&self) -> Option<&Pair> {
+        match self {
+            Self::Compound(CompoundValue::Pair(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as an `Array`.
+    ///
+    /// Returns `None` if the value is not an `Array`.
+    pub fn as_array(&self) -> Option<&Array> {
+        match self {
+            Self::Compound(CompoundValue::Array(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a `Map`.
+    ///
+    /// Returns `None` if the value is not a `Map`.
+    pub fn as_map(&self) -> Option<&Map> {
+        match self {
+            Self::Compound(CompoundValue::Map(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as an `Object`.
+    ///
+    /// Returns `None` if the value is not an `Object`.
+    pub fn as_object(&self) -> Option<&Object> {
+        match self {
+            Self::Compound(CompoundValue::Object(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as a struct.
+    ///
+    /// Returns `None` if the value is not a struct.
+    pub fn as_struct(&self) -> Option<&Struct> {
+        match self {
+            Self::Compound(CompoundValue::Struct(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Coerces the value to the given target type.
+    ///
+    /// The target type must be defined in the given types collection.
+    ///
+    /// Returns an error if the value cannot be coerced.
+    pub fn coerce(&self, types: &Types, target: Type) -> Result<Self> {
+        match (self, target) {
+            // Any value may be "coerced" to the indeterminate type
+            (_, Type::Union) => Ok(self.clone()),
+            (Self::None, _) => {
+                if target.is_optional() {
+                    return Ok(Self::None);
+                }
+
+                bail!(
+                    "cannot coerce `None` to non-optional type `{target}`",
+                    target = target.display(types)
+                );
+            }
+            (Self::Primitive(v), Type::Primitive(ty)) => v.coerce(ty.kind()).map(Self::Primitive),
+            (Self::Compound(v), _) => v.coerce(types, target).map(Self::Compound),
+            _ => bail!(
+                "cannot coerce type `{actual}` to type `{target}`",
+                actual = self.ty().display(types),
+                target = target.display(types)
+            ),
+        }
+    }
+
+    /// Determines if two values are equal according to the rules of WDL
+    /// equality.
+    ///
+    /// Returns `None` if the two values cannot be compared for equality.
+    pub fn equals(left: &Self, right: &Self) -> Option<bool> {
+        match (left, right) {
+            (Self::None, Self::None) => Some(true),
+            (Self::None, _) | (_, Self::None) => Some(false),
+            (Self::Primitive(left), Self::Primitive(right)) => PrimitiveValue::equals(left, right),
+            (Self::Compound(left), Self::Compound(right)) => CompoundValue::equals(left, right),
+            _ => None,
+        }
+    }
+
+    /// Returns an object that implements `Display` for formatting the value as
+    /// it would appear interpolated into a string.
+    ///
+    /// Returns `None` if the value cannot be interpolated.
+    pub fn raw(&self) -> Option<impl fmt::Display + '_> {
+        /// Helper for displaying a raw value.
+        struct Display<'a>(&'a Value);
+
+        impl fmt::Display for Display<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.0 {
+                    Value::None => Ok(()),
+                    Value::Primitive(v) => v.raw().fmt(f),
+                    Value::Compound(_) => unreachable!("value should not be compound"),
+                }
+            }
+        }
+
+        match self {
+            Self::None | Self::Primitive(_) => Some(Display(self)),
+            Self::Compound(_) => None,
+        }
+    }
+
+    /// Creates a value from a JSON value.
+    ///
+    /// The type of the resulting value is inferred from the JSON value: JSON
+    /// objects become `Object` values and JSON arrays become arrays of the
+    /// common type of their elements.
+    ///
+    /// Returns an error if the elements of a JSON array do not have a common
+    /// type.
+    pub fn from_json(types: &mut Types, json: JsonValue) -> Result<Self> {
+        match json {
+            JsonValue::Null => Ok(Self::None),
+            JsonValue::Bool(v) => Ok(v.into()),
+            JsonValue::Number(v) => match v.as_i64() {
+                Some(v) => Ok(v.into()),
+                None => Ok(v
+                    .as_f64()
+                    .with_context(|| format!("number `{v}` is out of range for a WDL value"))?
+                    .into()),
+            },
+            JsonValue::String(v) => Ok(v.into()),
+            JsonValue::Array(elements) => {
+                let elements = elements
+                    .into_iter()
+                    .map(|e| Self::from_json(types, e))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let mut element_ty: Option<Type> = None;
+                for element in &elements {
+                    element_ty = match element_ty {
+                        Some(ty) => {
+                            Some(common_type(types, ty, element.ty()).with_context(|| {
+                                format!(
+                                    "JSON array elements of type `{expected}` and type `{actual}` \
+                                     do not have a common type",
+                                    expected = ty.display(types),
+                                    actual = element.ty().display(types)
+                                )
+                            })?)
+                        }
+                        None => Some(element.ty()),
+                    };
+                }
+
+                let ty = types.add_array(ArrayType::new(element_ty.unwrap_or(Type::Union)));
+                Ok(Array::new(types, ty, elements)?.into())
+            }
+            JsonValue::Object(members) => Ok(Object::new(
+                members
+                    .into_iter()
+                    .map(|(k, v)| Ok((k, Self::from_json(types, v)?)))
+                    .collect::<Result<_>>()?,
+            )
+            .into()),
+        }
+    }
+
+    /// Converts the value to a JSON value.
+    ///
+    /// `Pair` values are represented as JSON objects with `left` and `right`
+    /// members.
+    ///
+    /// Returns an error if the value cannot be represented in JSON.
+    pub fn to_json(&self) -> Result<JsonValue> {
+        match self {
+            Self::None => Ok(JsonValue::Null),
+            Self::Primitive(v) => match v {
+                PrimitiveValue::Boolean(v) => Ok((*v).into()),
+                PrimitiveValue::Integer(v) => Ok((*v).into()),
+                PrimitiveValue::Float(v) => Ok(serde_json::Number::from_f64(v.0)
+                    .with_context(|| format!("float `{v}` cannot be represented in JSON"))?
+                    .into()),
+                PrimitiveValue::String(s)
+                | PrimitiveValue::File(s)
+                | PrimitiveValue::Directory(s) => Ok(s.as_str().into()),
+            },
+            Self::Compound(v) => match v {
+                CompoundValue::Pair(v) => Ok(serde_json::json!({
+                    "left": v.left().to_json()?,
+                    "right": v.right().to_json()?,
+                })),
+                CompoundValue::Array(v) => Ok(JsonValue::Array(
+                    v.elements()
+                        .iter()
+                        .map(Self::to_json)
+                        .collect::<Result<_>>()?,
+                )),
+                CompoundValue::Map(v) => Ok(JsonValue::Object(
+                    v.elements()
+                        .iter()
+                        .map(|(k, v)| Ok((k.raw().to_string(), v.to_json()?)))
+                        .collect::<Result<_>>()?,
+                )),
+                CompoundValue::Object(Object { members })
+                | CompoundValue::Struct(Struct { members, .. }) => Ok(JsonValue::Object(
+                    members
+                        .iter()
+                        .map(|(k, v)| Ok((k.clone(), v.to_json()?)))
+                        .collect::<Result<_>>()?,
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::Primitive(v) => v.fmt(f),
+            Self::Compound(v) => v.fmt(f),
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::Primitive(PrimitiveValue::Boolean(value))
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Self::Primitive(PrimitiveValue::Integer(value))
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Primitive(PrimitiveValue::Float(value.into()))
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::Primitive(PrimitiveValue::String(value.into()))
+    }
+}
+
+impl From<PrimitiveValue> for Value {
+    fn from(value: PrimitiveValue) -> Self {
+        Self::Primitive(value)
+    }
+}
+
+impl From<CompoundValue> for Value {
+    fn from(value: CompoundValue) -> Self {
+        Self::Compound(value)
+    }
+}
+
+impl From<Pair> for Value {
+    fn from(value: Pair) -> Self {
+        Self::Compound(CompoundValue::Pair(value))
+    }
+}
+
+impl From<Array> for Value {
+    fn from(value: Array) -> Self {
+        Self::Compound(CompoundValue::Array(value))
+    }
+}
+
+impl From<Map> for Value {
+    fn from(value: Map) -> Self {
+        Self::Compound(CompoundValue::Map(value))
+    }
+}
+
+impl From<Object> for Value {
+    fn from(value: Object) -> Self {
+        Self::Compound(CompoundValue::Object(value))
+    }
+}
+
+impl From<Struct> for Value {
+    fn from(value: Struct) -> Self {
+        Self::Compound(CompoundValue::Struct(value))
+    }
+}
+
+/// Represents a primitive WDL value.
+///
+/// Primitive values are hashable so that they may be used as `Map` keys.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PrimitiveValue {
+    /// The value is a `Boolean`.
+    Boolean(bool),
+    /// The value is an `Int`.
+    Integer(i64),
+    /// The value is a `Float`.
+    Float(OrderedFloat<f64>),
+    /// The value is a `String`.
+    String(Arc<String>),
+    /// The value is a `File`.
+    File(Arc<String>),
+    /// The value is a `Directory`.
+    Directory(Arc<String>),
+}
+
+impl PrimitiveValue {
+    /// Creates a new `String` value.
+    pub fn new_string(s: impl Into<String>) -> Self {
+        Self::String(Arc::new(s.into()))
+    }
+
+    /// Creates a new `File` value.
+    pub fn new_file(s: impl Into<String>) -> Self {
+        Self::File(Arc::new(s.into()))
+    }
+
+    /// Creates a new `Directory` value.
+    pub fn new_directory(s: impl Into<String>) -> Self {
+        Self::Directory(Arc::new(s.into()))
+    }
+
+    /// Gets the type of the value.
+    pub fn ty(&self) -> Type {
+        self.kind().into()
+    }
+
+    /// Gets the primitive type kind of the value.
+    pub fn kind(&self) -> PrimitiveTypeKind {
+        match self {
+            Self::Boolean(_) => PrimitiveTypeKind::Boolean,
+            Self::Integer(_) => PrimitiveTypeKind::Integer,
+            Self::Float(_) => PrimitiveTypeKind::Float,
+            Self::String(_) => PrimitiveTypeKind::String,
+            Self::File(_) => PrimitiveTypeKind::File,
+            Self::Directory(_) => PrimitiveTypeKind::Directory,
+        }
+    }
+
+    /// Gets the string representation of a `String`, `File`, or `Directory`
+    /// value.
+    ///
+    /// Returns `None` for other primitive values.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) | Self::File(s) | Self::Directory(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Coerces the value to the given primitive type kind.
+    ///
+    /// Returns an error if the value cannot be coerced.
+    pub fn coerce(&self, target: PrimitiveTypeKind) -> Result<Self> {
+        match (self, target) {
+            (Self::Boolean(_), PrimitiveTypeKind::Boolean)
+            | (Self::Integer(_), PrimitiveTypeKind::Integer)
+            | (Self::Float(_), PrimitiveTypeKind::Float)
+            | (Self::String(_), PrimitiveTypeKind::String)
+            | (Self::File(_), PrimitiveTypeKind::File)
+            | (Self::Directory(_), PrimitiveTypeKind::Directory) => Ok(self.clone()),
+            // Int -> Float
+            (Self::Integer(v), PrimitiveTypeKind::Float) => Ok(Self::Float((*v as f64).into())),
+            // String -> File, String -> Directory
+            (Self::String(s), PrimitiveTypeKind::File) => Ok(Self::File(s.clone())),
+            (Self::String(s), PrimitiveTypeKind::Directory) => Ok(Self::Directory(s.clone())),
+            // File -> String, Directory -> String
+            (Self::File(s), PrimitiveTypeKind::String)
+            | (Self::Directory(s), PrimitiveTypeKind::String) => Ok(Self::String(s.clone())),
+            _ => bail!(
+                "cannot coerce type `{actual}` to type `{target}`",
+                actual = PrimitiveType::new(self.kind()),
+                target = PrimitiveType::new(target)
+            ),
+        }
+    }
+
+    /// Determines if two primitive values are equal according to the rules of
+    /// WDL equality.
+    ///
+    /// Returns `None` if the two values cannot be compared for equality.
+    pub fn equals(left: &Self, right: &Self) -> Option<bool> {
+        match (left, right) {
+            (Self::Boolean(left), Self::Boolean(right)) => Some(left == right),
+            (Self::Integer(left), Self::Integer(right)) => Some(left == right),
+            (Self::Integer(left), Self::Float(right)) => Some(*left as f64 == right.0),
+            (Self::Float(left), Self::Integer(right)) => Some(left.0 == *right as f64),
+            (Self::Float(left), Self::Float(right)) => Some(left.0 == right.0),
+            (
+                Self::String(left) | Self::File(left) | Self::Directory(left),
+                Self::String(right) | Self::File(right) | Self::Directory(right),
+            ) => Some(left == right),
+            _ => None,
+        }
+    }
+
+    /// Returns an object that implements `Display` for formatting the value as
+    /// it would appear interpolated into a string.
+    pub fn raw(&self) -> impl fmt::Display + '_ {
+        /// Helper for displaying a raw primitive value.
+        struct Display<'a>(&'a PrimitiveValue);
+
+        impl fmt::Display for Display<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.0 {
+                    PrimitiveValue::Boolean(v) => write!(f, "{v}"),
+                    PrimitiveValue::Integer(v) => write!(f, "{v}"),
+                    PrimitiveValue::Float(v) => write!(f, "{v:.6}"),
+                    PrimitiveValue::String(s)
+                    | PrimitiveValue::File(s)
+                    | PrimitiveValue::Directory(s) => write!(f, "{s}"),
+                }
+            }
+        }
+
+        Display(self)
+    }
+}
+
+impl fmt::Display for PrimitiveValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Boolean(_) | Self::Integer(_) | Self::Float(_) => self.raw().fmt(f),
+            Self::String(s) | Self::File(s) | Self::Directory(s) => {
+                write!(f, "\"")?;
+                for c in s.chars() {
+                    match c {
+                        '\\' => write!(f, "\\\\")?,
+                        '"' => write!(f, "\\\"")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        '~' | '$' => write!(f, "\\{c}")?,
+                        c => write!(f, "{c}")?,
+                    }
+                }
+
+                write!(f, "\"")
+            }
+        }
+    }
+}
+
+/// Represents a compound WDL value.
+#[derive(Debug, Clone)]
+pub enum CompoundValue {
+    /// The value is a `Pair`.
+    Pair(Pair),
+    /// The value is an `Array`.
+    Array(Array),
+    /// The value is a `Map`.
+    Map(Map),
+    /// The value is an `Object`.
+    Object(Object),
+    /// The value is a struct.
+    Struct(Struct),
+}
+
+impl CompoundValue {
+    /// Gets the type of the value.
+    pub fn ty(&self) -> Type {
+        match self {
+            Self::Pair(v) => v.ty(),
+            Self::Array(v) => v.ty(),
+            Self::Map(v) => v.ty(),
+            Self::Object(_) => Type::Object,
+            Self::Struct(v) => v.ty(),
+        }
+    }
+
+    /// Coerces the value to the given target type.
+    ///
+    /// Returns an error if the value cannot be coerced.
+    pub fn coerce(&self, types: &Types, target: Type) -> Result<Self> {
+        let fail = || {
+            anyhow::anyhow!(
+                "cannot coerce type `{actual}` to type `{target}`",
+                actual = self.ty().display(types),
+                target = target.display(types)
+            )
+        };
+
+        let target_def = match target {
+            Type::Compound(ty) => types.type_definition(ty.definition()),
+            Type::Object | Type::OptionalObject => {
+                return match self {
+                    // Object -> Object
+                    Self::Object(_) => Ok(self.clone()),
+                    // Map[String, X] -> Object
+                    Self::Map(v) => Ok(Self::Object(Object::new(
+                        v.elements()
+                            .iter()
+                            .map(|(k, v)| {
+                                let k = k
+                                    .as_str()
+                                    .filter(|_| matches!(k, PrimitiveValue::String(_)))
+                                    .ok_or_else(fail)?;
+                                Ok((k.to_string(), v.clone()))
+                            })
+                            .collect::<Result<IndexMap<_, _>>>()?,
+                    ))),
+                    // Struct -> Object
+                    Self::Struct(v) => Ok(Self::Object(Object::new(v.members().clone()))),
+                    _ => Err(fail()),
+                };
+            }
+            _ => return Err(fail()),
+        };
+
+        match (self, target_def) {
+            // Array[X] -> Array[Y]
+            (Self::Array(v), CompoundTypeDef::Array(ty)) => {
+                if ty.is_non_empty() && v.is_empty() {
+                    bail!(
+                        "cannot coerce empty array value to non-empty array type `{target}`",
+                        target = target.display(types)
+                    );
+                }
+
+                Ok(Self::Array(Array::new(
+                    types,
+                    target.require(),
+                    v.elements().iter().cloned(),
+                )?))
+            }
+            // Pair[W, X] -> Pair[Y, Z]
+            (Self::Pair(v), CompoundTypeDef::Pair(_)) => Ok(Self::Pair(Pair::new(
+                types,
+                target.require(),
+                v.left().clone(),
+                v.right().clone(),
+            )?)),
+            // Map[W, X] -> Map[Y, Z]
+            (Self::Map(v), CompoundTypeDef::Map(_)) => Ok(Self::Map(Map::new(
+                types,
+                target.require(),
+                v.elements().iter().map(|(k, v)| (k.clone(), v.clone())),
+            )?)),
+            // Map[String, X] -> Struct
+            (Self::Map(v), CompoundTypeDef::Struct(_)) => {
+                let members = v
+                    .elements()
+                    .iter()
+                    .map(|(k, v)| match k {
+                        PrimitiveValue::String(k) => Ok((k.to_string(), v.clone())),
+                        _ => Err(fail()),
+                    })
+                    .collect::<Result<IndexMap<_, _>>>()?;
+                Ok(Self::Struct(Struct::new(types, target.require(), members)?))
+            }
+            // Object -> Map[String, X]
+            (Self::Object(v), CompoundTypeDef::Map(_)) => Ok(Self::Map(Map::new(
+                types,
+                target.require(),
+                v.members()
+                    .iter()
+                    .map(|(k, v)| (PrimitiveValue::new_string(k.clone()), v.clone())),
+            )?)),
+            // Object -> Struct
+            (Self::Object(v), CompoundTypeDef::Struct(_)) => Ok(Self::Struct(Struct::new(
+                types,
+                target.require(),
+                v.members().clone(),
+            )?)),
+            // Struct -> Map[String, X]
+            (Self::Struct(v), CompoundTypeDef::Map(_)) => Ok(Self::Map(Map::new(
+                types,
+                target.require(),
+                v.members()
+                    .iter()
+                    .map(|(k, v)| (PrimitiveValue::new_string(k.clone()), v.clone())),
+            )?)),
+            // Struct -> Struct
+            (Self::Struct(v), CompoundTypeDef::Struct(ty)) => {
+                if v.members().len() != ty.members().len() {
+                    return Err(fail());
+                }
+
+                Ok(Self::Struct(Struct::new(
+                    types,
+                    target.require(),
+                    v.members().clone(),
+                )?))
+            }
+            _ => Err(fail()),
+        }
+    }
+
+    /// Determines if two compound values are equal according to the rules of
+    /// WDL equality.
+    ///
+    /// Returns `None` if the two values cannot be compared for equality.
+    pub fn equals(left: &Self, right: &Self) -> Option<bool> {
+        /// Compares two iterators of values for equality.
+        fn all_equal<'a>(
+            mut left: impl ExactSizeIterator<Item = &'a Value>,
+            right: impl ExactSizeIterator<Item = &'a Value>,
+        ) -> Option<bool> {
+            if left.len() != right.len() {
+                return Some(false);
+            }
+
+            for r in right {
+                if !Value::equals(left.next()?, r)? {
+                    return Some(false);
+                }
+            }
+
+            Some(true)
+        }
+
+        /// Compares two member maps for equality.
+        fn members_equal(
+            left: &IndexMap<String, Value>,
+            right: &IndexMap<String, Value>,
+        ) -> Option<bool> {
+            if left.len() != right.len() {
+                return Some(false);
+            }
+
+            for (k, l) in left {
+                match right.get(k) {
+                    Some(r) => {
+                        if !Value::equals(l, r)? {
+                            return Some(false);
+                        }
+                    }
+                    None => return Some(false),
+                }
+            }
+
+            Some(true)
+        }
+
+        match (left, right) {
+            (Self::Pair(left), Self::Pair(right)) => Some(
+                Value::equals(left.left(), right.left())?
+                    && Value::equals(left.right(), right.right())?,
+            ),
+            (Self::Array(left), Self::Array(right)) => {
+                all_equal(left.elements().iter(), right.elements().iter())
+            }
+            (Self::Map(left), Self::Map(right)) => {
+                if left.len() != right.len() {
+                    return Some(false);
+                }
+
+                for ((lk, lv), (rk, rv)) in left.elements().iter().zip(right.elements().iter()) {
+                    if !PrimitiveValue::equals(lk, rk)? || !Value::equals(lv, rv)? {
+                        return Some(false);
+                    }
+                }
+
+                Some(true)
+            }
+            (Self::Object(left), Self::Object(right)) => {
+                members_equal(left.members(), right.members())
+            }
+            (Self::Struct(left), Self::Struct(right)) => {
+                if left.name() != right.name() {
+                    return None;
+                }
+
+                members_equal(left.members(), right.members())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CompoundValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pair(v) => v.fmt(f),
+            Self::Array(v) => v.fmt(f),
+            Self::Map(v) => v.fmt(f),
+            Self::Object(v) => v.fmt(f),
+            Self::Struct(v) => v.fmt(f),
+        }
+    }
+}
+
+/// Represents a `Pair` value.
+#[derive(Debug, Clone)]
+pub struct Pair {
+    /// The type of the pair.
+    ty: Type,
+    /// The left and right values of the pair.
+    values: Arc<(Value, Value)>,
+}
+
+impl Pair {
+    /// Creates a new `Pair` value.
+    ///
+    /// The values are coerced to the left and right types of the given pair
+    /// type.
+    ///
+    /// Returns an error if the type is not a pair type or if a value cannot be
+    /// coerced.
+    pub fn new(types: &Types, ty: Type, left: Value, right: Value) -> Result<Self> {
+        let pair_ty = compound_definition(types, ty)
+            .and_then(CompoundTypeDef::as_pair)
+            .with_context(|| format!("type `{ty}` is not a pair type", ty = ty.display(types)))?;
+
+        let left = left
+            .coerce(types, pair_ty.first_type())
+            .context("failed to coerce the left value of the pair")?;
+        let right = right
+            .coerce(types, pair_ty.second_type())
+            .context("failed to coerce the right value of the pair")?;
+        Ok(Self::new_unchecked(ty, left, right))
+    }
+
+    /// Creates a new `Pair` value without checking the values against the
+    /// type.
+    pub(crate) fn new_unchecked(ty: Type, left: Value, right: Value) -> Self {
+        Self {
+            ty,
+            values: Arc::new((left, right)),
+        }
+    }
+
+    /// Gets the type of the pair.
+    pub fn ty(&self) -> Type {
+        self.ty
+    }
+
+    /// Gets the left value of the pair.
+    pub fn left(&self) -> &Value {
+        &self.values.0
+    }
+
+    /// Gets the right value of the pair.
+    pub fn right(&self) -> &Value {
+        &self.values.1
+    }
+}
+
+impl fmt::Display for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({left}, {right})",
+            left = self.left(),
+            right = self.right()
+        )
+    }
+}
+
+/// Represents an `Array` value.
+#[derive(Debug, Clone)]
+pub struct Array {
+    /// The type of the array.
+    ty: Type,
+    /// The elements of the array.
+    elements: Arc<Vec<Value>>,
+}
+
+impl Array {
+    /// Creates a new `Array` value.
+    ///
+    /// The elements are coerced to the element type of the given array type.
+    ///
+    /// Returns an error if the type is not an array type or if an element
+    /// cannot be coerced.
+    pub fn new(types: &Types, ty: Type, elements: impl IntoIterator<Item = Value>) -> Result<Self> {
+        let element_ty = compound_definition(types, ty)
+            .and_then(CompoundTypeDef::as_array)
+            .with_context(|| format!("type `{ty}` is not an array type", ty = ty.display(types)))?
+            .element_type();
+
+        let elements = elements
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| {
+                v.coerce(types, element_ty)
+                    .with_context(|| format!("failed to coerce array element at index {i}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new_unchecked(ty, elements))
+    }
+
+    /// Creates a new `Array` value without checking the elements against the
+    /// type.
+    pub(crate) fn new_unchecked(ty: Type, elements: Vec<Value>) -> Self {
+        Self {
+            ty,
+            elements: Arc::new(elements),
+        }
+    }
+
+    /// Gets the type of the array.
+    pub fn ty(&self) -> Type {
+        self.ty
+    }
+
+    /// Gets the elements of the array.
+    pub fn elements(&self) -> &[Value] {
+        &self.elements
+    }
+
+    /// Gets the number of elements in the array.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Determines if the array is empty.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+impl fmt::Display for Array {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, element) in self.elements.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            element.fmt(f)?;
+        }
+
+        write!(f, "]")
+    }
+}
+
+/// Represents a `Map` value.
+#[derive(Debug, Clone)]
+pub struct Map {
+    /// The type of the map.
+    ty: Type,
+    /// The elements of the map.
+    elements: Arc<IndexMap<PrimitiveValue, Value>>,
+}
+
+impl Map {
+    /// Creates a new `Map` value.
+    ///
+    /// The keys and values are coerced to the key and value types of the given
+    /// map type.
+    ///
+    /// Returns an error if the type is not a map type or if a key or value
+    /// cannot be coerced.
+    pub fn new(
+        types: &Types,
+        ty: Type,
+        elements: impl IntoIterator<Item = (PrimitiveValue, Value)>,
+    ) -> Result<Self> {
+        let map_ty = compound_definition(types, ty)
+            .and_then(CompoundTypeDef::as_map)
+            .with_context(|| format!("type `{ty}` is not a map type", ty = ty.display(types)))?;
+
+        let key_ty = map_ty.key_type();
+        let value_ty = map_ty.value_type();
+        let elements = elements
+            .into_iter()
+            .map(|(k, v)| {
+                let k = match Value::from(k.clone()).coerce(types, key_ty)? {
+                    Value::Primitive(k) => k,
+                    _ => k,
+                };
+                let v = v
+                    .coerce(types, value_ty)
+                    .with_context(|| format!("failed to coerce the value of map key {k}"))?;
+                Ok((k, v))
+            })
+            .collect::<Result<IndexMap<_, _>>>()?;
+        Ok(Self::new_unchecked(ty, elements))
+    }
+
+    /// Creates a new `Map` value without checking the elements against the
+    /// type.
+    pub(crate) fn new_unchecked(ty: Type, elements: IndexMap<PrimitiveValue, Value>) -> Self {
+        Self {
+            ty,
+            elements: Arc::new(elements),
+        }
+    }
+
+    /// Gets the type of the map.
+    pub fn ty(&self) -> Type {
+        self.ty
+    }
+
+    /// Gets the elements of the map.
+    pub fn elements(&self) -> &IndexMap<PrimitiveValue, Value> {
+        &self.elements
+    }
+
+    /// Gets the value of the given key.
+    pub fn get(&self, key: &PrimitiveValue) -> Option<&Value> {
+        self.elements.get(key)
+    }
+
+    /// Gets the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Determines if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, (k, v)) in self.elements.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{k}: {v}")?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+/// Represents an `Object` value.
+#[derive(Debug, Clone)]
+pub struct Object {
+    /// The members of the object.
+    members: Arc<IndexMap<String, Value>>,
+}
+
+impl Object {
+    /// Creates a new `Object` value.
+    pub fn new(members: IndexMap<String, Value>) -> Self {
+        Self {
+            members: Arc::new(members),
+        }
+    }
+
+    /// Gets the members of the object.
+    pub fn members(&self) -> &IndexMap<String, Value> {
+        &self.members
+    }
+}
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "object {{")?;
+        fmt_members(f, &self.members)?;
+        write!(f, "}}")
+    }
+}
+
+/// Represents a struct value.
+#[derive(Debug, Clone)]
+pub struct Struct {
+    /// The type of the struct.
+    ty: Type,
+    /// The name of the struct.
+    name: Arc<String>,
+    /// The members of the struct.
+    members: Arc<IndexMap<String, Value>>,
+}
+
+impl Struct {
+    /// Creates a new struct value.
+    ///
+    /// The members are coerced to the member types of the given struct type;
+    /// missing members of an optional type are set to `None`.
+    ///
+    /// Returns an error if the type is not a struct type, if a member is
+    /// unknown or missing, or if a member cannot be coerced.
+    pub fn new(types: &Types, ty: Type, mut members: IndexMap<String, Value>) -> Result<Self> {
+        let struct_ty = compound_definition(types, ty)
+            .and_then(CompoundTypeDef::as_struct)
+            .with_context(|| format!("type `{ty}` is not a struct type", ty = ty.display(types)))?;
+
+        if let Some(name) = members
+            .keys()
+            .find(|k| !struct_ty.members().contains_key(*k))
+        {
+            bail!(
+                "struct `{ty}` does not have a member named `{name}`",
+                ty = struct_ty.name()
+            );
+        }
+
+        let members = struct_ty
+            .members()
+            .iter()
+            .map(|(name, member_ty)| {
+                let value = match members.swap_remove(name) {
+                    Some(value) => value
+                        .coerce(types, *member_ty)
+                        .with_context(|| format!("failed to coerce struct member `{name}`"))?,
+                    None if member_ty.is_optional() => Value::None,
+                    None => bail!(
+                        "struct `{ty}` requires a value for member `{name}`",
+                        ty = struct_ty.name()
+                    ),
+                };
+
+                Ok((name.clone(), value))
+            })
+            .collect::<Result<IndexMap<_, _>>>()?;
+
+        Ok(Self {
+            ty,
+            name: Arc::new(struct_ty.name().to_string()),
+            members: Arc::new(members),
+        })
+    }
+
+    /// Creates a new struct value without checking the members against the
+    /// struct type.
+    pub(crate) fn new_unchecked(
+        ty: Type,
+        name: impl Into<String>,
+        members: IndexMap<String, Value>,
+    ) -> Self {
+        Self {
+            ty,
+            name: Arc::new(name.into()),
+            members: Arc::new(members),
+        }
+    }
+
+    /// Gets the type of the struct.
+    pub fn ty(&self) -> Type {
+        self.ty
+    }
+
+    /// Gets the name of the struct.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the members of the struct.
+    pub fn members(&self) -> &IndexMap<String, Value> {
+        &self.members
+    }
+}
+
+impl fmt::Display for Struct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{name} {{", name = self.name)?;
+        fmt_members(f, &self.members)?;
+        write!(f, "}}")
+    }
+}
+
+/// Formats the members of an object or struct.
+fn fmt_members(f: &mut fmt::Formatter<'_>, members: &IndexMap<String, Value>) -> fmt::Result {
+    for (i, (k, v)) in members.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+
+        write!(f, "{k}: {v}")?;
+    }
+
+    Ok(())
+}
+
+/// Gets the compound type definition of the given type.
+///
+/// Returns `None` if the type is not a compound type.
+fn compound_definition(types: &Types, ty: Type) -> Option<&CompoundTypeDef> {
+    match ty {
+        Type::Compound(ty) => Some(types.type_definition(ty.definition())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_analysis::types::MapType;
+    use wdl_analysis::types::StructType;
+
+    use super::*;
+
+    #[test]
+    fn primitive_coercion() {
+        assert_eq!(
+            PrimitiveValue::Integer(1)
+                .coerce(PrimitiveTypeKind::Float)
+                .unwrap(),
+            PrimitiveValue::Float(1.0.into())
+        );
+        assert_eq!(
+            PrimitiveValue::new_string("foo")
+                .coerce(PrimitiveTypeKind::File)
+                .unwrap(),
+            PrimitiveValue::new_file("foo")
+        );
+        assert_eq!(
+            PrimitiveValue::Float(1.0.into())
+                .coerce(PrimitiveTypeKind::Integer)
+                .unwrap_err()
+                .to_string(),
+            "cannot coerce type `Float` to type `Int`"
+        );
+    }
+
+    #[test]
+    fn compound_coercion() {
+        let mut types = Types::new();
+        let array_float = types.add_array(ArrayType::new(PrimitiveTypeKind::Float));
+        let array_int = types.add_array(ArrayType::new(PrimitiveTypeKind::Integer));
+        let map_string_int = types.add_map(MapType::new(
+            PrimitiveTypeKind::String,
+            PrimitiveTypeKind::Integer,
+        ));
+        let foo = types.add_struct(StructType::new(
+            "Foo",
+            [
+                ("a", Type::from(PrimitiveTypeKind::Integer)),
+                ("b", PrimitiveTypeKind::Integer.into()),
+            ],
+        ));
+
+        let array = Array::new(&types, array_int, [1.into(), 2.into()]).unwrap();
+        let value = Value::from(array).coerce(&types, array_float).unwrap();
+        assert_eq!(value.to_string(), "[1.000000, 2.000000]");
+
+        let map = Map::new(
+            &types,
+            map_string_int,
+            [
+                (PrimitiveValue::new_string("a"), 1.into()),
+                (PrimitiveValue::new_string("b"), 2.into()),
+            ],
+        )
+        .unwrap();
+        let value = Value::from(map).coerce(&types, foo).unwrap();
+        assert_eq!(value.to_string(), "Foo {a: 1, b: 2}");
+
+        assert_eq!(
+            Value::None
+                .coerce(&types, PrimitiveTypeKind::Integer.into())
+                .unwrap_err()
+                .to_string(),
+            "cannot coerce `None` to non-optional type `Int`"
+        );
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(Value::equals(&1.into(), &1.0.into()), Some(true));
+        assert_eq!(
+            Value::equals(&"foo".to_string().into(), &"bar".to_string().into()),
+            Some(false)
+        );
+        assert_eq!(Value::equals(&Value::None, &1.into()), Some(false));
+        assert_eq!(Value::equals(&true.into(), &1.into()), None);
+    }
+}
@@ -0,0 +1,266 @@
+//! The WDL workflow evaluation tests.
+//!
+//! This test looks for directories in `tests/workflows`.
+//!
+//! Each directory is expected to contain:
+//!
+//! * `source.wdl` - the test input source to evaluate; the source must contain
+//!   a workflow. Other WDL files in the directory may be imported by the
+//!   source.
+//! * `inputs.json` - the inputs to the workflow (optional).
+//! * `outputs.json` - the expected outputs of the workflow, if the evaluation
+//!   is expected to succeed.
+//! * `error.txt` - the expected evaluation error, if the evaluation is expected
+//!   to fail.
+//!
+//! Paths within the workflow's root directory are normalized to begin with
+//! `<root>`.
+//!
+//! The `outputs.json` or `error.txt` file may be automatically generated or
+//! updated by setting the `BLESS` environment variable when running this test.
+
+use std::collections::HashSet;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::exit;
+
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use codespan_reporting::files::SimpleFile;
+use codespan_reporting::term;
+use codespan_reporting::term::termcolor::Buffer;
+use codespan_reporting::term::Config;
+use colored::Colorize;
+use pretty_assertions::StrComparison;
+use wdl_analysis::Analyzer;
+use wdl_ast::v1::DocumentItem;
+use wdl_ast::AstToken;
+use wdl_ast::Severity;
+use wdl_engine::v1::WorkflowEvaluator;
+use wdl_engine::Engine;
+use wdl_engine::EvaluationError;
+use wdl_engine::LocalTaskExecutionBackend;
+use wdl_engine::WorkflowInputs;
+
+fn find_tests() -> Vec<PathBuf> {
+    // Check for filter arguments consisting of test names
+    let mut filter = HashSet::new();
+    for arg in std::env::args().skip_while(|a| a != "--").skip(1) {
+        if !arg.starts_with('-') {
+            filter.insert(arg);
+        }
+    }
+
+    let mut tests: Vec<PathBuf> = Vec::new();
+    for entry in Path::new("tests/workflows").read_dir().unwrap() {
+        let entry = entry.expect("failed to read directory");
+        let path = entry.path();
+        if !path.is_dir()
+            || (!filter.is_empty()
+                && !filter.contains(entry.file_name().to_str().expect("name should be UTF-8")))
+        {
+            continue;
+        }
+
+        tests.push(path);
+    }
+
+    tests.sort();
+    tests
+}
+
+fn compare_result(path: &Path, result: &str) -> Result<()> {
+    let result = result.replace("\r\n", "\n");
+    if env::var_os("BLESS").is_some() {
+        fs::write(path, &result).with_context(|| {
+            format!(
+                "failed to write result file `{path}`",
+                path = path.display()
+            )
+        })?;
+        return Ok(());
+    }
+
+    let expected = fs::read_to_string(path)
+        .with_context(|| format!("failed to read result file `{path}`", path = path.display()))?
+        .replace("\r\n", "\n");
+
+    if expected != result {
+        bail!(
+            "result is not as expected:\n{}",
+            StrComparison::new(&expected, &result),
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_test(test: &Path) -> Result<()> {
+    let source_path = test.join("source.wdl");
+    let analyzer = Analyzer::new(|_, _, _, _| async {});
+    analyzer
+        .add_documents(vec![source_path.clone()])
+        .await
+        .context("failed to add document")?;
+    let results = analyzer
+        .analyze(())
+        .await
+        .context("failed to analyze document")?;
+    let result = results
+        .iter()
+        .find(|r| {
+            r.uri()
+                .to_file_path()
+                .map(|p| p.ends_with(&source_path))
+                .unwrap_or(false)
+        })
+        .context("failed to find analysis result")?;
+
+    if let Some(e) = result.parse_result().error() {
+        bail!(
+            "failed to read `{path}`: {e:#}",
+            path = source_path.display()
+        );
+    }
+
+    for result in &results {
+        if let Some(d) = result
+            .diagnostics()
+            .iter()
+            .find(|d| d.severity() == Severity::Error)
+        {
+            bail!(
+                "document `{uri}` has an analysis error: {msg}",
+                uri = result.uri(),
+                msg = d.message()
+            );
+        }
+    }
+
+    let document = result
+        .parse_result()
+        .document()
+        .expect("should have document");
+    let workflow = document
+        .ast()
+        .into_v1()
+        .context("document is not a V1 document")?
+        .items()
+        .find_map(|i| match i {
+            DocumentItem::Workflow(w) => Some(w),
+            _ => None,
+        })
+        .context("document does not contain a workflow")?;
+    let name = workflow.name();
+
+    let mut engine = Engine::new(LocalTaskExecutionBackend::new());
+    let inputs_path = test.join("inputs.json");
+    let inputs = if inputs_path.exists() {
+        let contents = fs::read_to_string(&inputs_path).context("failed to read inputs")?;
+        let object = match serde_json::from_str(&contents).context("failed to parse inputs")? {
+            serde_json::Value::Object(object) => object,
+            _ => bail!("inputs must be a JSON object"),
+        };
+        WorkflowInputs::parse_json(engine.types_mut(), name.as_str(), object)?
+    } else {
+        WorkflowInputs::default()
+    };
+
+    let root = tempfile::tempdir().context("failed to create temporary directory")?;
+    let root_path = root.path().to_string_lossy().to_string();
+    let cwd = env::current_dir()
+        .context("failed to get current directory")?
+        .to_string_lossy()
+        .to_string();
+    let normalize = |s: &str| s.replace(&root_path, "<root>").replace(&cwd, "<cwd>");
+
+    match WorkflowEvaluator::new(&mut engine)
+        .evaluate(&results, result, &inputs, root.path())
+        .await
+    {
+        Ok(evaluated) => {
+            let outputs = serde_json::Value::Object(
+                evaluated
+                    .outputs()
+                    .iter()
+                    .map(|(k, v)| Ok((format!("{name}.{k}", name = name.as_str()), v.to_json()?)))
+                    .collect::<Result<_>>()?,
+            );
+            compare_result(
+                &test.join("outputs.json"),
+                &normalize(&format!(
+                    "{}\n",
+                    serde_json::to_string_pretty(&outputs).expect("should serialize")
+                )),
+            )
+        }
+        Err(EvaluationError::Source(diagnostic)) => {
+            let source = fs::read_to_string(&source_path).context("failed to read source")?;
+            let file = SimpleFile::new(
+                source_path
+                    .strip_prefix(test)
+                    .unwrap()
+                    .display()
+                    .to_string(),
+                source,
+            );
+            let mut buffer = Buffer::no_color();
+            term::emit(
+                &mut buffer,
+                &Config::default(),
+                &file,
+                &diagnostic.to_codespan(),
+            )
+            .expect("should emit");
+            compare_result(
+                &test.join("error.txt"),
+                &normalize(&String::from_utf8(buffer.into_inner()).expect("should be UTF-8")),
+            )
+        }
+        Err(EvaluationError::Other(e)) => {
+            compare_result(&test.join("error.txt"), &normalize(&format!("{e:#}\n")))
+        }
+    }
+    .map_err(|e| anyhow!(e))
+}
+
+#[tokio::main]
+async fn main() {
+    let tests = find_tests();
+    println!("\nrunning {} tests\n", tests.len());
+
+    let mut errors = Vec::new();
+    for test in &tests {
+        let test_name = test.file_stem().and_then(OsStr::to_str).unwrap();
+        match run_test(test).await {
+            Ok(_) => {
+                println!("test {test_name} ... {ok}", ok = "ok".green());
+            }
+            Err(e) => {
+                println!("test {test_name} ... {failed}", failed = "failed".red());
+                errors.push((test_name, format!("{e:#}")));
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        eprintln!(
+            "\n{count} test(s) {failed}:",
+            count = errors.len(),
+            failed = "failed".red()
+        );
+
+        for (name, msg) in errors.iter() {
+            eprintln!("{name}: {msg}", msg = msg.red());
+        }
+
+        exit(1);
+    }
+
+    println!("\ntest result: ok. {count} passed\n", count = tests.len());
+}
@@ -0,0 +1,165 @@
+//! Formatting of Workflow Description Language (WDL) documents.
+//!
+//! The formatter operates on the concrete syntax tree (CST) of a document and
+//! is lossless: comments are preserved and the contents of command sections
+//! are written exactly as they appear in the source.
+//!
+//! The formatted output is intended to satisfy the style rules of `wdl-lint`;
+//! for example, sections are written in the expected order, imports are
+//! sorted, and multi-line lists have trailing commas.
+//!
+//! # Examples
+//!
+//! An example of formatting a WDL document:
+//!
+//! ```rust
+//! # let source = "version 1.1\nworkflow test {}";
+//! use wdl_format::Config;
+//! use wdl_format::Formatter;
+//!
+//! let formatter = Formatter::new(Config::default());
+//! match formatter.format_source(source) {
+//!     Ok(formatted) => {
+//!         // Use the formatted source
+//!     }
+//!     Err(diagnostics) => {
+//!         // Handle the failure to parse
+//!     }
+//! }
+//! ```
+
+#![warn(missing_docs)]
+#![warn(rust_2018_idioms)]
+#![warn(rust_2021_compatibility)]
+#![warn(missing_debug_implementations)]
+#![warn(clippy::missing_docs_in_private_items)]
+#![warn(rustdoc::broken_intra_doc_links)]
+
+use wdl_ast::AstNode;
+use wdl_ast::Diagnostic;
+use wdl_ast::Document;
+use wdl_ast::Severity;
+use wdl_ast::SyntaxKind;
+
+mod printer;
+mod v1;
+
+use printer::Printer;
+
+/// The default maximum line width of formatted output.
+pub const DEFAULT_MAX_LINE_WIDTH: usize = 90;
+
+/// The default number of spaces per indentation level.
+pub const DEFAULT_INDENT_SPACES: usize = 4;
+
+/// Represents the indentation to use in formatted output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// Indent with the given number of spaces per level.
+    Spaces(usize),
+    /// Indent with a tab character per level.
+    Tabs,
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Self::Spaces(DEFAULT_INDENT_SPACES)
+    }
+}
+
+/// Represents configuration for the formatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// The indentation to use.
+    indent: Indent,
+    /// The maximum line width.
+    max_line_width: usize,
+}
+
+impl Config {
+    /// Sets the indentation to use.
+    pub fn with_indent(mut self, indent: Indent) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Sets the maximum line width.
+    ///
+    /// The formatter splits lists, objects, and call inputs over multiple lines
+    /// when they would otherwise exceed the maximum line width.
+    pub fn with_max_line_width(mut self, max_line_width: usize) -> Self {
+        self.max_line_width = max_line_width;
+        self
+    }
+
+    /// Gets the indentation to use.
+    pub fn indent(&self) -> Indent {
+        self.indent
+    }
+
+    /// Gets the maximum line width.
+    pub fn max_line_width(&self) -> usize {
+        self.max_line_width
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            indent: Indent::default(),
+            max_line_width: DEFAULT_MAX_LINE_WIDTH,
+        }
+    }
+}
+
+/// Represents a WDL document formatter.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Formatter {
+    /// The configuration of the formatter.
+    config: Config,
+}
+
+impl Formatter {
+    /// Constructs a new formatter with the given configuration.
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// Gets the configuration of the formatter.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Formats the given document.
+    ///
+    /// The document is expected to have been parsed without errors.
+    ///
+    /// Returns an error if the document is not of a supported WDL version.
+    pub fn format(&self, document: &Document) -> Result<String, Diagnostic> {
+        let root = document.syntax();
+        if root
+            .descendants_with_tokens()
+            .any(|e| matches!(e.kind(), SyntaxKind::Unparsed | SyntaxKind::Abandoned))
+        {
+            return Err(Diagnostic::error(
+                "the document cannot be formatted because it is not a supported WDL document",
+            ));
+        }
+
+        let mut printer = Printer::new(&self.config, false);
+        v1::format_document(&mut printer, root);
+        Ok(printer.finish())
+    }
+
+    /// Parses and formats the given source.
+    ///
+    /// Returns the parse diagnostics if the source contains any errors.
+    pub fn format_source(&self, source: &str) -> Result<String, Vec<Diagnostic>> {
+        let (document, diagnostics) = Document::parse(source);
+        if diagnostics.iter().any(|d| d.severity() == Severity::Error) {
+            return Err(diagnostics);
+        }
+
+        self.format(&document).map_err(|d| vec![d])
+    }
+}
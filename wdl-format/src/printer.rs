@@ -0,0 +1,276 @@
+//! Implementation of the printer used to write formatted output.
+
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxToken;
+
+use crate::Config;
+use crate::Indent;
+
+/// The number of columns a tab character is assumed to occupy.
+const TAB_WIDTH: usize = 4;
+
+/// Represents the separation to write before the next token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Separator {
+    /// No separation.
+    None,
+    /// A single space.
+    Space,
+    /// A line break.
+    Newline,
+    /// A line break followed by an empty line.
+    BlankLine,
+}
+
+/// Represents a printer of formatted output.
+///
+/// The printer keeps track of the current indentation level and the separation
+/// requested before the next token; requested separations are merged so that
+/// the largest requested separation is written.
+#[derive(Debug)]
+pub struct Printer<'a> {
+    /// The formatter configuration.
+    config: &'a Config,
+    /// The formatted output.
+    output: String,
+    /// The current indentation level.
+    level: usize,
+    /// The separation to write before the next token.
+    separator: Separator,
+    /// Whether or not the last token written opened a block or list.
+    ///
+    /// Blank lines are not written directly after an opening token.
+    after_open: bool,
+    /// Whether or not the printer is in flat mode.
+    ///
+    /// In flat mode, lists and call inputs are never split over multiple lines.
+    flat: bool,
+}
+
+impl<'a> Printer<'a> {
+    /// Constructs a new printer.
+    pub fn new(config: &'a Config, flat: bool) -> Self {
+        Self {
+            config,
+            output: String::new(),
+            level: 0,
+            separator: Separator::None,
+            after_open: false,
+            flat,
+        }
+    }
+
+    /// Gets the formatter configuration.
+    pub fn config(&self) -> &'a Config {
+        self.config
+    }
+
+    /// Determines if the printer is in flat mode.
+    pub fn is_flat(&self) -> bool {
+        self.flat
+    }
+
+    /// Gets the column at which the next token will be written.
+    pub fn column(&self) -> usize {
+        match self.separator {
+            Separator::Newline | Separator::BlankLine => self.indent_width(self.level),
+            Separator::Space => self.line_width() + 1,
+            Separator::None => self.line_width(),
+        }
+    }
+
+    /// Requests a single space before the next token.
+    ///
+    /// The space is not written at the start of a line.
+    pub fn space(&mut self) {
+        if !self.output.is_empty() {
+            self.separator = self.separator.max(Separator::Space);
+        }
+    }
+
+    /// Requests a line break before the next token.
+    pub fn newline(&mut self) {
+        if !self.output.is_empty() {
+            self.separator = self.separator.max(Separator::Newline);
+        }
+    }
+
+    /// Requests an empty line before the next token.
+    ///
+    /// The empty line is not written directly after an opening token or
+    /// directly before a closing token.
+    pub fn blank_line(&mut self) {
+        if !self.output.is_empty() {
+            self.separator = self.separator.max(Separator::BlankLine);
+        }
+    }
+
+    /// Increases the indentation level.
+    pub fn indent(&mut self) {
+        self.level += 1;
+    }
+
+    /// Decreases the indentation level.
+    pub fn dedent(&mut self) {
+        self.level = self.level.saturating_sub(1);
+    }
+
+    /// Writes a token.
+    pub fn token(&mut self, text: &str) {
+        self.flush();
+        self.output.push_str(text);
+        self.after_open = false;
+    }
+
+    /// Writes a token that opens a block or list.
+    pub fn open(&mut self, text: &str) {
+        self.token(text);
+        self.after_open = true;
+    }
+
+    /// Writes a token that closes a block or list.
+    pub fn close(&mut self, text: &str) {
+        if self.separator == Separator::BlankLine {
+            self.separator = Separator::Newline;
+        }
+
+        self.token(text);
+    }
+
+    /// Writes a comment.
+    ///
+    /// A comment that follows other source on the same line is written at the
+    /// end of the current line; otherwise, the comment is written on its own
+    /// line.
+    ///
+    /// The next token is always written on a new line.
+    pub fn comment(&mut self, comment: &SyntaxToken) {
+        self.write_comment(comment, is_inline_comment(comment));
+    }
+
+    /// Writes a comment on its own line.
+    ///
+    /// This is used for comments that only follow tokens the formatter moves
+    /// (e.g. a comma moved to directly after a list element).
+    ///
+    /// The next token is always written on a new line.
+    pub fn line_comment(&mut self, comment: &SyntaxToken) {
+        self.write_comment(comment, false);
+    }
+
+    /// Writes a comment either at the end of the current line or on its own
+    /// line.
+    fn write_comment(&mut self, comment: &SyntaxToken, inline: bool) {
+        // Only trailing whitespace is removed; the text of the comment is written as is
+        let text = comment.text().trim_end();
+        if inline && !self.output.is_empty() {
+            // Write the comment at the end of the current line, keeping any requested line
+            // separation for the next token
+            self.trim_end();
+            self.output.push_str("  ");
+            self.output.push_str(text);
+            self.separator = self.separator.max(Separator::Newline);
+            return;
+        }
+
+        self.newline();
+        let after_open = self.after_open;
+        self.token(text);
+        self.after_open = after_open;
+        self.separator = Separator::Newline;
+    }
+
+    /// Finishes the printer and returns the formatted output.
+    ///
+    /// The output ends with a single newline.
+    pub fn finish(mut self) -> String {
+        self.trim_end();
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+
+        self.output
+    }
+
+    /// Consumes the printer and returns the output without a trailing newline.
+    pub fn into_output(self) -> String {
+        self.output
+    }
+
+    /// Writes the requested separation.
+    fn flush(&mut self) {
+        let separator = match self.separator {
+            Separator::BlankLine if self.after_open || self.output.is_empty() => Separator::Newline,
+            separator => separator,
+        };
+
+        match separator {
+            Separator::None => {}
+            Separator::Space => self.output.push(' '),
+            Separator::Newline | Separator::BlankLine => {
+                self.trim_end();
+                self.output.push('\n');
+                if separator == Separator::BlankLine {
+                    self.output.push('\n');
+                }
+
+                for _ in 0..self.level {
+                    match self.config.indent() {
+                        Indent::Spaces(n) => self.output.extend(std::iter::repeat(' ').take(n)),
+                        Indent::Tabs => self.output.push('\t'),
+                    }
+                }
+            }
+        }
+
+        self.separator = Separator::None;
+    }
+
+    /// Trims trailing spaces and tabs from the output.
+    fn trim_end(&mut self) {
+        let len = self.output.trim_end_matches([' ', '\t']).len();
+        self.output.truncate(len);
+    }
+
+    /// Gets the width of the current line of output.
+    fn line_width(&self) -> usize {
+        let line = match self.output.rfind('\n') {
+            Some(index) => &self.output[index + 1..],
+            None => &self.output,
+        };
+
+        line.chars()
+            .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+            .sum()
+    }
+
+    /// Gets the width of the indentation for the given level.
+    fn indent_width(&self, level: usize) -> usize {
+        match self.config.indent() {
+            Indent::Spaces(n) => n * level,
+            Indent::Tabs => TAB_WIDTH * level,
+        }
+    }
+}
+
+/// Determines if a comment follows other source on the same line.
+pub fn is_inline_comment(comment: &SyntaxToken) -> bool {
+    let mut prev = comment.prev_token();
+    while let Some(token) = prev {
+        match token.kind() {
+            SyntaxKind::Whitespace if token.text().contains('\n') => return false,
+            SyntaxKind::Whitespace => prev = token.prev_token(),
+            _ => return true,
+        }
+    }
+
+    false
+}
+
+/// Determines if an empty line precedes the given token in the source.
+pub fn has_blank_line_before(token: &SyntaxToken) -> bool {
+    token
+        .prev_token()
+        .map(|t| t.kind() == SyntaxKind::Whitespace && t.text().matches('\n').count() > 1)
+        .unwrap_or(false)
+}
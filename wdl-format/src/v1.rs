@@ -0,0 +1,794 @@
+//! Formatting of WDL 1.x documents.
+
+use wdl_ast::SyntaxElement;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::SyntaxToken;
+
+use crate::printer::has_blank_line_before;
+use crate::printer::is_inline_comment;
+use crate::printer::Printer;
+
+/// Represents an element of a block (e.g. a section of a task) along with its
+/// attached comments.
+#[derive(Debug)]
+struct Element {
+    /// The node of the element.
+    node: SyntaxNode,
+    /// The comments on the lines before the element.
+    ///
+    /// Each comment is paired with whether or not an empty line precedes it in
+    /// the source.
+    leading: Vec<(SyntaxToken, bool)>,
+    /// The comments at the end of the element's last line.
+    trailing: Vec<SyntaxToken>,
+    /// Whether or not an empty line precedes the element's node in the source.
+    blank_before: bool,
+}
+
+impl Element {
+    /// Determines if an empty line precedes the element (including its leading
+    /// comments) in the source.
+    fn has_blank_line_before(&self) -> bool {
+        self.leading
+            .first()
+            .map(|(_, blank)| *blank)
+            .unwrap_or(self.blank_before)
+    }
+}
+
+/// Represents the separation to use between the elements of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Separation {
+    /// Elements are separated by a line break.
+    Newline,
+    /// Elements are separated by an empty line.
+    BlankLine,
+    /// Elements are separated by an empty line only if one separates them in
+    /// the source.
+    Preserve,
+}
+
+/// Collects the elements from the given children of a node.
+///
+/// Returns the elements and any comments following the last element.
+fn collect_elements(
+    children: impl Iterator<Item = SyntaxElement>,
+) -> (Vec<Element>, Vec<(SyntaxToken, bool)>) {
+    let mut elements: Vec<Element> = Vec::new();
+    let mut leading = Vec::new();
+    for child in children {
+        match child {
+            SyntaxElement::Node(node) => {
+                let blank_before = node
+                    .first_token()
+                    .map(|t| has_blank_line_before(&t))
+                    .unwrap_or(false);
+                elements.push(Element {
+                    node,
+                    leading: std::mem::take(&mut leading),
+                    trailing: Vec::new(),
+                    blank_before,
+                });
+            }
+            SyntaxElement::Token(token) if token.kind() == SyntaxKind::Comment => {
+                match elements.last_mut() {
+                    Some(element) if leading.is_empty() && is_inline_comment(&token) => {
+                        element.trailing.push(token)
+                    }
+                    _ => {
+                        let blank = has_blank_line_before(&token);
+                        leading.push((token, blank));
+                    }
+                }
+            }
+            SyntaxElement::Token(_) => {}
+        }
+    }
+
+    (elements, leading)
+}
+
+/// Prints the given elements of a block.
+fn print_elements(
+    printer: &mut Printer<'_>,
+    elements: &[Element],
+    separation: impl Fn(Option<&Element>, &Element) -> Separation,
+) {
+    let mut prev = None;
+    for element in elements {
+        let separation = separation(prev, element);
+        match separation {
+            Separation::Newline => printer.newline(),
+            Separation::BlankLine => printer.blank_line(),
+            Separation::Preserve if element.has_blank_line_before() => printer.blank_line(),
+            Separation::Preserve => printer.newline(),
+        }
+
+        let allow_blank = separation != Separation::Newline;
+        for (i, (comment, blank)) in element.leading.iter().enumerate() {
+            if i > 0 && allow_blank && *blank {
+                printer.blank_line();
+            }
+
+            printer.comment(comment);
+        }
+
+        if !element.leading.is_empty() && allow_blank && element.blank_before {
+            printer.blank_line();
+        }
+
+        printer.newline();
+        format_node(printer, &element.node);
+
+        for comment in &element.trailing {
+            printer.comment(comment);
+        }
+
+        prev = Some(element);
+    }
+}
+
+/// Prints the comments that follow the last element of a block.
+fn print_dangling_comments(printer: &mut Printer<'_>, comments: &[(SyntaxToken, bool)]) {
+    for (comment, blank) in comments {
+        if *blank {
+            printer.blank_line();
+        }
+
+        printer.comment(comment);
+    }
+}
+
+/// Formats a document.
+pub fn format_document(printer: &mut Printer<'_>, root: &SyntaxNode) {
+    // Print the preamble comments and the version statement
+    let mut children = root.children_with_tokens().peekable();
+    let mut preamble = false;
+    while let Some(child) = children.next_if(|c| c.kind() != SyntaxKind::VersionStatementNode) {
+        if let SyntaxElement::Token(token) = child {
+            if token.kind() == SyntaxKind::Comment {
+                printer.newline();
+                printer.comment(&token);
+                preamble = true;
+            }
+        }
+    }
+
+    if let Some(SyntaxElement::Node(version)) = children.next() {
+        if preamble {
+            printer.blank_line();
+        }
+
+        printer.newline();
+        format_node(printer, &version);
+    }
+
+    // Separate the imports from the other document items and sort the imports
+    let (elements, dangling) = collect_elements(children);
+    let (mut imports, items): (Vec<_>, Vec<_>) = elements
+        .into_iter()
+        .partition(|e| e.node.kind() == SyntaxKind::ImportStatementNode);
+    imports.sort_by_cached_key(|e| format_flat(printer, &e.node));
+
+    if !imports.is_empty() {
+        printer.blank_line();
+        print_elements(printer, &imports, |prev, _| match prev {
+            Some(_) => Separation::Newline,
+            None => Separation::BlankLine,
+        });
+    }
+
+    print_elements(printer, &items, |_, _| Separation::BlankLine);
+    print_dangling_comments(printer, &dangling);
+}
+
+/// Formats the given node as a string in flat mode.
+fn format_flat(printer: &Printer<'_>, node: &SyntaxNode) -> String {
+    let mut flat = Printer::new(printer.config(), true);
+    format_node(&mut flat, node);
+    flat.into_output()
+}
+
+/// Determines if the given node fits on the current line when formatted in
+/// flat mode.
+fn fits(printer: &Printer<'_>, node: &SyntaxNode) -> bool {
+    let flat = format_flat(printer, node);
+    !flat.contains('\n')
+        && printer.column() + flat.chars().count() <= printer.config().max_line_width()
+}
+
+/// Determines if a comment in a list or call input block follows an element on
+/// the same line.
+///
+/// Commas are skipped as they are written directly after the elements; a
+/// comment that follows a comma on its own line is not considered to follow
+/// the element.
+fn follows_element(comment: &SyntaxToken) -> bool {
+    let mut prev = comment.prev_sibling_or_token();
+    while let Some(element) = prev {
+        match element.kind() {
+            SyntaxKind::Whitespace if element.to_string().contains('\n') => return false,
+            SyntaxKind::Whitespace | SyntaxKind::Comma => prev = element.prev_sibling_or_token(),
+            _ => return true,
+        }
+    }
+
+    false
+}
+
+/// Prints a comment in a list or call input block.
+fn list_comment(printer: &mut Printer<'_>, comment: &SyntaxToken) {
+    if follows_element(comment) {
+        printer.comment(comment);
+    } else {
+        printer.line_comment(comment);
+    }
+}
+
+/// Determines if the given node contains a comment.
+fn has_comment(node: &SyntaxNode) -> bool {
+    node.descendants_with_tokens()
+        .any(|e| e.kind() == SyntaxKind::Comment)
+}
+
+/// Formats a node.
+fn format_node(printer: &mut Printer<'_>, node: &SyntaxNode) {
+    match node.kind() {
+        SyntaxKind::VersionStatementNode
+        | SyntaxKind::ImportStatementNode
+        | SyntaxKind::ImportAliasNode
+        | SyntaxKind::UnboundDeclNode
+        | SyntaxKind::BoundDeclNode
+        | SyntaxKind::CallAliasNode
+        | SyntaxKind::CallAfterNode
+        | SyntaxKind::CallInputItemNode
+        | SyntaxKind::LogicalOrExprNode
+        | SyntaxKind::LogicalAndExprNode
+        | SyntaxKind::EqualityExprNode
+        | SyntaxKind::InequalityExprNode
+        | SyntaxKind::LessExprNode
+        | SyntaxKind::LessEqualExprNode
+        | SyntaxKind::GreaterExprNode
+        | SyntaxKind::GreaterEqualExprNode
+        | SyntaxKind::AdditionExprNode
+        | SyntaxKind::SubtractionExprNode
+        | SyntaxKind::MultiplicationExprNode
+        | SyntaxKind::DivisionExprNode
+        | SyntaxKind::ModuloExprNode
+        | SyntaxKind::ExponentiationExprNode => format_children(printer, node, |_, _| true),
+        SyntaxKind::StructDefinitionNode
+        | SyntaxKind::TaskDefinitionNode
+        | SyntaxKind::WorkflowDefinitionNode => format_definition(printer, node),
+        SyntaxKind::InputSectionNode
+        | SyntaxKind::OutputSectionNode
+        | SyntaxKind::RuntimeSectionNode
+        | SyntaxKind::RequirementsSectionNode
+        | SyntaxKind::HintsSectionNode
+        | SyntaxKind::MetadataSectionNode
+        | SyntaxKind::ParameterMetadataSectionNode => {
+            format_block(printer, node, |_, _| Separation::Newline)
+        }
+        SyntaxKind::ScatterStatementNode | SyntaxKind::ConditionalStatementNode => {
+            format_block(printer, node, |prev, _| match prev {
+                Some(_) => Separation::Preserve,
+                None => Separation::Newline,
+            })
+        }
+        SyntaxKind::IfExprNode => format_if(printer, node),
+        SyntaxKind::CommandSectionNode => format_command(printer, node),
+        SyntaxKind::CallStatementNode => format_call(printer, node),
+        SyntaxKind::RuntimeItemNode
+        | SyntaxKind::RequirementsItemNode
+        | SyntaxKind::HintsItemNode
+        | SyntaxKind::MetadataObjectItemNode
+        | SyntaxKind::LiteralMapItemNode
+        | SyntaxKind::LiteralObjectItemNode
+        | SyntaxKind::LiteralStructItemNode
+        | SyntaxKind::LiteralHintsItemNode
+        | SyntaxKind::LiteralInputItemNode
+        | SyntaxKind::LiteralOutputItemNode => format_children(printer, node, |_, next| {
+            next != SyntaxKind::Colon && next != SyntaxKind::Dot
+        }),
+        SyntaxKind::MetadataObjectNode | SyntaxKind::MetadataArrayNode => {
+            format_list(printer, node, true, true)
+        }
+        SyntaxKind::LiteralArrayNode
+        | SyntaxKind::LiteralMapNode
+        | SyntaxKind::LiteralObjectNode
+        | SyntaxKind::LiteralStructNode
+        | SyntaxKind::LiteralHintsNode
+        | SyntaxKind::LiteralInputNode
+        | SyntaxKind::LiteralOutputNode
+        | SyntaxKind::CallExprNode => format_list(printer, node, false, true),
+        SyntaxKind::LiteralPairNode | SyntaxKind::ParenthesizedExprNode => {
+            format_list(printer, node, false, false)
+        }
+        SyntaxKind::LiteralStringNode => format_string(printer, node),
+        _ => format_children(printer, node, |prev, _| prev == SyntaxKind::Comma),
+    }
+}
+
+/// Formats the children of a node.
+///
+/// If the node contains a comment, the lines following the comment are
+/// indented.
+///
+/// The given callback determines if a space should separate two adjacent
+/// children given their kinds.
+fn format_children(
+    printer: &mut Printer<'_>,
+    node: &SyntaxNode,
+    space: impl Fn(SyntaxKind, SyntaxKind) -> bool,
+) {
+    let mut indented = false;
+    let mut prev = None;
+    for child in node.children_with_tokens() {
+        if !indented && child.kind() == SyntaxKind::Comment {
+            printer.indent();
+            indented = true;
+        }
+
+        format_child(printer, &child, &mut prev, &space);
+    }
+
+    if indented {
+        printer.dedent();
+    }
+}
+
+/// Formats a child of a node.
+///
+/// The previous child kind is updated if the child is not trivia.
+fn format_child(
+    printer: &mut Printer<'_>,
+    child: &SyntaxElement,
+    prev: &mut Option<SyntaxKind>,
+    space: &impl Fn(SyntaxKind, SyntaxKind) -> bool,
+) {
+    let kind = child.kind();
+    match child {
+        SyntaxElement::Token(token) if kind == SyntaxKind::Comment => {
+            printer.comment(token);
+            return;
+        }
+        SyntaxElement::Token(_) if kind == SyntaxKind::Whitespace => return,
+        _ => {}
+    }
+
+    if let Some(prev) = *prev {
+        if space(prev, kind) {
+            printer.space();
+        }
+    }
+
+    match child {
+        SyntaxElement::Node(node) => format_node(printer, node),
+        SyntaxElement::Token(token) => printer.token(token.text()),
+    }
+
+    *prev = Some(kind);
+}
+
+/// Formats the header of a block or list (i.e. the children before the opening
+/// token).
+///
+/// Returns the remaining children, starting with the opening token.
+fn format_header(
+    printer: &mut Printer<'_>,
+    node: &SyntaxNode,
+    open: SyntaxKind,
+) -> std::iter::Peekable<impl Iterator<Item = SyntaxElement>> {
+    let mut children = node.children_with_tokens().peekable();
+    let mut prev = None;
+    while let Some(child) = children.next_if(|c| c.kind() != open) {
+        format_child(printer, &child, &mut prev, &|prev, next| {
+            prev != SyntaxKind::OpenParen && next != SyntaxKind::CloseParen
+        });
+    }
+
+    if prev.is_some() {
+        printer.space();
+    }
+
+    children
+}
+
+/// Formats a block with elements on their own lines.
+fn format_block(
+    printer: &mut Printer<'_>,
+    node: &SyntaxNode,
+    separation: impl Fn(Option<&Element>, &Element) -> Separation,
+) {
+    let mut children = format_header(printer, node, SyntaxKind::OpenBrace);
+    let open = children.next().expect("block should have an opening brace");
+    let (elements, dangling) =
+        collect_elements(children.take_while(|c| c.kind() != SyntaxKind::CloseBrace));
+    print_block(printer, &open, &elements, &dangling, separation);
+}
+
+/// Prints a block with the given elements.
+fn print_block(
+    printer: &mut Printer<'_>,
+    open: &SyntaxElement,
+    elements: &[Element],
+    dangling: &[(SyntaxToken, bool)],
+    separation: impl Fn(Option<&Element>, &Element) -> Separation,
+) {
+    printer.open(&open.to_string());
+    if elements.is_empty() && dangling.is_empty() {
+        printer.close("}");
+        return;
+    }
+
+    printer.indent();
+    print_elements(printer, elements, separation);
+    print_dangling_comments(printer, dangling);
+    printer.dedent();
+    printer.newline();
+    printer.close("}");
+}
+
+/// Gets the rank of an item in a struct, task, or workflow definition.
+///
+/// Items are ordered by rank; items of the same rank retain their relative
+/// order.
+fn rank(kind: SyntaxKind) -> usize {
+    match kind {
+        SyntaxKind::MetadataSectionNode => 0,
+        SyntaxKind::ParameterMetadataSectionNode => 1,
+        SyntaxKind::InputSectionNode => 2,
+        SyntaxKind::UnboundDeclNode
+        | SyntaxKind::BoundDeclNode
+        | SyntaxKind::CallStatementNode
+        | SyntaxKind::ScatterStatementNode
+        | SyntaxKind::ConditionalStatementNode => 3,
+        SyntaxKind::CommandSectionNode => 4,
+        SyntaxKind::OutputSectionNode => 5,
+        SyntaxKind::RuntimeSectionNode => 6,
+        SyntaxKind::RequirementsSectionNode => 7,
+        SyntaxKind::HintsSectionNode => 8,
+        _ => 9,
+    }
+}
+
+/// Formats a struct, task, or workflow definition.
+///
+/// The sections of the definition are ordered as follows: `meta`,
+/// `parameter_meta`, `input`, the body (i.e. declarations and statements),
+/// `command`, `output`, `runtime`, `requirements`, and `hints`.
+fn format_definition(printer: &mut Printer<'_>, node: &SyntaxNode) {
+    let is_struct = node.kind() == SyntaxKind::StructDefinitionNode;
+    let mut children = format_header(printer, node, SyntaxKind::OpenBrace);
+    let open = children
+        .next()
+        .expect("definition should have an opening brace");
+    let (mut elements, dangling) =
+        collect_elements(children.take_while(|c| c.kind() != SyntaxKind::CloseBrace));
+    elements.sort_by_key(|e| rank(e.node.kind()));
+
+    print_block(printer, &open, &elements, &dangling, |prev, next| {
+        let prev = match prev {
+            Some(prev) => prev,
+            None => return Separation::Newline,
+        };
+
+        let (prev, next) = (rank(prev.node.kind()), rank(next.node.kind()));
+        if prev != next || prev != 3 {
+            Separation::BlankLine
+        } else if is_struct {
+            // Struct members are not separated by empty lines
+            Separation::Newline
+        } else {
+            Separation::Preserve
+        }
+    });
+}
+
+/// Formats a command section.
+///
+/// The contents of the command section are written exactly as they appear in
+/// the source.
+fn format_command(printer: &mut Printer<'_>, node: &SyntaxNode) {
+    let mut prev = None;
+    let mut children = node.children_with_tokens();
+    for child in children.by_ref() {
+        let kind = child.kind();
+        if matches!(kind, SyntaxKind::OpenHeredoc | SyntaxKind::OpenBrace) {
+            printer.space();
+            let mut text = child.to_string();
+            for child in children.by_ref() {
+                text.push_str(&child.to_string());
+            }
+
+            printer.token(&text);
+            return;
+        }
+
+        format_child(printer, &child, &mut prev, &|_, _| true);
+    }
+}
+
+/// Formats a call statement.
+///
+/// A call with a single input is written on one line if it fits; otherwise,
+/// each input is written on its own line.
+fn format_call(printer: &mut Printer<'_>, node: &SyntaxNode) {
+    let mut children = node.children_with_tokens().peekable();
+    let mut prev = None;
+    while let Some(child) = children.next_if(|c| c.kind() != SyntaxKind::OpenBrace) {
+        format_child(printer, &child, &mut prev, &|_, _| true);
+    }
+
+    if children.next().is_none() {
+        return;
+    }
+
+    let rest: Vec<_> = children
+        .filter(|c| c.kind() != SyntaxKind::CloseBrace)
+        .collect();
+    let input_keyword = rest.iter().any(|c| c.kind() == SyntaxKind::InputKeyword);
+    let inputs = rest
+        .iter()
+        .filter(|c| c.kind() == SyntaxKind::CallInputItemNode)
+        .count();
+    let comments = rest.iter().any(|c| c.kind() == SyntaxKind::Comment);
+
+    // Omit an empty input block
+    if inputs == 0 && !comments {
+        return;
+    }
+
+    printer.space();
+    printer.open("{");
+    if input_keyword {
+        printer.space();
+        printer.token("input:");
+    }
+
+    if inputs == 1 && !comments && (printer.is_flat() || fits_call(printer, &rest)) {
+        let input = rest
+            .iter()
+            .find_map(|c| {
+                c.as_node()
+                    .filter(|n| n.kind() == SyntaxKind::CallInputItemNode)
+            })
+            .expect("should have an input");
+        printer.space();
+        format_node(printer, input);
+        printer.space();
+        printer.close("}");
+        return;
+    }
+
+    printer.indent();
+    for child in &rest {
+        match child {
+            SyntaxElement::Node(node) => {
+                printer.newline();
+                format_node(printer, node);
+                printer.token(",");
+            }
+            SyntaxElement::Token(token) if token.kind() == SyntaxKind::Comment => {
+                list_comment(printer, token)
+            }
+            SyntaxElement::Token(_) => {}
+        }
+    }
+
+    printer.dedent();
+    printer.newline();
+    printer.close("}");
+}
+
+/// Formats an `if` expression.
+///
+/// An `if` expression is written on one line if it fits and contains no
+/// comments; otherwise, the `if`, `then`, and `else` clauses are each written
+/// on their own line within parentheses. Parentheses are added if the
+/// expression is not already parenthesized.
+fn format_if(printer: &mut Printer<'_>, node: &SyntaxNode) {
+    if printer.is_flat() || (!has_comment(node) && fits(printer, node)) {
+        format_children(printer, node, |_, _| true);
+        return;
+    }
+
+    let parenthesized = node
+        .parent()
+        .map(|p| p.kind() == SyntaxKind::ParenthesizedExprNode)
+        .unwrap_or(false);
+    if !parenthesized {
+        printer.open("(");
+        printer.indent();
+        printer.newline();
+    }
+
+    let mut prev = None;
+    for child in node.children_with_tokens() {
+        if matches!(
+            child.kind(),
+            SyntaxKind::ThenKeyword | SyntaxKind::ElseKeyword
+        ) {
+            printer.newline();
+        }
+
+        format_child(printer, &child, &mut prev, &|_, _| true);
+    }
+
+    if !parenthesized {
+        printer.dedent();
+        printer.newline();
+        printer.close(")");
+    }
+}
+
+/// Determines if the single input of a call fits on the current line.
+fn fits_call(printer: &Printer<'_>, rest: &[SyntaxElement]) -> bool {
+    let input = rest
+        .iter()
+        .find_map(|c| {
+            c.as_node()
+                .filter(|n| n.kind() == SyntaxKind::CallInputItemNode)
+        })
+        .expect("should have an input");
+    let flat = format_flat(printer, input);
+
+    // Account for the separating spaces and the closing brace
+    !flat.contains('\n')
+        && printer.column() + flat.chars().count() + 3 <= printer.config().max_line_width()
+}
+
+/// Formats a list (e.g. an array literal or the arguments of a call
+/// expression).
+///
+/// A list is written on one line if it fits and contains no comments;
+/// otherwise, each element of the list is written on its own line followed by
+/// a comma.
+///
+/// If `always_split` is `true`, the list is always split over multiple lines.
+///
+/// If `trailing_comma` is `false`, the last element of a split list is not
+/// followed by a comma.
+fn format_list(
+    printer: &mut Printer<'_>,
+    node: &SyntaxNode,
+    always_split: bool,
+    trailing_comma: bool,
+) {
+    let open = node
+        .children_with_tokens()
+        .find(|c| {
+            matches!(
+                c.kind(),
+                SyntaxKind::OpenBracket | SyntaxKind::OpenBrace | SyntaxKind::OpenParen
+            )
+        })
+        .expect("list should have an opening token")
+        .kind();
+
+    // A call expression's name is not separated from its arguments
+    let is_call = node.kind() == SyntaxKind::CallExprNode;
+    let mut children = node.children_with_tokens().peekable();
+    let mut prev = None;
+    while let Some(child) = children.next_if(|c| c.kind() != open) {
+        format_child(printer, &child, &mut prev, &|_, _| true);
+    }
+
+    if prev.is_some() && !is_call {
+        printer.space();
+    }
+
+    let open = children.next().expect("list should have an opening token");
+    let rest: Vec<_> = children
+        .filter(|c| !matches!(c.kind(), SyntaxKind::Whitespace | SyntaxKind::Comma))
+        .collect();
+    let (close, rest) = rest.split_last().expect("list should have a closing token");
+    let count = rest.iter().filter(|c| c.as_node().is_some()).count();
+
+    let split = !printer.is_flat() && (always_split || has_comment(node) || !fits(printer, node));
+    if count == 0 && rest.is_empty() {
+        printer.open(&open.to_string());
+        if split {
+            printer.newline();
+        }
+
+        printer.close(&close.to_string());
+        return;
+    }
+
+    if !split {
+        printer.token(&open.to_string());
+        for (i, child) in rest.iter().enumerate() {
+            if i > 0 {
+                printer.token(",");
+                printer.space();
+            }
+
+            format_node(printer, child.as_node().expect("should be a node"));
+        }
+
+        printer.token(&close.to_string());
+        return;
+    }
+
+    printer.open(&open.to_string());
+    printer.indent();
+    let mut index = 0;
+    for child in rest {
+        match child {
+            SyntaxElement::Node(node) => {
+                index += 1;
+                printer.newline();
+                format_node(printer, node);
+                if trailing_comma || index < count {
+                    printer.token(",");
+                }
+            }
+            SyntaxElement::Token(token) => {
+                debug_assert_eq!(token.kind(), SyntaxKind::Comment);
+                list_comment(printer, token);
+            }
+        }
+    }
+
+    printer.dedent();
+    printer.newline();
+    printer.close(&close.to_string());
+}
+
+/// Formats a string literal.
+///
+/// Single quoted strings are written with double quotes.
+fn format_string(printer: &mut Printer<'_>, node: &SyntaxNode) {
+    let mut text = String::new();
+    write_string(&mut text, node);
+    printer.token(&text);
+}
+
+/// Writes the text of a string literal, converting any single quoted strings
+/// (including those nested in placeholders) to double quoted strings.
+fn write_string(text: &mut String, node: &SyntaxNode) {
+    let convert = node.kind() == SyntaxKind::LiteralStringNode
+        && node
+            .first_token()
+            .map(|t| t.kind() == SyntaxKind::SingleQuote)
+            .unwrap_or(false);
+
+    for child in node.children_with_tokens() {
+        match child {
+            SyntaxElement::Node(node) => write_string(text, &node),
+            SyntaxElement::Token(token) if convert && token.kind() == SyntaxKind::SingleQuote => {
+                text.push('"')
+            }
+            SyntaxElement::Token(token)
+                if convert && token.kind() == SyntaxKind::LiteralStringText =>
+            {
+                convert_single_quoted(text, token.text())
+            }
+            SyntaxElement::Token(token) => text.push_str(token.text()),
+        }
+    }
+}
+
+/// Writes the text of a single quoted string as the text of a double quoted
+/// string.
+///
+/// Unescaped double quotes are escaped and escaped single quotes are
+/// unescaped; all other escape sequences are written as they are.
+fn convert_single_quoted(text: &mut String, s: &str) {
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\'') => text.push('\''),
+                Some(c) => {
+                    text.push('\\');
+                    text.push(c);
+                }
+                None => text.push('\\'),
+            },
+            '"' => text.push_str("\\\""),
+            c => text.push(c),
+        }
+    }
+}
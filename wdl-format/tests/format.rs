@@ -0,0 +1,208 @@
+//! The format file tests.
+//!
+//! This test looks for directories in `tests/format`.
+//!
+//! Each directory is expected to contain:
+//!
+//! * `source.wdl` - the test input source to format.
+//! * `source.formatted.wdl` - the expected formatted output.
+//!
+//! The test also checks that the formatted output parses without errors, that
+//! formatting the output again produces the same output, and that the output
+//! does not violate any of the formatting lint rules.
+//!
+//! The `source.formatted.wdl` file may be automatically generated or updated
+//! by setting the `BLESS` environment variable when running this test.
+
+use std::collections::HashSet;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::exit;
+
+use colored::Colorize;
+use pretty_assertions::StrComparison;
+use wdl_ast::Document;
+use wdl_ast::Validator;
+use wdl_format::Config;
+use wdl_format::Formatter;
+use wdl_lint::LintVisitor;
+
+/// The lint rules that formatted output is expected to satisfy.
+const FORMATTING_RULES: &[&str] = &[
+    "BlankLinesBetweenElements",
+    "CallInputSpacing",
+    "CommentWhitespace",
+    "DoubleQuotes",
+    "EndingNewline",
+    "ExpressionSpacing",
+    "ImportPlacement",
+    "ImportSort",
+    "ImportWhitespace",
+    "KeyValuePairs",
+    "LineWidth",
+    "PreambleWhitespace",
+    "SectionOrdering",
+    "TrailingComma",
+    "Whitespace",
+];
+
+fn find_tests() -> Vec<PathBuf> {
+    // Check for filter arguments consisting of test names
+    let mut filter = HashSet::new();
+    for arg in std::env::args().skip_while(|a| a != "--").skip(1) {
+        if !arg.starts_with('-') {
+            filter.insert(arg);
+        }
+    }
+
+    let mut tests: Vec<PathBuf> = Vec::new();
+    for entry in Path::new("tests/format").read_dir().unwrap() {
+        let entry = entry.expect("failed to read directory");
+        let path = entry.path();
+        if !path.is_dir()
+            || (!filter.is_empty()
+                && !filter.contains(entry.file_name().to_str().expect("name should be UTF-8")))
+        {
+            continue;
+        }
+
+        tests.push(path);
+    }
+
+    tests.sort();
+    tests
+}
+
+fn compare_result(path: &Path, result: &str) -> Result<(), String> {
+    if env::var_os("BLESS").is_some() {
+        fs::write(path, result).map_err(|e| {
+            format!(
+                "failed to write result file `{path}`: {e}",
+                path = path.display()
+            )
+        })?;
+        return Ok(());
+    }
+
+    let expected = fs::read_to_string(path)
+        .map_err(|e| {
+            format!(
+                "failed to read result file `{path}`: {e}",
+                path = path.display()
+            )
+        })?
+        .replace("\r\n", "\n");
+
+    if expected != result {
+        return Err(format!(
+            "result is not as expected:\n{}",
+            StrComparison::new(&expected, result),
+        ));
+    }
+
+    Ok(())
+}
+
+fn format(source: &str) -> Result<String, String> {
+    Formatter::new(Config::default())
+        .format_source(source)
+        .map_err(|diagnostics| {
+            format!(
+                "failed to format source: {messages}",
+                messages = diagnostics
+                    .iter()
+                    .map(|d| d.message())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            )
+        })
+}
+
+fn run_test(test: &Path) -> Result<(), String> {
+    let path = test.join("source.wdl");
+    let source = std::fs::read_to_string(&path)
+        .map_err(|e| {
+            format!(
+                "failed to read source file `{path}`: {e}",
+                path = path.display()
+            )
+        })?
+        .replace("\r\n", "\n");
+
+    let formatted = format(&source)?;
+    compare_result(&path.with_extension("formatted.wdl"), &formatted)?;
+
+    let reformatted = format(&formatted).map_err(|e| format!("formatted output: {e}"))?;
+    if reformatted != formatted {
+        return Err(format!(
+            "formatting is not idempotent:\n{}",
+            StrComparison::new(&formatted, &reformatted),
+        ));
+    }
+
+    let (document, _) = Document::parse(&formatted);
+    let mut validator = Validator::default();
+    validator.add_visitor(LintVisitor::default());
+    if let Err(diagnostics) = validator.validate(&document) {
+        let violations: Vec<_> = diagnostics
+            .iter()
+            .filter(|d| {
+                d.rule()
+                    .map(|r| FORMATTING_RULES.contains(&r))
+                    .unwrap_or(false)
+            })
+            .map(|d| {
+                format!(
+                    "{rule}: {message}",
+                    rule = d.rule().unwrap(),
+                    message = d.message()
+                )
+            })
+            .collect();
+
+        if !violations.is_empty() {
+            return Err(format!(
+                "formatted output violates lint rules:\n{violations}",
+                violations = violations.join("\n")
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let tests = find_tests();
+    println!("\nrunning {} tests\n", tests.len());
+
+    let mut errors = Vec::new();
+    for test in &tests {
+        let test_name = test.file_stem().and_then(OsStr::to_str).unwrap();
+        match run_test(test) {
+            Ok(()) => println!("test {test_name} ... {ok}", ok = "ok".green()),
+            Err(e) => {
+                println!("test {test_name} ... {failed}", failed = "failed".red());
+                errors.push((test_name, e));
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        eprintln!(
+            "\n{count} test(s) {failed}:",
+            count = errors.len(),
+            failed = "failed".red()
+        );
+
+        for (name, msg) in errors.iter() {
+            eprintln!("{name}: {msg}", msg = msg.red());
+        }
+
+        exit(1);
+    }
+
+    println!("\ntest result: ok. {} passed\n", tests.len());
+}
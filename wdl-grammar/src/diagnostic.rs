@@ -7,7 +7,8 @@ use std::num::TryFromIntError;
 use rowan::TextRange;
 
 /// Represents a span of source.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Span {
     /// The start of the span.
     start: usize,
@@ -70,6 +71,8 @@ impl TryFrom<Span> for TextRange {
 
 /// Represents the severity of a diagnostic.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Severity {
     /// The diagnostic is displayed as an error.
     Error,
@@ -79,17 +82,70 @@ pub enum Severity {
     Note,
 }
 
+/// Represents a machine-applicable edit of source that fixes a diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Edit {
+    /// The span of source being replaced.
+    span: Span,
+    /// The replacement text.
+    replacement: String,
+}
+
+impl Edit {
+    /// Creates a new edit that replaces the given span with the given text.
+    ///
+    /// An empty span inserts the text; an empty replacement deletes the span.
+    pub fn new(span: impl ToSpan, replacement: impl Into<String>) -> Self {
+        Self {
+            span: span.to_span(),
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Gets the span of source being replaced.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Gets the replacement text.
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
+
+/// Represents the fix of a diagnostic.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Fix {
+    /// The optional message describing the fix.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    message: Option<String>,
+    /// The machine-applicable edits of the fix, sorted by span.
+    edits: Vec<Edit>,
+}
+
 /// Represents a diagnostic to display to the user.
+///
+/// With the `serde` feature enabled, a diagnostic serializes with the byte
+/// offsets of its spans; use `Diagnostic::located` to serialize a diagnostic
+/// with line and column locations.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Diagnostic {
     /// The optional rule associated with the diagnostic.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     rule: Option<String>,
     /// The default severity of the diagnostic.
     severity: Severity,
     /// The diagnostic message.
     message: String,
-    /// The optional fix suggestion for the diagnostic.
-    fix: Option<String>,
+    /// The optional fix for the diagnostic.
+    ///
+    /// The fix is boxed to keep the size of diagnostics small as they are
+    /// returned in the error variant of parser results.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    fix: Option<Box<Fix>>,
     /// The labels for the diagnostic.
     ///
     /// The first label in the collection is considered the primary label.
@@ -170,7 +226,18 @@ impl Diagnostic {
 
     /// Sets the fix message for the diagnostic.
     pub fn with_fix(mut self, fix: impl Into<String>) -> Self {
-        self.fix = Some(fix.into());
+        self.fix.get_or_insert_with(Default::default).message = Some(fix.into());
+        self
+    }
+
+    /// Adds a machine-applicable edit that fixes the diagnostic.
+    ///
+    /// The edits of a diagnostic are applied together and must not overlap.
+    pub fn with_edit(mut self, span: impl ToSpan, replacement: impl Into<String>) -> Self {
+        let edit = Edit::new(span, replacement);
+        let edits = &mut self.fix.get_or_insert_with(Default::default).edits;
+        let index = edits.partition_point(|e| e.span <= edit.span);
+        edits.insert(index, edit);
         self
     }
 
@@ -202,6 +269,11 @@ impl Diagnostic {
         self.severity
     }
 
+    /// Sets the severity level of the diagnostic.
+    pub fn set_severity(&mut self, severity: Severity) {
+        self.severity = severity;
+    }
+
     /// Gets the message of the diagnostic.
     pub fn message(&self) -> &str {
         &self.message
@@ -209,7 +281,17 @@ impl Diagnostic {
 
     /// Gets the optional fix of the diagnostic.
     pub fn fix(&self) -> Option<&str> {
-        self.fix.as_deref()
+        self.fix.as_ref()?.message.as_deref()
+    }
+
+    /// Gets the machine-applicable edits that fix the diagnostic.
+    ///
+    /// The edits are sorted by the start of their spans.
+    pub fn edits(&self) -> &[Edit] {
+        self.fix
+            .as_ref()
+            .map(|f| f.edits.as_slice())
+            .unwrap_or_default()
     }
 
     /// Gets the labels of the diagnostic.
@@ -241,7 +323,7 @@ impl Diagnostic {
 
         diagnostic.message.clone_from(&self.message);
 
-        if let Some(fix) = &self.fix {
+        if let Some(fix) = self.fix() {
             diagnostic.notes.push(format!("fix: {fix}"));
         }
 
@@ -273,10 +355,23 @@ impl Diagnostic {
 
         diagnostic
     }
+
+    /// Gets a serializable representation of this diagnostic that includes
+    /// the line and column locations of its labels and edits.
+    ///
+    /// The given lines must be of the source the diagnostic was reported for.
+    #[cfg(feature = "serde")]
+    pub fn located<'a>(&'a self, lines: &'a SourceLines<'a>) -> LocatedDiagnostic<'a> {
+        LocatedDiagnostic {
+            diagnostic: self,
+            lines,
+        }
+    }
 }
 
 /// Represents a label that annotates the source code.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Label {
     /// The optional message of the label (may be empty).
     message: String,
@@ -344,3 +439,243 @@ impl ToSpan for Span {
         *self
     }
 }
+
+/// Represents a one-based line and column location in source.
+///
+/// The column is counted in Unicode scalar values.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+pub struct Location {
+    /// The one-based line of the location.
+    line: usize,
+    /// The one-based column of the location.
+    column: usize,
+}
+
+#[cfg(feature = "serde")]
+impl Location {
+    /// Gets the one-based line of the location.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Gets the one-based column of the location.
+    ///
+    /// The column is counted in Unicode scalar values.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+/// Maps offsets of source to line and column locations.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone)]
+pub struct SourceLines<'a> {
+    /// The source being mapped.
+    source: &'a str,
+    /// The offsets of the start of each line.
+    starts: Vec<usize>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> SourceLines<'a> {
+    /// Creates a new mapping of the lines of the given source.
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            starts: std::iter::once(0)
+                .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+        }
+    }
+
+    /// Gets the location of the given offset.
+    ///
+    /// Offsets past the end of the source are clamped to the end.
+    pub fn location(&self, offset: usize) -> Location {
+        let offset = offset.min(self.source.len());
+        let line = self.starts.partition_point(|s| *s <= offset) - 1;
+        let start = self.starts[line];
+        let column = self
+            .source
+            .get(start..offset)
+            .map(|s| s.chars().count())
+            .unwrap_or(offset - start);
+
+        Location {
+            line: line + 1,
+            column: column + 1,
+        }
+    }
+}
+
+/// A serializable representation of a diagnostic that includes the line and
+/// column locations of its labels and edits.
+///
+/// See [Diagnostic::located].
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy)]
+pub struct LocatedDiagnostic<'a> {
+    /// The diagnostic being serialized.
+    diagnostic: &'a Diagnostic,
+    /// The lines of the diagnostic's source.
+    lines: &'a SourceLines<'a>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for LocatedDiagnostic<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// A label with its start and end locations.
+        #[derive(serde::Serialize)]
+        struct Label<'a> {
+            /// The message of the label.
+            message: &'a str,
+            /// The span of the label.
+            span: Span,
+            /// The location of the start of the label.
+            start: Location,
+            /// The location of the end of the label.
+            end: Location,
+        }
+
+        /// An edit with its start and end locations.
+        #[derive(serde::Serialize)]
+        struct Edit<'a> {
+            /// The span being replaced.
+            span: Span,
+            /// The location of the start of the span.
+            start: Location,
+            /// The location of the end of the span.
+            end: Location,
+            /// The replacement text.
+            replacement: &'a str,
+        }
+
+        /// A fix with located edits.
+        #[derive(serde::Serialize)]
+        struct Fix<'a> {
+            /// The optional message describing the fix.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            message: Option<&'a str>,
+            /// The edits of the fix.
+            edits: Vec<Edit<'a>>,
+        }
+
+        /// A diagnostic with located labels and edits.
+        #[derive(serde::Serialize)]
+        struct Diagnostic<'a> {
+            /// The optional rule associated with the diagnostic.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            rule: Option<&'a str>,
+            /// The severity of the diagnostic.
+            severity: Severity,
+            /// The diagnostic message.
+            message: &'a str,
+            /// The optional fix for the diagnostic.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            fix: Option<Fix<'a>>,
+            /// The labels of the diagnostic.
+            labels: Vec<Label<'a>>,
+        }
+
+        let diagnostic = self.diagnostic;
+        let lines = self.lines;
+        Diagnostic {
+            rule: diagnostic.rule(),
+            severity: diagnostic.severity(),
+            message: diagnostic.message(),
+            fix: diagnostic.fix.as_ref().map(|f| Fix {
+                message: f.message.as_deref(),
+                edits: f
+                    .edits
+                    .iter()
+                    .map(|e| Edit {
+                        span: e.span,
+                        start: lines.location(e.span.start),
+                        end: lines.location(e.span.end),
+                        replacement: &e.replacement,
+                    })
+                    .collect(),
+            }),
+            labels: diagnostic
+                .labels
+                .iter()
+                .map(|l| Label {
+                    message: &l.message,
+                    span: l.span,
+                    start: lines.location(l.span.start),
+                    end: lines.location(l.span.end),
+                })
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn source_lines() {
+        let lines = SourceLines::new("ab\nλx\n\nc");
+        let location = |offset| {
+            let location = lines.location(offset);
+            (location.line(), location.column())
+        };
+
+        assert_eq!(location(0), (1, 1));
+        assert_eq!(location(2), (1, 3));
+        assert_eq!(location(3), (2, 1));
+        assert_eq!(location(5), (2, 2));
+        assert_eq!(location(7), (3, 1));
+        assert_eq!(location(8), (4, 1));
+        assert_eq!(location(100), (4, 2));
+    }
+
+    #[test]
+    fn serialize_located() {
+        let source = "version 1.1\nworkflow  test {}\n";
+        let diagnostic = Diagnostic::warning("extra whitespace")
+            .with_rule("Whitespace")
+            .with_label("this whitespace", Span::new(20, 2))
+            .with_fix("remove the extra whitespace")
+            .with_edit(Span::new(20, 2), " ");
+
+        let lines = SourceLines::new(source);
+        assert_eq!(
+            serde_json::to_value(diagnostic.located(&lines)).expect("should serialize"),
+            serde_json::json!({
+                "rule": "Whitespace",
+                "severity": "warning",
+                "message": "extra whitespace",
+                "fix": {
+                    "message": "remove the extra whitespace",
+                    "edits": [{
+                        "span": { "start": 20, "end": 22 },
+                        "start": { "line": 2, "column": 9 },
+                        "end": { "line": 2, "column": 11 },
+                        "replacement": " ",
+                    }],
+                },
+                "labels": [{
+                    "message": "this whitespace",
+                    "span": { "start": 20, "end": 22 },
+                    "start": { "line": 2, "column": 9 },
+                    "end": { "line": 2, "column": 11 },
+                }],
+            })
+        );
+
+        assert_eq!(
+            serde_json::to_value(Diagnostic::error("bad")).expect("should serialize"),
+            serde_json::json!({
+                "severity": "error",
+                "message": "bad",
+                "labels": [],
+            })
+        );
+    }
+}
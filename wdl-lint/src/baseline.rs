@@ -0,0 +1,371 @@
+//! Implementation of lint baselines.
+//!
+//! A baseline records the lint diagnostics of existing documents so that new
+//! rules may be adopted incrementally: diagnostics recorded in the baseline
+//! are suppressed on later runs and only new diagnostics are reported.
+//!
+//! Each entry of a baseline is identified by the rule, the file, and a
+//! fingerprint of the diagnostic. The fingerprint is a hash of the message
+//! and of the trimmed source lines of the diagnostic's primary label; it does
+//! not include the location of the diagnostic, so entries still match after
+//! lines are added or removed elsewhere in the file.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use serde::Deserialize;
+use serde::Serialize;
+use wdl_ast::Diagnostic;
+
+/// The current version of the baseline file format.
+const BASELINE_VERSION: u32 = 1;
+
+/// The offset basis of the 64-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xCBF29CE484222325;
+
+/// The prime of the 64-bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x100000001B3;
+
+/// Computes the 64-bit FNV-1a hash of the given parts.
+///
+/// A stable hash is used so that fingerprints do not change between builds.
+fn hash<'a>(parts: impl IntoIterator<Item = &'a str>) -> u64 {
+    parts.into_iter().fold(FNV_OFFSET_BASIS, |hash, part| {
+        // Terminate each part so that adjacent parts cannot run together
+        part.bytes().chain([0]).fold(hash, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(FNV_PRIME)
+        })
+    })
+}
+
+/// Computes the fingerprint of a lint diagnostic reported for the given
+/// source.
+///
+/// Returns `None` if the diagnostic is not associated with a rule.
+fn fingerprint(source: &str, diagnostic: &Diagnostic) -> Option<String> {
+    let rule = diagnostic.rule()?;
+
+    // Use the full lines of the primary label so that the fingerprint does not
+    // depend on the columns of the label
+    let lines = diagnostic
+        .labels()
+        .next()
+        .and_then(|l| {
+            let span = l.span();
+            let start = source.get(..span.start())?.rfind('\n').map_or(0, |i| i + 1);
+            let end = source
+                .get(span.end()..)?
+                .find('\n')
+                .map_or(source.len(), |i| span.end() + i);
+            source.get(start..end)
+        })
+        .unwrap_or_default();
+
+    Some(format!(
+        "{hash:016x}",
+        hash = hash(
+            [rule, diagnostic.message()]
+                .into_iter()
+                .chain(lines.lines().map(str::trim))
+        )
+    ))
+}
+
+/// Represents an entry of a lint baseline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct BaselineEntry {
+    /// The identifier of the rule that reported the diagnostic.
+    rule: String,
+    /// The file the diagnostic was reported for.
+    file: String,
+    /// The fingerprint of the diagnostic.
+    fingerprint: String,
+    /// The message of the diagnostic.
+    message: String,
+    /// The number of matching diagnostics that were reported.
+    count: usize,
+}
+
+impl BaselineEntry {
+    /// Gets the identifier of the rule that reported the diagnostic.
+    pub fn rule(&self) -> &str {
+        &self.rule
+    }
+
+    /// Gets the file the diagnostic was reported for.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// Gets the fingerprint of the diagnostic.
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// Gets the message of the diagnostic.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets the number of matching diagnostics that were reported.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Represents a baseline of lint diagnostics.
+///
+/// Only diagnostics associated with a rule are recorded; the file names used
+/// are up to the caller, but must be the same when recording and suppressing
+/// diagnostics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Baseline {
+    /// The version of the baseline file format.
+    version: u32,
+    /// The entries of the baseline.
+    entries: Vec<BaselineEntry>,
+    /// The number of diagnostics matched against each entry.
+    #[serde(skip)]
+    matched: Vec<usize>,
+    /// The files whose diagnostics were matched against the baseline.
+    #[serde(skip)]
+    checked: HashSet<String>,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            version: BASELINE_VERSION,
+            entries: Default::default(),
+            matched: Default::default(),
+            checked: Default::default(),
+        }
+    }
+}
+
+impl Baseline {
+    /// Parses a baseline from the given JSON string.
+    pub fn parse(s: &str) -> Result<Self> {
+        let baseline: Self = serde_json::from_str(s).context("failed to parse lint baseline")?;
+        if baseline.version != BASELINE_VERSION {
+            bail!(
+                "unsupported lint baseline version {version}; expected version {BASELINE_VERSION}",
+                version = baseline.version
+            );
+        }
+
+        Ok(baseline)
+    }
+
+    /// Loads a baseline from the given file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path).with_context(|| {
+            format!(
+                "failed to read lint baseline `{path}`",
+                path = path.display()
+            )
+        })?;
+
+        Self::parse(&contents)
+            .with_context(|| format!("invalid lint baseline `{path}`", path = path.display()))
+    }
+
+    /// Saves the baseline to the given file.
+    ///
+    /// The entries are sorted so that the file is stable across runs.
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        self.entries.sort_by(|a, b| {
+            (&a.file, &a.rule, &a.fingerprint).cmp(&(&b.file, &b.rule, &b.fingerprint))
+        });
+
+        let mut contents =
+            serde_json::to_string_pretty(self).context("failed to serialize lint baseline")?;
+        contents.push('\n');
+        fs::write(path, contents).with_context(|| {
+            format!(
+                "failed to write lint baseline `{path}`",
+                path = path.display()
+            )
+        })
+    }
+
+    /// Gets the entries of the baseline.
+    pub fn entries(&self) -> &[BaselineEntry] {
+        &self.entries
+    }
+
+    /// Records the lint diagnostics reported for the given file and source.
+    pub fn add(&mut self, file: &str, source: &str, diagnostics: &[Diagnostic]) {
+        for diagnostic in diagnostics {
+            let Some(fingerprint) = fingerprint(source, diagnostic) else {
+                continue;
+            };
+
+            let rule = diagnostic.rule().expect("diagnostic should have a rule");
+            match self
+                .entries
+                .iter_mut()
+                .find(|e| e.file == file && e.rule == rule && e.fingerprint == fingerprint)
+            {
+                Some(entry) => entry.count += 1,
+                None => self.entries.push(BaselineEntry {
+                    rule: rule.to_string(),
+                    file: file.to_string(),
+                    fingerprint,
+                    message: diagnostic.message().to_string(),
+                    count: 1,
+                }),
+            }
+        }
+    }
+
+    /// Removes the diagnostics reported for the given file and source that
+    /// are recorded in the baseline.
+    ///
+    /// An entry suppresses at most as many diagnostics as it recorded.
+    ///
+    /// Returns the diagnostics that are not recorded in the baseline.
+    pub fn suppress(
+        &mut self,
+        file: &str,
+        source: &str,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Vec<Diagnostic> {
+        self.checked.insert(file.to_string());
+        self.matched.resize(self.entries.len(), 0);
+
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                let Some(fingerprint) = fingerprint(source, diagnostic) else {
+                    return true;
+                };
+
+                let index = self.entries.iter().enumerate().position(|(i, e)| {
+                    e.file == file
+                        && Some(e.rule.as_str()) == diagnostic.rule()
+                        && e.fingerprint == fingerprint
+                        && self.matched[i] < e.count
+                });
+
+                match index {
+                    Some(index) => {
+                        self.matched[index] += 1;
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect()
+    }
+
+    /// Gets the entries of the files passed to [Baseline::suppress] that
+    /// matched fewer diagnostics than they recorded.
+    ///
+    /// These entries have been fixed and may be removed from the baseline.
+    ///
+    /// Returns each entry along with the number of its diagnostics that were
+    /// fixed.
+    pub fn fixed(&self) -> impl Iterator<Item = (&BaselineEntry, usize)> {
+        self.entries.iter().enumerate().filter_map(|(i, e)| {
+            if !self.checked.contains(&e.file) {
+                return None;
+            }
+
+            let matched = self.matched.get(i).copied().unwrap_or(0);
+            (matched < e.count).then(|| (e, e.count - matched))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use wdl_ast::Span;
+
+    use super::*;
+
+    /// Gets a diagnostic for the first occurrence of `text` in the source.
+    fn diagnostic(source: &str, rule: &str, text: &str) -> Diagnostic {
+        let start = source.find(text).expect("text should be in source");
+        Diagnostic::warning(format!("found `{text}`"))
+            .with_rule(rule)
+            .with_highlight(Span::new(start, text.len()))
+    }
+
+    #[test]
+    fn suppress_shifted_diagnostics() {
+        let source = "version 1.1\n\nworkflow test {\n    Int a = 1\n}\n";
+        let mut baseline = Baseline::default();
+        baseline.add(
+            "test.wdl",
+            source,
+            &[
+                diagnostic(source, "Rule", "Int a"),
+                Diagnostic::error("not a lint"),
+            ],
+        );
+        assert_eq!(baseline.entries().len(), 1);
+
+        let source =
+            "version 1.1\n\n# A comment\nworkflow test {\n        Int a = 1\n    Int b = 2\n}\n";
+        let diagnostics = vec![
+            diagnostic(source, "Rule", "Int a"),
+            diagnostic(source, "Rule", "Int b"),
+            Diagnostic::error("not a lint"),
+        ];
+
+        let remaining = baseline.suppress("test.wdl", source, diagnostics.clone());
+        assert_eq!(remaining, &diagnostics[1..]);
+        assert_eq!(baseline.fixed().count(), 0);
+
+        // Diagnostics of other files are not suppressed
+        let remaining = baseline.suppress("other.wdl", source, diagnostics.clone());
+        assert_eq!(remaining, diagnostics);
+    }
+
+    #[test]
+    fn fixed_entries() {
+        let source = "version 1.1\n\nworkflow test {\n    Int a = 1\n    Int a = 1\n}\n";
+        let diagnostics = [
+            diagnostic(source, "Rule", "Int a"),
+            diagnostic(source, "Rule", "Int a"),
+        ];
+
+        let mut baseline = Baseline::default();
+        baseline.add("test.wdl", source, &diagnostics);
+        baseline.add("other.wdl", source, &diagnostics);
+        assert_eq!(baseline.entries().len(), 2);
+        assert_eq!(baseline.entries()[0].count(), 2);
+
+        let mut baseline =
+            Baseline::parse(&serde_json::to_string(&baseline).expect("baseline should serialize"))
+                .expect("baseline should parse");
+
+        // Only one of the two recorded diagnostics is still reported
+        let remaining = baseline.suppress("test.wdl", source, diagnostics[..1].to_vec());
+        assert!(remaining.is_empty());
+
+        let fixed: Vec<_> = baseline
+            .fixed()
+            .map(|(e, count)| (e.file(), e.rule(), count))
+            .collect();
+        assert_eq!(fixed, [("test.wdl", "Rule", 1)]);
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let e = Baseline::parse(r#"{ "version": 2, "entries": [] }"#)
+            .expect_err("baseline should not parse");
+        assert_eq!(
+            e.to_string(),
+            "unsupported lint baseline version 2; expected version 1"
+        );
+    }
+}
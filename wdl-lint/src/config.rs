@@ -0,0 +1,548 @@
+//! Implementation of lint configuration files.
+//!
+//! A lint configuration file is either a `.wdl-lint.toml` file or the `lint`
+//! table of a `wdl.toml` file.
+//!
+//! An example configuration:
+//!
+//! ```toml
+//! # Require every exception comment to give a reason
+//! require-exception-reasons = true
+//!
+//! # Disable rules by identifier or by tag
+//! disable = ["Sorting", "Todo"]
+//!
+//! # Enable rules that were disabled by a tag
+//! enable = ["ImportSort"]
+//!
+//! [rules.LineWidth]
+//! severity = "warning"
+//! max-width = 120
+//!
+//! [rules.SnakeCase]
+//! exceptions = ["sampleID"]
+//!
+//! [rules.MissingMetas]
+//! required-keys = ["author"]
+//!
+//! # Overrides apply to documents matching any of the globs; the globs are
+//! # relative to the directory containing the configuration file
+//! [[overrides]]
+//! files = ["tests/**/*.wdl"]
+//! disable = ["MissingMetas"]
+//! ```
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use glob::MatchOptions;
+use glob::Pattern;
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde::Deserializer;
+use wdl_ast::Severity;
+
+use crate::rules;
+use crate::Rule;
+use crate::Tag;
+
+/// The names of the lint configuration files, in order of precedence.
+pub const CONFIG_FILE_NAMES: [&str; 2] = [".wdl-lint.toml", "wdl.toml"];
+
+/// The name of the configuration file whose lint configuration is in its
+/// `lint` table.
+const PROJECT_FILE_NAME: &str = "wdl.toml";
+
+/// The options used when matching override globs.
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Deserializes an optional severity.
+fn deserialize_severity<'de, D>(deserializer: D) -> Result<Option<Severity>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let severity = String::deserialize(deserializer)?;
+    match severity.as_str() {
+        "error" => Ok(Some(Severity::Error)),
+        "warning" => Ok(Some(Severity::Warning)),
+        "note" => Ok(Some(Severity::Note)),
+        _ => Err(serde::de::Error::custom(format!(
+            "unknown severity `{severity}`; expected `error`, `warning`, or `note`"
+        ))),
+    }
+}
+
+/// Determines if a rule is selected by the given rule identifier or tag.
+fn selects(selector: &str, rule: &dyn Rule) -> bool {
+    selector == rule.id()
+        || selector
+            .parse::<Tag>()
+            .map(|t| rule.tags().contains(t))
+            .unwrap_or(false)
+}
+
+/// Represents the configuration of an individual lint rule.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct RuleConfig {
+    /// The severity of the rule's diagnostics.
+    #[serde(default, deserialize_with = "deserialize_severity")]
+    severity: Option<Severity>,
+    /// The maximum width of a line for the `LineWidth` rule.
+    max_width: Option<usize>,
+    /// The names excepted by the `SnakeCase` and `PascalCase` rules.
+    exceptions: Option<Vec<String>>,
+    /// The keys required in `meta` sections by the `MissingMetas` rule.
+    required_keys: Option<Vec<String>>,
+}
+
+impl RuleConfig {
+    /// Merges another rule configuration into this one.
+    ///
+    /// The settings of the other configuration take precedence.
+    fn merge(&mut self, other: &Self) {
+        if other.severity.is_some() {
+            self.severity = other.severity;
+        }
+
+        if other.max_width.is_some() {
+            self.max_width = other.max_width;
+        }
+
+        if other.exceptions.is_some() {
+            self.exceptions.clone_from(&other.exceptions);
+        }
+
+        if other.required_keys.is_some() {
+            self.required_keys.clone_from(&other.required_keys);
+        }
+    }
+
+    /// Validates the options of the configuration for the given rule.
+    fn validate(&self, id: &str) -> Result<()> {
+        let options = [
+            ("max-width", self.max_width.is_some(), &["LineWidth"][..]),
+            (
+                "exceptions",
+                self.exceptions.is_some(),
+                &["SnakeCase", "PascalCase"][..],
+            ),
+            (
+                "required-keys",
+                self.required_keys.is_some(),
+                &["MissingMetas"][..],
+            ),
+        ];
+
+        for (option, set, rules) in options {
+            if set && !rules.contains(&id) {
+                bail!("lint rule `{id}` does not support the `{option}` option");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents the settings of a configuration that apply to documents
+/// matching a set of globs.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Override {
+    /// The globs of the documents the override applies to.
+    files: Vec<String>,
+    /// The rule identifiers or tags to enable.
+    #[serde(default)]
+    enable: Vec<String>,
+    /// The rule identifiers or tags to disable.
+    #[serde(default)]
+    disable: Vec<String>,
+    /// The configuration of individual rules.
+    #[serde(default)]
+    rules: IndexMap<String, RuleConfig>,
+}
+
+/// Represents the contents of a `wdl.toml` file.
+#[derive(Debug, Default, Deserialize)]
+struct Project {
+    /// The lint configuration.
+    #[serde(default)]
+    lint: Config,
+}
+
+/// Represents a lint configuration.
+///
+/// By default, every rule is enabled with its default settings.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    /// The directory containing the configuration file.
+    ///
+    /// Override globs are relative to this directory.
+    #[serde(skip)]
+    root: Option<PathBuf>,
+    /// The rule identifiers or tags to enable.
+    ///
+    /// Rules are enabled after rules are disabled.
+    #[serde(default)]
+    enable: Vec<String>,
+    /// The rule identifiers or tags to disable.
+    #[serde(default)]
+    disable: Vec<String>,
+    /// The configuration of individual rules.
+    #[serde(default)]
+    rules: IndexMap<String, RuleConfig>,
+    /// The overrides of the configuration.
+    #[serde(default)]
+    overrides: Vec<Override>,
+    /// Whether or not exception comments are required to give a reason.
+    #[serde(default)]
+    require_exception_reasons: bool,
+}
+
+impl Config {
+    /// Parses a lint configuration from TOML.
+    ///
+    /// Override globs are relative to the current directory.
+    pub fn parse(source: &str) -> Result<Self> {
+        let config: Self = toml::from_str(source).context("failed to parse lint configuration")?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Loads a lint configuration file.
+    ///
+    /// If the file is a `wdl.toml` file, the configuration is read from its
+    /// `lint` table.
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = std::fs::read_to_string(path).with_context(|| {
+            format!(
+                "failed to read lint configuration file `{path}`",
+                path = path.display()
+            )
+        })?;
+
+        let mut config = if path.file_name().and_then(|n| n.to_str()) == Some(PROJECT_FILE_NAME) {
+            toml::from_str::<Project>(&source).map(|p| p.lint)
+        } else {
+            toml::from_str::<Self>(&source)
+        }
+        .with_context(|| {
+            format!(
+                "failed to parse lint configuration file `{path}`",
+                path = path.display()
+            )
+        })?;
+
+        config.validate().with_context(|| {
+            format!(
+                "invalid lint configuration file `{path}`",
+                path = path.display()
+            )
+        })?;
+
+        config.root = std::path::absolute(path)
+            .ok()
+            .and_then(|p| p.parent().map(Path::to_path_buf));
+        Ok(config)
+    }
+
+    /// Discovers the lint configuration file for a document by walking up
+    /// the directories from the document's path.
+    ///
+    /// See [`CONFIG_FILE_NAMES`] for the names of the files searched for in
+    /// each directory.
+    ///
+    /// Returns `Ok(None)` if no configuration file was found.
+    pub fn discover(path: &Path) -> Result<Option<Self>> {
+        let path = std::path::absolute(path).with_context(|| {
+            format!(
+                "failed to determine the absolute path of `{path}`",
+                path = path.display()
+            )
+        })?;
+
+        for dir in path.ancestors().filter(|d| d.is_dir()) {
+            for name in CONFIG_FILE_NAMES {
+                let file = dir.join(name);
+                if file.is_file() {
+                    return Self::load(&file).map(Some);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Gets the path of the directory containing the configuration file.
+    ///
+    /// Returns `None` if the configuration was not loaded from a file.
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    /// Determines if exception comments are required to give a reason.
+    pub fn require_exception_reasons(&self) -> bool {
+        self.require_exception_reasons
+    }
+
+    /// Gets the enabled rules for the document at the given path.
+    ///
+    /// The rules are configured with the options of the configuration.
+    pub fn rules(&self, path: &Path) -> Vec<Box<dyn Rule>> {
+        let (enabled, config) = self.resolve(path);
+        rules()
+            .into_iter()
+            .zip(enabled)
+            .filter_map(|(rule, enabled)| {
+                if !enabled {
+                    return None;
+                }
+
+                let Some(config) = config.get(rule.id()) else {
+                    return Some(rule);
+                };
+
+                Some(match rule.id() {
+                    "LineWidth" => match config.max_width {
+                        Some(max_width) => Box::new(rules::LineWidthRule::new(max_width)),
+                        None => rule,
+                    },
+                    "SnakeCase" => match &config.exceptions {
+                        Some(exceptions) => {
+                            Box::new(rules::SnakeCaseRule::new(exceptions.iter().cloned()))
+                        }
+                        None => rule,
+                    },
+                    "PascalCase" => match &config.exceptions {
+                        Some(exceptions) => {
+                            Box::new(rules::PascalCaseRule::new(exceptions.iter().cloned()))
+                        }
+                        None => rule,
+                    },
+                    "MissingMetas" => match &config.required_keys {
+                        Some(keys) => Box::new(rules::MissingMetasRule::new(keys.iter().cloned())),
+                        None => rule,
+                    },
+                    _ => rule,
+                })
+            })
+            .collect()
+    }
+
+    /// Gets the severity overrides of the rules for the document at the given
+    /// path.
+    pub fn severities(&self, path: &Path) -> IndexMap<String, Severity> {
+        self.resolve(path)
+            .1
+            .into_iter()
+            .filter_map(|(id, c)| Some((id, c.severity?)))
+            .collect()
+    }
+
+    /// Validates the rule identifiers, tags, and options of the
+    /// configuration.
+    fn validate(&self) -> Result<()> {
+        let rules = rules();
+        let validate_selectors = |selectors: &[String]| {
+            for selector in selectors {
+                if !rules.iter().any(|r| selects(selector, r.as_ref())) {
+                    bail!("unknown lint rule or tag `{selector}`");
+                }
+            }
+
+            Ok(())
+        };
+
+        let validate_rules = |config: &IndexMap<String, RuleConfig>| {
+            for (id, config) in config {
+                if !rules.iter().any(|r| r.id() == id) {
+                    bail!("unknown lint rule `{id}`");
+                }
+
+                config.validate(id)?;
+            }
+
+            Ok(())
+        };
+
+        validate_selectors(&self.enable)?;
+        validate_selectors(&self.disable)?;
+        validate_rules(&self.rules)?;
+
+        for o in &self.overrides {
+            for glob in &o.files {
+                Pattern::new(glob)
+                    .with_context(|| format!("invalid glob `{glob}` in lint configuration"))?;
+            }
+
+            validate_selectors(&o.enable)?;
+            validate_selectors(&o.disable)?;
+            validate_rules(&o.rules)?;
+        }
+
+        Ok(())
+    }
+
+    /// Determines if an override applies to the document at the given path.
+    fn applies(&self, o: &Override, path: &Path) -> bool {
+        let path = match &self.root {
+            Some(root) => match std::path::absolute(path) {
+                Ok(path) => match path.strip_prefix(root) {
+                    Ok(path) => path.to_path_buf(),
+                    Err(_) => return false,
+                },
+                Err(_) => return false,
+            },
+            None => path.to_path_buf(),
+        };
+
+        o.files.iter().any(|g| {
+            Pattern::new(g)
+                .map(|p| p.matches_path_with(&path, MATCH_OPTIONS))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Resolves the configuration for the document at the given path.
+    ///
+    /// Returns whether or not each rule (in the order of [`rules`]) is enabled
+    /// and the merged configuration of individual rules.
+    fn resolve(&self, path: &Path) -> (Vec<bool>, IndexMap<String, RuleConfig>) {
+        let rules = rules();
+        let mut enabled = vec![true; rules.len()];
+        let mut config: IndexMap<String, RuleConfig> = IndexMap::new();
+
+        let mut apply =
+            |enable: &[String], disable: &[String], rc: &IndexMap<String, RuleConfig>| {
+                for (rule, enabled) in rules.iter().zip(enabled.iter_mut()) {
+                    if disable.iter().any(|s| selects(s, rule.as_ref())) {
+                        *enabled = false;
+                    }
+
+                    if enable.iter().any(|s| selects(s, rule.as_ref())) {
+                        *enabled = true;
+                    }
+                }
+
+                for (id, c) in rc {
+                    config.entry(id.clone()).or_default().merge(c);
+                }
+            };
+
+        apply(&self.enable, &self.disable, &self.rules);
+        for o in self.overrides.iter().filter(|o| self.applies(o, path)) {
+            apply(&o.enable, &o.disable, &o.rules);
+        }
+
+        (enabled, config)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Gets the identifiers of the rules enabled for the given path.
+    fn ids(config: &Config, path: &str) -> Vec<&'static str> {
+        config
+            .rules(Path::new(path))
+            .iter()
+            .map(|r| r.id())
+            .collect()
+    }
+
+    #[test]
+    fn default_config() {
+        let config = Config::default();
+        assert_eq!(
+            ids(&config, "test.wdl"),
+            rules().iter().map(|r| r.id()).collect::<Vec<_>>()
+        );
+        assert!(config.severities(Path::new("test.wdl")).is_empty());
+    }
+
+    #[test]
+    fn enable_and_disable() {
+        let config = Config::parse(
+            r#"
+disable = ["Sorting", "LineWidth"]
+enable = ["ImportSort"]
+
+[rules.Todo]
+severity = "warning"
+
+[[overrides]]
+files = ["tests/**/*.wdl"]
+disable = ["Todo"]
+enable = ["LineWidth"]
+"#,
+        )
+        .expect("should parse");
+
+        let enabled = ids(&config, "test.wdl");
+        assert!(enabled.contains(&"ImportSort"));
+        assert!(enabled.contains(&"Todo"));
+        assert!(!enabled.contains(&"InputSorting"));
+        assert!(!enabled.contains(&"LineWidth"));
+        assert_eq!(
+            config.severities(Path::new("test.wdl")),
+            IndexMap::from([("Todo".to_string(), Severity::Warning)])
+        );
+
+        let enabled = ids(&config, "tests/a/test.wdl");
+        assert!(!enabled.contains(&"Todo"));
+        assert!(enabled.contains(&"LineWidth"));
+    }
+
+    #[test]
+    fn configured_visitor() {
+        let config = Config::parse(
+            r#"
+enable = ["SnakeCase"]
+disable = ["Naming", "Completeness"]
+
+[rules.SnakeCase]
+severity = "error"
+exceptions = ["okName"]
+"#,
+        )
+        .expect("should parse");
+
+        let (document, diagnostics) =
+            wdl_ast::Document::parse("version 1.1\n\nworkflow badName {\n    Int okName = 1\n}\n");
+        assert!(diagnostics.is_empty());
+
+        let mut validator = wdl_ast::Validator::default();
+        validator.add_visitor(crate::LintVisitor::with_config(
+            &config,
+            Path::new("test.wdl"),
+        ));
+        let diagnostics = validator.validate(&document).unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule(), Some("SnakeCase"));
+        assert_eq!(diagnostics[0].severity(), Severity::Error);
+        assert!(diagnostics[0].message().contains("badName"));
+    }
+
+    #[test]
+    fn invalid_config() {
+        let error = |source| format!("{:#}", Config::parse(source).unwrap_err());
+
+        assert!(error("disable = [\"Foo\"]").contains("unknown lint rule or tag `Foo`"));
+        assert!(error("[rules.Foo]\nseverity = \"note\"").contains("unknown lint rule `Foo`"));
+        assert!(error("[rules.Todo]\nseverity = \"fatal\"").contains("unknown severity `fatal`"));
+        assert!(error("[rules.Todo]\nmax-width = 10")
+            .contains("lint rule `Todo` does not support the `max-width` option"));
+        assert!(error("unknown = 1").contains("unknown field `unknown`"));
+    }
+}
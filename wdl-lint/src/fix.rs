@@ -0,0 +1,194 @@
+//! Implementation of applying the fixes of lint diagnostics.
+
+use wdl_ast::AstNode;
+use wdl_ast::Diagnostic;
+use wdl_ast::Document;
+use wdl_ast::Edit;
+use wdl_ast::SyntaxKind;
+use wdl_ast::Validator;
+
+use crate::LintVisitor;
+
+/// The maximum number of times a document is linted and fixed.
+///
+/// Fixing a document may result in new diagnostics being reported (or in
+/// previously conflicting fixes becoming applicable), so fixes are applied
+/// until there are no more to apply; this bounds the number of passes in case
+/// fixes do not converge.
+const MAX_PASSES: usize = 10;
+
+/// Determines if two edits conflict.
+///
+/// Edits conflict if they overlap or if they insert at the same position.
+fn conflicts(a: &Edit, b: &Edit) -> bool {
+    let (a, b) = (a.span(), b.span());
+    a.start() == b.start() || (a.start() < b.end() && b.start() < a.end())
+}
+
+/// Gets the sorted text of the comments in the given document.
+///
+/// Trailing whitespace is not considered part of the text of a comment, as
+/// fixes may remove it.
+fn comments(document: &Document) -> Vec<String> {
+    let mut comments: Vec<_> = document
+        .syntax()
+        .descendants_with_tokens()
+        .filter(|c| c.kind() == SyntaxKind::Comment)
+        .map(|c| c.to_string().trim_end().to_string())
+        .collect();
+    comments.sort();
+    comments
+}
+
+/// Applies the machine-applicable fixes of the given diagnostics to the given
+/// source.
+///
+/// The edits of a diagnostic are applied together; a diagnostic is skipped if
+/// any of its edits conflict with an edit of a diagnostic that was already
+/// applied.
+///
+/// Returns the fixed source and the number of diagnostics that were fixed.
+pub fn apply_fixes(source: &str, diagnostics: &[Diagnostic]) -> (String, usize) {
+    let mut edits: Vec<&Edit> = Vec::new();
+    let mut fixed = 0;
+    for diagnostic in diagnostics {
+        if diagnostic.edits().is_empty()
+            || diagnostic
+                .edits()
+                .iter()
+                .any(|e| edits.iter().any(|o| conflicts(e, o)))
+        {
+            continue;
+        }
+
+        edits.extend(diagnostic.edits());
+        fixed += 1;
+    }
+
+    // Apply the edits from the end of the source so that the spans of the
+    // remaining edits stay valid
+    edits.sort_by_key(|e| e.span());
+    let mut source = source.to_string();
+    for edit in edits.into_iter().rev() {
+        let span = edit.span();
+        source.replace_range(span.start()..span.end(), edit.replacement());
+    }
+
+    (source, fixed)
+}
+
+/// Represents the result of fixing a document.
+#[derive(Debug, Clone)]
+pub struct Fixed {
+    /// The fixed source of the document.
+    pub source: String,
+    /// The number of lint diagnostics that were fixed.
+    pub fixed: usize,
+    /// The diagnostics of the fixed source.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Lints the given source with the given visitor and applies the fixes of
+/// lint diagnostics until no more fixes can be applied.
+///
+/// Only diagnostics reported by lint rules are fixed.
+///
+/// A pass of fixes that results in source that no longer parses or that is
+/// missing any of the comments of the document is discarded.
+///
+/// Returns the parse diagnostics if the given source fails to parse.
+pub fn fix(source: &str, visitor: LintVisitor) -> Result<Fixed, Vec<Diagnostic>> {
+    let (document, diagnostics) = Document::parse(source);
+    if !diagnostics.is_empty() {
+        return Err(diagnostics);
+    }
+
+    let mut validator = Validator::default();
+    validator.add_visitor(visitor);
+
+    let original = comments(&document);
+    let mut result = Fixed {
+        source: source.to_string(),
+        fixed: 0,
+        diagnostics: validator.validate(&document).err().unwrap_or_default(),
+    };
+
+    for _ in 0..MAX_PASSES {
+        let lints: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.rule().is_some())
+            .cloned()
+            .collect();
+
+        let (source, fixed) = apply_fixes(&result.source, &lints);
+        if fixed == 0 {
+            break;
+        }
+
+        let (document, diagnostics) = Document::parse(&source);
+        if !diagnostics.is_empty() || comments(&document) != original {
+            break;
+        }
+
+        result = Fixed {
+            diagnostics: validator.validate(&document).err().unwrap_or_default(),
+            source,
+            fixed: result.fixed + fixed,
+        };
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn conflicting_fixes() {
+        let diagnostics = [
+            Diagnostic::note("a").with_edit(wdl_ast::Span::new(0, 1), "A"),
+            Diagnostic::note("b")
+                .with_edit(wdl_ast::Span::new(0, 0), "B")
+                .with_edit(wdl_ast::Span::new(2, 1), "B"),
+            Diagnostic::note("c").with_edit(wdl_ast::Span::new(1, 1), "C"),
+            Diagnostic::note("d"),
+        ];
+
+        assert_eq!(apply_fixes("abc", &diagnostics), ("ACc".to_string(), 2));
+    }
+
+    #[test]
+    fn fix_to_fixed_point() {
+        let source = "version 1.1\n\nimport \"b.wdl\"   \nimport 'a.wdl'\n\nworkflow test {\n    \
+                      Array[String] a = [\n        'a',\n        'b'\n    ]\n}";
+        let fixed = fix(source, LintVisitor::default()).expect("should parse");
+        assert_eq!(
+            fixed.source,
+            "version 1.1\n\nimport \"a.wdl\"\nimport \"b.wdl\"\n\nworkflow test {\n    \
+             Array[String] a = [\n        \"a\",\n        \"b\",\n    ]\n}\n"
+        );
+        assert_eq!(fixed.fixed, 7);
+        assert!(fixed.diagnostics.iter().all(|d| d.edits().is_empty()));
+    }
+
+    #[test]
+    fn comments_survive() {
+        let source = include_str!("../tests/lints/trailing-comma/source.wdl");
+        let fixed = fix(source, LintVisitor::default()).expect("should parse");
+        assert!(fixed.fixed > 0);
+
+        let (original, _) = Document::parse(source);
+        let (document, diagnostics) = Document::parse(&fixed.source);
+        assert!(diagnostics.is_empty());
+        assert_eq!(comments(&document), comments(&original));
+    }
+
+    #[test]
+    fn unparsable_source() {
+        assert!(fix("version 1.1\n\nworkflow {", LintVisitor::default()).is_err());
+    }
+}
@@ -32,11 +32,19 @@
 use wdl_ast::Diagnostics;
 use wdl_ast::Visitor;
 
+mod baseline;
+mod config;
+mod fix;
 pub mod rules;
+mod sarif;
 mod tags;
 pub(crate) mod util;
 mod visitor;
 
+pub use baseline::*;
+pub use config::*;
+pub use fix::*;
+pub use sarif::*;
 pub use tags::*;
 pub use visitor::*;
 pub use wdl_ast as ast;
@@ -105,6 +113,7 @@ pub fn rules() -> Vec<Box<dyn Rule>> {
         Box::<rules::DisallowedOutputNameRule>::default(),
         Box::<rules::ContainerValue>::default(),
         Box::<rules::MissingRequirementsRule>::default(),
+        Box::<rules::UnusedExceptionRule>::default(),
     ];
 
     // Ensure all the rule ids are unique and pascal case
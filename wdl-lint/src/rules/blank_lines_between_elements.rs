@@ -508,12 +508,10 @@ fn skip_preceding_comments(syntax: &SyntaxNode) -> NodeOrToken<SyntaxNode, Synta
                 // else is on that line.
                 if let Some(before_cur) = cur.prev_sibling_or_token() {
                     match before_cur.kind() {
-                        SyntaxKind::Whitespace => {
-                            if before_cur.to_string().contains('\n') {
-                                // The 'cur' comment is on is on its own line.
-                                // It "belongs" to the current element.
-                                preceding_comments.push(cur.clone());
-                            }
+                        SyntaxKind::Whitespace if before_cur.to_string().contains('\n') => {
+                            // The 'cur' comment is on is on its own line.
+                            // It "belongs" to the current element.
+                            preceding_comments.push(cur.clone());
                         }
                         _ => {
                             // The 'cur' comment is on the same line as this
@@ -538,10 +536,10 @@ fn skip_preceding_comments(syntax: &SyntaxNode) -> NodeOrToken<SyntaxNode, Synta
         prev = cur.prev_sibling_or_token()
     }
 
-    return preceding_comments
+    preceding_comments
         .last()
         .unwrap_or(&NodeOrToken::Node(syntax.clone()))
-        .clone();
+        .clone()
 }
 
 /// Is first body element?
@@ -158,10 +158,8 @@ impl Visitor for CallInputSpacingRule {
         call.syntax()
             .children_with_tokens()
             .for_each(|c| match c.kind() {
-                SyntaxKind::Whitespace => {
-                    if c.to_string().contains('\n') {
-                        newline_seen += 1;
-                    }
+                SyntaxKind::Whitespace if c.to_string().contains('\n') => {
+                    newline_seen += 1;
                 }
                 SyntaxKind::CallInputItemNode => {
                     if newline_seen == 0 && inputs > 1 {
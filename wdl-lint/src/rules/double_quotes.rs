@@ -2,8 +2,11 @@
 
 use wdl_ast::v1::Expr;
 use wdl_ast::v1::LiteralExpr;
+use wdl_ast::v1::LiteralString;
 use wdl_ast::v1::LiteralStringKind;
+use wdl_ast::v1::StringPart;
 use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
 use wdl_ast::Document;
@@ -20,11 +23,35 @@ use crate::TagSet;
 const ID: &str = "DoubleQuotes";
 
 /// Creates a "use double quotes" diagnostic.
-fn use_double_quotes(span: Span) -> Diagnostic {
-    Diagnostic::warning("string defined with single quotes")
+fn use_double_quotes(string: &LiteralString) -> Diagnostic {
+    let span = string.span();
+    let mut diagnostic = Diagnostic::warning("string defined with single quotes")
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("change the single quotes to double quotes")
+        .with_edit(Span::new(span.start(), 1), "\"")
+        .with_edit(Span::new(span.end() - 1, 1), "\"");
+
+    // Escape any double quotes in the text of the string
+    for part in string.parts() {
+        let StringPart::Text(text) = part else {
+            continue;
+        };
+
+        let start = text.span().start();
+        let mut escaped = false;
+        for (offset, c) in text.as_str().char_indices() {
+            match c {
+                '\\' => escaped = !escaped,
+                '"' if !escaped => {
+                    diagnostic = diagnostic.with_edit(Span::new(start + offset, 0), "\\");
+                }
+                _ => escaped = false,
+            }
+        }
+    }
+
+    diagnostic
 }
 
 /// Detects strings that are not defined with double quotes.
@@ -76,7 +103,7 @@ impl Visitor for DoubleQuotesRule {
 
         if let Expr::Literal(LiteralExpr::String(s)) = expr {
             if s.kind() == LiteralStringKind::SingleQuoted {
-                state.add(use_double_quotes(s.span()));
+                state.add(use_double_quotes(s));
             }
         }
     }
@@ -25,6 +25,7 @@ fn missing_ending_newline(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_label("expected a newline to follow this", span)
         .with_fix("add an empty line at the end of the file")
+        .with_edit(Span::new(span.end(), 0), "\n")
 }
 
 /// Creates a "multiple ending newline" diagnostic.
@@ -40,6 +41,7 @@ fn multiple_ending_newline(span: Span, count: usize) -> Diagnostic {
             span,
         )
         .with_fix("remove all but one empty line at the end of the file")
+        .with_edit(span, "")
 }
 
 /// Detects missing newline at the end of the document.
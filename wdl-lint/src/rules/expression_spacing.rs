@@ -227,7 +227,7 @@ impl Visitor for ExpressionSpacingRule {
         }
 
         match expr {
-            Expr::LogicalNot(_) | Expr::Negation(_) => {
+            Expr::LogicalNot(_) | Expr::Negation(_)
                 // No following spacing allowed
                 if expr
                     .syntax()
@@ -235,10 +235,9 @@ impl Visitor for ExpressionSpacingRule {
                     .filter(|t| t.kind() == SyntaxKind::Whitespace)
                     .count()
                     > 0
-                {
+                => {
                     state.add(prefix_whitespace(expr.syntax().text_range().to_span()));
                 }
-            }
             Expr::Parenthesized(_) => {
                 // Find the actual open and close parentheses
                 let open = expr
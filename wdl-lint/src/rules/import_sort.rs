@@ -4,6 +4,7 @@ use wdl_ast::v1::ImportStatement;
 use wdl_ast::AstNode;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
+use wdl_ast::Direction;
 use wdl_ast::Document;
 use wdl_ast::Span;
 use wdl_ast::SupportedVersion;
@@ -21,11 +22,94 @@ use crate::TagSet;
 const ID: &str = "ImportSort";
 
 /// Creates an import not sorted diagnostic.
-fn import_not_sorted(span: Span) -> Diagnostic {
-    Diagnostic::note("imports are not sorted lexicographically")
+///
+/// The fix replaces each of the given imports, along with the comments
+/// directly preceding it, with the import that belongs at its position once
+/// sorted.
+///
+/// No fix is attached if a comment between the imports does not directly
+/// precede an import, as there is no way to know which import it belongs to.
+fn import_not_sorted(span: Span, imports: &[SyntaxNode]) -> Diagnostic {
+    let diagnostic = Diagnostic::note("imports are not sorted lexicographically")
         .with_rule(ID)
         .with_highlight(span)
-        .with_fix("sort the imports lexicographically")
+        .with_fix("sort the imports lexicographically");
+
+    let blocks = imports.iter().map(import_block).collect::<Vec<_>>();
+    let unattached = imports
+        .iter()
+        .zip(blocks.iter().skip(1))
+        .any(|(import, (next, _))| {
+            import
+                .siblings_with_tokens(Direction::Next)
+                .skip(1)
+                .take_while(|c| usize::from(c.text_range().start()) < next.start())
+                .any(|c| c.kind() == SyntaxKind::Comment)
+        });
+    if unattached {
+        return diagnostic;
+    }
+
+    let mut sorted = imports
+        .iter()
+        .zip(&blocks)
+        .map(|(import, (_, text))| (import.text().to_string(), text))
+        .collect::<Vec<_>>();
+    sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    blocks
+        .iter()
+        .zip(sorted)
+        .filter(|((_, text), (_, sorted))| text != *sorted)
+        .fold(diagnostic, |diagnostic, ((span, _), (_, text))| {
+            diagnostic.with_edit(*span, text.as_str())
+        })
+}
+
+/// Gets the span and text of the given import along with the comments
+/// directly preceding it.
+///
+/// A comment is not included if it is separated from the import by a blank
+/// line or if it follows another element on the same line.
+fn import_block(import: &SyntaxNode) -> (Span, String) {
+    let mut start = import.text_range().start();
+    let mut prev = import.prev_sibling_or_token();
+    while let Some(element) = prev {
+        match element.kind() {
+            SyntaxKind::Whitespace if element.to_string().matches('\n').count() > 1 => break,
+            SyntaxKind::Whitespace => {}
+            SyntaxKind::Comment => {
+                let trailing = match element.prev_sibling_or_token() {
+                    Some(p) if p.kind() == SyntaxKind::Whitespace => !p.to_string().contains('\n'),
+                    Some(_) => true,
+                    None => false,
+                };
+
+                if trailing {
+                    break;
+                }
+
+                start = element.text_range().start();
+            }
+            _ => break,
+        }
+
+        prev = element.prev_sibling_or_token();
+    }
+
+    let span = Span::new(
+        usize::from(start),
+        usize::from(import.text_range().end() - start),
+    );
+    let text = import
+        .siblings_with_tokens(Direction::Prev)
+        .take_while(|c| c.text_range().start() >= start)
+        .collect::<Vec<_>>()
+        .iter()
+        .rev()
+        .map(|c| c.to_string())
+        .collect();
+    (span, text)
 }
 
 /// Creates an improper comment diagnostic.
@@ -82,13 +166,14 @@ impl Visitor for ImportSortRule {
             .syntax()
             .children_with_tokens()
             .filter(|c| c.kind() == SyntaxKind::ImportStatementNode)
-            .map(|c| c.into_node().unwrap());
+            .map(|c| c.into_node().unwrap())
+            .collect::<Vec<_>>();
 
-        let mut prev_import: Option<SyntaxNode> = None;
-        for import in imports {
+        let mut prev_import: Option<&SyntaxNode> = None;
+        for import in &imports {
             if let Some(prev) = prev_import {
                 if import.text().to_string() < prev.text().to_string() {
-                    state.add(import_not_sorted(import.text_range().to_span()));
+                    state.add(import_not_sorted(import.text_range().to_span(), &imports));
                     return; // Only report one sorting diagnostic at a time.
                 }
             }
@@ -128,7 +128,10 @@ impl Visitor for KeyValuePairsRule {
             .into_token()
             .expect("should have a token")
             .to_string();
-        let parent_ws = tmp.split('\n').last().expect("should have indentation");
+        let parent_ws = tmp
+            .split('\n')
+            .next_back()
+            .expect("should have indentation");
 
         if !item.syntax().to_string().contains('\n') {
             state.add(all_on_one_line(item.syntax().text_range().to_span()));
@@ -176,7 +179,10 @@ impl Visitor for KeyValuePairsRule {
                 {
                     // If there was no newline, that is already reported
                     let ws = prior_ws.to_string();
-                    let ws = ws.split('\n').last().expect("should have a last element");
+                    let ws = ws
+                        .split('\n')
+                        .next_back()
+                        .expect("should have a last element");
                     let expected_ws = parent_ws.to_owned() + INDENT;
 
                     if ws != expected_ws {
@@ -199,7 +205,7 @@ impl Visitor for KeyValuePairsRule {
                 let ws = prior_ws.to_string();
                 let ws = ws
                     .split('\n')
-                    .last()
+                    .next_back()
                     .expect("there should be a last element");
                 let expected_ws = parent_ws.to_owned();
 
@@ -236,7 +242,10 @@ impl Visitor for KeyValuePairsRule {
             .into_token()
             .expect("should have a token")
             .to_string();
-        let parent_ws = tmp.split('\n').last().expect("should have indentation");
+        let parent_ws = tmp
+            .split('\n')
+            .next_back()
+            .expect("should have indentation");
 
         // If the array is all on one line, report that
         if !item.syntax().to_string().contains('\n') {
@@ -287,7 +296,7 @@ impl Visitor for KeyValuePairsRule {
                     let ws = prior_ws.to_string();
                     let ws = ws
                         .split('\n')
-                        .last()
+                        .next_back()
                         .expect("there should be a last element");
                     let expected_ws = parent_ws.to_owned() + INDENT;
 
@@ -311,7 +320,7 @@ impl Visitor for KeyValuePairsRule {
                 let ws = prior_ws.to_string();
                 let ws = ws
                     .split('\n')
-                    .last()
+                    .next_back()
                     .expect("there should be a last element");
                 let expected_ws = parent_ws.to_owned();
 
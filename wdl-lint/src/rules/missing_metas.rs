@@ -2,15 +2,19 @@
 
 use std::fmt;
 
+use wdl_ast::v1::MetadataSection;
 use wdl_ast::v1::TaskDefinition;
 use wdl_ast::v1::WorkflowDefinition;
 use wdl_ast::version::V1;
+use wdl_ast::AstNode;
 use wdl_ast::AstToken;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
 use wdl_ast::Document;
 use wdl_ast::Ident;
+use wdl_ast::Span;
 use wdl_ast::SupportedVersion;
+use wdl_ast::ToSpan;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
 
@@ -36,6 +40,7 @@ impl fmt::Display for Section {
 }
 
 /// The context for which section is missing.
+#[derive(Debug, Clone, Copy)]
 enum Context {
     /// A task.
     Task,
@@ -88,11 +93,60 @@ fn missing_sections(name: Ident, context: Context) -> Diagnostic {
     ))
 }
 
+/// Creates a "missing meta key" diagnostic.
+fn missing_key(name: &Ident, key: &str, context: Context, span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "{context} `{name}` is missing the `{key}` key in its `meta` section",
+        name = name.as_str(),
+    ))
+    .with_rule(ID)
+    .with_label(
+        format!("this `meta` section is missing the `{key}` key"),
+        span,
+    )
+    .with_fix(format!("add the `{key}` key to the `meta` section"))
+}
+
 /// A lint rule for missing meta and parameter_meta sections.
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone)]
 pub struct MissingMetasRule {
     /// The version of the WDL document being linted.
     version: Option<SupportedVersion>,
+    /// The keys that are required to be in every `meta` section.
+    required_keys: Vec<String>,
+}
+
+impl MissingMetasRule {
+    /// Constructs a new missing metas rule with the given keys that are
+    /// required to be in every `meta` section.
+    pub fn new(required_keys: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            required_keys: required_keys.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Checks that a `meta` section contains the required keys.
+    fn check_required_keys(
+        &self,
+        state: &mut Diagnostics,
+        name: &Ident,
+        context: Context,
+        section: &MetadataSection,
+    ) {
+        let span = section
+            .syntax()
+            .first_token()
+            .expect("section should have a token")
+            .text_range()
+            .to_span();
+
+        for key in &self.required_keys {
+            if !section.items().any(|i| i.name().as_str() == key) {
+                state.add(missing_key(name, key, context, span));
+            }
+        }
+    }
 }
 
 impl Rule for MissingMetasRule {
@@ -130,7 +184,10 @@ impl Visitor for MissingMetasRule {
         }
 
         // Reset the visitor upon document entry
-        *self = Default::default();
+        *self = Self {
+            required_keys: std::mem::take(&mut self.required_keys),
+            ..Default::default()
+        };
         self.version = Some(version);
     }
 
@@ -157,6 +214,10 @@ impl Visitor for MissingMetasRule {
                 Context::Task,
             ));
         }
+
+        if let Some(section) = task.metadata() {
+            self.check_required_keys(state, &task.name(), Context::Task, &section);
+        }
     }
 
     fn workflow_definition(
@@ -189,6 +250,10 @@ impl Visitor for MissingMetasRule {
                 Context::Workflow,
             ));
         }
+
+        if let Some(section) = workflow.metadata() {
+            self.check_required_keys(state, &workflow.name(), Context::Workflow, &section);
+        }
     }
 
     fn struct_definition(
@@ -217,5 +282,9 @@ impl Visitor for MissingMetasRule {
                 Context::Struct,
             ));
         }
+
+        if let Some(section) = def.metadata().next() {
+            self.check_required_keys(state, &def.name(), Context::Struct, &section);
+        }
     }
 }
@@ -179,12 +179,13 @@ fn check_matching(state: &mut Diagnostics, rule: &mut NonmatchingOutputRule<'_>)
 
 /// Handle missing `meta.outputs` and reset the visitor.
 fn handle_meta_outputs_and_reset(state: &mut Diagnostics, rule: &mut NonmatchingOutputRule<'_>) {
-    if rule.current_meta_span.is_some()
-        && rule.current_meta_outputs_span.is_none()
-        && !rule.output_keys.is_empty()
-    {
+    if let (Some(meta_span), None, false) = (
+        rule.current_meta_span,
+        rule.current_meta_outputs_span,
+        rule.output_keys.is_empty(),
+    ) {
         state.add(missing_outputs_in_meta(
-            rule.current_meta_span.expect("should have a `meta` span"),
+            meta_span,
             rule.name.as_deref().expect("should have a name"),
             rule.ty.expect("should have a type"),
         ));
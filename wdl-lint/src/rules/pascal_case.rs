@@ -1,5 +1,7 @@
 //! A lint rule that ensures structs are defined with pascal case names.
 
+use std::collections::HashSet;
+
 use convert_case::Boundary;
 use convert_case::Case;
 use convert_case::Converter;
@@ -29,8 +31,21 @@ fn use_pascal_case(name: &str, properly_cased_name: &str, span: Span) -> Diagnos
 }
 
 /// Detects structs defined without a pascal case name.
-#[derive(Default, Debug, Clone, Copy)]
-pub struct PascalCaseRule;
+#[derive(Default, Debug, Clone)]
+pub struct PascalCaseRule {
+    /// The names that are permitted to not be PascalCase.
+    exceptions: HashSet<String>,
+}
+
+impl PascalCaseRule {
+    /// Constructs a new pascal case rule with the given names that are
+    /// permitted to not be PascalCase.
+    pub fn new(exceptions: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            exceptions: exceptions.into_iter().collect(),
+        }
+    }
+}
 
 impl Rule for PascalCaseRule {
     fn id(&self) -> &'static str {
@@ -78,7 +93,9 @@ impl Visitor for PascalCaseRule {
         }
 
         // Reset the visitor upon document entry
-        *self = Default::default();
+        *self = Self {
+            exceptions: std::mem::take(&mut self.exceptions),
+        };
     }
 
     fn struct_definition(
@@ -92,6 +109,10 @@ impl Visitor for PascalCaseRule {
         }
 
         let name = def.name();
+        if self.exceptions.contains(name.as_str()) {
+            return;
+        }
+
         check_name(name.as_str(), name.span(), state);
     }
 }
@@ -12,10 +12,10 @@ use wdl_ast::VersionStatement;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
 
+use crate::visitor::is_exception_comment;
 use crate::Rule;
 use crate::Tag;
 use crate::TagSet;
-use crate::EXCEPT_COMMENT_PREFIX;
 
 /// The identifier for the preamble comments rule.
 const ID: &str = "PreambleComments";
@@ -115,7 +115,7 @@ impl Visitor for PreambleCommentsRule {
 
         let check = |text: &str| {
             let double_pound = text == "##" || text.starts_with("## ");
-            let except = text.starts_with(EXCEPT_COMMENT_PREFIX);
+            let except = is_exception_comment(text);
             (self.finished && !double_pound) || (!self.finished && (double_pound | except))
         };
 
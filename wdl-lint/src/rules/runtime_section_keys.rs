@@ -101,6 +101,27 @@ fn keys_v1_1() -> &'static HashMap<&'static str, KeyKind> {
     })
 }
 
+/// Gets the reserved `runtime` section keys for the given version.
+///
+/// Deprecated keys are not included and the keys are returned in sorted order.
+///
+/// Returns an empty list for versions that deprecate the `runtime` section.
+pub fn reserved_runtime_keys(version: SupportedVersion) -> Vec<&'static str> {
+    let keys = match version {
+        SupportedVersion::V1(V1::Zero) => keys_v1_0(),
+        SupportedVersion::V1(V1::One) => keys_v1_1(),
+        _ => return Vec::new(),
+    };
+
+    let mut keys = keys
+        .iter()
+        .filter(|(_, kind)| !matches!(kind, KeyKind::Deprecated(_)))
+        .map(|(key, _)| *key)
+        .collect::<Vec<_>>();
+    keys.sort_unstable();
+    keys
+}
+
 /// Serializes a list of items using the Oxford comma.
 fn serialize_oxford_comma<T: std::fmt::Display>(items: &[T]) -> Option<String> {
     let len = items.len();
@@ -1,6 +1,7 @@
 //! A lint rule for ensuring tasks, workflows, and variables are named using
 //! snake_case.
 
+use std::collections::HashSet;
 use std::fmt;
 
 use convert_case::Boundary;
@@ -69,7 +70,19 @@ fn snake_case(context: Context, name: &str, properly_cased_name: &str, span: Spa
 
 /// Checks if the given name is snake case, and if not adds a warning to the
 /// diagnostics.
-fn check_name(context: Context, name: &str, span: Span, diagnostics: &mut Diagnostics) {
+///
+/// Names in the given set of exceptions are not checked.
+fn check_name(
+    context: Context,
+    name: &str,
+    span: Span,
+    exceptions: &HashSet<String>,
+    diagnostics: &mut Diagnostics,
+) {
+    if exceptions.contains(name) {
+        return;
+    }
+
     let converter = Converter::new()
         .remove_boundaries(&[Boundary::DigitLower, Boundary::LowerDigit])
         .to_case(Case::Snake);
@@ -81,8 +94,10 @@ fn check_name(context: Context, name: &str, span: Span, diagnostics: &mut Diagno
 }
 
 /// Detects non-snake_cased identifiers.
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone)]
 pub struct SnakeCaseRule {
+    /// The names that are permitted to not be snake_case.
+    exceptions: HashSet<String>,
     /// Whether the visitor is currently within a struct.
     within_struct: bool,
     /// Whether the visitor is currently within an input section.
@@ -92,6 +107,15 @@ pub struct SnakeCaseRule {
 }
 
 impl SnakeCaseRule {
+    /// Constructs a new snake_case rule with the given names that are
+    /// permitted to not be snake_case.
+    pub fn new(exceptions: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            exceptions: exceptions.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
     /// Determines current declaration context.
     fn determine_decl_context(&self) -> Context {
         if self.within_struct {
@@ -140,7 +164,10 @@ impl Visitor for SnakeCaseRule {
         }
 
         // Reset the visitor upon document entry
-        *self = Default::default();
+        *self = Self {
+            exceptions: std::mem::take(&mut self.exceptions),
+            ..Default::default()
+        };
     }
 
     fn struct_definition(
@@ -202,7 +229,13 @@ impl Visitor for SnakeCaseRule {
         }
 
         let name = task.name();
-        check_name(Context::Task, name.as_str(), name.span(), state);
+        check_name(
+            Context::Task,
+            name.as_str(),
+            name.span(),
+            &self.exceptions,
+            state,
+        );
     }
 
     fn workflow_definition(
@@ -216,7 +249,13 @@ impl Visitor for SnakeCaseRule {
         }
 
         let name = workflow.name();
-        check_name(Context::Workflow, name.as_str(), name.span(), state);
+        check_name(
+            Context::Workflow,
+            name.as_str(),
+            name.span(),
+            &self.exceptions,
+            state,
+        );
     }
 
     fn bound_decl(&mut self, state: &mut Self::State, reason: VisitReason, decl: &BoundDecl) {
@@ -226,7 +265,7 @@ impl Visitor for SnakeCaseRule {
 
         let name = decl.name();
         let context = self.determine_decl_context();
-        check_name(context, name.as_str(), name.span(), state);
+        check_name(context, name.as_str(), name.span(), &self.exceptions, state);
     }
 
     fn unbound_decl(&mut self, state: &mut Self::State, reason: VisitReason, decl: &UnboundDecl) {
@@ -236,6 +275,6 @@ impl Visitor for SnakeCaseRule {
 
         let name = decl.name();
         let context = self.determine_decl_context();
-        check_name(context, name.as_str(), name.span(), state);
+        check_name(context, name.as_str(), name.span(), &self.exceptions, state);
     }
 }
@@ -7,9 +7,13 @@ use wdl_ast::v1::MetadataArray;
 use wdl_ast::AstNode;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
+use wdl_ast::Direction;
 use wdl_ast::Document;
 use wdl_ast::Span;
 use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::SyntaxToken;
 use wdl_ast::ToSpan;
 use wdl_ast::VisitReason;
 use wdl_ast::Visitor;
@@ -27,14 +31,34 @@ fn missing_trailing_comma(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("add a comma after this element")
+        .with_edit(Span::new(span.end(), 0), ",")
 }
 
 /// Diagnostic message for extraneous content before trailing comma.
-fn extraneous_content(span: Span) -> Diagnostic {
-    Diagnostic::note("extraneous whitespace and/or comments before trailing comma")
-        .with_rule(ID)
-        .with_highlight(span)
-        .with_fix("remove this extraneous content")
+///
+/// The fix moves the comma to directly after the element only when the content
+/// is whitespace; comments are never removed.
+fn extraneous_content(element: &SyntaxNode, comma: &SyntaxToken) -> Diagnostic {
+    let start = element.text_range().end();
+    let span = Span::new(
+        usize::from(start),
+        usize::from(comma.text_range().start() - start),
+    );
+    let diagnostic =
+        Diagnostic::note("extraneous whitespace and/or comments before trailing comma")
+            .with_rule(ID)
+            .with_highlight(span)
+            .with_fix("remove this extraneous content");
+
+    let mut content = element
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .take_while(|c| c.kind() != SyntaxKind::Comma);
+    if content.any(|c| c.kind() == SyntaxKind::Comment) {
+        return diagnostic;
+    }
+
+    diagnostic.with_edit(span, "")
 }
 
 /// Detects missing trailing commas.
@@ -101,12 +125,7 @@ impl Visitor for TrailingCommaRule {
                 if let Some(comma) = next_comma {
                     if !comma_is_next {
                         // Comma found, but not next, extraneous trivia
-                        state.add(extraneous_content(Span::new(
-                            usize::from(last_child.syntax().text_range().end()),
-                            usize::from(
-                                comma.text_range().start() - last_child.syntax().text_range().end(),
-                            ),
-                        )));
+                        state.add(extraneous_content(last_child.syntax(), &comma));
                     }
                 } else {
                     // No comma found, report missing
@@ -136,12 +155,7 @@ impl Visitor for TrailingCommaRule {
                 if let Some(comma) = next_comma {
                     if !comma_is_next {
                         // Comma found, but not next, extraneous trivia
-                        state.add(extraneous_content(Span::new(
-                            usize::from(last_child.syntax().text_range().end()),
-                            usize::from(
-                                comma.text_range().start() - last_child.syntax().text_range().end(),
-                            ),
-                        )));
+                        state.add(extraneous_content(last_child.syntax(), &comma));
                     }
                 } else {
                     // No comma found, report missing
@@ -174,10 +188,7 @@ impl Visitor for TrailingCommaRule {
             let (next_comma, comma_is_next) = find_next_comma(input.syntax());
             if let Some(nc) = next_comma {
                 if !comma_is_next {
-                    state.add(extraneous_content(Span::new(
-                        usize::from(input.syntax().text_range().end()),
-                        usize::from(nc.text_range().start() - input.syntax().text_range().end()),
-                    )));
+                    state.add(extraneous_content(input.syntax(), &nc));
                 }
             } else {
                 state.add(missing_trailing_comma(
@@ -198,22 +209,16 @@ impl Visitor for TrailingCommaRule {
                 LiteralExpr::Array(_)
                 | LiteralExpr::Map(_)
                 | LiteralExpr::Object(_)
-                | LiteralExpr::Struct(_) => {
+                | LiteralExpr::Struct(_)
                     // Check if array is multi-line
-                    if l.syntax().to_string().contains('\n') && l.syntax().children().count() > 1 {
+                    if l.syntax().to_string().contains('\n') && l.syntax().children().count() > 1 => {
                         let last_child = l.syntax().children().last();
                         if let Some(last_child) = last_child {
                             let (next_comma, comma_is_next) = find_next_comma(&last_child);
                             if let Some(comma) = next_comma {
                                 if !comma_is_next {
                                     // Comma found, but not next, extraneous trivia
-                                    state.add(extraneous_content(Span::new(
-                                        usize::from(last_child.text_range().end()),
-                                        usize::from(
-                                            comma.text_range().start()
-                                                - last_child.text_range().end(),
-                                        ),
-                                    )));
+                                    state.add(extraneous_content(&last_child, &comma));
                                 }
                             } else {
                                 // No comma found, report missing
@@ -222,7 +227,6 @@ impl Visitor for TrailingCommaRule {
                             }
                         }
                     }
-                }
                 _ => {}
             }
         }
@@ -0,0 +1,63 @@
+//! A lint rule for exceptions that do not suppress any diagnostics.
+
+use wdl_ast::Diagnostic;
+use wdl_ast::Diagnostics;
+use wdl_ast::Document;
+use wdl_ast::Span;
+use wdl_ast::SupportedVersion;
+use wdl_ast::VisitReason;
+use wdl_ast::Visitor;
+
+use crate::Rule;
+use crate::Tag;
+use crate::TagSet;
+
+/// The identifier for the unused exception rule.
+const ID: &str = "UnusedException";
+
+/// Creates an "unused exception" diagnostic.
+pub(crate) fn unused_exception(selector: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!(
+        "exception for `{selector}` does not suppress any diagnostics"
+    ))
+    .with_rule(ID)
+    .with_label("this exception is unused", span)
+    .with_fix("remove the exception")
+}
+
+/// Detects exception comments that do not suppress any diagnostics.
+///
+/// The exceptions are tracked by the lint visitor, which reports the
+/// diagnostics of this rule when this rule is enabled.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct UnusedExceptionRule;
+
+impl Rule for UnusedExceptionRule {
+    fn id(&self) -> &'static str {
+        ID
+    }
+
+    fn description(&self) -> &'static str {
+        "Ensures that exception comments suppress diagnostics."
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Exception comments (e.g. `#@ except: SnakeCase`) suppress the diagnostics of lint rules. \
+         An exception that does not suppress any diagnostics is misleading, as it suggests that \
+         the excepted source does not follow the rule; such exceptions are often left behind after \
+         the source was changed to follow the rule and should be removed."
+    }
+
+    fn tags(&self) -> TagSet {
+        TagSet::new(&[Tag::Clarity])
+    }
+}
+
+impl Visitor for UnusedExceptionRule {
+    type State = Diagnostics;
+
+    fn document(&mut self, _: &mut Self::State, _: VisitReason, _: &Document, _: SupportedVersion) {
+        // This is intentionally empty, as the lint visitor reports the
+        // diagnostics of this rule.
+    }
+}
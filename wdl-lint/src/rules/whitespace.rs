@@ -26,6 +26,7 @@ fn only_whitespace(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("remove the whitespace from this line")
+        .with_edit(span, "")
 }
 
 /// Creates a "trailing whitespace" diagnostic.
@@ -34,14 +35,18 @@ fn trailing_whitespace(span: Span) -> Diagnostic {
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("remove the trailing whitespace from this line")
+        .with_edit(span, "")
 }
 
 /// Creates a "more than one blank line" diagnostic.
-fn more_than_one_blank_line(span: Span) -> Diagnostic {
+///
+/// The fix removes the given span of unnecessary blank lines.
+fn more_than_one_blank_line(span: Span, lines: Span) -> Diagnostic {
     Diagnostic::warning("more than one blank line in a row")
         .with_rule(ID)
         .with_highlight(span)
         .with_fix("remove the unnecessary blank lines")
+        .with_edit(lines, "")
 }
 
 /// Detects undesired whitespace.
@@ -153,10 +158,12 @@ impl Visitor for WhitespaceRule {
         // The "ending newline" rule will catch blank lines at the end of the file
         if !is_last {
             if let Some(start) = blank_start {
-                state.add(more_than_one_blank_line(Span::new(
-                    span.start() + start,
-                    span.len() - start,
-                )));
+                // Don't remove the indentation of the line following the blank lines
+                let end = text.rfind('\n').expect("should have a newline") + 1;
+                state.add(more_than_one_blank_line(
+                    Span::new(span.start() + start, span.len() - start),
+                    Span::new(span.start() + start, end - start),
+                ));
             }
         }
     }
@@ -0,0 +1,308 @@
+//! Implementation of SARIF logs of diagnostics.
+//!
+//! See the [SARIF 2.1.0 specification](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html).
+
+use serde_json::json;
+use serde_json::Value;
+use wdl_ast::Diagnostic;
+use wdl_ast::Severity;
+use wdl_ast::SourceLines;
+use wdl_ast::Span;
+
+use crate::rules;
+
+/// Represents the tool that reported the diagnostics of a SARIF log.
+#[derive(Debug, Clone, Copy)]
+pub struct SarifTool<'a> {
+    /// The name of the tool.
+    pub name: &'a str,
+    /// The version of the tool.
+    pub version: &'a str,
+    /// The URI of the tool's homepage.
+    pub information_uri: &'a str,
+}
+
+/// Represents the diagnostics reported for a source file in a SARIF log.
+#[derive(Debug, Clone, Copy)]
+pub struct SarifFile<'a> {
+    /// The path of the source file.
+    ///
+    /// The path is used as the URI of the file; backslashes are replaced with
+    /// forward slashes.
+    pub path: &'a str,
+    /// The source of the file.
+    pub source: &'a str,
+    /// The diagnostics reported for the file.
+    pub diagnostics: &'a [Diagnostic],
+}
+
+/// Creates a SARIF 2.1.0 log of the diagnostics of the given files.
+///
+/// Descriptions of lint rules are included for every rule that is referenced
+/// by a diagnostic.
+pub fn sarif_log(tool: SarifTool<'_>, files: &[SarifFile<'_>]) -> Value {
+    let mut ids: Vec<&str> = files
+        .iter()
+        .flat_map(|f| f.diagnostics.iter().filter_map(|d| d.rule()))
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let lint_rules = rules();
+    let rules: Vec<_> = ids
+        .iter()
+        .map(|id| match lint_rules.iter().find(|r| r.id() == *id) {
+            Some(rule) => {
+                let mut descriptor = json!({
+                    "id": id,
+                    "shortDescription": { "text": rule.description() },
+                    "fullDescription": { "text": rule.explanation() },
+                });
+                if let Some(url) = rule.url() {
+                    descriptor["helpUri"] = url.into();
+                }
+                descriptor
+            }
+            None => json!({ "id": id }),
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for file in files {
+        let lines = SourceLines::new(file.source);
+        let uri = file.path.replace('\\', "/");
+        let region = |span: Span| {
+            let start = lines.location(span.start());
+            let end = lines.location(span.end());
+            json!({
+                "startLine": start.line(),
+                "startColumn": start.column(),
+                "endLine": end.line(),
+                "endColumn": end.column(),
+            })
+        };
+
+        for diagnostic in file.diagnostics {
+            let mut labels = diagnostic.labels();
+            let mut location = json!({
+                "physicalLocation": { "artifactLocation": { "uri": uri } }
+            });
+            if let Some(label) = labels.next() {
+                location["physicalLocation"]["region"] = region(label.span());
+                if !label.message().is_empty() {
+                    location["message"] = json!({ "text": label.message() });
+                }
+            }
+
+            let mut result = json!({
+                "level": match diagnostic.severity() {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                    Severity::Note => "note",
+                },
+                "message": { "text": diagnostic.message() },
+                "locations": [location],
+            });
+
+            if let Some(rule) = diagnostic.rule() {
+                result["ruleId"] = rule.into();
+            }
+
+            let related: Vec<_> = labels
+                .enumerate()
+                .map(|(i, label)| {
+                    json!({
+                        "id": i + 1,
+                        "message": { "text": label.message() },
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": uri },
+                            "region": region(label.span()),
+                        },
+                    })
+                })
+                .collect();
+            if !related.is_empty() {
+                result["relatedLocations"] = related.into();
+            }
+
+            if !diagnostic.edits().is_empty() {
+                let replacements: Vec<_> = diagnostic
+                    .edits()
+                    .iter()
+                    .map(|e| {
+                        json!({
+                            "deletedRegion": region(e.span()),
+                            "insertedContent": { "text": e.replacement() },
+                        })
+                    })
+                    .collect();
+
+                result["fixes"] = json!([{
+                    "description": { "text": diagnostic.fix().unwrap_or("apply fix") },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": uri },
+                        "replacements": replacements,
+                    }],
+                }]);
+            }
+
+            results.push(result);
+        }
+    }
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": tool.name,
+                    "version": tool.version,
+                    "informationUri": tool.information_uri,
+                    "rules": rules,
+                },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn log() {
+        let source = "version 1.1\n\nworkflow test {\n    String s = 'hi'\n}\n";
+        let diagnostics = [
+            Diagnostic::note("string defined with single quotes")
+                .with_rule("DoubleQuotes")
+                .with_label("this string", Span::new(44, 4))
+                .with_label("this workflow", Span::new(22, 4))
+                .with_fix("change the single quotes to double quotes")
+                .with_edit(Span::new(44, 1), "\"")
+                .with_edit(Span::new(47, 1), "\""),
+            Diagnostic::error("unknown name").with_highlight(Span::new(0, 7)),
+        ];
+
+        let tool = SarifTool {
+            name: "wdl",
+            version: "1.0.0",
+            information_uri: "https://example.com",
+        };
+        let log = sarif_log(
+            tool,
+            &[SarifFile {
+                path: "dir\\source.wdl",
+                source,
+                diagnostics: &diagnostics,
+            }],
+        );
+
+        let rule = rules()
+            .into_iter()
+            .find(|r| r.id() == "DoubleQuotes")
+            .expect("should have rule");
+        let uri = json!({ "uri": "dir/source.wdl" });
+        assert_eq!(
+            log,
+            json!({
+                "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+                "version": "2.1.0",
+                "runs": [{
+                    "tool": {
+                        "driver": {
+                            "name": "wdl",
+                            "version": "1.0.0",
+                            "informationUri": "https://example.com",
+                            "rules": [{
+                                "id": "DoubleQuotes",
+                                "shortDescription": { "text": rule.description() },
+                                "fullDescription": { "text": rule.explanation() },
+                            }],
+                        },
+                    },
+                    "columnKind": "unicodeCodePoints",
+                    "results": [
+                        {
+                            "level": "note",
+                            "ruleId": "DoubleQuotes",
+                            "message": { "text": "string defined with single quotes" },
+                            "locations": [{
+                                "message": { "text": "this string" },
+                                "physicalLocation": {
+                                    "artifactLocation": uri,
+                                    "region": {
+                                        "startLine": 4,
+                                        "startColumn": 16,
+                                        "endLine": 4,
+                                        "endColumn": 20,
+                                    },
+                                },
+                            }],
+                            "relatedLocations": [{
+                                "id": 1,
+                                "message": { "text": "this workflow" },
+                                "physicalLocation": {
+                                    "artifactLocation": uri,
+                                    "region": {
+                                        "startLine": 3,
+                                        "startColumn": 10,
+                                        "endLine": 3,
+                                        "endColumn": 14,
+                                    },
+                                },
+                            }],
+                            "fixes": [{
+                                "description": {
+                                    "text": "change the single quotes to double quotes"
+                                },
+                                "artifactChanges": [{
+                                    "artifactLocation": uri,
+                                    "replacements": [
+                                        {
+                                            "deletedRegion": {
+                                                "startLine": 4,
+                                                "startColumn": 16,
+                                                "endLine": 4,
+                                                "endColumn": 17,
+                                            },
+                                            "insertedContent": { "text": "\"" },
+                                        },
+                                        {
+                                            "deletedRegion": {
+                                                "startLine": 4,
+                                                "startColumn": 19,
+                                                "endLine": 4,
+                                                "endColumn": 20,
+                                            },
+                                            "insertedContent": { "text": "\"" },
+                                        },
+                                    ],
+                                }],
+                            }],
+                        },
+                        {
+                            "level": "error",
+                            "message": { "text": "unknown name" },
+                            "locations": [{
+                                "physicalLocation": {
+                                    "artifactLocation": uri,
+                                    "region": {
+                                        "startLine": 1,
+                                        "startColumn": 1,
+                                        "endLine": 1,
+                                        "endColumn": 8,
+                                    },
+                                },
+                            }],
+                        },
+                    ],
+                }],
+            })
+        );
+    }
+}
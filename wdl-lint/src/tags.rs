@@ -48,6 +48,25 @@ impl std::fmt::Display for Tag {
     }
 }
 
+impl std::str::FromStr for Tag {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Completeness" => Ok(Self::Completeness),
+            "Naming" => Ok(Self::Naming),
+            "Spacing" => Ok(Self::Spacing),
+            "Style" => Ok(Self::Style),
+            "Clarity" => Ok(Self::Clarity),
+            "Portability" => Ok(Self::Portability),
+            "Correctness" => Ok(Self::Correctness),
+            "Sorting" => Ok(Self::Sorting),
+            "Deprecated" => Ok(Self::Deprecated),
+            _ => Err(()),
+        }
+    }
+}
+
 /// A set of lint tags.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TagSet(u32);
@@ -1,15 +1,18 @@
 //! Implementation of the lint visitor.
 
 use std::collections::HashSet;
+use std::path::Path;
 
 use indexmap::IndexMap;
 use wdl_ast::v1;
 use wdl_ast::AstNode;
+use wdl_ast::AstToken;
 use wdl_ast::Comment;
 use wdl_ast::Diagnostic;
 use wdl_ast::Diagnostics;
 use wdl_ast::Direction;
 use wdl_ast::Document;
+use wdl_ast::Severity;
 use wdl_ast::Span;
 use wdl_ast::SupportedVersion;
 use wdl_ast::SyntaxElement;
@@ -21,11 +24,55 @@ use wdl_ast::Visitor;
 use wdl_ast::Whitespace;
 
 use crate::rules;
+use crate::rules::unused_exception;
+use crate::rules::UnusedExceptionRule;
+use crate::Config;
 use crate::Rule;
+use crate::Tag;
 
 /// The prefix of `except` comments.
 pub const EXCEPT_COMMENT_PREFIX: &str = "#@ except:";
 
+/// The prefix of `except-next-line` comments.
+pub const EXCEPT_NEXT_LINE_COMMENT_PREFIX: &str = "#@ except-next-line:";
+
+/// The prefix of `except-begin` comments.
+pub const EXCEPT_BEGIN_COMMENT_PREFIX: &str = "#@ except-begin:";
+
+/// The prefix of `except-end` comments.
+///
+/// Unlike the other exception comments, the colon and selectors of an
+/// `except-end` comment are optional.
+pub const EXCEPT_END_COMMENT_PREFIX: &str = "#@ except-end";
+
+/// The prefix of a selector in an exception comment that selects the rules
+/// with a tag.
+pub const EXCEPT_TAG_PREFIX: &str = "tag:";
+
+/// The separator between the selectors and the reason of an exception
+/// comment.
+pub const EXCEPT_REASON_SEPARATOR: &str = "--";
+
+/// Determines if the given comment text is an exception comment.
+pub(crate) fn is_exception_comment(text: &str) -> bool {
+    text.starts_with(EXCEPT_COMMENT_PREFIX)
+        || text.starts_with(EXCEPT_NEXT_LINE_COMMENT_PREFIX)
+        || text.starts_with(EXCEPT_BEGIN_COMMENT_PREFIX)
+        || end_comment_selectors(text).is_some()
+}
+
+/// Gets the text following the prefix of an `except-end` comment.
+///
+/// Returns `None` if the text is not an `except-end` comment.
+fn end_comment_selectors(text: &str) -> Option<&str> {
+    let rest = text.strip_prefix(EXCEPT_END_COMMENT_PREFIX)?;
+    if rest.trim().is_empty() {
+        return Some("");
+    }
+
+    rest.strip_prefix(':')
+}
+
 /// Creates an "unknown rule" diagnostic.
 fn unknown_rule(id: &str, span: Span) -> Diagnostic {
     Diagnostic::note(format!("unknown lint rule `{id}`"))
@@ -33,41 +80,233 @@ fn unknown_rule(id: &str, span: Span) -> Diagnostic {
         .with_fix("remove the rule from the exception list")
 }
 
+/// Creates an "unknown tag" diagnostic.
+fn unknown_tag(tag: &str, span: Span) -> Diagnostic {
+    Diagnostic::note(format!("unknown lint tag `{tag}`"))
+        .with_label("cannot make an exception for this tag", span)
+        .with_fix("remove the tag from the exception list")
+}
+
+/// Creates a "missing exception reason" diagnostic.
+fn missing_reason(span: Span) -> Diagnostic {
+    Diagnostic::warning("exception is missing a reason")
+        .with_label("this exception must give a reason", span)
+        .with_fix(format!(
+            "add a reason after the exception list, separated by `{EXCEPT_REASON_SEPARATOR}`"
+        ))
+}
+
+/// Creates an "unmatched exception end" diagnostic.
+fn unmatched_end(span: Span) -> Diagnostic {
+    Diagnostic::note("`#@ except-end` comment without a matching `#@ except-begin` comment")
+        .with_label("this comment does not end an exception range", span)
+        .with_fix("remove the comment or add a matching `#@ except-begin` comment before it")
+}
+
+/// Creates an "unterminated exception range" diagnostic.
+fn unterminated_begin(span: Span) -> Diagnostic {
+    Diagnostic::note("`#@ except-begin` comment without a matching `#@ except-end` comment")
+        .with_label("this exception applies to the rest of the document", span)
+        .with_fix("add a matching `#@ except-end` comment after the excepted source")
+}
+
+/// Represents what is selected by a selector of an exception comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selection {
+    /// The selector selects the rule with the given identifier.
+    Rule(String),
+    /// The selector selects the rules with the given tag.
+    Tag(Tag),
+}
+
+/// Represents a selector of an exception comment.
+#[derive(Debug, Clone)]
+struct Selector {
+    /// What is selected.
+    selection: Selection,
+    /// The text of the selector.
+    text: String,
+    /// The span of the selector.
+    span: Span,
+    /// Whether or not the selector suppressed a diagnostic.
+    used: bool,
+}
+
+/// Represents an exception made by an exception comment.
+#[derive(Debug, Clone)]
+struct Exception {
+    /// The span of the comment making the exception.
+    comment: Span,
+    /// The span of source the exception applies to.
+    ///
+    /// This is `None` for exceptions that apply to the entire document.
+    range: Option<Span>,
+    /// The selectors of the exception.
+    selectors: Vec<Selector>,
+}
+
+impl Exception {
+    /// Determines if the exception applies to the given offset.
+    ///
+    /// An offset of `None` represents the entire document; only exceptions
+    /// for the entire document apply to it.
+    fn applies(&self, offset: Option<usize>) -> bool {
+        match (self.range, offset) {
+            (None, _) => true,
+            (Some(range), Some(offset)) => range.start() <= offset && offset <= range.end(),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Suppresses the given diagnostic if it is excepted by the given exceptions.
+///
+/// A diagnostic is excepted if the start of its primary label is within the
+/// source of an exception for the diagnostic's rule; a diagnostic with
+/// multiple labels is also excepted if the starts of all of its other labels
+/// are. Diagnostics without labels are only excepted by exceptions for the
+/// entire document.
+///
+/// Every selector of the excepting exceptions that selects the diagnostic's
+/// rule is marked as used.
+///
+/// Returns `true` if the diagnostic was suppressed.
+fn suppress(
+    rules: &IndexMap<&'static str, Box<dyn Rule>>,
+    exceptions: &mut [Exception],
+    diagnostic: &Diagnostic,
+) -> bool {
+    let Some(id) = diagnostic.rule() else {
+        return false;
+    };
+
+    let selects = |selection: &Selection| match selection {
+        Selection::Rule(rule) => rule == id,
+        Selection::Tag(tag) => rules.get(id).is_some_and(|r| r.tags().contains(*tag)),
+    };
+
+    // Gets the indexes of the exceptions for the rule that apply to an offset
+    let excepting = |exceptions: &[Exception], offset: Option<usize>| -> Vec<usize> {
+        exceptions
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.applies(offset) && e.selectors.iter().any(|s| selects(&s.selection)))
+            .map(|(i, _)| i)
+            .collect()
+    };
+
+    let mut labels = diagnostic.labels().map(|l| l.span().start());
+    let mut used = excepting(exceptions, labels.next());
+    if used.is_empty() {
+        let others: Vec<_> = labels.map(|o| excepting(exceptions, Some(o))).collect();
+        if others.is_empty() || others.iter().any(Vec::is_empty) {
+            return false;
+        }
+
+        used = others.concat();
+    }
+
+    for index in used {
+        for selector in &mut exceptions[index].selectors {
+            if selects(&selector.selection) {
+                selector.used = true;
+            }
+        }
+    }
+
+    true
+}
+
 /// A visitor that runs linting rules.
 ///
-/// By default, the visitor runs all lint rules.
+/// By default, the visitor runs all lint rules; use
+/// [`with_config`](Self::with_config) to run the rules of a lint
+/// configuration.
+///
+/// This visitor respects exception comments that suppress the diagnostics of
+/// lint rules:
 ///
-/// This visitor respects `#@ except` comments that precede AST nodes.
+/// * `#@ except: <selectors>` suppresses diagnostics within the immediately
+///   following AST node; if the comment comes before the version statement, it
+///   suppresses diagnostics for the entire document.
+/// * `#@ except-next-line: <selectors>` suppresses diagnostics on the line
+///   following the comment.
+/// * `#@ except-begin: <selectors>` suppresses diagnostics until a matching `#@
+///   except-end` comment; the end comment may repeat the selectors of the begin
+///   comment to end a range other than the innermost one.
 ///
-/// The format for the comment is `#@ except: <ids>`, where `ids` is a
-/// comma-separated list of lint rule identifiers.
+/// The selectors are a comma-separated list of lint rule identifiers or of
+/// tags prefixed with `tag:` (e.g. `tag:Spacing`).
 ///
-/// Any `#@ except` comments that come before the version statement will disable
-/// the rule for the entire document.
+/// The selectors may be followed by a reason for the exception, separated by
+/// `--` (e.g. `#@ except: SnakeCase -- matches the upstream name`); a
+/// configuration may require that every exception gives a reason.
 ///
-/// Otherwise, `#@ except` comments disable the rule for the immediately
-/// following AST node.
+/// A diagnostic is suppressed if the start of its primary label is within
+/// the source an exception applies to or, for diagnostics with multiple
+/// labels, if the starts of all of its other labels are.
 #[allow(missing_debug_implementations)]
 pub struct LintVisitor {
     /// The map of rule name to rule.
     rules: IndexMap<&'static str, Box<dyn Rule>>,
-    /// A stack of exceptions; the first is the offset of the syntax element
-    /// with the comment and the second is the set of exceptions.
-    exceptions: Vec<(usize, HashSet<String>)>,
+    /// The identifiers of all known rules.
+    ///
+    /// Exceptions may name known rules that are not enabled.
+    known: HashSet<&'static str>,
+    /// The exceptions made by the exception comments of the document.
+    exceptions: Vec<Exception>,
+    /// The exceptions made by `#@ except-begin` comments that have not yet
+    /// been ended.
+    open: Vec<Exception>,
+    /// The spans of the `#@ except` comments of the document.
+    ///
+    /// An `#@ except` comment that does not precede an AST node does not make
+    /// an exception.
+    except_comments: Vec<Span>,
+    /// The source of the document being linted.
+    source: String,
+    /// Whether or not exception comments are required to give a reason.
+    require_reasons: bool,
+    /// The map of rule name to the severity overriding the severity of the
+    /// rule's diagnostics.
+    severities: IndexMap<String, Severity>,
 }
 
 impl LintVisitor {
     /// Creates a new linting visitor with the given rules.
     pub fn new(rules: impl IntoIterator<Item = Box<dyn Rule>>) -> Self {
+        let rules: IndexMap<_, _> = rules.into_iter().map(|r| (r.id(), r)).collect();
         Self {
-            rules: rules.into_iter().map(|r| (r.id(), r)).collect(),
+            known: rules
+                .keys()
+                .copied()
+                .chain(crate::rules().iter().map(|r| r.id()))
+                .collect(),
+            rules,
             exceptions: Default::default(),
+            open: Default::default(),
+            except_comments: Default::default(),
+            source: Default::default(),
+            require_reasons: false,
+            severities: Default::default(),
         }
     }
 
-    /// Invokes a callback on each rule provided the rule is not currently
-    /// excepted.
-    fn each_enabled_rule<F>(
+    /// Creates a new linting visitor with the rules and severities of the
+    /// given configuration for the document at the given path.
+    pub fn with_config(config: &Config, path: &Path) -> Self {
+        Self {
+            require_reasons: config.require_exception_reasons(),
+            severities: config.severities(path),
+            ..Self::new(config.rules(path))
+        }
+    }
+
+    /// Invokes a callback on each rule.
+    ///
+    /// When entering a node, the exceptions made by the `#@ except` comments
+    /// preceding the node are recorded.
+    fn each_rule<F>(
         &mut self,
         state: &mut Diagnostics,
         reason: VisitReason,
@@ -76,83 +315,249 @@ impl LintVisitor {
     ) where
         F: FnMut(&mut Diagnostics, &mut dyn Rule),
     {
-        let start = node.text_range().start().into();
-
         if reason == VisitReason::Enter {
-            let exceptions = self.exceptions_for(state, node);
-            if !exceptions.is_empty() {
-                self.exceptions.push((start, exceptions));
-            }
+            self.add_exceptions(state, node);
         }
 
-        for (id, rule) in &mut self.rules {
-            if self.exceptions.iter().any(|(_, set)| set.contains(*id)) {
-                continue;
-            }
-
+        for rule in self.rules.values_mut() {
             cb(state, rule.as_mut());
         }
-
-        if reason == VisitReason::Exit {
-            if let Some((prev, _)) = self.exceptions.last() {
-                if *prev == start {
-                    self.exceptions.pop();
-                }
-            }
-        }
     }
 
-    /// Gets the set of excepted rule ids for the given syntax node.
-    fn exceptions_for(&self, state: &mut Diagnostics, node: &SyntaxNode) -> HashSet<String> {
+    /// Records the exceptions made by the `#@ except` comments preceding the
+    /// given syntax node.
+    fn add_exceptions(&mut self, state: &mut Diagnostics, node: &SyntaxNode) {
         let siblings = node
             .siblings_with_tokens(Direction::Prev)
             .skip(1)
             .take_while(|s| s.kind() == SyntaxKind::Whitespace || s.kind() == SyntaxKind::Comment)
             .filter_map(SyntaxElement::into_token);
 
-        let mut set = HashSet::default();
         for sibling in siblings {
             if sibling.kind() == SyntaxKind::Whitespace {
                 continue;
             }
 
-            if let Some(ids) = sibling.text().strip_prefix(EXCEPT_COMMENT_PREFIX) {
-                let start: usize = sibling.text_range().start().into();
-                let mut offset = EXCEPT_COMMENT_PREFIX.len();
-                for id in ids.split(',') {
-                    // First trim the start so we can determine how much whitespace was removed
-                    let trimmed_start = id.trim_start();
-                    // Next trim the end
-                    let trimmed: &str = trimmed_start.trim_end();
-
-                    if !self.rules.contains_key(trimmed) {
-                        // Calculate the span based off the current offset and how much whitespace
-                        // was trimmed
-                        let span = Span::new(
-                            start + offset + (id.len() - trimmed_start.len()),
-                            trimmed.len(),
-                        );
-
-                        state.add(unknown_rule(trimmed, span));
-                    } else {
-                        set.insert(trimmed.to_string());
+            let Some(text) = sibling.text().strip_prefix(EXCEPT_COMMENT_PREFIX) else {
+                continue;
+            };
+
+            let start: usize = sibling.text_range().start().into();
+            if self.exceptions.iter().any(|e| e.comment.start() == start) {
+                continue;
+            }
+
+            let comment = Span::new(start, sibling.text().len());
+            let selectors =
+                self.parse_selectors(state, comment, text, start + EXCEPT_COMMENT_PREFIX.len());
+
+            // Comments before the version statement apply to the entire document
+            let range = if node.kind() == SyntaxKind::VersionStatementNode {
+                None
+            } else {
+                let end: usize = node.text_range().end().into();
+                Some(Span::new(start, end - start))
+            };
+
+            self.exceptions.push(Exception {
+                comment,
+                range,
+                selectors,
+            });
+        }
+    }
+
+    /// Parses the selectors of an exception comment.
+    ///
+    /// The given text is the text of the comment following its prefix, which
+    /// starts at the given offset.
+    ///
+    /// Diagnostics are added for unknown rules and tags and for a missing
+    /// reason (if reasons are required).
+    fn parse_selectors(
+        &self,
+        state: &mut Diagnostics,
+        comment: Span,
+        text: &str,
+        mut offset: usize,
+    ) -> Vec<Selector> {
+        let (list, reason) = match text.find(EXCEPT_REASON_SEPARATOR) {
+            Some(index) => (
+                &text[..index],
+                Some(text[index + EXCEPT_REASON_SEPARATOR.len()..].trim()),
+            ),
+            None => (text, None),
+        };
+
+        if self.require_reasons && reason.map(str::is_empty).unwrap_or(true) {
+            state.add(missing_reason(comment));
+        }
+
+        let mut selectors = Vec::new();
+        for id in list.split(',') {
+            // First trim the start so we can determine how much whitespace was removed
+            let trimmed_start = id.trim_start();
+            // Next trim the end
+            let trimmed: &str = trimmed_start.trim_end();
+
+            // Calculate the span based off the current offset and how much whitespace
+            // was trimmed
+            let span = Span::new(offset + (id.len() - trimmed_start.len()), trimmed.len());
+            offset += id.len() + 1 /* comma */;
+
+            let selection = match trimmed.strip_prefix(EXCEPT_TAG_PREFIX) {
+                Some(tag) => match tag.parse() {
+                    Ok(tag) => Selection::Tag(tag),
+                    Err(_) => {
+                        state.add(unknown_tag(tag, span));
+                        continue;
                     }
+                },
+                None if self.known.contains(trimmed) => Selection::Rule(trimmed.to_string()),
+                None => {
+                    state.add(unknown_rule(trimmed, span));
+                    continue;
+                }
+            };
+
+            selectors.push(Selector {
+                selection,
+                text: trimmed.to_string(),
+                span,
+                used: false,
+            });
+        }
+
+        selectors
+    }
+
+    /// Gets the span of the line following the given offset.
+    ///
+    /// The span is empty at the end of the source if there is no following
+    /// line.
+    fn next_line(&self, offset: usize) -> Span {
+        let start = self.source[offset..]
+            .find('\n')
+            .map(|i| offset + i + 1)
+            .unwrap_or(self.source.len());
+        let end = self.source[start..]
+            .find('\n')
+            .map(|i| start + i)
+            .unwrap_or(self.source.len());
+        Span::new(start, end - start)
+    }
+
+    /// Ends the exception range of an `#@ except-end` comment.
+    ///
+    /// If the comment has selectors, the innermost range with the same
+    /// selectors is ended; otherwise, the innermost range is ended.
+    fn end_exception(&mut self, state: &mut Diagnostics, comment: Span, text: &str) {
+        let mut selectors: Vec<_> = text
+            .split(EXCEPT_REASON_SEPARATOR)
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+        selectors.sort_unstable();
+
+        let index = self.open.iter().rposition(|e| {
+            if selectors.is_empty() {
+                return true;
+            }
 
-                    offset += id.len() + 1 /* comma */;
+            let mut texts: Vec<_> = e.selectors.iter().map(|s| s.text.as_str()).collect();
+            texts.sort_unstable();
+            texts == selectors
+        });
+
+        match index {
+            Some(index) => {
+                let mut exception = self.open.remove(index);
+                let start = exception.comment.start();
+                exception.range = Some(Span::new(start, comment.end() - start));
+                self.exceptions.push(exception);
+            }
+            None => state.add(unmatched_end(comment)),
+        }
+    }
+
+    /// Suppresses the excepted lint diagnostics of the document.
+    ///
+    /// If the unused exception rule is enabled, diagnostics are added for
+    /// exceptions that did not suppress any diagnostics.
+    fn apply_exceptions(&mut self, state: &mut Diagnostics) {
+        // Any remaining ranges apply to the rest of the document
+        for mut exception in std::mem::take(&mut self.open) {
+            state.add(unterminated_begin(exception.comment));
+            let start = exception.comment.start();
+            exception.range = Some(Span::new(start, self.source.len() - start));
+            self.exceptions.push(exception);
+        }
+
+        // `#@ except` comments that do not precede a node apply to nothing
+        for comment in std::mem::take(&mut self.except_comments) {
+            if self
+                .exceptions
+                .iter()
+                .any(|e| e.comment.start() == comment.start())
+            {
+                continue;
+            }
+
+            let text = &self.source[comment.start()..comment.end()];
+            let selectors = self.parse_selectors(
+                state,
+                comment,
+                &text[EXCEPT_COMMENT_PREFIX.len()..],
+                comment.start() + EXCEPT_COMMENT_PREFIX.len(),
+            );
+
+            self.exceptions.push(Exception {
+                comment,
+                range: Some(Span::new(comment.start(), 0)),
+                selectors,
+            });
+        }
+
+        if self.exceptions.is_empty() {
+            return;
+        }
+
+        state.retain(|d| !suppress(&self.rules, &mut self.exceptions, d));
+
+        let unused_id = UnusedExceptionRule.id();
+        if !self.rules.contains_key(unused_id) {
+            return;
+        }
+
+        let mut unused = Vec::new();
+        for exception in &self.exceptions {
+            for selector in &exception.selectors {
+                // Only report selectors of rules that are enabled
+                let enabled = match &selector.selection {
+                    Selection::Rule(id) => id != unused_id && self.rules.contains_key(id.as_str()),
+                    Selection::Tag(tag) => self.rules.values().any(|r| r.tags().contains(*tag)),
+                };
+
+                if enabled && !selector.used {
+                    unused.push(unused_exception(&selector.text, selector.span));
                 }
             }
         }
 
-        set
+        for diagnostic in unused {
+            if !suppress(&self.rules, &mut self.exceptions, &diagnostic) {
+                state.add(diagnostic);
+            }
+        }
     }
 }
 
 impl Default for LintVisitor {
     fn default() -> Self {
-        Self {
-            rules: rules().into_iter().map(|r| (r.id(), r)).collect(),
-            exceptions: Default::default(),
-        }
+        Self::new(rules())
     }
 }
 
@@ -169,9 +574,12 @@ impl Visitor for LintVisitor {
         if reason == VisitReason::Enter {
             // Reset state for a new document
             self.exceptions.clear();
+            self.open.clear();
+            self.except_comments.clear();
+            self.source = doc.syntax().text().to_string();
         }
 
-        self.each_enabled_rule(
+        self.each_rule(
             state,
             reason,
             doc.version_statement()
@@ -181,24 +589,60 @@ impl Visitor for LintVisitor {
                 rule.document(state, reason, doc, version);
             },
         );
-    }
 
-    fn whitespace(&mut self, state: &mut Self::State, whitespace: &Whitespace) {
-        for (id, rule) in &mut self.rules {
-            if self.exceptions.iter().any(|(_, set)| set.contains(*id)) {
-                continue;
+        if reason == VisitReason::Exit {
+            self.apply_exceptions(state);
+
+            if !self.severities.is_empty() {
+                for diagnostic in state.iter_mut() {
+                    if let Some(severity) = diagnostic.rule().and_then(|r| self.severities.get(r)) {
+                        diagnostic.set_severity(*severity);
+                    }
+                }
             }
+        }
+    }
 
+    fn whitespace(&mut self, state: &mut Self::State, whitespace: &Whitespace) {
+        for rule in self.rules.values_mut() {
             rule.whitespace(state, whitespace);
         }
     }
 
     fn comment(&mut self, state: &mut Self::State, comment: &Comment) {
-        for (id, rule) in &mut self.rules {
-            if self.exceptions.iter().any(|(_, set)| set.contains(*id)) {
-                continue;
-            }
+        let text = comment.as_str();
+        let span = comment.span();
+        if text.starts_with(EXCEPT_COMMENT_PREFIX) {
+            self.except_comments.push(span);
+        } else if let Some(rest) = text.strip_prefix(EXCEPT_NEXT_LINE_COMMENT_PREFIX) {
+            let selectors = self.parse_selectors(
+                state,
+                span,
+                rest,
+                span.start() + EXCEPT_NEXT_LINE_COMMENT_PREFIX.len(),
+            );
+            self.exceptions.push(Exception {
+                comment: span,
+                range: Some(self.next_line(span.end())),
+                selectors,
+            });
+        } else if let Some(rest) = text.strip_prefix(EXCEPT_BEGIN_COMMENT_PREFIX) {
+            let selectors = self.parse_selectors(
+                state,
+                span,
+                rest,
+                span.start() + EXCEPT_BEGIN_COMMENT_PREFIX.len(),
+            );
+            self.open.push(Exception {
+                comment: span,
+                range: None,
+                selectors,
+            });
+        } else if let Some(rest) = end_comment_selectors(text) {
+            self.end_exception(state, span, rest);
+        }
 
+        for rule in self.rules.values_mut() {
             rule.comment(state, comment);
         }
     }
@@ -209,9 +653,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         stmt: &VersionStatement,
     ) {
-        // We call every rule here as we've already disabled the global rules based on
-        // the version statement
-        for (_, rule) in &mut self.rules {
+        for rule in self.rules.values_mut() {
             rule.version_statement(state, reason, stmt);
         }
     }
@@ -222,7 +664,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         stmt: &v1::ImportStatement,
     ) {
-        self.each_enabled_rule(state, reason, stmt.syntax(), |state, rule| {
+        self.each_rule(state, reason, stmt.syntax(), |state, rule| {
             rule.import_statement(state, reason, stmt)
         });
     }
@@ -233,7 +675,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         def: &v1::StructDefinition,
     ) {
-        self.each_enabled_rule(state, reason, def.syntax(), |state, rule| {
+        self.each_rule(state, reason, def.syntax(), |state, rule| {
             rule.struct_definition(state, reason, def)
         });
     }
@@ -244,7 +686,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         task: &v1::TaskDefinition,
     ) {
-        self.each_enabled_rule(state, reason, task.syntax(), |state, rule| {
+        self.each_rule(state, reason, task.syntax(), |state, rule| {
             rule.task_definition(state, reason, task)
         });
     }
@@ -255,7 +697,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         workflow: &v1::WorkflowDefinition,
     ) {
-        self.each_enabled_rule(state, reason, workflow.syntax(), |state, rule| {
+        self.each_rule(state, reason, workflow.syntax(), |state, rule| {
             rule.workflow_definition(state, reason, workflow)
         });
     }
@@ -266,7 +708,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         section: &v1::InputSection,
     ) {
-        self.each_enabled_rule(state, reason, section.syntax(), |state, rule| {
+        self.each_rule(state, reason, section.syntax(), |state, rule| {
             rule.input_section(state, reason, section)
         });
     }
@@ -277,7 +719,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         section: &v1::OutputSection,
     ) {
-        self.each_enabled_rule(state, reason, section.syntax(), |state, rule| {
+        self.each_rule(state, reason, section.syntax(), |state, rule| {
             rule.output_section(state, reason, section)
         });
     }
@@ -288,17 +730,13 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         section: &v1::CommandSection,
     ) {
-        self.each_enabled_rule(state, reason, section.syntax(), |state, rule| {
+        self.each_rule(state, reason, section.syntax(), |state, rule| {
             rule.command_section(state, reason, section)
         });
     }
 
     fn command_text(&mut self, state: &mut Self::State, text: &v1::CommandText) {
-        for (id, rule) in &mut self.rules {
-            if self.exceptions.iter().any(|(_, set)| set.contains(*id)) {
-                continue;
-            }
-
+        for rule in self.rules.values_mut() {
             rule.command_text(state, text);
         }
     }
@@ -309,7 +747,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         section: &v1::RequirementsSection,
     ) {
-        self.each_enabled_rule(state, reason, section.syntax(), |state, rule| {
+        self.each_rule(state, reason, section.syntax(), |state, rule| {
             rule.requirements_section(state, reason, section)
         });
     }
@@ -320,7 +758,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         section: &v1::HintsSection,
     ) {
-        self.each_enabled_rule(state, reason, section.syntax(), |state, rule| {
+        self.each_rule(state, reason, section.syntax(), |state, rule| {
             rule.hints_section(state, reason, section)
         });
     }
@@ -331,7 +769,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         section: &v1::RuntimeSection,
     ) {
-        self.each_enabled_rule(state, reason, section.syntax(), |state, rule| {
+        self.each_rule(state, reason, section.syntax(), |state, rule| {
             rule.runtime_section(state, reason, section)
         });
     }
@@ -342,7 +780,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         item: &v1::RuntimeItem,
     ) {
-        self.each_enabled_rule(state, reason, item.syntax(), |state, rule| {
+        self.each_rule(state, reason, item.syntax(), |state, rule| {
             rule.runtime_item(state, reason, item)
         });
     }
@@ -353,7 +791,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         section: &v1::MetadataSection,
     ) {
-        self.each_enabled_rule(state, reason, section.syntax(), |state, rule| {
+        self.each_rule(state, reason, section.syntax(), |state, rule| {
             rule.metadata_section(state, reason, section)
         });
     }
@@ -364,7 +802,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         section: &v1::ParameterMetadataSection,
     ) {
-        self.each_enabled_rule(state, reason, section.syntax(), |state, rule| {
+        self.each_rule(state, reason, section.syntax(), |state, rule| {
             rule.parameter_metadata_section(state, reason, section)
         });
     }
@@ -375,7 +813,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         object: &v1::MetadataObject,
     ) {
-        self.each_enabled_rule(state, reason, object.syntax(), |state, rule| {
+        self.each_rule(state, reason, object.syntax(), |state, rule| {
             rule.metadata_object(state, reason, object)
         });
     }
@@ -386,7 +824,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         item: &v1::MetadataObjectItem,
     ) {
-        self.each_enabled_rule(state, reason, item.syntax(), |state, rule| {
+        self.each_rule(state, reason, item.syntax(), |state, rule| {
             rule.metadata_object_item(state, reason, item)
         });
     }
@@ -397,7 +835,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         item: &v1::MetadataArray,
     ) {
-        self.each_enabled_rule(state, reason, item.syntax(), |state, rule| {
+        self.each_rule(state, reason, item.syntax(), |state, rule| {
             rule.metadata_array(state, reason, item)
         });
     }
@@ -408,29 +846,25 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         decl: &v1::UnboundDecl,
     ) {
-        self.each_enabled_rule(state, reason, decl.syntax(), |state, rule| {
+        self.each_rule(state, reason, decl.syntax(), |state, rule| {
             rule.unbound_decl(state, reason, decl)
         });
     }
 
     fn bound_decl(&mut self, state: &mut Self::State, reason: VisitReason, decl: &v1::BoundDecl) {
-        self.each_enabled_rule(state, reason, decl.syntax(), |state, rule| {
+        self.each_rule(state, reason, decl.syntax(), |state, rule| {
             rule.bound_decl(state, reason, decl)
         });
     }
 
     fn expr(&mut self, state: &mut Self::State, reason: VisitReason, expr: &v1::Expr) {
-        self.each_enabled_rule(state, reason, expr.syntax(), |state, rule| {
+        self.each_rule(state, reason, expr.syntax(), |state, rule| {
             rule.expr(state, reason, expr)
         });
     }
 
     fn string_text(&mut self, state: &mut Self::State, text: &v1::StringText) {
-        for (id, rule) in &mut self.rules {
-            if self.exceptions.iter().any(|(_, set)| set.contains(*id)) {
-                continue;
-            }
-
+        for rule in self.rules.values_mut() {
             rule.string_text(state, text);
         }
     }
@@ -441,7 +875,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         placeholder: &v1::Placeholder,
     ) {
-        self.each_enabled_rule(state, reason, placeholder.syntax(), |state, rule| {
+        self.each_rule(state, reason, placeholder.syntax(), |state, rule| {
             rule.placeholder(state, reason, placeholder)
         });
     }
@@ -452,7 +886,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         stmt: &v1::ConditionalStatement,
     ) {
-        self.each_enabled_rule(state, reason, stmt.syntax(), |state, rule| {
+        self.each_rule(state, reason, stmt.syntax(), |state, rule| {
             rule.conditional_statement(state, reason, stmt)
         });
     }
@@ -463,7 +897,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         stmt: &v1::ScatterStatement,
     ) {
-        self.each_enabled_rule(state, reason, stmt.syntax(), |state, rule| {
+        self.each_rule(state, reason, stmt.syntax(), |state, rule| {
             rule.scatter_statement(state, reason, stmt)
         });
     }
@@ -474,7 +908,7 @@ impl Visitor for LintVisitor {
         reason: VisitReason,
         stmt: &v1::CallStatement,
     ) {
-        self.each_enabled_rule(state, reason, stmt.syntax(), |state, rule| {
+        self.each_rule(state, reason, stmt.syntax(), |state, rule| {
             rule.call_statement(state, reason, stmt)
         });
     }
@@ -512,4 +946,35 @@ workflow test {
             .validate(&document)
             .expect("should not have any diagnostics");
     }
+
+    #[test]
+    fn it_requires_reasons() {
+        let source = r#"## Test source
+#@ except: MissingMetas, MissingOutput -- this is a test
+
+version 1.1
+
+workflow test {
+    #@ except-next-line: SnakeCase
+    String NotOk = "OK"
+}
+"#;
+
+        let (document, diagnostics) = wdl_ast::Document::parse(source);
+        assert!(diagnostics.is_empty());
+
+        let config = Config::parse("require-exception-reasons = true").expect("should parse");
+        let mut validator = Validator::default();
+        validator.add_visitor(LintVisitor::with_config(&config, Path::new("source.wdl")));
+
+        let diagnostics = validator
+            .validate(&document)
+            .expect_err("should have diagnostics");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message(), "exception is missing a reason");
+        assert_eq!(
+            diagnostics[0].labels().next().map(|l| l.span()),
+            Some(Span::new(source.find("#@ except-next-line").unwrap(), 30))
+        );
+    }
 }
@@ -0,0 +1,257 @@
+//! Implementation of code actions for WDL documents.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use tower_lsp::lsp_types::CodeAction;
+use tower_lsp::lsp_types::CodeActionKind;
+use tower_lsp::lsp_types::CodeActionOrCommand;
+use tower_lsp::lsp_types::CodeActionResponse;
+use tower_lsp::lsp_types::Range;
+use tower_lsp::lsp_types::TextEdit;
+use tower_lsp::lsp_types::WorkspaceEdit;
+use wdl_analysis::AnalysisResult;
+
+use crate::proto;
+
+/// Gets the quick fixes for the diagnostics of a document that intersect the
+/// given range.
+///
+/// A quick fix is offered for every diagnostic that has machine-applicable
+/// edits.
+///
+/// The given source is used as the source of the diagnostics associated with
+/// the quick fixes.
+///
+/// Returns `Ok(None)` if the document could not be parsed.
+pub fn code_actions(
+    result: &AnalysisResult,
+    range: Range,
+    source: &str,
+) -> Result<Option<CodeActionResponse>> {
+    let Some(lines) = result.parse_result().lines() else {
+        return Ok(None);
+    };
+
+    let start = proto::offset(lines, range.start)?;
+    let end = proto::offset(lines, range.end)?;
+
+    let mut actions = Vec::new();
+    for diagnostic in result.diagnostics() {
+        if diagnostic.edits().is_empty() {
+            continue;
+        }
+
+        // Only offer fixes for diagnostics whose primary label intersects the range
+        let Some(label) = diagnostic.labels().next() else {
+            continue;
+        };
+
+        let span = label.span();
+        if span.start() > end || span.end() < start {
+            continue;
+        }
+
+        let edits = diagnostic
+            .edits()
+            .iter()
+            .map(|e| {
+                Ok(TextEdit::new(
+                    proto::range_from_span(lines, e.span())?,
+                    e.replacement().to_string(),
+                ))
+            })
+            .collect::<Result<_>>()?;
+
+        actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+            title: diagnostic.fix().unwrap_or("apply fix").to_string(),
+            kind: Some(CodeActionKind::QUICKFIX),
+            diagnostics: Some(vec![proto::diagnostic(
+                result.uri(),
+                lines,
+                source,
+                diagnostic,
+            )?]),
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(result.uri().as_ref().clone(), edits)])),
+                ..Default::default()
+            }),
+            is_preferred: Some(true),
+            ..Default::default()
+        }));
+    }
+
+    Ok(Some(actions))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use tower_lsp::lsp_types::Position;
+
+    use super::*;
+    use crate::test_util::analyze_with_lints;
+
+    /// Applies the quick fix titled `title` to the given source.
+    ///
+    /// The code actions are requested for the entire source.
+    async fn fix(source: &str, title: &str) -> String {
+        let (_dir, results) = analyze_with_lints(&[("source.wdl", source)]).await;
+        let lines = results[0]
+            .parse_result()
+            .lines()
+            .expect("should have lines");
+        let end = proto::position(lines, source.len()).expect("should be valid");
+        let actions = code_actions(&results[0], Range::new(Position::new(0, 0), end), "wdl")
+            .expect("should get code actions")
+            .expect("should have code actions");
+
+        let action = actions
+            .into_iter()
+            .find_map(|a| match a {
+                CodeActionOrCommand::CodeAction(a) if a.title == title => Some(a),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("should have a code action titled `{title}`"));
+
+        let mut edits = action
+            .edit
+            .expect("should have an edit")
+            .changes
+            .expect("should have changes")
+            .remove(results[0].uri().as_ref())
+            .expect("should have edits for the document");
+
+        // Apply the edits in reverse so that earlier offsets remain valid
+        edits.sort_by_key(|e| e.range.start);
+        let mut fixed = source.to_string();
+        for edit in edits.into_iter().rev() {
+            let start = proto::offset(lines, edit.range.start).expect("should be valid");
+            let end = proto::offset(lines, edit.range.end).expect("should be valid");
+            fixed.replace_range(start..end, &edit.new_text);
+        }
+
+        fixed
+    }
+
+    #[tokio::test]
+    async fn lint_fixes() {
+        assert_eq!(
+            fix(
+                "version 1.1\n\nworkflow test {\n    String s = 'say \"hi\"'\n}\n",
+                "change the single quotes to double quotes",
+            )
+            .await,
+            "version 1.1\n\nworkflow test {\n    String s = \"say \\\"hi\\\"\"\n}\n"
+        );
+
+        assert_eq!(
+            fix(
+                "version 1.1\n\nworkflow test {\n    Array[Int] a = [\n        1,\n        2\n    \
+                 ]\n}\n",
+                "add a comma after this element",
+            )
+            .await,
+            "version 1.1\n\nworkflow test {\n    Array[Int] a = [\n        1,\n        2,\n    \
+             ]\n}\n"
+        );
+
+        assert_eq!(
+            fix(
+                "version 1.1\n\nworkflow test {\n    Array[Int] a = [\n        1,\n        2\n    \
+                 ,\n    ]\n}\n",
+                "remove this extraneous content",
+            )
+            .await,
+            "version 1.1\n\nworkflow test {\n    Array[Int] a = [\n        1,\n        2,\n    \
+             ]\n}\n"
+        );
+
+        assert_eq!(
+            fix(
+                "version 1.1\n\nworkflow test {}",
+                "add an empty line at the end of the file"
+            )
+            .await,
+            "version 1.1\n\nworkflow test {}\n"
+        );
+
+        assert_eq!(
+            fix(
+                "version 1.1\n\nworkflow test {}\n\n\n",
+                "remove all but one empty line at the end of the file",
+            )
+            .await,
+            "version 1.1\n\nworkflow test {}\n"
+        );
+
+        assert_eq!(
+            fix(
+                "version 1.1\n\nimport \"c.wdl\"\n# comment\nimport \"a.wdl\"\nimport \
+                 \"b.wdl\"\n\nworkflow test {}\n",
+                "sort the imports lexicographically",
+            )
+            .await,
+            "version 1.1\n\n# comment\nimport \"a.wdl\"\nimport \"b.wdl\"\nimport \
+             \"c.wdl\"\n\nworkflow test {}\n"
+        );
+
+        assert_eq!(
+            fix(
+                "version 1.1\n\nworkflow test {\n    Int a = 1\n\n\n\n    Int b = 2\n}\n",
+                "remove the unnecessary blank lines",
+            )
+            .await,
+            "version 1.1\n\nworkflow test {\n    Int a = 1\n\n    Int b = 2\n}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn analysis_fixes() {
+        let source = r#"version 1.1
+
+task foo {
+    input {
+        String name
+    }
+
+    command <<<>>>
+
+    output {
+        String out = name
+    }
+}
+
+workflow test {
+    call foo { input: nme = "a" }
+    call foo as foo_2 { input: name = "b" }
+    call foo
+}
+"#;
+
+        assert_eq!(
+            fix(source, "did you mean `name`?").await,
+            source.replace("nme =", "name =")
+        );
+        assert_eq!(
+            fix(
+                source,
+                "add an `as` clause to the call to specify a different name"
+            )
+            .await,
+            source.replace("    call foo\n", "    call foo as foo_3\n")
+        );
+
+        let source =
+            "version 1.0\n\nworkflow test {\n    call foo { a = 1 }\n}\n\ntask foo {\n    input \
+             {\n        Int a\n    }\n\n    command <<<>>>\n}\n";
+        assert_eq!(
+            fix(
+                source,
+                "add an `input` keyword followed by a colon before any call inputs"
+            )
+            .await,
+            source.replace("{ a = 1 }", "{ input: a = 1 }")
+        );
+    }
+}
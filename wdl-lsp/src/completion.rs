@@ -0,0 +1,655 @@
+//! Implementation of completion.
+//!
+//! As a document being edited is often incomplete, member access and call
+//! target completions are determined from the text preceding the position
+//! rather than from the syntax tree.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use anyhow::Result;
+use tower_lsp::lsp_types::CompletionItem;
+use tower_lsp::lsp_types::CompletionItemKind;
+use tower_lsp::lsp_types::CompletionResponse;
+use tower_lsp::lsp_types::InsertTextFormat;
+use tower_lsp::lsp_types::Position;
+use wdl_analysis::scope::DocumentScope;
+use wdl_analysis::scope::NameContext;
+use wdl_analysis::stdlib::Function;
+use wdl_analysis::stdlib::FunctionSignature;
+use wdl_analysis::stdlib::TypeParameters;
+use wdl_analysis::stdlib::STDLIB;
+use wdl_analysis::types::v1::task_hint_keys;
+use wdl_analysis::types::v1::workflow_hint_keys;
+use wdl_analysis::types::CompoundTypeDef;
+use wdl_analysis::types::Type;
+use wdl_analysis::AnalysisResult;
+use wdl_ast::version::V1;
+use wdl_ast::AstNode;
+use wdl_ast::SupportedVersion;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::REQUIREMENTS_KEY_ALIASES;
+use wdl_ast::SUPPORTED_REQUIREMENTS_KEYS;
+use wdl_lint::rules::reserved_runtime_keys;
+
+use crate::proto;
+use crate::syntax::member_type;
+
+/// Determines if the given character may be part of an identifier.
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Determines if the given text ends with the `call` keyword.
+fn ends_with_call(text: &str) -> bool {
+    text.strip_suffix("call")
+        .map(|s| !s.ends_with(is_ident_char))
+        .unwrap_or(false)
+}
+
+/// Creates a completion item.
+fn item(label: &str, kind: CompletionItemKind, detail: Option<String>) -> CompletionItem {
+    CompletionItem {
+        label: label.to_string(),
+        kind: Some(kind),
+        detail,
+        ..Default::default()
+    }
+}
+
+/// Gets the completions at the given position in a document.
+///
+/// Returns `Ok(None)` if there are no completions for the position.
+pub fn completion(
+    result: &AnalysisResult,
+    position: Position,
+) -> Result<Option<CompletionResponse>> {
+    let (Some(document), Some(lines)) = (
+        result.parse_result().document(),
+        result.parse_result().lines(),
+    ) else {
+        return Ok(None);
+    };
+
+    let offset = proto::offset(lines, position)?;
+    let root = document.syntax();
+    let text = root.to_string();
+    let Some(line) = text
+        .get(..offset)
+        .map(|t| &t[t.rfind('\n').map(|i| i + 1).unwrap_or(0)..])
+    else {
+        return Ok(None);
+    };
+
+    // Ignore the partial identifier being completed
+    let line = line.trim_end_matches(is_ident_char);
+    let scope = result.scope();
+    let items = if let Some(chain) = line.strip_suffix('.') {
+        let start = chain
+            .trim_end_matches(|c| is_ident_char(c) || c == '.')
+            .len();
+        let names = chain[start..].split('.').collect::<Vec<_>>();
+        if names.iter().any(|n| n.is_empty()) {
+            return Ok(None);
+        }
+
+        if names.len() == 1 && ends_with_call(chain[..start].trim_end()) {
+            namespace_completions(scope, names[0])
+        } else {
+            member_completions(scope, offset, &names)
+        }
+    } else if ends_with_call(line.trim_end()) {
+        call_completions(scope)
+    } else if let Some(items) = key_completions(scope, root, offset) {
+        Some(items)
+    } else {
+        name_completions(scope, offset)
+    };
+
+    Ok(items.map(CompletionResponse::Array))
+}
+
+/// Gets the completions of the tasks and workflow of an import namespace.
+fn namespace_completions(scope: &DocumentScope, namespace: &str) -> Option<Vec<CompletionItem>> {
+    let scope = scope.namespace(namespace)?.scope();
+    let mut items = scope
+        .tasks()
+        .map(|(name, _)| item(name, CompletionItemKind::FUNCTION, Some("task".into())))
+        .collect::<Vec<_>>();
+
+    if let Some((name, _)) = scope.workflow() {
+        items.push(item(
+            name,
+            CompletionItemKind::FUNCTION,
+            Some("workflow".into()),
+        ));
+    }
+
+    Some(items)
+}
+
+/// Gets the completions of the target of a call statement.
+///
+/// The targets are the tasks in the document and the import namespaces.
+fn call_completions(scope: &DocumentScope) -> Option<Vec<CompletionItem>> {
+    let mut items = scope
+        .tasks()
+        .map(|(name, _)| item(name, CompletionItemKind::FUNCTION, Some("task".into())))
+        .collect::<Vec<_>>();
+
+    items.extend(
+        scope
+            .namespaces()
+            .map(|(name, _)| item(name, CompletionItemKind::MODULE, Some("namespace".into()))),
+    );
+
+    Some(items)
+}
+
+/// Gets the completions of the members of a struct, call outputs, or pair.
+///
+/// The given names are the names preceding the member access (e.g. `a.b` for
+/// `a.b.`).
+fn member_completions(
+    scope: &DocumentScope,
+    offset: usize,
+    names: &[&str],
+) -> Option<Vec<CompletionItem>> {
+    let types = scope.types();
+    let (first, rest) = names.split_first()?;
+    let mut ty = scope.find_scope_by_position(offset)?.lookup(first)?.ty()?;
+    for name in rest {
+        ty = member_type(types, ty, name)?;
+    }
+
+    let Type::Compound(compound) = ty else {
+        return None;
+    };
+
+    let members = |members: &mut dyn Iterator<Item = (&String, &Type)>| {
+        members
+            .map(|(name, ty)| {
+                item(
+                    name,
+                    CompletionItemKind::FIELD,
+                    Some(ty.display(types).to_string()),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    match types.type_definition(compound.definition()) {
+        CompoundTypeDef::Struct(ty) => Some(members(&mut ty.members().iter())),
+        CompoundTypeDef::Call(ty) => Some(members(&mut ty.outputs().iter())),
+        CompoundTypeDef::Pair(ty) => Some(vec![
+            item(
+                "left",
+                CompletionItemKind::FIELD,
+                Some(ty.first_type().display(types).to_string()),
+            ),
+            item(
+                "right",
+                CompletionItemKind::FIELD,
+                Some(ty.second_type().display(types).to_string()),
+            ),
+        ]),
+        _ => None,
+    }
+}
+
+/// Gets the completions of the keys of a `runtime`, `requirements`, or `hints`
+/// section.
+///
+/// Returns `None` if the offset is not at the position of a key in one of the
+/// sections.
+fn key_completions(
+    scope: &DocumentScope,
+    root: &SyntaxNode,
+    offset: usize,
+) -> Option<Vec<CompletionItem>> {
+    let version = scope.version()?;
+    let position = u32::try_from(offset).ok()?.into();
+    let token = root.token_at_offset(position).left_biased()?;
+
+    let section = token.parent_ancestors().find(|n| {
+        matches!(
+            n.kind(),
+            SyntaxKind::RuntimeSectionNode
+                | SyntaxKind::RequirementsSectionNode
+                | SyntaxKind::HintsSectionNode
+        )
+    })?;
+
+    // Find the token preceding the key being completed
+    let mut prev = Some(token.clone());
+    if token.kind() == SyntaxKind::Ident {
+        prev = token.prev_token();
+    }
+
+    while let Some(t) = prev
+        .as_ref()
+        .filter(|t| matches!(t.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment))
+    {
+        prev = t.prev_token();
+    }
+
+    // A key follows either the opening brace of the section or a complete item
+    let prev = prev?;
+    let is_key = match prev.kind() {
+        SyntaxKind::OpenBrace => prev.parent().as_ref() == Some(&section),
+        _ => prev
+            .parent_ancestors()
+            .take_while(|n| n != &section)
+            .any(|n| {
+                matches!(
+                    n.kind(),
+                    SyntaxKind::RuntimeItemNode
+                        | SyntaxKind::RequirementsItemNode
+                        | SyntaxKind::HintsItemNode
+                ) && n.text_range().end() == prev.text_range().end()
+            }),
+    };
+
+    if !is_key {
+        return None;
+    }
+
+    let keys: Vec<&str> = match section.kind() {
+        SyntaxKind::RuntimeSectionNode => reserved_runtime_keys(version),
+        SyntaxKind::RequirementsSectionNode if version >= SupportedVersion::V1(V1::Two) => {
+            SUPPORTED_REQUIREMENTS_KEYS
+                .iter()
+                .copied()
+                .filter(|k| !REQUIREMENTS_KEY_ALIASES.iter().any(|(_, alias)| alias == k))
+                .collect()
+        }
+        SyntaxKind::HintsSectionNode if version >= SupportedVersion::V1(V1::Two) => {
+            match section.parent()?.kind() {
+                SyntaxKind::WorkflowDefinitionNode => workflow_hint_keys().collect(),
+                _ => task_hint_keys().collect(),
+            }
+        }
+        _ => Vec::new(),
+    };
+
+    // Exclude the keys already present in the section
+    let present = section
+        .children()
+        .filter(|n| !n.text_range().contains_inclusive(position))
+        .filter_map(|n| {
+            n.children_with_tokens()
+                .find(|c| c.kind() == SyntaxKind::Ident)
+                .map(|c| c.to_string())
+        })
+        .collect::<HashSet<_>>();
+
+    Some(
+        keys.into_iter()
+            .filter(|k| !present.contains(*k))
+            .map(|k| item(k, CompletionItemKind::PROPERTY, None))
+            .collect(),
+    )
+}
+
+/// Gets the completions of the names in scope, the structs, and the standard
+/// library functions.
+fn name_completions(scope: &DocumentScope, offset: usize) -> Option<Vec<CompletionItem>> {
+    let types = scope.types();
+    let mut seen = HashSet::new();
+    let mut items = Vec::new();
+    let mut current = scope.find_scope_by_position(offset);
+    while let Some(s) = current {
+        for (name, n) in s.names() {
+            if !seen.insert(name.to_string()) {
+                continue;
+            }
+
+            let kind = match n.context() {
+                NameContext::Call(_) => CompletionItemKind::MODULE,
+                _ => CompletionItemKind::VARIABLE,
+            };
+
+            items.push(item(
+                name,
+                kind,
+                n.ty().map(|ty| ty.display(types).to_string()),
+            ));
+        }
+
+        current = s.parent();
+    }
+
+    // Names are only in scope within a task or workflow
+    if items.is_empty() && scope.find_scope_by_position(offset).is_none() {
+        return None;
+    }
+
+    items.extend(
+        scope
+            .structs()
+            .map(|(name, _)| item(name, CompletionItemKind::STRUCT, Some("struct".into()))),
+    );
+
+    let version = scope.version();
+    items.extend(
+        STDLIB
+            .functions()
+            .filter(|(_, f)| version.map(|v| f.minimum_version() <= v).unwrap_or(true))
+            .map(|(name, f)| function_completion(name, f)),
+    );
+
+    Some(items)
+}
+
+/// Gets the completion of a standard library function.
+///
+/// The completion inserts a snippet with placeholders for the required
+/// parameters of the function's first signature.
+fn function_completion(name: &str, function: &Function) -> CompletionItem {
+    let signature: &FunctionSignature = match function {
+        Function::Monomorphic(f) => f.signature(),
+        Function::Polymorphic(f) => &f.signatures()[0],
+    };
+
+    let params = TypeParameters::new(signature.type_parameters());
+    let mut snippet = format!("{name}(");
+    for (i, ty) in signature
+        .parameters()
+        .iter()
+        .take(signature.required())
+        .enumerate()
+    {
+        if i > 0 {
+            snippet.push_str(", ");
+        }
+
+        let ty = ty.display(STDLIB.types(), &params).to_string();
+        write!(
+            &mut snippet,
+            "${{{n}:{ty}}}",
+            n = i + 1,
+            ty = escape_snippet(&ty)
+        )
+        .unwrap();
+    }
+
+    snippet.push(')');
+
+    CompletionItem {
+        insert_text: Some(snippet),
+        insert_text_format: Some(InsertTextFormat::SNIPPET),
+        ..item(
+            name,
+            CompletionItemKind::FUNCTION,
+            Some(format!(
+                "{name}{sig}",
+                sig = signature.display(STDLIB.types(), &params)
+            )),
+        )
+    }
+}
+
+/// Escapes text for use in a snippet placeholder.
+fn escape_snippet(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '$' | '}' | '\\') {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::test_util::analyze;
+    use crate::test_util::cursor;
+    use crate::test_util::result;
+
+    const LIB: &str = r#"version 1.1
+
+struct Point {
+    Int x
+    Int y
+}
+
+task greet {
+    input {
+        String name
+    }
+
+    command <<<>>>
+
+    output {
+        String greeting = "hello ~{name}"
+        Pair[Int, String] pair = (1, name)
+    }
+}
+
+task farewell {
+    command <<<>>>
+}
+"#;
+
+    /// Gets the completions at the `|` marker in the given source.
+    ///
+    /// The source is analyzed alongside a `lib.wdl` document.
+    async fn complete(source: &str) -> Vec<CompletionItem> {
+        let (source, position) = cursor(source);
+        let (_dir, results) = analyze(&[("lib.wdl", LIB), ("source.wdl", &source)]).await;
+        match completion(result(&results, "source.wdl"), position).expect("should complete") {
+            Some(CompletionResponse::Array(items)) => items,
+            Some(_) => panic!("expected an array of completions"),
+            None => Vec::new(),
+        }
+    }
+
+    /// Gets the sorted labels of the given completions.
+    fn labels(items: &[CompletionItem]) -> Vec<&str> {
+        let mut labels = items.iter().map(|i| i.label.as_str()).collect::<Vec<_>>();
+        labels.sort_unstable();
+        labels
+    }
+
+    #[tokio::test]
+    async fn it_completes_names_in_scope() {
+        let items = complete(
+            r#"version 1.1
+
+import "lib.wdl"
+
+workflow test {
+    input {
+        Int a
+        Array[Int] numbers
+    }
+
+    scatter (n in numbers) {
+        Int doubled = |n * 2
+    }
+}
+"#,
+        )
+        .await;
+
+        let labels = labels(&items);
+        for label in [
+            "a",
+            "n",
+            "numbers",
+            "doubled",
+            "Point",
+            "length",
+            "select_first",
+        ] {
+            assert!(labels.contains(&label), "missing completion `{label}`");
+        }
+
+        // Functions introduced in later versions are excluded
+        assert!(!labels.contains(&"contains_key"));
+
+        let a = items.iter().find(|i| i.label == "a").unwrap();
+        assert_eq!(a.detail.as_deref(), Some("Int"));
+
+        let length = items.iter().find(|i| i.label == "length").unwrap();
+        assert_eq!(length.insert_text.as_deref(), Some("length(${1:Array[X]})"));
+        assert_eq!(length.insert_text_format, Some(InsertTextFormat::SNIPPET));
+    }
+
+    #[tokio::test]
+    async fn it_completes_members() {
+        let source = r#"version 1.1
+
+import "lib.wdl"
+
+workflow test {
+    input {
+        lib.Point p
+    }
+
+    call lib.greet { input: name = "world" }
+
+    output {
+        Int x = |
+    }
+}
+"#;
+
+        let items = complete(&source.replace("= |", "= p.|")).await;
+        assert_eq!(labels(&items), ["x", "y"]);
+        assert_eq!(items[0].detail.as_deref(), Some("Int"));
+
+        let items = complete(&source.replace("= |", "= greet.|")).await;
+        assert_eq!(labels(&items), ["greeting", "pair"]);
+
+        let items = complete(&source.replace("= |", "= greet.pair.|")).await;
+        assert_eq!(labels(&items), ["left", "right"]);
+
+        let items = complete(&source.replace("= |", "= missing.|")).await;
+        assert!(items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_completes_call_targets() {
+        let source = r#"version 1.1
+
+import "lib.wdl" as lib
+
+task local {
+    command <<<>>>
+}
+
+workflow test {
+    call |
+}
+"#;
+
+        let items = complete(&source.replace("call |", "call lib.|")).await;
+        assert_eq!(labels(&items), ["farewell", "greet"]);
+
+        let items = complete(source).await;
+        assert_eq!(labels(&items), ["lib", "local"]);
+    }
+
+    #[tokio::test]
+    async fn it_completes_runtime_keys() {
+        let source = r#"version 1.1
+
+task test {
+    command <<<>>>
+
+    runtime {
+        container: "ubuntu:latest"
+        |
+    }
+}
+"#;
+
+        let items = complete(source).await;
+        assert_eq!(
+            labels(&items),
+            [
+                "cpu",
+                "disks",
+                "gpu",
+                "inputs",
+                "localizationOptional",
+                "maxCpu",
+                "maxMemory",
+                "maxRetries",
+                "memory",
+                "outputs",
+                "returnCodes",
+                "shortTask"
+            ]
+        );
+
+        let items = complete(&source.replace("version 1.1", "version 1.0")).await;
+        assert_eq!(labels(&items), ["docker", "memory"]);
+
+        // A partially typed key is completed
+        let items = complete(&source.replace("        |", "        mem|")).await;
+        assert!(labels(&items).contains(&"memory"));
+
+        // Values are not completed as keys
+        let items =
+            complete(&source.replace("latest\"\n        |", "latest\"\n        cpu: |")).await;
+        assert!(!labels(&items).contains(&"memory"));
+    }
+
+    #[tokio::test]
+    async fn it_completes_requirements_and_hints_keys() {
+        let source = r#"version 1.2
+
+task test {
+    command <<<>>>
+
+    requirements {
+        container: "ubuntu:latest"
+        |
+    }
+}
+
+workflow w {
+    hints {
+    }
+}
+"#;
+
+        let items = complete(source).await;
+        assert_eq!(
+            labels(&items),
+            [
+                "cpu",
+                "disks",
+                "fpga",
+                "gpu",
+                "max_retries",
+                "memory",
+                "return_codes"
+            ]
+        );
+
+        let items = complete(&source.replace("requirements {", "hints {")).await;
+        assert_eq!(labels(&items), {
+            let mut keys = task_hint_keys().collect::<Vec<_>>();
+            keys.sort_unstable();
+            keys
+        });
+
+        let items = complete(
+            &source
+                .replace("        |\n", "")
+                .replace("    hints {\n    }", "    hints {\n        |\n    }"),
+        )
+        .await;
+        assert_eq!(labels(&items), workflow_hint_keys().collect::<Vec<_>>());
+    }
+}
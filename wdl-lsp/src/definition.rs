@@ -0,0 +1,646 @@
+//! Implementation of go to definition and find references.
+//!
+//! Definitions are resolved on demand from the syntax tree and the scope
+//! information of the analyzed documents; references are found by resolving
+//! every identifier of the same name in the analyzed documents.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use tower_lsp::lsp_types::GotoDefinitionResponse;
+use tower_lsp::lsp_types::Location;
+use tower_lsp::lsp_types::Position;
+use url::Url;
+use wdl_analysis::scope::NameContext;
+use wdl_analysis::types::CompoundTypeDef;
+use wdl_analysis::types::Type;
+use wdl_analysis::AnalysisResult;
+use wdl_ast::v1;
+use wdl_ast::v1::CallStatement;
+use wdl_ast::v1::CallTarget;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Span;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::SyntaxToken;
+use wdl_ast::ToSpan;
+
+use crate::proto;
+use crate::syntax::expr_type;
+use crate::syntax::ident_at;
+
+/// Represents the location of a definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    /// The URI of the document containing the definition.
+    pub uri: Arc<Url>,
+    /// The span of the name of the definition.
+    pub span: Span,
+}
+
+impl Definition {
+    /// Constructs a new definition for the given document and span.
+    pub(crate) fn new(result: &AnalysisResult, span: Span) -> Self {
+        Self {
+            uri: result.uri().clone(),
+            span,
+        }
+    }
+
+    /// Converts the definition into an LSP location.
+    ///
+    /// Returns `None` if the document of the definition is not in the given
+    /// results.
+    pub fn to_location(&self, results: &[AnalysisResult]) -> Option<Location> {
+        let result = find_result(results, &self.uri)?;
+        let lines = result.parse_result().lines()?;
+        Some(Location::new(
+            self.uri.as_ref().clone(),
+            proto::range_from_span(lines, self.span).ok()?,
+        ))
+    }
+}
+
+/// Finds the analysis result for the given document URI.
+pub(crate) fn find_result<'a>(
+    results: &'a [AnalysisResult],
+    uri: &Url,
+) -> Option<&'a AnalysisResult> {
+    results.iter().find(|r| r.uri().as_ref() == uri)
+}
+
+/// Gets the V1 AST of an analyzed document.
+pub(crate) fn v1_ast(result: &AnalysisResult) -> Option<v1::Ast> {
+    result.parse_result().document()?.ast().into_v1()
+}
+
+/// Gets the name of a declaration node.
+pub(crate) fn decl_name(decl: &SyntaxNode) -> Option<SyntaxToken> {
+    decl.children_with_tokens()
+        .filter_map(|c| c.into_token())
+        .find(|t| t.kind() == SyntaxKind::Ident)
+}
+
+/// Finds the span of the name of a declaration in the given section of a task
+/// or workflow definition.
+pub(crate) fn find_section_decl(
+    definition: &SyntaxNode,
+    section: SyntaxKind,
+    name: &str,
+) -> Option<Span> {
+    definition
+        .children()
+        .filter(|n| n.kind() == section)
+        .flat_map(|s| s.children())
+        .filter_map(|d| decl_name(&d))
+        .find(|t| t.text() == name)
+        .map(|t| t.text_range().to_span())
+}
+
+/// Resolves the callee of a call statement's target.
+///
+/// Returns the analysis result of the document containing the callee and the
+/// task or workflow definition node.
+pub(crate) fn resolve_callee<'a>(
+    results: &'a [AnalysisResult],
+    result: &'a AnalysisResult,
+    target: &CallTarget,
+) -> Option<(&'a AnalysisResult, SyntaxNode)> {
+    let mut names = target.names().collect::<Vec<_>>();
+    let callee = names.pop()?;
+    let result = match names.first() {
+        Some(ns) => find_result(results, result.scope().namespace(ns.as_str())?.source())?,
+        None => result,
+    };
+
+    let ast = v1_ast(result)?;
+    let node = ast
+        .tasks()
+        .find(|t| t.name().as_str() == callee.as_str())
+        .map(|t| t.syntax().clone())
+        .or_else(|| {
+            ast.workflows()
+                .find(|w| w.name().as_str() == callee.as_str())
+                .map(|w| w.syntax().clone())
+        })?;
+
+    Some((result, node))
+}
+
+/// Resolves a struct by name in the given document.
+///
+/// Imported structs are resolved to their definition in the imported
+/// document.
+pub(crate) fn resolve_struct(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    name: &str,
+) -> Option<Definition> {
+    let s = result.scope().struct_by_name(name)?;
+    let ast = v1_ast(result)?;
+    match s.namespace() {
+        None => ast
+            .structs()
+            .find(|s| s.name().as_str() == name)
+            .map(|s| Definition::new(result, s.name().span())),
+        Some(ns) => {
+            // Account for a struct that was aliased by the import
+            let original = ast
+                .imports()
+                .find(|i| i.namespace().map(|(n, _)| n == ns).unwrap_or(false))
+                .and_then(|i| {
+                    i.aliases().find_map(|a| {
+                        let (from, to) = a.names();
+                        (to.as_str() == name).then(|| from.as_str().to_string())
+                    })
+                })
+                .unwrap_or_else(|| name.to_string());
+
+            let source = find_result(results, result.scope().namespace(ns)?.source())?;
+            resolve_struct(results, source, &original)
+        }
+    }
+}
+
+/// Resolves an import namespace by name in the given document.
+fn resolve_namespace(result: &AnalysisResult, name: &str) -> Option<Definition> {
+    v1_ast(result)?.imports().find_map(|i| {
+        let (ns, span) = i.namespace()?;
+        (ns == name).then(|| Definition::new(result, span))
+    })
+}
+
+/// Resolves a member of a struct or call output given the type of the member
+/// access target.
+fn resolve_member(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    ty: Type,
+    member: &str,
+) -> Option<Definition> {
+    let Type::Compound(compound) = ty else {
+        return None;
+    };
+
+    let scope = result.scope();
+    match scope.types().type_definition(compound.definition()) {
+        CompoundTypeDef::Call(call) => {
+            let (result, callee) = match call.name().split_once('.') {
+                Some((ns, callee)) => {
+                    (find_result(results, scope.namespace(ns)?.source())?, callee)
+                }
+                None => (result, call.name()),
+            };
+
+            let ast = v1_ast(result)?;
+            let node = ast
+                .tasks()
+                .find(|t| t.name().as_str() == callee)
+                .map(|t| t.syntax().clone())
+                .or_else(|| {
+                    ast.workflows()
+                        .find(|w| w.name().as_str() == callee)
+                        .map(|w| w.syntax().clone())
+                })?;
+            find_section_decl(&node, SyntaxKind::OutputSectionNode, member)
+                .map(|span| Definition::new(result, span))
+        }
+        CompoundTypeDef::Struct(_) => {
+            // Find the name of the struct in this document as it may be aliased
+            let name = scope
+                .structs()
+                .find(|(_, s)| s.ty().map(|t| t == ty).unwrap_or(false))
+                .map(|(n, _)| n)?;
+            resolve_struct_member(results, result, name, member)
+        }
+        _ => None,
+    }
+}
+
+/// Resolves a member of the struct with the given name in the given document.
+fn resolve_struct_member(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    name: &str,
+    member: &str,
+) -> Option<Definition> {
+    let definition = resolve_struct(results, result, name)?;
+    let result = find_result(results, &definition.uri)?;
+    let root = result.parse_result().document()?.syntax().clone();
+    let node = ident_at(&root, definition.span.start())?.parent()?;
+    node.children()
+        .filter_map(|d| decl_name(&d))
+        .find(|t| t.text() == member)
+        .map(|t| Definition::new(result, t.text_range().to_span()))
+}
+
+/// Resolves the definition of the given identifier token in a document.
+///
+/// Returns `None` if the identifier does not refer to a known definition.
+pub fn resolve(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    token: &SyntaxToken,
+) -> Option<Definition> {
+    let parent = token.parent()?;
+    let name = token.text();
+    let span = token.text_range().to_span();
+    let scope = result.scope();
+    let lookup = |offset: usize| match scope
+        .find_scope_by_position(offset)?
+        .lookup(name)?
+        .context()
+    {
+        NameContext::Task(_) => None,
+        context => Some(Definition::new(result, context.span())),
+    };
+
+    match parent.kind() {
+        SyntaxKind::NameRefNode | SyntaxKind::CallAfterNode => lookup(span.start()),
+        SyntaxKind::UnboundDeclNode
+        | SyntaxKind::BoundDeclNode
+        | SyntaxKind::ScatterStatementNode
+        | SyntaxKind::StructDefinitionNode
+        | SyntaxKind::TaskDefinitionNode
+        | SyntaxKind::WorkflowDefinitionNode
+        | SyntaxKind::CallAliasNode
+        | SyntaxKind::ImportStatementNode => Some(Definition::new(result, span)),
+        SyntaxKind::TypeRefNode | SyntaxKind::LiteralStructNode => {
+            resolve_struct(results, result, name)
+        }
+        SyntaxKind::LiteralStructItemNode => {
+            let literal = parent.parent()?;
+            let name = literal
+                .children_with_tokens()
+                .filter_map(|c| c.into_token())
+                .find(|t| t.kind() == SyntaxKind::Ident)?;
+            resolve_struct_member(results, result, name.text(), token.text())
+        }
+        SyntaxKind::ImportAliasNode => {
+            let import = parent.parent()?;
+            let ns = wdl_ast::v1::ImportStatement::cast(import)?.namespace()?.0;
+            let is_source = token
+                .siblings_with_tokens(rowan::Direction::Prev)
+                .all(|t| t.kind() != SyntaxKind::AsKeyword);
+            if is_source {
+                let source = find_result(results, scope.namespace(&ns)?.source())?;
+                resolve_struct(results, source, name)
+            } else {
+                resolve_struct(results, result, name)
+            }
+        }
+        SyntaxKind::CallTargetNode => {
+            let is_namespace = token
+                .siblings_with_tokens(rowan::Direction::Next)
+                .any(|t| t.kind() == SyntaxKind::Dot);
+            if is_namespace {
+                return resolve_namespace(result, name);
+            }
+
+            let target = CallTarget::cast(parent)?;
+            let (result, callee) = resolve_callee(results, result, &target)?;
+            let name = callee
+                .children_with_tokens()
+                .filter_map(|c| c.into_token())
+                .find(|t| t.kind() == SyntaxKind::Ident)?;
+            Some(Definition::new(result, name.text_range().to_span()))
+        }
+        SyntaxKind::CallInputItemNode => {
+            let call = CallStatement::cast(parent.parent()?)?;
+            let (result, callee) = resolve_callee(results, result, &call.target())?;
+            find_section_decl(&callee, SyntaxKind::InputSectionNode, name)
+                .map(|span| Definition::new(result, span))
+        }
+        SyntaxKind::AccessExprNode => {
+            let target = parent.first_child()?;
+            let ty = expr_type(scope, &target)?;
+            resolve_member(results, result, ty, name)
+        }
+        SyntaxKind::MetadataObjectItemNode => {
+            // Keys of `parameter_meta` sections refer to inputs and outputs
+            if parent.parent()?.kind() != SyntaxKind::ParameterMetadataSectionNode {
+                return None;
+            }
+
+            lookup(span.start())
+        }
+        _ => None,
+    }
+}
+
+/// Gets the definition of the identifier at the given position in a document.
+///
+/// Returns `Ok(None)` if there is no identifier at the position or if its
+/// definition could not be resolved.
+pub fn goto_definition(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    position: Position,
+) -> Result<Option<GotoDefinitionResponse>> {
+    let (Some(document), Some(lines)) = (
+        result.parse_result().document(),
+        result.parse_result().lines(),
+    ) else {
+        return Ok(None);
+    };
+
+    let offset = proto::offset(lines, position)?;
+    Ok(ident_at(document.syntax(), offset)
+        .and_then(|token| resolve(results, result, &token))
+        .and_then(|definition| definition.to_location(results))
+        .map(GotoDefinitionResponse::Scalar))
+}
+
+/// Finds the references to the definition of the identifier at the given
+/// position in a document.
+///
+/// References are searched for in all of the given results.
+///
+/// Returns `Ok(None)` if there is no identifier at the position or if its
+/// definition could not be resolved.
+pub fn find_references(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    position: Position,
+    include_declaration: bool,
+) -> Result<Option<Vec<Location>>> {
+    let (Some(document), Some(lines)) = (
+        result.parse_result().document(),
+        result.parse_result().lines(),
+    ) else {
+        return Ok(None);
+    };
+
+    let offset = proto::offset(lines, position)?;
+    let Some(token) = ident_at(document.syntax(), offset) else {
+        return Ok(None);
+    };
+
+    let Some(definition) = resolve(results, result, &token) else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        references(results, &definition, token.text())
+            .into_iter()
+            .map(|(result, token)| Definition::new(result, token.text_range().to_span()))
+            .filter(|r| include_declaration || *r != definition)
+            .filter_map(|r| r.to_location(results))
+            .collect(),
+    ))
+}
+
+/// Finds the references to the given definition in the given results.
+///
+/// The given name is the name used at the location the references were
+/// requested from; it may differ from the definition's name when a struct is
+/// aliased by an import.
+///
+/// Returns the identifier tokens of the references along with the results of
+/// the documents containing them; the definition itself is included in the
+/// references.
+pub(crate) fn references<'a>(
+    results: &'a [AnalysisResult],
+    definition: &Definition,
+    name: &str,
+) -> Vec<(&'a AnalysisResult, SyntaxToken)> {
+    let mut names = vec![name.to_string()];
+    if let Some(name) = find_result(results, &definition.uri)
+        .and_then(|r| r.parse_result().document())
+        .and_then(|d| ident_at(d.syntax(), definition.span.start()))
+    {
+        if !names.iter().any(|n| n == name.text()) {
+            names.push(name.text().to_string());
+        }
+    }
+
+    let mut references = Vec::new();
+    for result in results {
+        let Some(document) = result.parse_result().document() else {
+            continue;
+        };
+
+        for token in document
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|e| e.into_token())
+            .filter(|t| t.kind() == SyntaxKind::Ident && names.iter().any(|n| n == t.text()))
+        {
+            if resolve(results, result, &token).as_ref() == Some(definition) {
+                references.push((result, token));
+            }
+        }
+    }
+
+    references
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::test_util;
+    use crate::test_util::position_of;
+    use crate::test_util::result;
+
+    const LIB: &str = r#"version 1.1
+
+struct Point {
+    Int x
+    Int y
+}
+
+task greet {
+    input {
+        String name
+    }
+
+    command <<<>>>
+
+    output {
+        String greeting = "hello ~{name}"
+    }
+}
+"#;
+
+    const MAIN: &str = r#"version 1.1
+
+import "lib.wdl" as lib alias Point as Coord
+
+workflow test {
+    input {
+        Array[String] names
+        Coord origin
+    }
+
+    scatter (n in names) {
+        call lib.greet { input: name = n }
+    }
+
+    output {
+        Array[String] greetings = greet.greeting
+        Int x = origin.x
+        Int count = length(names)
+    }
+
+    parameter_meta {
+        names: "The names to greet"
+    }
+}
+"#;
+
+    /// Analyzes the test documents, returning the temporary directory
+    /// containing them and the analysis results.
+    async fn analyze() -> (TempDir, Vec<AnalysisResult>) {
+        test_util::analyze(&[("lib.wdl", LIB), ("main.wdl", MAIN)]).await
+    }
+
+    /// Describes a location as its file name and start position.
+    fn describe(location: &Location) -> (String, Position) {
+        (
+            location
+                .uri
+                .path_segments()
+                .and_then(|mut s| s.next_back())
+                .expect("should have file name")
+                .to_string(),
+            location.range.start,
+        )
+    }
+
+    /// Gets the definition of the `nth` occurrence of `needle` in the main
+    /// document.
+    fn definition(
+        results: &[AnalysisResult],
+        needle: &str,
+        nth: usize,
+    ) -> Option<(String, Position)> {
+        match goto_definition(
+            results,
+            result(results, "main.wdl"),
+            position_of(MAIN, needle, nth),
+        )
+        .expect("should resolve definition")?
+        {
+            GotoDefinitionResponse::Scalar(location) => Some(describe(&location)),
+            _ => panic!("expected a single location"),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_resolves_local_definitions() {
+        let (_dir, results) = analyze().await;
+        let main = |needle, nth| Some(("main.wdl".to_string(), position_of(MAIN, needle, nth)));
+
+        // Scatter variable
+        assert_eq!(definition(&results, "n }", 0), main("n in", 0));
+        // Workflow input
+        assert_eq!(definition(&results, "names)", 0), main("names\n", 0));
+        assert_eq!(definition(&results, "names)", 1), main("names\n", 0));
+        // Parameter metadata key
+        assert_eq!(definition(&results, "names:", 0), main("names\n", 0));
+        // Import namespace
+        assert_eq!(definition(&results, "lib.greet {", 0), main("lib alias", 0));
+        // Declarations resolve to themselves
+        assert_eq!(definition(&results, "count", 0), main("count", 0));
+        // Standard library functions have no definition
+        assert_eq!(definition(&results, "length", 0), None);
+    }
+
+    #[tokio::test]
+    async fn it_resolves_imported_definitions() {
+        let (_dir, results) = analyze().await;
+        let lib = |needle, nth| Some(("lib.wdl".to_string(), position_of(LIB, needle, nth)));
+
+        // Call target
+        assert_eq!(definition(&results, "greet {", 0), lib("greet {", 0));
+        // Call input
+        assert_eq!(definition(&results, "name = n", 0), lib("name\n", 0));
+        // Call output
+        assert_eq!(definition(&results, "greeting\n", 0), lib("greeting =", 0));
+        // Aliased struct, both in the import and as a type
+        assert_eq!(definition(&results, "Point as", 0), lib("Point {", 0));
+        assert_eq!(definition(&results, "Coord\n", 0), lib("Point {", 0));
+        assert_eq!(definition(&results, "Coord origin", 0), lib("Point {", 0));
+        // Struct member
+        assert_eq!(definition(&results, "x\n", 0), lib("x\n", 0));
+    }
+
+    /// Gets the sorted references to the `nth` occurrence of `needle` in the
+    /// given document.
+    fn references(
+        results: &[AnalysisResult],
+        name: &str,
+        needle: &str,
+        nth: usize,
+        include_declaration: bool,
+    ) -> Vec<(String, Position)> {
+        let source = if name == "lib.wdl" { LIB } else { MAIN };
+        let mut locations = find_references(
+            results,
+            result(results, name),
+            position_of(source, needle, nth),
+            include_declaration,
+        )
+        .expect("should find references")
+        .expect("should have references")
+        .iter()
+        .map(describe)
+        .collect::<Vec<_>>();
+        locations.sort_by_key(|(name, p)| (name.clone(), p.line, p.character));
+        locations
+    }
+
+    #[tokio::test]
+    async fn it_finds_local_references() {
+        let (_dir, results) = analyze().await;
+        let main = |needle, nth| ("main.wdl".to_string(), position_of(MAIN, needle, nth));
+
+        assert_eq!(
+            references(&results, "main.wdl", "names)", 1, true),
+            [
+                main("names\n", 0),
+                main("names)", 0),
+                main("names)", 1),
+                main("names:", 0),
+            ]
+        );
+        assert_eq!(
+            references(&results, "main.wdl", "names\n", 0, false),
+            [main("names)", 0), main("names)", 1), main("names:", 0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_finds_references_across_documents() {
+        let (_dir, results) = analyze().await;
+        let lib = |needle| ("lib.wdl".to_string(), position_of(LIB, needle, 0));
+        let main = |needle| ("main.wdl".to_string(), position_of(MAIN, needle, 0));
+
+        // Uses of the call's name refer to the call rather than the task
+        assert_eq!(
+            references(&results, "lib.wdl", "greet {", 0, true),
+            [lib("greet {"), main("greet {")]
+        );
+        assert_eq!(
+            references(&results, "lib.wdl", "greet {", 0, false),
+            [main("greet {")]
+        );
+        assert_eq!(
+            references(&results, "main.wdl", "greeting\n", 0, true),
+            [lib("greeting ="), main("greeting\n")]
+        );
+        assert_eq!(
+            references(&results, "main.wdl", "Coord origin", 0, true),
+            [
+                lib("Point {"),
+                main("Point as"),
+                main("Coord\n"),
+                main("Coord origin")
+            ]
+        );
+    }
+}
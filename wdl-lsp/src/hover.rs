@@ -0,0 +1,473 @@
+//! Implementation of hover information for WDL documents.
+
+use std::fmt::Write;
+
+use anyhow::Result;
+use tower_lsp::lsp_types::Hover;
+use tower_lsp::lsp_types::HoverContents;
+use tower_lsp::lsp_types::MarkupContent;
+use tower_lsp::lsp_types::MarkupKind;
+use tower_lsp::lsp_types::Position;
+use wdl_analysis::inputs::input_description;
+use wdl_analysis::scope::DocumentScope;
+use wdl_analysis::scope::NameContext;
+use wdl_analysis::stdlib::Function;
+use wdl_analysis::stdlib::TypeParameters;
+use wdl_analysis::stdlib::STDLIB;
+use wdl_analysis::types::CompoundTypeDef;
+use wdl_analysis::types::Type;
+use wdl_analysis::types::Types;
+use wdl_analysis::AnalysisResult;
+use wdl_ast::v1::CallStatement;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::WorkflowDefinition;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::ToSpan;
+
+use crate::proto;
+use crate::syntax::expr_type;
+use crate::syntax::ident_at;
+use crate::syntax::member_type;
+
+/// Formats an optional type for display.
+fn display_type(types: &Types, ty: Option<Type>) -> String {
+    ty.map(|ty| ty.display(types).to_string())
+        .unwrap_or_else(|| "<unknown>".to_string())
+}
+
+/// Finds the description of an input from the `parameter_meta` section of the
+/// task or workflow that contains the given node.
+fn enclosing_input_description(node: &SyntaxNode, name: &str) -> Option<String> {
+    node.ancestors().find_map(|n| match n.kind() {
+        SyntaxKind::TaskDefinitionNode => TaskDefinition::cast(n)?
+            .parameter_metadata()
+            .and_then(|s| input_description(&s, name)),
+        SyntaxKind::WorkflowDefinitionNode => WorkflowDefinition::cast(n)?
+            .parameter_metadata()
+            .and_then(|s| input_description(&s, name)),
+        _ => None,
+    })
+}
+
+/// Finds the description of an input of the named task or workflow in the
+/// given document.
+fn callee_input_description(root: &SyntaxNode, callee: &str, name: &str) -> Option<String> {
+    root.children().find_map(|n| match n.kind() {
+        SyntaxKind::TaskDefinitionNode => {
+            let task = TaskDefinition::cast(n)?;
+            if task.name().as_str() != callee {
+                return None;
+            }
+
+            task.parameter_metadata()
+                .and_then(|s| input_description(&s, name))
+        }
+        SyntaxKind::WorkflowDefinitionNode => {
+            let workflow = WorkflowDefinition::cast(n)?;
+            if workflow.name().as_str() != callee {
+                return None;
+            }
+
+            workflow
+                .parameter_metadata()
+                .and_then(|s| input_description(&s, name))
+        }
+        _ => None,
+    })
+}
+
+/// Formats the given code and optional description as markdown.
+fn markdown(code: &str, description: Option<String>) -> String {
+    let mut markdown = format!("```wdl\n{code}\n```");
+    if let Some(description) = description {
+        write!(&mut markdown, "\n\n{description}").unwrap();
+    }
+
+    markdown
+}
+
+/// Gets the hover information for a name in scope.
+fn name_hover(
+    root: &SyntaxNode,
+    scope: &DocumentScope,
+    offset: usize,
+    name: &str,
+) -> Option<String> {
+    let n = scope.find_scope_by_position(offset)?.lookup(name)?;
+    let types = scope.types();
+    let (kind, description) = match n.context() {
+        NameContext::Input(span) => {
+            let description = ident_at(root, span.start())
+                .and_then(|t| enclosing_input_description(&t.parent()?, name));
+            ("input", description)
+        }
+        NameContext::Output(_) => ("output", None),
+        NameContext::Decl(_) => ("declaration", None),
+        NameContext::ScatterVariable(_) => ("scatter variable", None),
+        NameContext::Task(_) => ("task", None),
+        NameContext::Call(_) => {
+            return Some(call_hover(types, name, n.ty()?));
+        }
+    };
+
+    Some(markdown(
+        &format!("({kind}) {ty} {name}", ty = display_type(types, n.ty())),
+        description,
+    ))
+}
+
+/// Gets the hover information for a call given the call's type.
+fn call_hover(types: &Types, name: &str, ty: Type) -> String {
+    let Type::Compound(compound) = ty else {
+        return markdown(&format!("(call) {name}"), None);
+    };
+
+    let CompoundTypeDef::Call(call) = types.type_definition(compound.definition()) else {
+        return markdown(&format!("(call) {name}"), None);
+    };
+
+    let mut code = format!(
+        "(call) {name}: {kind} {callee}",
+        kind = call.kind(),
+        callee = call.name()
+    );
+
+    if !call.outputs().is_empty() {
+        code.push_str("\n\noutput {\n");
+        for (name, ty) in call.outputs() {
+            writeln!(&mut code, "    {ty} {name}", ty = ty.display(types)).unwrap();
+        }
+        code.push('}');
+    }
+
+    markdown(&code, None)
+}
+
+/// Gets the hover information for a struct.
+fn struct_hover(scope: &DocumentScope, name: &str) -> Option<String> {
+    let s = scope.struct_by_name(name)?;
+    let types = scope.types();
+    let ty = types.struct_type(s.ty()?)?;
+
+    let mut code = format!("struct {name} {{\n");
+    for (name, ty) in ty.members() {
+        writeln!(&mut code, "    {ty} {name}", ty = ty.display(types)).unwrap();
+    }
+    code.push('}');
+
+    let description = s
+        .namespace()
+        .map(|ns| format!("Imported from namespace `{ns}`."));
+    Some(markdown(&code, description))
+}
+
+/// Gets the hover information for a standard library function.
+fn function_hover(name: &str) -> Option<String> {
+    let mut code = String::new();
+    match STDLIB.function(name)? {
+        Function::Monomorphic(f) => {
+            let params = TypeParameters::new(f.signature().type_parameters());
+            write!(
+                &mut code,
+                "{name}{sig}",
+                sig = f.signature().display(STDLIB.types(), &params)
+            )
+            .unwrap();
+        }
+        Function::Polymorphic(f) => {
+            for (i, signature) in f.signatures().iter().enumerate() {
+                if i > 0 {
+                    code.push('\n');
+                }
+
+                let params = TypeParameters::new(signature.type_parameters());
+                write!(
+                    &mut code,
+                    "{name}{sig}",
+                    sig = signature.display(STDLIB.types(), &params)
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    Some(markdown(&code, None))
+}
+
+/// Gets the hover information for an input key of a call statement.
+fn call_input_hover(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    call: CallStatement,
+    name: &str,
+) -> Option<String> {
+    let mut names = call.target().names().collect::<Vec<_>>();
+    let callee = names.pop()?;
+    let (scope, uri) = match names.first() {
+        Some(ns) => {
+            let ns = result.scope().namespace(ns.as_str())?;
+            (ns.scope(), ns.source())
+        }
+        None => (result.scope().as_ref(), result.uri()),
+    };
+
+    let inputs = match scope.task_inputs(callee.as_str()) {
+        Some(inputs) => inputs,
+        None => scope
+            .workflow()
+            .filter(|(n, _)| *n == callee.as_str())
+            .and_then(|_| scope.workflow_inputs())?,
+    };
+
+    let input = inputs.get(name)?;
+    let description = results
+        .iter()
+        .find(|r| r.uri() == uri)
+        .and_then(|r| r.parse_result().document())
+        .and_then(|d| callee_input_description(d.syntax(), callee.as_str(), name));
+
+    Some(markdown(
+        &format!(
+            "(input) {ty} {name}",
+            ty = display_type(scope.types(), input.ty())
+        ),
+        description,
+    ))
+}
+
+/// Gets the hover information for the given position in a document.
+///
+/// The `results` are used to find the documents of imported namespaces.
+///
+/// Returns `Ok(None)` if there is no hover information at the position.
+pub fn hover(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    position: Position,
+) -> Result<Option<Hover>> {
+    let (Some(document), Some(lines)) = (
+        result.parse_result().document(),
+        result.parse_result().lines(),
+    ) else {
+        return Ok(None);
+    };
+
+    let root = document.syntax();
+    let offset = proto::offset(lines, position)?;
+    let Some(token) = ident_at(root, offset) else {
+        return Ok(None);
+    };
+
+    let Some(parent) = token.parent() else {
+        return Ok(None);
+    };
+
+    let scope = result.scope();
+    let name = token.text();
+    let offset = token.text_range().start().into();
+    let contents = match parent.kind() {
+        SyntaxKind::NameRefNode => name_hover(root, scope, offset, name),
+        SyntaxKind::UnboundDeclNode | SyntaxKind::BoundDeclNode => {
+            match parent.parent().map(|p| p.kind()) {
+                Some(SyntaxKind::StructDefinitionNode) => {
+                    let ty = parent
+                        .first_child()
+                        .map(|t| t.text().to_string())
+                        .unwrap_or_default();
+                    Some(markdown(&format!("(struct member) {ty} {name}"), None))
+                }
+                _ => name_hover(root, scope, offset, name),
+            }
+        }
+        SyntaxKind::ScatterStatementNode => {
+            // The scatter variable is only in scope within the braces of the statement
+            let brace = parent
+                .children_with_tokens()
+                .find(|c| c.kind() == SyntaxKind::OpenBrace);
+            brace.and_then(|b| name_hover(root, scope, b.text_range().end().into(), name))
+        }
+        SyntaxKind::CallExprNode => function_hover(name),
+        SyntaxKind::TypeRefNode | SyntaxKind::StructDefinitionNode => struct_hover(scope, name),
+        SyntaxKind::AccessExprNode => parent.first_child().and_then(|target| {
+            let ty = member_type(scope.types(), expr_type(scope, &target)?, name)?;
+            Some(markdown(
+                &format!("{ty} {name}", ty = ty.display(scope.types())),
+                None,
+            ))
+        }),
+        SyntaxKind::CallTargetNode | SyntaxKind::CallAliasNode => {
+            let is_namespace = parent.kind() == SyntaxKind::CallTargetNode
+                && token
+                    .siblings_with_tokens(rowan::Direction::Next)
+                    .any(|t| t.kind() == SyntaxKind::Dot);
+            if is_namespace {
+                scope.namespace(name).map(|ns| {
+                    markdown(
+                        &format!("(namespace) {name}"),
+                        Some(format!("Imported from `{uri}`.", uri = ns.source())),
+                    )
+                })
+            } else {
+                parent
+                    .parent()
+                    .and_then(CallStatement::cast)
+                    .and_then(|call| {
+                        let name = match call.alias() {
+                            Some(alias) => alias.name(),
+                            None => call.target().names().last()?,
+                        };
+                        let n = scope
+                            .find_scope_by_position(offset)?
+                            .lookup(name.as_str())?;
+                        Some(call_hover(scope.types(), name.as_str(), n.ty()?))
+                    })
+            }
+        }
+        SyntaxKind::CallInputItemNode => parent
+            .parent()
+            .and_then(CallStatement::cast)
+            .and_then(|call| call_input_hover(results, result, call, name)),
+        SyntaxKind::ImportStatementNode => scope.namespace(name).map(|ns| {
+            markdown(
+                &format!("(namespace) {name}"),
+                Some(format!("Imported from `{uri}`.", uri = ns.source())),
+            )
+        }),
+        _ => None,
+    };
+
+    Ok(contents.map(|value| Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: proto::range_from_span(lines, token.text_range().to_span()).ok(),
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::test_util::analyze;
+    use crate::test_util::position_of;
+
+    /// Gets the hover information at the start of the `nth` occurrence of
+    /// `needle` in the given source.
+    async fn hover_at(source: &str, needle: &str, nth: usize) -> Option<String> {
+        let (_dir, results) = analyze(&[("source.wdl", source)]).await;
+        let hover = hover(&results, &results[0], position_of(source, needle, nth))
+            .expect("should get hover")?;
+
+        match hover.contents {
+            HoverContents::Markup(content) => Some(content.value),
+            _ => panic!("expected markup content"),
+        }
+    }
+
+    const SOURCE: &str = r#"version 1.1
+
+struct Point {
+    Int x
+    Int y
+}
+
+task greet {
+    input {
+        String name
+        Point point
+    }
+
+    command <<<>>>
+
+    output {
+        String greeting = "hello ~{name}"
+    }
+
+    parameter_meta {
+        name: {
+            description: "The name to greet"
+        }
+    }
+}
+
+workflow test {
+    input {
+        Array[String] names
+    }
+
+    scatter (n in names) {
+        call greet { input: name = n, point = Point { x: 1, y: 2 } }
+    }
+
+    output {
+        Array[String] greetings = greet.greeting
+        Int count = length(names)
+    }
+}
+"#;
+
+    #[tokio::test]
+    async fn it_shows_input_descriptions() {
+        assert_eq!(
+            hover_at(SOURCE, "name\n", 0).await.as_deref(),
+            Some("```wdl\n(input) String name\n```\n\nThe name to greet")
+        );
+        assert_eq!(
+            hover_at(SOURCE, "name}", 0).await.as_deref(),
+            Some("```wdl\n(input) String name\n```\n\nThe name to greet")
+        );
+        assert_eq!(
+            hover_at(SOURCE, "name = n", 0).await.as_deref(),
+            Some("```wdl\n(input) String name\n```\n\nThe name to greet")
+        );
+    }
+
+    #[tokio::test]
+    async fn it_shows_name_types() {
+        assert_eq!(
+            hover_at(SOURCE, "names)", 0).await.as_deref(),
+            Some("```wdl\n(input) Array[String] names\n```")
+        );
+        assert_eq!(
+            hover_at(SOURCE, "n in", 0).await.as_deref(),
+            Some("```wdl\n(scatter variable) String n\n```")
+        );
+        assert_eq!(
+            hover_at(SOURCE, "greetings", 0).await.as_deref(),
+            Some("```wdl\n(output) Array[String] greetings\n```")
+        );
+        assert_eq!(
+            hover_at(SOURCE, "greeting\n", 0).await.as_deref(),
+            Some("```wdl\nArray[String] greeting\n```")
+        );
+    }
+
+    #[tokio::test]
+    async fn it_shows_calls_and_structs() {
+        assert_eq!(
+            hover_at(SOURCE, "greet {", 1).await.as_deref(),
+            Some("```wdl\n(call) greet: task greet\n\noutput {\n    String greeting\n}\n```")
+        );
+        assert_eq!(
+            hover_at(SOURCE, "Point point", 0).await.as_deref(),
+            Some("```wdl\nstruct Point {\n    Int x\n    Int y\n}\n```")
+        );
+    }
+
+    #[tokio::test]
+    async fn it_shows_function_signatures() {
+        assert_eq!(
+            hover_at(SOURCE, "length", 0).await.as_deref(),
+            Some(
+                "```wdl\nlength(Array[X]) -> Int\nlength(Map[K, V]) -> Int\nlength(Object) -> \
+                 Int\nlength(String) -> Int\n```"
+            )
+        );
+        assert_eq!(hover_at(SOURCE, "version", 0).await, None);
+    }
+}
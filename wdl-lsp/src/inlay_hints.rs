@@ -0,0 +1,307 @@
+//! Implementation of inlay hints.
+//!
+//! Inlay hints are provided for the element types of scatter variables, the
+//! promoted types of declarations in scatter and conditional statements, and
+//! the output types of calls.
+
+use anyhow::Result;
+use tower_lsp::lsp_types::InlayHint;
+use tower_lsp::lsp_types::InlayHintKind;
+use tower_lsp::lsp_types::InlayHintLabel;
+use tower_lsp::lsp_types::InlayHintTooltip;
+use tower_lsp::lsp_types::Range;
+use wdl_analysis::scope::DocumentScope;
+use wdl_analysis::types::CompoundTypeDef;
+use wdl_analysis::types::Type;
+use wdl_analysis::AnalysisResult;
+use wdl_ast::v1::CallStatement;
+use wdl_ast::v1::Decl;
+use wdl_ast::v1::ScatterStatement;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+
+use crate::proto;
+
+/// Represents the offset, label, tooltip, and whether or not to pad the left
+/// of an inlay hint.
+///
+/// A hint that is not padded on the left is padded on the right.
+type Hint = (usize, String, String, bool);
+
+/// Gets the outermost scatter or conditional statement enclosing the given
+/// node.
+fn outermost_block(node: &SyntaxNode) -> Option<SyntaxNode> {
+    node.ancestors()
+        .skip(1)
+        .take_while(|n| n.kind() != SyntaxKind::WorkflowDefinitionNode)
+        .filter(|n| {
+            matches!(
+                n.kind(),
+                SyntaxKind::ScatterStatementNode | SyntaxKind::ConditionalStatementNode
+            )
+        })
+        .last()
+}
+
+/// Gets the type of a name as visible at the given position.
+///
+/// Returns `None` if the type is indeterminate.
+fn name_type(scope: &DocumentScope, position: usize, name: &str) -> Option<Type> {
+    scope
+        .find_scope_by_position(position)?
+        .lookup(name)?
+        .ty()
+        .filter(|ty| *ty != Type::Union)
+}
+
+/// Gets the position at which the names declared in the given node are
+/// visible with their promoted types.
+///
+/// This is the start of the outermost enclosing scatter or conditional
+/// statement, which is in the scope containing the statement; otherwise, it
+/// is the start of the node itself.
+fn visible_position(node: &SyntaxNode) -> usize {
+    outermost_block(node)
+        .unwrap_or_else(|| node.clone())
+        .text_range()
+        .start()
+        .into()
+}
+
+/// Gets the inlay hints of a document.
+///
+/// Only the hints within the given range are returned.
+///
+/// Returns `Ok(None)` if the document could not be parsed.
+pub fn inlay_hints(result: &AnalysisResult, range: Range) -> Result<Option<Vec<InlayHint>>> {
+    let (Some(document), Some(lines)) = (
+        result.parse_result().document(),
+        result.parse_result().lines(),
+    ) else {
+        return Ok(None);
+    };
+
+    let start = proto::offset(lines, range.start)?;
+    let end = proto::offset(lines, range.end)?;
+    let scope = result.scope();
+
+    let mut hints = Vec::new();
+    let nodes = document.syntax().descendants().filter(|n| {
+        let range = n.text_range();
+        usize::from(range.end()) >= start && usize::from(range.start()) <= end
+    });
+
+    for node in nodes {
+        let hint = match node.kind() {
+            SyntaxKind::ScatterStatementNode => scatter_hint(scope, &node),
+            SyntaxKind::BoundDeclNode | SyntaxKind::UnboundDeclNode => decl_hint(scope, &node),
+            SyntaxKind::CallStatementNode => call_hint(scope, &node),
+            _ => None,
+        };
+
+        let Some((offset, label, tooltip, padding_left)) = hint else {
+            continue;
+        };
+
+        if offset < start || offset > end {
+            continue;
+        }
+
+        hints.push(InlayHint {
+            position: proto::position(lines, offset)?,
+            label: InlayHintLabel::String(label),
+            kind: Some(InlayHintKind::TYPE),
+            text_edits: None,
+            tooltip: Some(InlayHintTooltip::String(tooltip)),
+            padding_left: Some(padding_left),
+            padding_right: Some(!padding_left),
+            data: None,
+        });
+    }
+
+    Ok(Some(hints))
+}
+
+/// Gets the inlay hint for the element type of a scatter variable.
+///
+/// The hint is placed before the variable, like the type of a declaration.
+fn scatter_hint(scope: &DocumentScope, node: &SyntaxNode) -> Option<Hint> {
+    let statement = ScatterStatement::cast(node.clone())?;
+    let variable = statement.variable();
+
+    // The variable is only in scope within the braces of the statement
+    let brace = node
+        .children_with_tokens()
+        .find(|c| c.kind() == SyntaxKind::OpenBrace)?;
+    let ty = name_type(scope, brace.text_range().end().into(), variable.as_str())?;
+
+    Some((
+        variable.span().start(),
+        ty.display(scope.types()).to_string(),
+        format!(
+            "the type of the elements of the scatter expression bound to `{name}`",
+            name = variable.as_str()
+        ),
+        false,
+    ))
+}
+
+/// Gets the inlay hint for the promoted type of a declaration in a scatter or
+/// conditional statement.
+///
+/// The hint is placed at the end of the declaration.
+fn decl_hint(scope: &DocumentScope, node: &SyntaxNode) -> Option<Hint> {
+    outermost_block(node)?;
+
+    let decl = Decl::cast(node.clone())?;
+    let name = decl.name();
+    let ty = name_type(scope, visible_position(node), name.as_str())?;
+
+    Some((
+        node.text_range().end().into(),
+        format!("-> {ty}", ty = ty.display(scope.types())),
+        format!(
+            "the type of `{name}` outside of the enclosing statement",
+            name = name.as_str()
+        ),
+        true,
+    ))
+}
+
+/// Gets the inlay hint for the output types of a call.
+///
+/// If the call is in a scatter or conditional statement, the promoted output
+/// types are used.
+///
+/// The hint is placed at the end of the call statement.
+fn call_hint(scope: &DocumentScope, node: &SyntaxNode) -> Option<Hint> {
+    let statement = CallStatement::cast(node.clone())?;
+    let name = match statement.alias() {
+        Some(alias) => alias.name(),
+        None => statement.target().names().last()?,
+    };
+
+    let Type::Compound(ty) = name_type(scope, visible_position(node), name.as_str())? else {
+        return None;
+    };
+
+    let CompoundTypeDef::Call(ty) = scope.types().type_definition(ty.definition()) else {
+        return None;
+    };
+
+    if ty.outputs().is_empty() {
+        return None;
+    }
+
+    let outputs = ty
+        .outputs()
+        .iter()
+        .map(|(name, ty)| format!("{ty} {name}", ty = ty.display(scope.types())))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let tooltip = if outermost_block(node).is_some() {
+        format!(
+            "the outputs of `{name}` outside of the enclosing statement",
+            name = name.as_str()
+        )
+    } else {
+        format!("the outputs of `{name}`", name = name.as_str())
+    };
+
+    Some((
+        node.text_range().end().into(),
+        format!("-> {outputs}"),
+        tooltip,
+        true,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use tower_lsp::lsp_types::Position;
+
+    use super::*;
+    use crate::test_util::analyze;
+
+    /// The source of the document used in the tests.
+    const SOURCE: &str = r#"version 1.1
+
+task greet {
+    input {
+        String name
+    }
+
+    command <<<>>>
+
+    output {
+        String greeting = "hello ~{name}"
+        Int length = 0
+    }
+}
+
+workflow test {
+    input {
+        Array[String] names
+    }
+
+    scatter (name in names) {
+        String upper = name
+        call greet { input: name }
+
+        if (true) {
+            Int n = 1
+        }
+    }
+
+    call greet as single { input: name = "world" }
+}
+"#;
+
+    /// Gets the line, character, and label of the inlay hints in the given
+    /// range of the test document.
+    async fn hints(range: Range) -> Vec<(u32, u32, String)> {
+        let (_dir, results) = analyze(&[("source.wdl", SOURCE)]).await;
+        inlay_hints(&results[0], range)
+            .expect("should get inlay hints")
+            .expect("should have inlay hints")
+            .into_iter()
+            .map(|h| match h.label {
+                InlayHintLabel::String(label) => (h.position.line, h.position.character, label),
+                InlayHintLabel::LabelParts(_) => panic!("expected a string label"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn it_provides_hints() {
+        assert_eq!(
+            hints(Range::new(Position::new(0, 0), Position::new(31, 0))).await,
+            [
+                (20, 13, "String".to_string()),
+                (21, 27, "-> Array[String]".to_string()),
+                (
+                    22,
+                    34,
+                    "-> Array[String] greeting, Array[Int] length".to_string()
+                ),
+                (25, 21, "-> Array[Int?]".to_string()),
+                (29, 50, "-> String greeting, Int length".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_provides_hints_in_range() {
+        assert_eq!(
+            hints(Range::new(Position::new(24, 0), Position::new(30, 0))).await,
+            [
+                (25, 21, "-> Array[Int?]".to_string()),
+                (29, 50, "-> String greeting, Int length".to_string()),
+            ]
+        );
+    }
+}
@@ -7,7 +7,19 @@
 #![warn(clippy::missing_docs_in_private_items)]
 #![warn(rustdoc::broken_intra_doc_links)]
 
+mod code_action;
+mod completion;
+mod definition;
+mod hover;
+mod inlay_hints;
 mod proto;
+mod rename;
+mod semantic_tokens;
 mod server;
+mod signature_help;
+mod symbols;
+mod syntax;
+#[cfg(test)]
+mod test_util;
 
 pub use server::*;
@@ -0,0 +1,870 @@
+//! Implementation of rename.
+//!
+//! A rename replaces every reference to the definition of the renamed name,
+//! including references from other documents in the workspace.
+//!
+//! A rename is refused if it would introduce a conflicting name.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::bail;
+use anyhow::Result;
+use tower_lsp::lsp_types::Position;
+use tower_lsp::lsp_types::PrepareRenameResponse;
+use tower_lsp::lsp_types::TextEdit;
+use tower_lsp::lsp_types::WorkspaceEdit;
+use url::Url;
+use wdl_analysis::scope::Name;
+use wdl_analysis::scope::ScopeRef;
+use wdl_analysis::AnalysisResult;
+use wdl_ast::v1::CallStatement;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::Span;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::SyntaxToken;
+use wdl_ast::ToSpan;
+use wdl_grammar::lexer::v1::Logos;
+use wdl_grammar::lexer::v1::Token;
+
+use crate::definition::decl_name;
+use crate::definition::find_result;
+use crate::definition::find_section_decl;
+use crate::definition::references;
+use crate::definition::resolve;
+use crate::definition::resolve_callee;
+use crate::definition::resolve_struct;
+use crate::definition::v1_ast;
+use crate::definition::Definition;
+use crate::proto;
+use crate::syntax::ident_at;
+
+/// Represents the target of a rename.
+#[derive(Debug)]
+struct Target {
+    /// The definition being renamed.
+    definition: Definition,
+    /// The current name being renamed.
+    name: String,
+    /// The document introducing a struct alias being renamed.
+    ///
+    /// When renaming a struct alias, only the alias and its uses in the
+    /// importing document are renamed.
+    alias: Option<Arc<Url>>,
+}
+
+/// Represents the text edits of a rename.
+///
+/// Edits are keyed by document and span so that an edit of a reference may be
+/// replaced by a more specific edit.
+type Edits = BTreeMap<(Arc<Url>, usize, usize), String>;
+
+/// Determines if the given name is a valid WDL identifier.
+fn is_ident(name: &str) -> bool {
+    let mut lexer = Token::lexer(name);
+    matches!(lexer.next(), Some(Ok(Token::Ident))) && lexer.next().is_none()
+}
+
+/// Gets the token of the name of the call introduced by a call statement.
+fn call_name(call: &CallStatement) -> Option<SyntaxToken> {
+    match call.alias() {
+        Some(alias) => Some(alias.name().syntax().clone()),
+        None => call.target().names().last().map(|n| n.syntax().clone()),
+    }
+}
+
+/// Finds a name in the given scope or its child scopes.
+fn find_descendant(scope: ScopeRef<'_>, name: &str) -> Option<Name> {
+    scope
+        .names()
+        .find_map(|(n, found)| (n == name).then_some(found))
+        .or_else(|| scope.children().find_map(|c| find_descendant(c, name)))
+}
+
+/// Finds a name that would conflict with a name introduced in the given scope.
+///
+/// As names cannot be shadowed, both the parent scopes and the child scopes
+/// are searched.
+fn find_conflict(scope: ScopeRef<'_>, name: &str) -> Option<Name> {
+    scope
+        .lookup(name)
+        .or_else(|| scope.children().find_map(|c| find_descendant(c, name)))
+}
+
+/// Gets the target of a rename of the given identifier token.
+///
+/// Returns `None` if the token cannot be renamed.
+fn target(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    token: &SyntaxToken,
+) -> Option<Target> {
+    let definition = resolve(results, result, token)?;
+
+    // Only definitions named by an identifier can be renamed (e.g. a namespace
+    // derived from an import URI cannot be)
+    let defined = find_result(results, &definition.uri)?;
+    let name = ident_at(
+        defined.parse_result().document()?.syntax(),
+        definition.span.start(),
+    )?;
+    if name.text_range().to_span() != definition.span {
+        return None;
+    }
+
+    // A name that differs from its definition is a struct alias
+    if token.text() != name.text() {
+        return Some(Target {
+            definition,
+            name: token.text().to_string(),
+            alias: Some(result.uri().clone()),
+        });
+    }
+
+    Some(Target {
+        definition,
+        name: name.text().to_string(),
+        alias: None,
+    })
+}
+
+/// Prepares a rename of the identifier at the given position in a document.
+///
+/// Returns `Ok(None)` if there is no identifier at the position that can be
+/// renamed.
+pub fn prepare_rename(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    position: Position,
+) -> Result<Option<PrepareRenameResponse>> {
+    let (Some(document), Some(lines)) = (
+        result.parse_result().document(),
+        result.parse_result().lines(),
+    ) else {
+        return Ok(None);
+    };
+
+    let offset = proto::offset(lines, position)?;
+    let Some(token) = ident_at(document.syntax(), offset) else {
+        return Ok(None);
+    };
+
+    if target(results, result, &token).is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(PrepareRenameResponse::Range(proto::range_from_span(
+        lines,
+        token.text_range().to_span(),
+    )?)))
+}
+
+/// Renames the identifier at the given position in a document.
+///
+/// References are renamed in all of the given results.
+///
+/// Returns an error if the new name is not a valid identifier or if the
+/// rename would introduce a conflicting name.
+///
+/// Returns `Ok(None)` if there is no identifier at the position that can be
+/// renamed.
+pub fn rename(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    position: Position,
+    new_name: &str,
+) -> Result<Option<WorkspaceEdit>> {
+    if !is_ident(new_name) {
+        bail!("`{new_name}` is not a valid WDL identifier");
+    }
+
+    let (Some(document), Some(lines)) = (
+        result.parse_result().document(),
+        result.parse_result().lines(),
+    ) else {
+        return Ok(None);
+    };
+
+    let offset = proto::offset(lines, position)?;
+    let Some(target) =
+        ident_at(document.syntax(), offset).and_then(|t| target(results, result, &t))
+    else {
+        return Ok(None);
+    };
+
+    let mut edits = Edits::new();
+    if target.name != new_name {
+        match &target.alias {
+            Some(uri) => rename_alias(results, &target, uri, new_name, &mut edits)?,
+            None => rename_definition(results, &target, new_name, &mut edits)?,
+        }
+    }
+
+    let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    for ((uri, start, end), text) in edits {
+        let Some(lines) = find_result(results, &uri).and_then(|r| r.parse_result().lines()) else {
+            continue;
+        };
+
+        changes
+            .entry(uri.as_ref().clone())
+            .or_default()
+            .push(TextEdit::new(
+                proto::range_from_span(lines, Span::new(start, end - start))?,
+                text,
+            ));
+    }
+
+    Ok(Some(WorkspaceEdit::new(changes)))
+}
+
+/// Adds an edit for the given reference.
+fn add_edit(edits: &mut Edits, reference: &Definition, text: String) {
+    edits.insert(
+        (
+            reference.uri.clone(),
+            reference.span.start(),
+            reference.span.end(),
+        ),
+        text,
+    );
+}
+
+/// Renames a struct alias introduced by an import.
+fn rename_alias(
+    results: &[AnalysisResult],
+    target: &Target,
+    uri: &Arc<Url>,
+    new_name: &str,
+    edits: &mut Edits,
+) -> Result<()> {
+    let Some(result) = find_result(results, uri) else {
+        return Ok(());
+    };
+
+    if result.scope().struct_by_name(new_name).is_some() {
+        bail!(
+            "cannot rename `{name}` to `{new_name}` as a struct named `{new_name}` already exists",
+            name = target.name
+        );
+    }
+
+    for (result, token) in references(results, &target.definition, &target.name) {
+        if result.uri() == uri && token.text() == target.name {
+            add_edit(
+                edits,
+                &Definition::new(result, token.text_range().to_span()),
+                new_name.to_string(),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Renames a definition and all of its references.
+fn rename_definition(
+    results: &[AnalysisResult],
+    target: &Target,
+    new_name: &str,
+    edits: &mut Edits,
+) -> Result<()> {
+    let name = &target.name;
+    let Some(defined) = find_result(results, &target.definition.uri) else {
+        return Ok(());
+    };
+
+    let Some(node) = defined
+        .parse_result()
+        .document()
+        .and_then(|d| ident_at(d.syntax(), target.definition.span.start()))
+        .and_then(|t| t.parent())
+    else {
+        return Ok(());
+    };
+
+    // Calls without an alias are named after the task or workflow being called, so
+    // they are renamed along with it
+    let mut definitions = vec![target.definition.clone()];
+    match node.kind() {
+        SyntaxKind::TaskDefinitionNode | SyntaxKind::WorkflowDefinitionNode => {
+            let scope = defined.scope();
+            if scope.task_by_name(new_name).is_some()
+                || scope
+                    .workflow()
+                    .map(|(n, _)| n == new_name)
+                    .unwrap_or(false)
+            {
+                bail!(
+                    "cannot rename `{name}` to `{new_name}` as a task or workflow named \
+                     `{new_name}` already exists"
+                );
+            }
+
+            for result in results {
+                for call in calls(result).filter(|c| c.alias().is_none()) {
+                    let Some(token) = call_name(&call) else {
+                        continue;
+                    };
+
+                    if resolve(results, result, &token).as_ref() != Some(&target.definition) {
+                        continue;
+                    }
+
+                    let span = token.text_range().to_span();
+                    if let Some(prev) = result
+                        .scope()
+                        .find_scope_by_position(span.start())
+                        .and_then(|s| find_conflict(s, new_name))
+                    {
+                        bail!(
+                            "cannot rename `{name}` to `{new_name}` as a call to it would \
+                             conflict with the {context} named `{new_name}`",
+                            context = prev.context()
+                        );
+                    }
+
+                    definitions.push(Definition::new(result, span));
+                }
+            }
+        }
+        SyntaxKind::StructDefinitionNode => {
+            for result in results {
+                if result.scope().struct_by_name(new_name).is_some()
+                    && resolve_struct(results, result, name).as_ref() == Some(&target.definition)
+                {
+                    bail!(
+                        "cannot rename `{name}` to `{new_name}` as a struct named `{new_name}` \
+                         already exists"
+                    );
+                }
+            }
+        }
+        SyntaxKind::ImportStatementNode => {
+            if defined.scope().namespace(new_name).is_some() {
+                bail!(
+                    "cannot rename `{name}` to `{new_name}` as an import namespace named \
+                     `{new_name}` already exists"
+                );
+            }
+        }
+        SyntaxKind::UnboundDeclNode | SyntaxKind::BoundDeclNode
+            if node
+                .parent()
+                .map(|p| p.kind() == SyntaxKind::StructDefinitionNode)
+                .unwrap_or(false) =>
+        {
+            if node
+                .parent()
+                .into_iter()
+                .flat_map(|p| p.children())
+                .filter_map(|d| decl_name(&d))
+                .any(|t| t.text() == new_name)
+            {
+                bail!(
+                    "cannot rename `{name}` to `{new_name}` as a struct member named `{new_name}` \
+                     already exists"
+                );
+            }
+        }
+        kind => {
+            // The scatter variable is introduced into the scope of the scatter's body
+            let offset = if kind == SyntaxKind::ScatterStatementNode {
+                node.children_with_tokens()
+                    .find(|c| c.kind() == SyntaxKind::OpenBrace)
+                    .map(|c| c.text_range().to_span().end())
+                    .unwrap_or(target.definition.span.start())
+            } else {
+                target.definition.span.start()
+            };
+
+            if let Some(prev) = defined
+                .scope()
+                .find_scope_by_position(offset)
+                .and_then(|s| find_conflict(s, new_name))
+            {
+                bail!(
+                    "cannot rename `{name}` to `{new_name}` as it would conflict with the \
+                     {context} named `{new_name}`",
+                    context = prev.context()
+                );
+            }
+        }
+    }
+
+    for definition in &definitions {
+        for (result, token) in references(results, definition, name) {
+            if token.text() == name {
+                add_edit(
+                    edits,
+                    &Definition::new(result, token.text_range().to_span()),
+                    new_name.to_string(),
+                );
+            }
+        }
+    }
+
+    rename_call_inputs(results, &definitions, name, new_name, edits);
+    rename_dotted_names(results, &definitions, name, new_name, edits);
+    Ok(())
+}
+
+/// Gets the call statements of a document.
+fn calls(result: &AnalysisResult) -> impl Iterator<Item = CallStatement> {
+    result
+        .parse_result()
+        .document()
+        .map(|d| d.syntax().clone())
+        .into_iter()
+        .flat_map(|root| {
+            root.descendants()
+                .filter_map(CallStatement::cast)
+                .collect::<Vec<_>>()
+        })
+}
+
+/// Renames call inputs that omit their expression (e.g. `call foo { input: x
+/// }`).
+///
+/// Such an input refers to both the callee's input and the name in scope, so
+/// the input is expanded into an explicit assignment of the name.
+fn rename_call_inputs(
+    results: &[AnalysisResult],
+    definitions: &[Definition],
+    name: &str,
+    new_name: &str,
+    edits: &mut Edits,
+) {
+    for result in results {
+        for call in calls(result) {
+            for input in call.inputs().filter(|i| i.expr().is_none()) {
+                let token = input.name().syntax().clone();
+                if token.text() != name {
+                    continue;
+                }
+
+                let reference = Definition::new(result, token.text_range().to_span());
+                let callee = resolve(results, result, &token);
+                let local = result
+                    .scope()
+                    .find_scope_by_position(reference.span.start())
+                    .and_then(|s| s.lookup(name))
+                    .map(|n| Definition::new(result, n.context().span()));
+
+                if callee.map(|d| definitions.contains(&d)).unwrap_or(false) {
+                    add_edit(edits, &reference, format!("{new_name} = {name}"));
+                } else if local.map(|d| definitions.contains(&d)).unwrap_or(false) {
+                    add_edit(edits, &reference, format!("{name} = {new_name}"));
+                }
+            }
+        }
+    }
+}
+
+/// Renames the segments of dotted names in `meta` and `parameter_meta` string
+/// values.
+///
+/// Dotted names are the names used to specify inputs in an inputs file (e.g.
+/// `workflow.call.input`).
+fn rename_dotted_names(
+    results: &[AnalysisResult],
+    definitions: &[Definition],
+    name: &str,
+    new_name: &str,
+    edits: &mut Edits,
+) {
+    for result in results {
+        let Some(document) = result.parse_result().document() else {
+            continue;
+        };
+
+        for text in document
+            .syntax()
+            .descendants()
+            .filter(|n| n.kind() == SyntaxKind::LiteralStringNode && is_metadata(n))
+            .filter_map(|n| {
+                // Only consider strings without escapes or placeholders
+                let mut parts = n.children_with_tokens().filter(|c| {
+                    !matches!(c.kind(), SyntaxKind::DoubleQuote | SyntaxKind::SingleQuote)
+                });
+                match (parts.next(), parts.next()) {
+                    (Some(part), None) if part.kind() == SyntaxKind::LiteralStringText => {
+                        part.into_token()
+                    }
+                    _ => None,
+                }
+            })
+        {
+            let start = text.text_range().to_span().start();
+            for (offset, segment, definition) in resolve_dotted_name(results, result, text.text()) {
+                if segment == name && definitions.contains(&definition) {
+                    add_edit(
+                        edits,
+                        &Definition::new(result, Span::new(start + offset, segment.len())),
+                        new_name.to_string(),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Determines if a node is within a `meta` or `parameter_meta` section.
+fn is_metadata(node: &SyntaxNode) -> bool {
+    node.ancestors().any(|a| {
+        matches!(
+            a.kind(),
+            SyntaxKind::MetadataSectionNode | SyntaxKind::ParameterMetadataSectionNode
+        )
+    })
+}
+
+/// Resolves the segments of a dotted name relative to the given document.
+///
+/// The first segment names a task or workflow in the document, any following
+/// segments name calls, and the last segment names an input.
+///
+/// Returns the offset, text, and definition of each segment that resolved.
+fn resolve_dotted_name<'a>(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    name: &'a str,
+) -> Vec<(usize, &'a str, Definition)> {
+    let mut resolved = Vec::new();
+    let mut segments = name.split('.').scan(0, |offset, segment| {
+        let start = *offset;
+        *offset += segment.len() + 1;
+        Some((start, segment))
+    });
+
+    let Some((_, first)) = segments.next() else {
+        return resolved;
+    };
+
+    let Some(ast) = v1_ast(result) else {
+        return resolved;
+    };
+
+    let Some((node, span)) = ast
+        .tasks()
+        .find(|t| t.name().as_str() == first)
+        .map(|t| (t.syntax().clone(), t.name().span()))
+        .or_else(|| {
+            ast.workflows()
+                .find(|w| w.name().as_str() == first)
+                .map(|w| (w.syntax().clone(), w.name().span()))
+        })
+    else {
+        return resolved;
+    };
+
+    resolved.push((0, first, Definition::new(result, span)));
+
+    let mut current = (result, node);
+    let mut segments = segments.peekable();
+    while let Some((offset, segment)) = segments.next() {
+        let (result, node) = &current;
+        if segments.peek().is_none() {
+            if let Some(span) = find_section_decl(node, SyntaxKind::InputSectionNode, segment) {
+                resolved.push((offset, segment, Definition::new(result, span)));
+            }
+
+            break;
+        }
+
+        let Some((call, token)) =
+            node.descendants()
+                .filter_map(CallStatement::cast)
+                .find_map(|c| {
+                    let token = call_name(&c)?;
+                    (token.text() == segment).then_some((c, token))
+                })
+        else {
+            break;
+        };
+
+        resolved.push((
+            offset,
+            segment,
+            Definition::new(result, token.text_range().to_span()),
+        ));
+
+        match resolve_callee(results, result, &call.target()) {
+            Some(callee) => current = callee,
+            None => break,
+        }
+    }
+
+    resolved
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::test_util;
+    use crate::test_util::offset_of;
+    use crate::test_util::position_of;
+    use crate::test_util::result;
+
+    const LIB: &str = r#"version 1.1
+
+struct Point {
+    Int x
+    Int y
+}
+
+task greet {
+    meta {
+        example: "greet.name"
+    }
+
+    input {
+        String name
+        Point? point
+    }
+
+    command <<<
+        echo "~{name}"
+    >>>
+
+    output {
+        String greeting = "hello ~{name}"
+    }
+
+    parameter_meta {
+        name: "The name to greet"
+    }
+}
+"#;
+
+    const MAIN: &str = r#"version 1.1
+
+import "lib.wdl" as lib alias Point as Coord
+
+workflow test {
+    meta {
+        example: "test.greet.name"
+    }
+
+    input {
+        String name
+        Coord origin = Coord { x: 0, y: 0 }
+    }
+
+    call lib.greet { input: name }
+    call lib.greet as again { input: name = name }
+
+    output {
+        String greeting = greet.greeting
+        Int x = origin.x
+    }
+}
+"#;
+
+    /// Analyzes the test documents, returning the temporary directory
+    /// containing them and the analysis results.
+    async fn analyze() -> (TempDir, Vec<AnalysisResult>) {
+        test_util::analyze(&[("lib.wdl", LIB), ("main.wdl", MAIN)]).await
+    }
+
+    /// Gets the source of the given test document.
+    fn source(name: &str) -> &'static str {
+        if name == "lib.wdl" {
+            LIB
+        } else {
+            MAIN
+        }
+    }
+
+    /// Renames the `nth` occurrence of `needle` in the given document.
+    ///
+    /// Returns the names and edited sources of the changed documents.
+    fn rename_at(
+        results: &[AnalysisResult],
+        name: &str,
+        needle: &str,
+        nth: usize,
+        new_name: &str,
+    ) -> Result<Vec<(String, String)>> {
+        let edit = rename(
+            results,
+            result(results, name),
+            position_of(source(name), needle, nth),
+            new_name,
+        )?
+        .expect("should rename");
+
+        let mut changed = Vec::new();
+        for (uri, mut edits) in edit.changes.expect("should have changes") {
+            let name = uri
+                .path_segments()
+                .and_then(|mut s| s.next_back())
+                .expect("should have file name")
+                .to_string();
+            let mut source = source(&name).to_string();
+            edits.sort_by_key(|e| (e.range.start.line, e.range.start.character));
+            for edit in edits.iter().rev() {
+                let start = offset_of(&source, edit.range.start);
+                let end = offset_of(&source, edit.range.end);
+                source.replace_range(start..end, &edit.new_text);
+            }
+
+            changed.push((name, source));
+        }
+
+        changed.sort();
+        Ok(changed)
+    }
+
+    #[tokio::test]
+    async fn it_renames_task_inputs() {
+        let (_dir, results) = analyze().await;
+        let changed =
+            rename_at(&results, "lib.wdl", "name\n", 0, "who").expect("should rename input");
+        assert_eq!(
+            changed,
+            [
+                (
+                    "lib.wdl".to_string(),
+                    LIB.replace("\"greet.name\"", "\"greet.who\"")
+                        .replace("String name", "String who")
+                        .replace("~{name}", "~{who}")
+                        .replace("name: \"The", "who: \"The")
+                ),
+                (
+                    "main.wdl".to_string(),
+                    MAIN.replace("\"test.greet.name\"", "\"test.greet.who\"")
+                        .replace("input: name }", "input: who = name }")
+                        .replace("input: name = name", "input: who = name")
+                )
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_renames_local_names_in_call_inputs() {
+        let (_dir, results) = analyze().await;
+        let changed = rename_at(&results, "main.wdl", "name\n", 0, "who")
+            .expect("should rename workflow input");
+        assert_eq!(
+            changed,
+            [(
+                "main.wdl".to_string(),
+                MAIN.replace("String name", "String who")
+                    .replace("input: name }", "input: name = who }")
+                    .replace("input: name = name", "input: name = who")
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_renames_tasks_and_their_calls() {
+        let (_dir, results) = analyze().await;
+        let changed =
+            rename_at(&results, "lib.wdl", "greet {", 0, "hello").expect("should rename task");
+        assert_eq!(
+            changed,
+            [
+                (
+                    "lib.wdl".to_string(),
+                    LIB.replace("task greet", "task hello")
+                        .replace("\"greet.name\"", "\"hello.name\"")
+                ),
+                (
+                    "main.wdl".to_string(),
+                    MAIN.replace("\"test.greet.name\"", "\"test.hello.name\"")
+                        .replace("lib.greet", "lib.hello")
+                        .replace("greet.greeting", "hello.greeting")
+                )
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_renames_structs_and_aliases() {
+        let (_dir, results) = analyze().await;
+        let changed =
+            rename_at(&results, "lib.wdl", "Point {", 0, "Location").expect("should rename struct");
+        assert_eq!(
+            changed,
+            [
+                ("lib.wdl".to_string(), LIB.replace("Point", "Location")),
+                (
+                    "main.wdl".to_string(),
+                    MAIN.replace("alias Point", "alias Location")
+                )
+            ]
+        );
+
+        let changed = rename_at(&results, "main.wdl", "Coord origin", 0, "Origin")
+            .expect("should rename alias");
+        assert_eq!(
+            changed,
+            [("main.wdl".to_string(), MAIN.replace("Coord", "Origin"))]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_refuses_conflicting_renames() {
+        let (_dir, results) = analyze().await;
+        let error = |name, needle, new_name| {
+            rename_at(&results, name, needle, 0, new_name)
+                .expect_err("rename should fail")
+                .to_string()
+        };
+
+        assert_eq!(
+            error("main.wdl", "name\n", "origin"),
+            "cannot rename `name` to `origin` as it would conflict with the input named `origin`"
+        );
+        assert_eq!(
+            error("main.wdl", "again", "greet"),
+            "cannot rename `again` to `greet` as it would conflict with the call named `greet`"
+        );
+        assert_eq!(
+            error("lib.wdl", "greet {", "again"),
+            "cannot rename `greet` to `again` as a call to it would conflict with the call named \
+             `again`"
+        );
+        assert_eq!(
+            error("lib.wdl", "x\n", "y"),
+            "cannot rename `x` to `y` as a struct member named `y` already exists"
+        );
+        assert_eq!(
+            error("lib.wdl", "name\n", "input"),
+            "`input` is not a valid WDL identifier"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_prepares_renames() {
+        let (_dir, results) = analyze().await;
+        let prepare = |name, needle| {
+            prepare_rename(
+                &results,
+                result(&results, name),
+                position_of(source(name), needle, 0),
+            )
+            .expect("should prepare rename")
+        };
+
+        let start = position_of(MAIN, "origin.x", 0);
+        assert_eq!(
+            prepare("main.wdl", "origin.x"),
+            Some(PrepareRenameResponse::Range(
+                tower_lsp::lsp_types::Range::new(
+                    start,
+                    Position::new(start.line, start.character + 6)
+                )
+            ))
+        );
+        assert_eq!(prepare("main.wdl", "Int x"), None);
+        assert_eq!(prepare("main.wdl", "version"), None);
+    }
+}
@@ -0,0 +1,531 @@
+//! Implementation of semantic tokens for WDL documents.
+
+use anyhow::Result;
+use line_index::LineIndex;
+use tower_lsp::lsp_types::Range;
+use tower_lsp::lsp_types::SemanticToken;
+use tower_lsp::lsp_types::SemanticTokenModifier;
+use tower_lsp::lsp_types::SemanticTokenType;
+use tower_lsp::lsp_types::SemanticTokens;
+use tower_lsp::lsp_types::SemanticTokensLegend;
+use wdl_analysis::scope::NameContext;
+use wdl_analysis::stdlib::STDLIB;
+use wdl_analysis::AnalysisResult;
+use wdl_ast::AstNode;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::SyntaxToken;
+
+use crate::proto;
+
+/// Represents the type of a semantic token.
+///
+/// The discriminant of a variant is its index in the legend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenType {
+    /// The token is a keyword.
+    Keyword,
+    /// The token is a primitive or compound type keyword.
+    Type,
+    /// The token is the name of a struct.
+    Struct,
+    /// The token is an import namespace.
+    Namespace,
+    /// The token is the name of a task, workflow, or standard library
+    /// function.
+    Function,
+    /// The token is the name of a call.
+    Method,
+    /// The token is the name of an input.
+    Parameter,
+    /// The token is the name of an output, a struct member, or a key.
+    ///
+    /// Outputs are treated as properties as they are accessed as members of
+    /// calls.
+    Property,
+    /// The token is the name of a private declaration or scatter variable.
+    Variable,
+    /// The token is a string or command text.
+    String,
+    /// The token is a number.
+    Number,
+    /// The token is a comment.
+    Comment,
+    /// The token is an operator.
+    Operator,
+    /// The token delimits a placeholder.
+    Macro,
+}
+
+/// The token types of the legend, in the order of [`TokenType`].
+const TOKEN_TYPES: &[SemanticTokenType] = &[
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::STRUCT,
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::METHOD,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::STRING,
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::COMMENT,
+    SemanticTokenType::OPERATOR,
+    SemanticTokenType::MACRO,
+];
+
+/// The modifier for a token that declares a name.
+const DECLARATION: u32 = 1 << 0;
+
+/// The modifier for a token that refers to the standard library.
+const DEFAULT_LIBRARY: u32 = 1 << 1;
+
+/// The token modifiers of the legend, in the order of their bits.
+const TOKEN_MODIFIERS: &[SemanticTokenModifier] = &[
+    SemanticTokenModifier::DECLARATION,
+    SemanticTokenModifier::DEFAULT_LIBRARY,
+];
+
+/// Gets the legend of the semantic tokens provided by the server.
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+/// Gets the token type of a declaration's name.
+fn decl_type(decl: &SyntaxNode) -> TokenType {
+    match decl.parent().map(|p| p.kind()) {
+        Some(SyntaxKind::InputSectionNode) => TokenType::Parameter,
+        Some(SyntaxKind::OutputSectionNode) | Some(SyntaxKind::StructDefinitionNode) => {
+            TokenType::Property
+        }
+        _ => TokenType::Variable,
+    }
+}
+
+/// Gets the token type of a name introduced into a scope.
+fn context_type(context: NameContext) -> (TokenType, u32) {
+    match context {
+        NameContext::Input(_) => (TokenType::Parameter, 0),
+        NameContext::Output(_) => (TokenType::Property, 0),
+        NameContext::Decl(_) | NameContext::ScatterVariable(_) => (TokenType::Variable, 0),
+        NameContext::Call(_) => (TokenType::Method, 0),
+        NameContext::Task(_) => (TokenType::Variable, DEFAULT_LIBRARY),
+    }
+}
+
+/// Classifies an identifier token based on its parent node and the scope it
+/// is in.
+fn classify_ident(result: &AnalysisResult, token: &SyntaxToken) -> Option<(TokenType, u32)> {
+    let parent = token.parent()?;
+    let lookup = || {
+        result
+            .scope()
+            .find_scope_by_position(token.text_range().start().into())
+            .and_then(|s| s.lookup(token.text()))
+            .map(|n| n.context())
+    };
+
+    Some(match parent.kind() {
+        SyntaxKind::UnboundDeclNode | SyntaxKind::BoundDeclNode => {
+            (decl_type(&parent), DECLARATION)
+        }
+        SyntaxKind::NameRefNode => lookup()
+            .map(context_type)
+            .unwrap_or((TokenType::Variable, 0)),
+        SyntaxKind::ScatterStatementNode => (TokenType::Variable, DECLARATION),
+        SyntaxKind::StructDefinitionNode => (TokenType::Struct, DECLARATION),
+        SyntaxKind::TaskDefinitionNode | SyntaxKind::WorkflowDefinitionNode => {
+            (TokenType::Function, DECLARATION)
+        }
+        SyntaxKind::CallAliasNode => (TokenType::Method, DECLARATION),
+        SyntaxKind::CallAfterNode => (TokenType::Method, 0),
+        SyntaxKind::CallTargetNode => {
+            let is_namespace = token
+                .siblings_with_tokens(rowan::Direction::Next)
+                .any(|t| t.kind() == SyntaxKind::Dot);
+            if is_namespace {
+                (TokenType::Namespace, 0)
+            } else {
+                (TokenType::Function, 0)
+            }
+        }
+        SyntaxKind::ImportStatementNode => (TokenType::Namespace, DECLARATION),
+        SyntaxKind::ImportAliasNode | SyntaxKind::TypeRefNode | SyntaxKind::LiteralStructNode => {
+            (TokenType::Struct, 0)
+        }
+        SyntaxKind::CallInputItemNode => (TokenType::Parameter, 0),
+        SyntaxKind::CallExprNode => {
+            if STDLIB.function(token.text()).is_some() {
+                (TokenType::Function, DEFAULT_LIBRARY)
+            } else {
+                (TokenType::Function, 0)
+            }
+        }
+        SyntaxKind::MetadataObjectItemNode => {
+            // Keys of `parameter_meta` sections refer to inputs and outputs
+            if parent.parent()?.kind() == SyntaxKind::ParameterMetadataSectionNode {
+                lookup()
+                    .map(context_type)
+                    .unwrap_or((TokenType::Property, 0))
+            } else {
+                (TokenType::Property, 0)
+            }
+        }
+        SyntaxKind::AccessExprNode
+        | SyntaxKind::LiteralStructItemNode
+        | SyntaxKind::LiteralObjectItemNode
+        | SyntaxKind::LiteralHintsItemNode
+        | SyntaxKind::LiteralInputItemNode
+        | SyntaxKind::LiteralOutputItemNode
+        | SyntaxKind::RuntimeItemNode
+        | SyntaxKind::RequirementsItemNode
+        | SyntaxKind::HintsItemNode => (TokenType::Property, 0),
+        SyntaxKind::PlaceholderSepOptionNode | SyntaxKind::PlaceholderDefaultOptionNode => {
+            (TokenType::Keyword, 0)
+        }
+        _ => return None,
+    })
+}
+
+/// Classifies a token of a document.
+///
+/// Returns `None` if the token has no semantic token type.
+fn classify(result: &AnalysisResult, token: &SyntaxToken) -> Option<(TokenType, u32)> {
+    let ty = match token.kind() {
+        SyntaxKind::Ident => return classify_ident(result, token),
+        SyntaxKind::Comment => TokenType::Comment,
+        SyntaxKind::Version | SyntaxKind::Float | SyntaxKind::Integer => TokenType::Number,
+        SyntaxKind::SingleQuote
+        | SyntaxKind::DoubleQuote
+        | SyntaxKind::LiteralStringText
+        | SyntaxKind::OpenHeredoc
+        | SyntaxKind::CloseHeredoc
+        | SyntaxKind::LiteralCommandText => TokenType::String,
+        SyntaxKind::PlaceholderOpen => TokenType::Macro,
+        SyntaxKind::CloseBrace
+            if token.parent().map(|p| p.kind()) == Some(SyntaxKind::PlaceholderNode) =>
+        {
+            TokenType::Macro
+        }
+        SyntaxKind::ArrayTypeKeyword
+        | SyntaxKind::BooleanTypeKeyword
+        | SyntaxKind::DirectoryTypeKeyword
+        | SyntaxKind::FileTypeKeyword
+        | SyntaxKind::FloatTypeKeyword
+        | SyntaxKind::IntTypeKeyword
+        | SyntaxKind::MapTypeKeyword
+        | SyntaxKind::ObjectTypeKeyword
+        | SyntaxKind::PairTypeKeyword
+        | SyntaxKind::StringTypeKeyword => TokenType::Type,
+        SyntaxKind::AfterKeyword
+        | SyntaxKind::AliasKeyword
+        | SyntaxKind::AsKeyword
+        | SyntaxKind::CallKeyword
+        | SyntaxKind::CommandKeyword
+        | SyntaxKind::ElseKeyword
+        | SyntaxKind::FalseKeyword
+        | SyntaxKind::HintsKeyword
+        | SyntaxKind::IfKeyword
+        | SyntaxKind::ImportKeyword
+        | SyntaxKind::InKeyword
+        | SyntaxKind::InputKeyword
+        | SyntaxKind::MetaKeyword
+        | SyntaxKind::NoneKeyword
+        | SyntaxKind::NullKeyword
+        | SyntaxKind::ObjectKeyword
+        | SyntaxKind::OutputKeyword
+        | SyntaxKind::ParameterMetaKeyword
+        | SyntaxKind::RequirementsKeyword
+        | SyntaxKind::RuntimeKeyword
+        | SyntaxKind::ScatterKeyword
+        | SyntaxKind::StructKeyword
+        | SyntaxKind::TaskKeyword
+        | SyntaxKind::ThenKeyword
+        | SyntaxKind::TrueKeyword
+        | SyntaxKind::VersionKeyword
+        | SyntaxKind::WorkflowKeyword => TokenType::Keyword,
+        SyntaxKind::Assignment
+        | SyntaxKind::QuestionMark
+        | SyntaxKind::Exclamation
+        | SyntaxKind::Plus
+        | SyntaxKind::Minus
+        | SyntaxKind::LogicalOr
+        | SyntaxKind::LogicalAnd
+        | SyntaxKind::Asterisk
+        | SyntaxKind::Exponentiation
+        | SyntaxKind::Slash
+        | SyntaxKind::Percent
+        | SyntaxKind::Equal
+        | SyntaxKind::NotEqual
+        | SyntaxKind::LessEqual
+        | SyntaxKind::GreaterEqual
+        | SyntaxKind::Less
+        | SyntaxKind::Greater => TokenType::Operator,
+        _ => return None,
+    };
+
+    Some((ty, 0))
+}
+
+/// Encodes the given classified tokens relative to each other.
+///
+/// Tokens that span multiple lines are split into a token per line.
+fn encode(
+    lines: &LineIndex,
+    tokens: impl Iterator<Item = (SyntaxToken, TokenType, u32)>,
+) -> Result<Vec<SemanticToken>> {
+    let mut encoded = Vec::new();
+    let mut prev_line = 0;
+    let mut prev_start = 0;
+    for (token, ty, modifiers) in tokens {
+        let mut offset = usize::from(token.text_range().start());
+        for text in token.text().split_inclusive('\n') {
+            let start = offset;
+            offset += text.len();
+
+            let text = text.trim_end_matches(['\r', '\n']);
+            if text.is_empty() {
+                continue;
+            }
+
+            let position = proto::position(lines, start)?;
+            let delta_line = position.line - prev_line;
+            let delta_start = if delta_line == 0 {
+                position.character - prev_start
+            } else {
+                position.character
+            };
+
+            encoded.push(SemanticToken {
+                delta_line,
+                delta_start,
+                length: text.encode_utf16().count().try_into()?,
+                token_type: ty as u32,
+                token_modifiers_bitset: modifiers,
+            });
+
+            prev_line = position.line;
+            prev_start = position.character;
+        }
+    }
+
+    Ok(encoded)
+}
+
+/// Gets the semantic tokens of a document.
+///
+/// If a range is given, only the tokens that intersect the range are
+/// returned.
+///
+/// Returns `Ok(None)` if the document could not be parsed.
+pub fn semantic_tokens(
+    result: &AnalysisResult,
+    range: Option<Range>,
+) -> Result<Option<SemanticTokens>> {
+    let (Some(document), Some(lines)) = (
+        result.parse_result().document(),
+        result.parse_result().lines(),
+    ) else {
+        return Ok(None);
+    };
+
+    let (start, end) = match range {
+        Some(range) => (
+            proto::offset(lines, range.start)?,
+            proto::offset(lines, range.end)?,
+        ),
+        None => (0, usize::MAX),
+    };
+
+    let tokens = document
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| {
+            let range = t.text_range();
+            usize::from(range.end()) > start && usize::from(range.start()) < end
+        })
+        .filter_map(|t| {
+            let (ty, modifiers) = classify(result, &t)?;
+            Some((t, ty, modifiers))
+        });
+
+    Ok(Some(SemanticTokens {
+        result_id: None,
+        data: encode(lines, tokens)?,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use tower_lsp::lsp_types::Position;
+
+    use super::*;
+    use crate::test_util::analyze;
+
+    /// Analyzes the given source and decodes its semantic tokens into the text
+    /// and type of each token.
+    async fn tokens(source: &str, range: Option<Range>) -> Vec<(String, String, u32)> {
+        let (_dir, results) = analyze(&[("source.wdl", source)]).await;
+        let tokens = semantic_tokens(&results[0], range)
+            .expect("should get tokens")
+            .expect("should have tokens");
+
+        let lines: Vec<_> = source.lines().collect();
+        let mut line = 0;
+        let mut start = 0;
+        tokens
+            .data
+            .into_iter()
+            .map(|t| {
+                if t.delta_line > 0 {
+                    line += t.delta_line as usize;
+                    start = 0;
+                }
+
+                start += t.delta_start as usize;
+                let text = lines[line][start..start + t.length as usize].to_string();
+                (
+                    text,
+                    TOKEN_TYPES[t.token_type as usize].as_str().to_string(),
+                    t.token_modifiers_bitset,
+                )
+            })
+            .collect()
+    }
+
+    /// Finds the type and modifiers of the first token with the given text.
+    fn find<'a>(tokens: &'a [(String, String, u32)], text: &str) -> (&'a str, u32) {
+        tokens
+            .iter()
+            .find(|(t, ..)| t == text)
+            .map(|(_, ty, modifiers)| (ty.as_str(), *modifiers))
+            .unwrap_or_else(|| panic!("should have a token for `{text}`"))
+    }
+
+    const SOURCE: &str = r#"version 1.1
+
+struct Point {
+    Int x
+}
+
+task greet {
+    input {
+        String name
+        Point p
+    }
+
+    String greeting = "hello ${name}"
+
+    command <<<
+        echo ~{greeting} \
+            ~{p.x}
+    >>>
+
+    output {
+        String out = read_string(stdout())
+    }
+}
+
+workflow main {
+    input {
+        Array[String] names
+    }
+
+    scatter (n in names) {
+        call greet as g { input: name = n }
+    }
+
+    output {
+        Array[String] all = g.out
+    }
+}
+"#;
+
+    #[tokio::test]
+    async fn token_types() {
+        let tokens = tokens(SOURCE, None).await;
+
+        assert_eq!(find(&tokens, "version"), ("keyword", 0));
+        assert_eq!(find(&tokens, "Point"), ("struct", DECLARATION));
+        assert_eq!(find(&tokens, "x"), ("property", DECLARATION));
+        assert_eq!(find(&tokens, "greet"), ("function", DECLARATION));
+        assert_eq!(find(&tokens, "String"), ("type", 0));
+        assert_eq!(find(&tokens, "name"), ("parameter", DECLARATION));
+        assert_eq!(find(&tokens, "greeting"), ("variable", DECLARATION));
+        assert_eq!(find(&tokens, "out"), ("property", DECLARATION));
+        assert_eq!(find(&tokens, "read_string"), ("function", DEFAULT_LIBRARY));
+        assert_eq!(find(&tokens, "g"), ("method", DECLARATION));
+        assert_eq!(find(&tokens, "n"), ("variable", DECLARATION));
+        assert_eq!(find(&tokens, "hello "), ("string", 0));
+
+        // Placeholders in strings and commands
+        let placeholders = tokens
+            .iter()
+            .filter(|(_, ty, _)| ty == "macro")
+            .map(|(t, ..)| t.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(placeholders, ["${", "}", "~{", "}", "~{", "}"]);
+
+        // Command text is split into a token per line
+        let command = tokens
+            .iter()
+            .filter(|(_, ty, _)| ty == "string")
+            .map(|(t, ..)| t.as_str())
+            .skip_while(|t| *t != "<<<")
+            .take_while(|t| *t != ">>>")
+            .collect::<Vec<_>>();
+        assert_eq!(
+            command,
+            ["<<<", "        echo ", " \\", "            ", "    "]
+        );
+
+        // References are classified by the names they refer to
+        let refs = tokens
+            .iter()
+            .filter(|(t, ..)| ["name", "greeting", "p", "n", "names", "g"].contains(&t.as_str()))
+            .filter(|(.., modifiers)| *modifiers & DECLARATION == 0)
+            .map(|(t, ty, _)| (t.as_str(), ty.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            refs,
+            [
+                ("name", "parameter"),
+                ("greeting", "variable"),
+                ("p", "parameter"),
+                ("names", "parameter"),
+                ("name", "parameter"),
+                ("n", "variable"),
+                ("g", "method"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn token_range() {
+        let tokens = tokens(
+            SOURCE,
+            Some(Range::new(Position::new(30, 0), Position::new(31, 0))),
+        )
+        .await;
+
+        assert_eq!(
+            tokens,
+            [
+                ("call".to_string(), "keyword".to_string(), 0),
+                ("greet".to_string(), "function".to_string(), 0),
+                ("as".to_string(), "keyword".to_string(), 0),
+                ("g".to_string(), "method".to_string(), DECLARATION),
+                ("input".to_string(), "keyword".to_string(), 0),
+                ("name".to_string(), "parameter".to_string(), 0),
+                ("=".to_string(), "operator".to_string(), 0),
+                ("n".to_string(), "variable".to_string(), 0),
+            ]
+        );
+    }
+}
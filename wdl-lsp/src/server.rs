@@ -18,6 +18,7 @@ use tower_lsp::Client;
 use tower_lsp::LanguageServer;
 use tower_lsp::LspService;
 use uuid::Uuid;
+use wdl_analysis::AnalysisResult;
 use wdl_analysis::Analyzer;
 use wdl_analysis::IncrementalChange;
 use wdl_analysis::SourceEdit;
@@ -26,7 +27,16 @@ use wdl_analysis::SourcePositionEncoding;
 use wdl_ast::Validator;
 use wdl_lint::LintVisitor;
 
+use crate::code_action;
+use crate::completion;
+use crate::definition;
+use crate::hover;
+use crate::inlay_hints;
 use crate::proto;
+use crate::rename;
+use crate::semantic_tokens;
+use crate::signature_help;
+use crate::symbols;
 
 /// LSP features supported by the client.
 #[derive(Clone, Copy, Debug, Default)]
@@ -252,6 +262,20 @@ impl Server {
             .unwrap_or(env!("CARGO_PKG_VERSION"))
     }
 
+    /// Analyzes the given document.
+    ///
+    /// Returns the analysis results of the document and its dependencies.
+    async fn analyze_document(&self, uri: Url) -> RpcResult<Vec<AnalysisResult>> {
+        self.analyzer
+            .analyze_document(ProgressToken::default(), uri)
+            .await
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: e.to_string().into(),
+                data: None,
+            })
+    }
+
     /// Registers a generic watcher for all files/directories in the workspace.
     async fn register_watcher(&self) {
         self.client
@@ -318,6 +342,44 @@ impl LanguageServer for Server {
                     }),
                     ..Default::default()
                 }),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec![".".to_string()]),
+                    ..Default::default()
+                }),
+                signature_help_provider: Some(SignatureHelpOptions {
+                    trigger_characters: Some(vec![
+                        "(".to_string(),
+                        ",".to_string(),
+                        "{".to_string(),
+                    ]),
+                    ..Default::default()
+                }),
+                definition_provider: Some(OneOf::Left(true)),
+                inlay_hint_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                })),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                        ..Default::default()
+                    },
+                )),
+                semantic_tokens_provider: Some(
+                    SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        SemanticTokensOptions {
+                            work_done_progress_options: Default::default(),
+                            legend: semantic_tokens::legend(),
+                            range: Some(true),
+                            full: Some(SemanticTokensFullOptions::Bool(true)),
+                        },
+                    ),
+                ),
                 diagnostic_provider: Some(DiagnosticServerCapabilities::Options(
                     DiagnosticOptions {
                         inter_file_dependencies: true,
@@ -464,9 +526,192 @@ impl LanguageServer for Server {
     ) -> RpcResult<DocumentDiagnosticReportResult> {
         log::debug!("received `textDocument/diagnostic` request: {params:#?}");
 
-        let results: Vec<wdl_analysis::AnalysisResult> = self
+        let results = self
+            .analyze_document(params.text_document.uri.clone())
+            .await?;
+
+        proto::document_diagnostic_report(params, results, self.name())
+            .ok_or_else(RpcError::request_cancelled)
+    }
+
+    async fn hover(&self, params: HoverParams) -> RpcResult<Option<Hover>> {
+        log::debug!("received `textDocument/hover` request: {params:#?}");
+
+        let uri = params.text_document_position_params.text_document.uri;
+        let results = self.analyze_document(uri.clone()).await?;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        hover::hover(
+            &results,
+            result,
+            params.text_document_position_params.position,
+        )
+        .map_err(|e| RpcError {
+            code: ErrorCode::InternalError,
+            message: e.to_string().into(),
+            data: None,
+        })
+    }
+
+    async fn completion(&self, params: CompletionParams) -> RpcResult<Option<CompletionResponse>> {
+        log::debug!("received `textDocument/completion` request: {params:#?}");
+
+        let uri = params.text_document_position.text_document.uri;
+        let results = self.analyze_document(uri.clone()).await?;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        completion::completion(result, params.text_document_position.position).map_err(|e| {
+            RpcError {
+                code: ErrorCode::InternalError,
+                message: e.to_string().into(),
+                data: None,
+            }
+        })
+    }
+
+    async fn signature_help(
+        &self,
+        params: SignatureHelpParams,
+    ) -> RpcResult<Option<SignatureHelp>> {
+        log::debug!("received `textDocument/signatureHelp` request: {params:#?}");
+
+        let uri = params.text_document_position_params.text_document.uri;
+        let results = self.analyze_document(uri.clone()).await?;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        signature_help::signature_help(
+            &results,
+            result,
+            params.text_document_position_params.position,
+        )
+        .map_err(|e| RpcError {
+            code: ErrorCode::InternalError,
+            message: e.to_string().into(),
+            data: None,
+        })
+    }
+
+    async fn inlay_hint(&self, params: InlayHintParams) -> RpcResult<Option<Vec<InlayHint>>> {
+        log::debug!("received `textDocument/inlayHint` request: {params:#?}");
+
+        let uri = params.text_document.uri;
+        let results = self.analyze_document(uri.clone()).await?;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        inlay_hints::inlay_hints(result, params.range).map_err(|e| RpcError {
+            code: ErrorCode::InternalError,
+            message: e.to_string().into(),
+            data: None,
+        })
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> RpcResult<Option<CodeActionResponse>> {
+        log::debug!("received `textDocument/codeAction` request: {params:#?}");
+
+        // Only quick fixes are provided
+        if let Some(only) = &params.context.only {
+            if !only
+                .iter()
+                .any(|k| CodeActionKind::QUICKFIX.as_str().starts_with(k.as_str()))
+            {
+                return Ok(None);
+            }
+        }
+
+        let uri = params.text_document.uri;
+        let results = self.analyze_document(uri.clone()).await?;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        code_action::code_actions(result, params.range, self.name()).map_err(|e| RpcError {
+            code: ErrorCode::InternalError,
+            message: e.to_string().into(),
+            data: None,
+        })
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> RpcResult<Option<SemanticTokensResult>> {
+        log::debug!("received `textDocument/semanticTokens/full` request: {params:#?}");
+
+        let uri = params.text_document.uri;
+        let results = self.analyze_document(uri.clone()).await?;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        semantic_tokens::semantic_tokens(result, None)
+            .map(|t| t.map(SemanticTokensResult::Tokens))
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: e.to_string().into(),
+                data: None,
+            })
+    }
+
+    async fn semantic_tokens_range(
+        &self,
+        params: SemanticTokensRangeParams,
+    ) -> RpcResult<Option<SemanticTokensRangeResult>> {
+        log::debug!("received `textDocument/semanticTokens/range` request: {params:#?}");
+
+        let uri = params.text_document.uri;
+        let results = self.analyze_document(uri.clone()).await?;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        semantic_tokens::semantic_tokens(result, Some(params.range))
+            .map(|t| t.map(SemanticTokensRangeResult::Tokens))
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: e.to_string().into(),
+                data: None,
+            })
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> RpcResult<Option<GotoDefinitionResponse>> {
+        log::debug!("received `textDocument/definition` request: {params:#?}");
+
+        let uri = params.text_document_position_params.text_document.uri;
+        let results = self.analyze_document(uri.clone()).await?;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        definition::goto_definition(
+            &results,
+            result,
+            params.text_document_position_params.position,
+        )
+        .map_err(|e| RpcError {
+            code: ErrorCode::InternalError,
+            message: e.to_string().into(),
+            data: None,
+        })
+    }
+
+    async fn references(&self, params: ReferenceParams) -> RpcResult<Option<Vec<Location>>> {
+        log::debug!("received `textDocument/references` request: {params:#?}");
+
+        // References may be in any document in the workspace
+        let results = self
             .analyzer
-            .analyze_document(ProgressToken::default(), params.text_document.uri.clone())
+            .analyze(ProgressToken::default())
             .await
             .map_err(|e| RpcError {
                 code: ErrorCode::InternalError,
@@ -474,8 +719,118 @@ impl LanguageServer for Server {
                 data: None,
             })?;
 
-        proto::document_diagnostic_report(params, results, self.name())
-            .ok_or_else(RpcError::request_cancelled)
+        let uri = params.text_document_position.text_document.uri;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        definition::find_references(
+            &results,
+            result,
+            params.text_document_position.position,
+            params.context.include_declaration,
+        )
+        .map_err(|e| RpcError {
+            code: ErrorCode::InternalError,
+            message: e.to_string().into(),
+            data: None,
+        })
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> RpcResult<Option<PrepareRenameResponse>> {
+        log::debug!("received `textDocument/prepareRename` request: {params:#?}");
+
+        let uri = params.text_document.uri;
+        let results = self.analyze_document(uri.clone()).await?;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        rename::prepare_rename(&results, result, params.position).map_err(|e| RpcError {
+            code: ErrorCode::InternalError,
+            message: e.to_string().into(),
+            data: None,
+        })
+    }
+
+    async fn rename(&self, params: RenameParams) -> RpcResult<Option<WorkspaceEdit>> {
+        log::debug!("received `textDocument/rename` request: {params:#?}");
+
+        // References may be in any document in the workspace
+        let results = self
+            .analyzer
+            .analyze(ProgressToken::default())
+            .await
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: e.to_string().into(),
+                data: None,
+            })?;
+
+        let uri = params.text_document_position.text_document.uri;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        // Refused renames are reported to the user as invalid parameters
+        rename::rename(
+            &results,
+            result,
+            params.text_document_position.position,
+            &params.new_name,
+        )
+        .map_err(|e| RpcError {
+            code: ErrorCode::InvalidParams,
+            message: e.to_string().into(),
+            data: None,
+        })
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> RpcResult<Option<DocumentSymbolResponse>> {
+        log::debug!("received `textDocument/documentSymbol` request: {params:#?}");
+
+        let uri = params.text_document.uri;
+        let results = self.analyze_document(uri.clone()).await?;
+        let Some(result) = results.iter().find(|r| r.uri().as_ref() == &uri) else {
+            return Ok(None);
+        };
+
+        symbols::document_symbol(result).map_err(|e| RpcError {
+            code: ErrorCode::InternalError,
+            message: e.to_string().into(),
+            data: None,
+        })
+    }
+
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> RpcResult<Option<Vec<SymbolInformation>>> {
+        log::debug!("received `workspace/symbol` request: {params:#?}");
+
+        let results = self
+            .analyzer
+            .analyze(ProgressToken::default())
+            .await
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: e.to_string().into(),
+                data: None,
+            })?;
+
+        symbols::workspace_symbol(&results, &params.query)
+            .map(Some)
+            .map_err(|e| RpcError {
+                code: ErrorCode::InternalError,
+                message: e.to_string().into(),
+                data: None,
+            })
     }
 
     async fn workspace_diagnostic(
@@ -0,0 +1,453 @@
+//! Implementation of signature help.
+//!
+//! Signature help is provided for calls to standard library functions and for
+//! the inputs of call statements.
+
+use anyhow::Result;
+use rowan::TokenAtOffset;
+use tower_lsp::lsp_types::Documentation;
+use tower_lsp::lsp_types::ParameterInformation;
+use tower_lsp::lsp_types::ParameterLabel;
+use tower_lsp::lsp_types::Position;
+use tower_lsp::lsp_types::SignatureHelp;
+use tower_lsp::lsp_types::SignatureInformation;
+use wdl_analysis::inputs::input_description;
+use wdl_analysis::scope::DocumentScope;
+use wdl_analysis::stdlib::Function;
+use wdl_analysis::stdlib::FunctionSignature;
+use wdl_analysis::stdlib::TypeParameters;
+use wdl_analysis::stdlib::STDLIB;
+use wdl_analysis::types::PrimitiveTypeKind;
+use wdl_analysis::types::Type;
+use wdl_analysis::types::Types;
+use wdl_analysis::AnalysisResult;
+use wdl_ast::v1::CallStatement;
+use wdl_ast::v1::InputSection;
+use wdl_ast::v1::ParameterMetadataSection;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::WorkflowDefinition;
+use wdl_ast::AstNode;
+use wdl_ast::AstToken;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::SyntaxToken;
+
+use crate::definition::resolve_callee;
+use crate::proto;
+use crate::syntax::expr_type;
+
+/// Gets the signature help at the given position in a document.
+///
+/// Returns `Ok(None)` if the position is not within the arguments of a
+/// standard library function call or the input block of a call statement.
+pub fn signature_help(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    position: Position,
+) -> Result<Option<SignatureHelp>> {
+    let (Some(document), Some(lines)) = (
+        result.parse_result().document(),
+        result.parse_result().lines(),
+    ) else {
+        return Ok(None);
+    };
+
+    let offset = proto::offset(lines, position)?;
+    let Some(token) = token_before(document.syntax(), offset) else {
+        return Ok(None);
+    };
+
+    for node in token.parent_ancestors() {
+        match node.kind() {
+            SyntaxKind::CallExprNode if within(&node, SyntaxKind::OpenParen, offset) => {
+                return Ok(function_help(result.scope(), &node, offset));
+            }
+            SyntaxKind::CallStatementNode if within(&node, SyntaxKind::OpenBrace, offset) => {
+                return Ok(call_help(results, result, &node, offset));
+            }
+            // Stop at the enclosing statement or definition
+            SyntaxKind::TaskDefinitionNode
+            | SyntaxKind::WorkflowDefinitionNode
+            | SyntaxKind::StructDefinitionNode
+            | SyntaxKind::CallStatementNode => break,
+            _ => continue,
+        }
+    }
+
+    Ok(None)
+}
+
+/// Gets the token that ends at or contains the given offset.
+fn token_before(root: &SyntaxNode, offset: usize) -> Option<SyntaxToken> {
+    let offset = u32::try_from(offset).ok()?.into();
+    if !root.text_range().contains_inclusive(offset) {
+        return None;
+    }
+
+    match root.token_at_offset(offset) {
+        TokenAtOffset::None => None,
+        TokenAtOffset::Single(token) => Some(token),
+        TokenAtOffset::Between(left, _) => Some(left),
+    }
+}
+
+/// Determines if the given offset is after the given opening delimiter of a
+/// node and not after its closing delimiter.
+///
+/// A node without a closing delimiter (i.e. an incomplete node) contains
+/// every offset after its opening delimiter.
+fn within(node: &SyntaxNode, open: SyntaxKind, offset: usize) -> bool {
+    let close = match open {
+        SyntaxKind::OpenParen => SyntaxKind::CloseParen,
+        SyntaxKind::OpenBrace => SyntaxKind::CloseBrace,
+        _ => unreachable!("unsupported delimiter"),
+    };
+
+    let mut tokens = node.children_with_tokens().filter_map(|c| c.into_token());
+    let Some(open) = tokens.find(|t| t.kind() == open) else {
+        return false;
+    };
+
+    if offset < usize::from(open.text_range().end()) {
+        return false;
+    }
+
+    match tokens.find(|t| t.kind() == close) {
+        Some(close) => offset <= usize::from(close.text_range().start()),
+        None => true,
+    }
+}
+
+/// Gets the number of commas in a node that precede the given offset.
+///
+/// This is the index of the argument or input at the offset.
+fn active_index(node: &SyntaxNode, offset: usize) -> usize {
+    node.children_with_tokens()
+        .filter(|c| c.kind() == SyntaxKind::Comma && usize::from(c.text_range().end()) <= offset)
+        .count()
+}
+
+/// Converts a byte range of a label into label offsets in UTF-16 code units.
+fn label_offsets(label: &str, start: usize, end: usize) -> ParameterLabel {
+    let begin = label[..start].encode_utf16().count();
+    let len = label[start..end].encode_utf16().count();
+    ParameterLabel::LabelOffsets([begin as u32, (begin + len) as u32])
+}
+
+/// Gets the type of a function call argument.
+///
+/// The type of a literal, name reference, or member access is determined;
+/// the type of any other expression is indeterminate.
+///
+/// The type is imported into the given types collection.
+fn argument_type(scope: &DocumentScope, types: &mut Types, expr: &SyntaxNode) -> Type {
+    match expr.kind() {
+        SyntaxKind::LiteralBooleanNode => PrimitiveTypeKind::Boolean.into(),
+        SyntaxKind::LiteralIntegerNode => PrimitiveTypeKind::Integer.into(),
+        SyntaxKind::LiteralFloatNode => PrimitiveTypeKind::Float.into(),
+        SyntaxKind::LiteralStringNode => PrimitiveTypeKind::String.into(),
+        SyntaxKind::LiteralNoneNode => Type::None,
+        _ => expr_type(scope, expr)
+            .map(|ty| types.import(scope.types(), ty))
+            .unwrap_or(Type::Union),
+    }
+}
+
+/// Gets the signature help for a call to a standard library function.
+///
+/// Every overload of the function is listed; the active signature is the
+/// first overload that may still be bound to the arguments preceding the
+/// active argument.
+fn function_help(scope: &DocumentScope, node: &SyntaxNode, offset: usize) -> Option<SignatureHelp> {
+    let name = node
+        .children_with_tokens()
+        .find(|c| c.kind() == SyntaxKind::Ident)?
+        .into_token()?;
+    let function = STDLIB.function(name.text())?;
+    let signatures: &[FunctionSignature] = match function {
+        Function::Monomorphic(f) => std::slice::from_ref(f.signature()),
+        Function::Polymorphic(f) => f.signatures(),
+    };
+
+    let active = active_index(node, offset);
+    let mut types = Types::default();
+    let arguments = node
+        .children()
+        .take(active)
+        .map(|e| argument_type(scope, &mut types, &e))
+        .collect::<Vec<_>>();
+
+    let active_signature = signatures
+        .iter()
+        .position(|s| s.bind_partial(&mut types, &arguments).is_ok())
+        .unwrap_or(0);
+
+    let signatures = signatures
+        .iter()
+        .map(|signature| {
+            let params = TypeParameters::new(signature.type_parameters());
+            let label = format!(
+                "{name}{sig}",
+                name = name.text(),
+                sig = signature.display(STDLIB.types(), &params)
+            );
+
+            // Find each parameter in order within the label
+            let mut start = name.text().len();
+            let parameters = signature
+                .parameters()
+                .iter()
+                .map(|p| {
+                    let text = p.display(STDLIB.types(), &params).to_string();
+                    let begin = start + label[start..].find(&text).unwrap_or(0);
+                    start = begin + text.len();
+                    ParameterInformation {
+                        label: label_offsets(&label, begin, start),
+                        documentation: None,
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            SignatureInformation {
+                active_parameter: (active < parameters.len()).then_some(active as u32),
+                label,
+                documentation: None,
+                parameters: Some(parameters),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Some(SignatureHelp {
+        signatures,
+        active_signature: Some(active_signature as u32),
+        active_parameter: Some(active as u32),
+    })
+}
+
+/// Gets the signature help for the inputs of a call statement.
+///
+/// The signature lists the inputs of the callee that have not yet been
+/// supplied by the call; the active parameter is the input being edited.
+fn call_help(
+    results: &[AnalysisResult],
+    result: &AnalysisResult,
+    node: &SyntaxNode,
+    offset: usize,
+) -> Option<SignatureHelp> {
+    let call = CallStatement::cast(node.clone())?;
+    let target = call.target();
+    let (_, callee) = resolve_callee(results, result, &target)?;
+    let (input, metadata) = callee_sections(callee)?;
+
+    // The input being edited is the one containing the offset
+    let mut editing = None;
+    let mut supplied = Vec::new();
+    for item in call.inputs() {
+        let range = item.syntax().text_range();
+        let name = item.name().as_str().to_string();
+        if usize::from(range.start()) <= offset && offset <= usize::from(range.end()) {
+            editing = Some(name);
+        } else {
+            supplied.push(name);
+        }
+    }
+
+    let mut label = format!("call {target} {{ input: ", target = target.syntax());
+    let mut parameters = Vec::new();
+    let mut active_parameter = None;
+    for decl in input.declarations() {
+        let name = decl.name();
+        if supplied.iter().any(|s| s == name.as_str()) {
+            continue;
+        }
+
+        if !parameters.is_empty() {
+            label.push_str(", ");
+        }
+
+        if editing.as_deref() == Some(name.as_str()) {
+            active_parameter = Some(parameters.len() as u32);
+        }
+
+        let start = label.len();
+        label.push_str(&format!(
+            "{ty} {name}",
+            ty = decl.ty(),
+            name = name.as_str()
+        ));
+        if let Some(expr) = decl.expr() {
+            label.push_str(&format!(" = {expr}", expr = expr.syntax()));
+        }
+
+        parameters.push(ParameterInformation {
+            label: label_offsets(&label, start, label.len()),
+            documentation: metadata
+                .as_ref()
+                .and_then(|m| input_description(m, name.as_str()))
+                .map(Documentation::String),
+        });
+    }
+
+    label.push_str(" }");
+
+    Some(SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation: None,
+            parameters: Some(parameters),
+            active_parameter,
+        }],
+        active_signature: Some(0),
+        active_parameter,
+    })
+}
+
+/// Gets the input and parameter metadata sections of a task or workflow.
+fn callee_sections(node: SyntaxNode) -> Option<(InputSection, Option<ParameterMetadataSection>)> {
+    match node.kind() {
+        SyntaxKind::TaskDefinitionNode => {
+            let task = TaskDefinition::cast(node)?;
+            Some((task.input()?, task.parameter_metadata()))
+        }
+        SyntaxKind::WorkflowDefinitionNode => {
+            let workflow = WorkflowDefinition::cast(node)?;
+            Some((workflow.input()?, workflow.parameter_metadata()))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::test_util::analyze;
+    use crate::test_util::cursor;
+
+    /// Gets the signature help at the `|` marker in the given source.
+    async fn signatures(source: &str) -> Option<SignatureHelp> {
+        let (source, position) = cursor(source);
+        let (_dir, results) = analyze(&[("source.wdl", &source)]).await;
+        signature_help(&results, &results[0], position).expect("should get signature help")
+    }
+
+    /// Gets the labels of the signatures and the active signature and
+    /// parameter of the given signature help.
+    fn summarize(help: &SignatureHelp) -> (Vec<&str>, Option<u32>, Option<u32>) {
+        (
+            help.signatures.iter().map(|s| s.label.as_str()).collect(),
+            help.active_signature,
+            help.active_parameter,
+        )
+    }
+
+    #[tokio::test]
+    async fn function_overloads() {
+        let overloads = vec![
+            "min(Int, Int) -> Int",
+            "min(Int, Float) -> Float",
+            "min(Float, Int) -> Float",
+            "min(Float, Float) -> Float",
+        ];
+
+        let help = signatures("version 1.1\n\nworkflow test {\n    Int x = min(|)\n}\n")
+            .await
+            .expect("should have signature help");
+        assert_eq!(summarize(&help), (overloads.clone(), Some(0), Some(0)));
+        assert_eq!(
+            help.signatures[0].parameters.as_ref().unwrap()[1].label,
+            ParameterLabel::LabelOffsets([9, 12])
+        );
+
+        let help = signatures("version 1.1\n\nworkflow test {\n    Float x = min(1.5, |)\n}\n")
+            .await
+            .expect("should have signature help");
+        assert_eq!(summarize(&help), (overloads.clone(), Some(2), Some(1)));
+
+        let help = signatures(
+            "version 1.1\n\nworkflow test {\n    Float f = 1.5\n    Float x = min(f, 1|)\n}\n",
+        )
+        .await
+        .expect("should have signature help");
+        assert_eq!(summarize(&help), (overloads.clone(), Some(2), Some(1)));
+
+        // Nested calls use the innermost call
+        let help =
+            signatures("version 1.1\n\nworkflow test {\n    Float x = min(floor(|1.5), 1)\n}\n")
+                .await
+                .expect("should have signature help");
+        assert_eq!(
+            summarize(&help),
+            (vec!["floor(Float) -> Int"], Some(0), Some(0))
+        );
+
+        // The call may be incomplete
+        let help = signatures("version 1.1\n\nworkflow test {\n    Float x = min(1, |\n}\n")
+            .await
+            .expect("should have signature help");
+        assert_eq!(summarize(&help), (overloads, Some(0), Some(1)));
+
+        assert!(
+            signatures("version 1.1\n\nworkflow test {\n    Float x = min(1, 2)|\n}\n")
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn call_inputs() {
+        let source = r#"version 1.1
+
+task greet {
+    input {
+        String name
+        Int count = 1
+        Boolean? loud
+    }
+
+    parameter_meta {
+        name: "the name to greet"
+    }
+
+    command <<<>>>
+}
+
+workflow test {
+    call greet { input: loud = true, |name = "world" }
+}
+"#;
+
+        let help = signatures(source)
+            .await
+            .expect("should have signature help");
+        assert_eq!(
+            summarize(&help),
+            (
+                vec!["call greet { input: String name, Int count = 1 }"],
+                Some(0),
+                Some(0)
+            )
+        );
+
+        let parameters = help.signatures[0].parameters.as_ref().unwrap();
+        assert_eq!(parameters[0].label, ParameterLabel::LabelOffsets([20, 31]));
+        assert_eq!(
+            parameters[0].documentation,
+            Some(Documentation::String("the name to greet".to_string()))
+        );
+        assert_eq!(parameters[1].documentation, None);
+
+        let help = signatures(
+            &source
+                .replace("|name", "name")
+                .replace("{ input", "{| input"),
+        )
+        .await
+        .expect("should have signature help");
+        assert_eq!(
+            summarize(&help),
+            (vec!["call greet { input: Int count = 1 }"], Some(0), None)
+        );
+    }
+}
@@ -0,0 +1,579 @@
+//! Implementation of document and workspace symbols.
+
+use anyhow::Result;
+use line_index::LineIndex;
+use tower_lsp::lsp_types::DocumentSymbol;
+use tower_lsp::lsp_types::DocumentSymbolResponse;
+use tower_lsp::lsp_types::Location;
+use tower_lsp::lsp_types::SymbolInformation;
+use tower_lsp::lsp_types::SymbolKind;
+use wdl_analysis::AnalysisResult;
+use wdl_ast::v1::CallStatement;
+use wdl_ast::v1::ConditionalStatement;
+use wdl_ast::v1::Decl;
+use wdl_ast::v1::DocumentItem;
+use wdl_ast::v1::ScatterStatement;
+use wdl_ast::v1::StructDefinition;
+use wdl_ast::v1::TaskDefinition;
+use wdl_ast::v1::TaskItem;
+use wdl_ast::v1::WorkflowDefinition;
+use wdl_ast::v1::WorkflowItem;
+use wdl_ast::v1::WorkflowStatement;
+use wdl_ast::AstNode;
+use wdl_ast::AstNodeExt;
+use wdl_ast::AstToken;
+use wdl_ast::Span;
+use wdl_ast::ToSpan;
+
+use crate::proto;
+
+/// Creates a document symbol.
+fn symbol(
+    lines: &LineIndex,
+    name: String,
+    detail: Option<String>,
+    kind: SymbolKind,
+    span: Span,
+    selection: Span,
+    children: Vec<DocumentSymbol>,
+) -> Result<DocumentSymbol> {
+    #[allow(deprecated)]
+    Ok(DocumentSymbol {
+        name,
+        detail,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: proto::range_from_span(lines, span)?,
+        selection_range: proto::range_from_span(lines, selection)?,
+        children: if children.is_empty() {
+            None
+        } else {
+            Some(children)
+        },
+    })
+}
+
+/// Creates a document symbol for a section of a task or workflow.
+///
+/// The section is named after its keyword.
+fn section_symbol(
+    lines: &LineIndex,
+    section: &impl AstNode<Language = wdl_ast::WorkflowDescriptionLanguage>,
+    children: Vec<DocumentSymbol>,
+) -> Result<DocumentSymbol> {
+    let keyword = section
+        .syntax()
+        .first_token()
+        .expect("section should have a keyword");
+    symbol(
+        lines,
+        keyword.text().to_string(),
+        None,
+        SymbolKind::NAMESPACE,
+        section.span(),
+        keyword.text_range().to_span(),
+        children,
+    )
+}
+
+/// Creates a document symbol for a declaration.
+fn decl_symbol(lines: &LineIndex, decl: &Decl) -> Result<DocumentSymbol> {
+    let name = decl.name();
+    symbol(
+        lines,
+        name.as_str().to_string(),
+        Some(decl.ty().to_string()),
+        SymbolKind::VARIABLE,
+        decl.span(),
+        name.span(),
+        Vec::new(),
+    )
+}
+
+/// Collapses the whitespace of the given source text.
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Gets the hierarchical symbols of a document.
+///
+/// Returns `Ok(None)` if the document could not be parsed.
+pub fn document_symbol(result: &AnalysisResult) -> Result<Option<DocumentSymbolResponse>> {
+    let (Some(document), Some(lines)) = (
+        result.parse_result().document(),
+        result.parse_result().lines(),
+    ) else {
+        return Ok(None);
+    };
+
+    let Some(ast) = document.ast().into_v1() else {
+        return Ok(None);
+    };
+
+    let mut symbols = Vec::new();
+    for item in ast.items() {
+        match item {
+            DocumentItem::Import(_) => continue,
+            DocumentItem::Struct(s) => symbols.push(struct_symbol(lines, &s)?),
+            DocumentItem::Task(t) => symbols.push(task_symbol(lines, &t)?),
+            DocumentItem::Workflow(w) => symbols.push(workflow_symbol(lines, &w)?),
+        }
+    }
+
+    Ok(Some(DocumentSymbolResponse::Nested(symbols)))
+}
+
+/// Creates a document symbol for a struct definition.
+fn struct_symbol(lines: &LineIndex, definition: &StructDefinition) -> Result<DocumentSymbol> {
+    let name = definition.name();
+    symbol(
+        lines,
+        name.as_str().to_string(),
+        Some("struct".into()),
+        SymbolKind::STRUCT,
+        definition.span(),
+        name.span(),
+        definition
+            .members()
+            .map(|m| {
+                let name = m.name();
+                symbol(
+                    lines,
+                    name.as_str().to_string(),
+                    Some(m.ty().to_string()),
+                    SymbolKind::FIELD,
+                    m.span(),
+                    name.span(),
+                    Vec::new(),
+                )
+            })
+            .collect::<Result<_>>()?,
+    )
+}
+
+/// Creates a document symbol for a task definition.
+fn task_symbol(lines: &LineIndex, definition: &TaskDefinition) -> Result<DocumentSymbol> {
+    let mut children = Vec::new();
+    for item in definition.items() {
+        children.push(match item {
+            TaskItem::Input(s) => section_symbol(
+                lines,
+                &s,
+                s.declarations()
+                    .map(|d| decl_symbol(lines, &d))
+                    .collect::<Result<_>>()?,
+            )?,
+            TaskItem::Output(s) => section_symbol(
+                lines,
+                &s,
+                s.declarations()
+                    .map(|d| decl_symbol(lines, &Decl::Bound(d)))
+                    .collect::<Result<_>>()?,
+            )?,
+            TaskItem::Command(s) => section_symbol(lines, &s, Vec::new())?,
+            TaskItem::Requirements(s) => section_symbol(lines, &s, Vec::new())?,
+            TaskItem::Hints(s) => section_symbol(lines, &s, Vec::new())?,
+            TaskItem::Runtime(s) => section_symbol(lines, &s, Vec::new())?,
+            TaskItem::Metadata(s) => section_symbol(lines, &s, Vec::new())?,
+            TaskItem::ParameterMetadata(s) => section_symbol(lines, &s, Vec::new())?,
+            TaskItem::Declaration(d) => decl_symbol(lines, &Decl::Bound(d))?,
+        });
+    }
+
+    let name = definition.name();
+    symbol(
+        lines,
+        name.as_str().to_string(),
+        Some("task".into()),
+        SymbolKind::FUNCTION,
+        definition.span(),
+        name.span(),
+        children,
+    )
+}
+
+/// Creates a document symbol for a workflow definition.
+fn workflow_symbol(lines: &LineIndex, definition: &WorkflowDefinition) -> Result<DocumentSymbol> {
+    let mut children = Vec::new();
+    for item in definition.items() {
+        children.push(match item {
+            WorkflowItem::Input(s) => section_symbol(
+                lines,
+                &s,
+                s.declarations()
+                    .map(|d| decl_symbol(lines, &d))
+                    .collect::<Result<_>>()?,
+            )?,
+            WorkflowItem::Output(s) => section_symbol(
+                lines,
+                &s,
+                s.declarations()
+                    .map(|d| decl_symbol(lines, &Decl::Bound(d)))
+                    .collect::<Result<_>>()?,
+            )?,
+            WorkflowItem::Conditional(s) => conditional_symbol(lines, &s)?,
+            WorkflowItem::Scatter(s) => scatter_symbol(lines, &s)?,
+            WorkflowItem::Call(s) => call_symbol(lines, &s)?,
+            WorkflowItem::Metadata(s) => section_symbol(lines, &s, Vec::new())?,
+            WorkflowItem::ParameterMetadata(s) => section_symbol(lines, &s, Vec::new())?,
+            WorkflowItem::Hints(s) => section_symbol(lines, &s, Vec::new())?,
+            WorkflowItem::Declaration(d) => decl_symbol(lines, &Decl::Bound(d))?,
+        });
+    }
+
+    let name = definition.name();
+    symbol(
+        lines,
+        name.as_str().to_string(),
+        Some("workflow".into()),
+        SymbolKind::FUNCTION,
+        definition.span(),
+        name.span(),
+        children,
+    )
+}
+
+/// Creates the document symbols for the given workflow statements.
+fn statement_symbols(
+    lines: &LineIndex,
+    statements: impl Iterator<Item = WorkflowStatement>,
+) -> Result<Vec<DocumentSymbol>> {
+    statements
+        .map(|s| match s {
+            WorkflowStatement::Conditional(s) => conditional_symbol(lines, &s),
+            WorkflowStatement::Scatter(s) => scatter_symbol(lines, &s),
+            WorkflowStatement::Call(s) => call_symbol(lines, &s),
+            WorkflowStatement::Declaration(d) => decl_symbol(lines, &Decl::Bound(d)),
+        })
+        .collect()
+}
+
+/// Creates a document symbol for a conditional statement.
+fn conditional_symbol(
+    lines: &LineIndex,
+    statement: &ConditionalStatement,
+) -> Result<DocumentSymbol> {
+    let keyword = statement
+        .syntax()
+        .first_token()
+        .expect("statement should have a keyword");
+    symbol(
+        lines,
+        format!(
+            "if ({expr})",
+            expr = collapse(&statement.expr().syntax().to_string())
+        ),
+        None,
+        SymbolKind::NAMESPACE,
+        statement.span(),
+        keyword.text_range().to_span(),
+        statement_symbols(lines, statement.statements())?,
+    )
+}
+
+/// Creates a document symbol for a scatter statement.
+fn scatter_symbol(lines: &LineIndex, statement: &ScatterStatement) -> Result<DocumentSymbol> {
+    let variable = statement.variable();
+    symbol(
+        lines,
+        format!(
+            "scatter ({variable} in {expr})",
+            variable = variable.as_str(),
+            expr = collapse(&statement.expr().syntax().to_string())
+        ),
+        None,
+        SymbolKind::NAMESPACE,
+        statement.span(),
+        variable.span(),
+        statement_symbols(lines, statement.statements())?,
+    )
+}
+
+/// Creates a document symbol for a call statement.
+///
+/// The symbol is named after the call and its detail is the call target.
+fn call_symbol(lines: &LineIndex, statement: &CallStatement) -> Result<DocumentSymbol> {
+    let target = statement.target();
+    let name = match statement.alias() {
+        Some(alias) => alias.name(),
+        None => target
+            .names()
+            .last()
+            .expect("call target should have a name"),
+    };
+
+    symbol(
+        lines,
+        name.as_str().to_string(),
+        Some(format!(
+            "call {target}",
+            target = target
+                .names()
+                .map(|n| n.as_str().to_string())
+                .collect::<Vec<_>>()
+                .join(".")
+        )),
+        SymbolKind::MODULE,
+        statement.span(),
+        name.span(),
+        Vec::new(),
+    )
+}
+
+/// Scores a fuzzy match of a query against a name.
+///
+/// The characters of the query must appear in the name in order, ignoring
+/// case; matches of consecutive characters and matches at the start of a word
+/// score higher.
+///
+/// Returns `None` if the query does not match the name.
+fn fuzzy_score(query: &str, name: &str) -> Option<usize> {
+    let mut score = 0;
+    let mut prev: Option<(usize, char)> = None;
+    let mut chars = name.char_indices();
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let mut matched = false;
+        let mut last = prev.map(|(_, c)| c);
+        for (i, c) in chars.by_ref() {
+            if c.eq_ignore_ascii_case(&q) {
+                score += 1;
+                if prev.map(|(p, _)| p + 1 == i).unwrap_or(i == 0) {
+                    // Consecutive match or a match at the start of the name
+                    score += 2;
+                } else if last
+                    .map(|l| l == '_' || (l.is_lowercase() && c.is_uppercase()))
+                    .unwrap_or(false)
+                {
+                    // Match at the start of a word
+                    score += 2;
+                }
+
+                prev = Some((i, c));
+                matched = true;
+                break;
+            }
+
+            last = Some(c);
+        }
+
+        if !matched {
+            return None;
+        }
+    }
+
+    Some(score)
+}
+
+/// Finds the tasks and workflows in the given results that match a query.
+///
+/// The symbols are sorted by how well they match the query.
+pub fn workspace_symbol(results: &[AnalysisResult], query: &str) -> Result<Vec<SymbolInformation>> {
+    let mut symbols = Vec::new();
+    for result in results {
+        let (Some(document), Some(lines)) = (
+            result.parse_result().document(),
+            result.parse_result().lines(),
+        ) else {
+            continue;
+        };
+
+        let Some(ast) = document.ast().into_v1() else {
+            continue;
+        };
+
+        let names = ast
+            .tasks()
+            .map(|t| t.name())
+            .chain(ast.workflows().map(|w| w.name()));
+        for name in names {
+            let Some(score) = fuzzy_score(query, name.as_str()) else {
+                continue;
+            };
+
+            #[allow(deprecated)]
+            let symbol = SymbolInformation {
+                name: name.as_str().to_string(),
+                kind: SymbolKind::FUNCTION,
+                tags: None,
+                deprecated: None,
+                location: Location::new(
+                    result.uri().as_ref().clone(),
+                    proto::range_from_span(lines, name.span())?,
+                ),
+                container_name: None,
+            };
+
+            symbols.push((score, symbol));
+        }
+    }
+
+    symbols.sort_by(|(a, a_symbol), (b, b_symbol)| {
+        b.cmp(a)
+            .then_with(|| a_symbol.name.cmp(&b_symbol.name))
+            .then_with(|| a_symbol.location.uri.cmp(&b_symbol.location.uri))
+    });
+    Ok(symbols.into_iter().map(|(_, s)| s).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::test_util::analyze;
+
+    /// Flattens the given symbols into an indented outline.
+    fn outline(symbols: &[DocumentSymbol], depth: usize, lines: &mut Vec<String>) {
+        for symbol in symbols {
+            let detail = symbol
+                .detail
+                .as_ref()
+                .map(|d| format!(": {d}"))
+                .unwrap_or_default();
+            lines.push(format!(
+                "{indent}{name}{detail}",
+                indent = "  ".repeat(depth),
+                name = symbol.name
+            ));
+            outline(
+                symbol.children.as_deref().unwrap_or_default(),
+                depth + 1,
+                lines,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn document_outline() {
+        let (_dir, results) = analyze(&[(
+            "source.wdl",
+            r#"version 1.1
+
+struct Point {
+    Int x
+    Int y
+}
+
+task greet {
+    input {
+        String name
+    }
+
+    String greeting = "hello ${name}"
+
+    command <<<>>>
+
+    output {
+        String out = greeting
+    }
+
+    runtime {}
+}
+
+workflow main {
+    input {
+        Array[String] names
+        Boolean enabled
+    }
+
+    scatter (name in names) {
+        call greet { input: name }
+    }
+
+    if (enabled   &&
+        true) {
+        call greet as other { input: name = "world" }
+    }
+
+    output {
+        Array[String] out = greet.out
+    }
+}
+"#,
+        )])
+        .await;
+
+        let Some(DocumentSymbolResponse::Nested(symbols)) =
+            document_symbol(&results[0]).expect("should get symbols")
+        else {
+            panic!("expected nested symbols");
+        };
+
+        let mut lines = Vec::new();
+        outline(&symbols, 0, &mut lines);
+        assert_eq!(
+            lines,
+            [
+                "Point: struct",
+                "  x: Int",
+                "  y: Int",
+                "greet: task",
+                "  input",
+                "    name: String",
+                "  greeting: String",
+                "  command",
+                "  output",
+                "    out: String",
+                "  runtime",
+                "main: workflow",
+                "  input",
+                "    names: Array[String]",
+                "    enabled: Boolean",
+                "  scatter (name in names)",
+                "    greet: call greet",
+                "  if (enabled && true)",
+                "    other: call greet",
+                "  output",
+                "    out: Array[String]",
+            ]
+        );
+
+        // The selection range of a task should be its name
+        assert_eq!(symbols[1].selection_range.start.line, 7);
+        assert_eq!(symbols[1].selection_range.start.character, 5);
+        assert_eq!(symbols[1].selection_range.end.character, 10);
+    }
+
+    #[tokio::test]
+    async fn workspace_fuzzy_search() {
+        let (_dir, results) = analyze(&[
+            (
+                "a.wdl",
+                "version 1.1\n\ntask align_reads {\n    command <<<>>>\n}\n\nworkflow alignment \
+                 {}\n",
+            ),
+            (
+                "b.wdl",
+                "version 1.1\n\ntask call_variants {\n    command <<<>>>\n}\n\ntask \
+                 validate_alignment {\n    command <<<>>>\n}\n",
+            ),
+        ])
+        .await;
+
+        let names = |query| {
+            workspace_symbol(&results, query)
+                .expect("should get symbols")
+                .into_iter()
+                .map(|s| s.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names("align"),
+            ["align_reads", "alignment", "validate_alignment"]
+        );
+        assert_eq!(names("ar"), ["align_reads", "call_variants"]);
+        assert_eq!(names("CV"), ["call_variants"]);
+        assert_eq!(names("xyz"), Vec::<String>::new());
+        assert_eq!(names("").len(), 4);
+    }
+
+    #[test]
+    fn fuzzy_scores() {
+        assert_eq!(fuzzy_score("", "foo"), Some(0));
+        assert_eq!(fuzzy_score("abc", "ab"), None);
+        assert!(fuzzy_score("foo", "foo_bar") > fuzzy_score("foo", "xfoo"));
+        assert!(fuzzy_score("fb", "foo_bar") > fuzzy_score("fb", "foxbar"));
+    }
+}
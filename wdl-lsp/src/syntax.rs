@@ -0,0 +1,79 @@
+//! Helpers for inspecting the syntax of analyzed documents.
+
+use rowan::TokenAtOffset;
+use wdl_analysis::scope::DocumentScope;
+use wdl_analysis::types::CompoundTypeDef;
+use wdl_analysis::types::Type;
+use wdl_analysis::types::Types;
+use wdl_ast::SyntaxKind;
+use wdl_ast::SyntaxNode;
+use wdl_ast::SyntaxToken;
+
+/// Finds the identifier token at the given offset in a document.
+///
+/// If the offset is between two tokens, the identifier on either side is
+/// returned.
+pub(crate) fn ident_at(root: &SyntaxNode, offset: usize) -> Option<SyntaxToken> {
+    let offset = u32::try_from(offset).ok()?.into();
+    if !root.text_range().contains_inclusive(offset) {
+        return None;
+    }
+
+    match root.token_at_offset(offset) {
+        TokenAtOffset::None => None,
+        TokenAtOffset::Single(token) => Some(token),
+        TokenAtOffset::Between(left, right) => {
+            if right.kind() == SyntaxKind::Ident {
+                Some(right)
+            } else {
+                Some(left)
+            }
+        }
+    }
+    .filter(|t| t.kind() == SyntaxKind::Ident)
+}
+
+/// Gets the type of a member of the given type.
+///
+/// The member may be a struct member, a call output, or `left` or `right` of a
+/// pair.
+pub(crate) fn member_type(types: &Types, ty: Type, member: &str) -> Option<Type> {
+    let Type::Compound(ty) = ty else {
+        return None;
+    };
+
+    match types.type_definition(ty.definition()) {
+        CompoundTypeDef::Struct(ty) => ty.members().get(member).copied(),
+        CompoundTypeDef::Call(ty) => ty.outputs().get(member).copied(),
+        CompoundTypeDef::Pair(ty) => match member {
+            "left" => Some(ty.first_type()),
+            "right" => Some(ty.second_type()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Gets the type of a name reference or member access expression.
+///
+/// Returns `None` if the expression is not a name reference or member access
+/// or if the type could not be determined.
+pub(crate) fn expr_type(scope: &DocumentScope, expr: &SyntaxNode) -> Option<Type> {
+    match expr.kind() {
+        SyntaxKind::NameRefNode => {
+            let name = expr
+                .children_with_tokens()
+                .find(|c| c.kind() == SyntaxKind::Ident)?;
+            scope
+                .find_scope_by_position(expr.text_range().start().into())?
+                .lookup(&name.to_string())?
+                .ty()
+        }
+        SyntaxKind::AccessExprNode => {
+            let target = expr.first_child()?;
+            let member = expr.last_token()?;
+            member_type(scope.types(), expr_type(scope, &target)?, member.text())
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,114 @@
+//! Utilities for testing the language server features.
+//!
+//! Test sources are ASCII, so the byte offsets of the sources are also the
+//! UTF-16 offsets used by positions.
+
+use std::fs;
+
+use tempfile::TempDir;
+use tower_lsp::lsp_types::Position;
+use wdl_analysis::AnalysisResult;
+use wdl_analysis::Analyzer;
+use wdl_ast::Validator;
+use wdl_lint::LintVisitor;
+
+/// The marker of the cursor position in a test source.
+const CURSOR: char = '|';
+
+/// Analyzes the given documents.
+///
+/// Each document is a tuple of its file name and source.
+///
+/// Returns the temporary directory containing the documents and the analysis
+/// results; the directory is deleted when dropped.
+pub async fn analyze(documents: &[(&str, &str)]) -> (TempDir, Vec<AnalysisResult>) {
+    analyze_documents(documents, false).await
+}
+
+/// Analyzes the given documents with the lint rules enabled.
+///
+/// Each document is a tuple of its file name and source.
+///
+/// Returns the temporary directory containing the documents and the analysis
+/// results; the directory is deleted when dropped.
+pub async fn analyze_with_lints(documents: &[(&str, &str)]) -> (TempDir, Vec<AnalysisResult>) {
+    analyze_documents(documents, true).await
+}
+
+/// Analyzes the given documents, optionally with the lint rules enabled.
+async fn analyze_documents(
+    documents: &[(&str, &str)],
+    lint: bool,
+) -> (TempDir, Vec<AnalysisResult>) {
+    let dir = TempDir::new().expect("failed to create temporary directory");
+    let mut paths = Vec::new();
+    for (name, source) in documents {
+        let path = dir.path().join(name);
+        fs::write(&path, source).expect("failed to create test file");
+        paths.push(path);
+    }
+
+    let analyzer = Analyzer::new_with_validator(
+        |_: (), _, _, _| async {},
+        move || {
+            let mut validator = Validator::default();
+            if lint {
+                validator.add_visitor(LintVisitor::default());
+            }
+
+            validator
+        },
+    );
+    analyzer
+        .add_documents(paths)
+        .await
+        .expect("should add documents");
+    let results = analyzer.analyze(()).await.expect("should analyze");
+    assert_eq!(results.len(), documents.len());
+    (dir, results)
+}
+
+/// Gets the analysis result for the given file name.
+pub fn result<'a>(results: &'a [AnalysisResult], name: &str) -> &'a AnalysisResult {
+    results
+        .iter()
+        .find(|r| r.uri().path().ends_with(&format!("/{name}")))
+        .expect("should have result")
+}
+
+/// Gets the position of the given offset in the given source.
+pub fn position(source: &str, offset: usize) -> Position {
+    let line = source[..offset].matches('\n').count();
+    let character = offset - source[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    Position::new(line as u32, character as u32)
+}
+
+/// Gets the position of the start of the `nth` occurrence of `needle` in the
+/// given source.
+pub fn position_of(source: &str, needle: &str, nth: usize) -> Position {
+    let offset = source
+        .match_indices(needle)
+        .nth(nth)
+        .expect("should find needle")
+        .0;
+    position(source, offset)
+}
+
+/// Gets the offset of the given position in the given source.
+pub fn offset_of(source: &str, position: Position) -> usize {
+    let line: usize = source
+        .split_inclusive('\n')
+        .take(position.line as usize)
+        .map(str::len)
+        .sum();
+    line + position.character as usize
+}
+
+/// Removes the `|` cursor marker from the given source.
+///
+/// Returns the source without the marker and the position of the marker.
+pub fn cursor(source: &str) -> (String, Position) {
+    let offset = source.find(CURSOR).expect("should have a cursor marker");
+    let position = position(source, offset);
+    (source.replacen(CURSOR, "", 1), position)
+}
@@ -1,7 +1,9 @@
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::fs;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -10,19 +12,46 @@ use anyhow::Context;
 use anyhow::Result;
 use clap::Args;
 use clap::Parser;
+use clap::ValueEnum;
 use codespan_reporting::files::SimpleFile;
 use codespan_reporting::term::emit;
 use codespan_reporting::term::termcolor::ColorChoice;
 use codespan_reporting::term::termcolor::StandardStream;
 use codespan_reporting::term::Config;
 use colored::Colorize;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
+use rayon::prelude::*;
+use wdl::ast::v1::DocumentItem;
+use wdl::ast::AstNode;
+use wdl::ast::AstToken;
 use wdl::ast::Diagnostic;
 use wdl::ast::Document;
+use wdl::ast::Severity;
+use wdl::ast::SourceLines;
 use wdl::ast::SyntaxNode;
 use wdl::ast::Validator;
+use wdl::engine::v1::TaskEvaluator;
+use wdl::engine::v1::WorkflowEvaluator;
+use wdl::engine::Engine;
+use wdl::engine::EvaluationError;
+use wdl::engine::LocalTaskExecutionBackend;
+use wdl::engine::TaskInputs;
+use wdl::engine::WorkflowInputs;
+use wdl::format::Config as FormatConfig;
+use wdl::format::Formatter;
+use wdl::format::Indent;
+use wdl::lint::sarif_log;
+use wdl::lint::Baseline;
+use wdl::lint::Config as LintConfig;
 use wdl::lint::LintVisitor;
+use wdl::lint::SarifFile;
+use wdl::lint::SarifTool;
+use wdl_analysis::inputs::inputs_template;
+use wdl_analysis::inputs::validate_inputs;
+use wdl_analysis::path_to_uri;
 use wdl_analysis::AnalysisResult;
 use wdl_analysis::Analyzer;
 
@@ -51,37 +80,294 @@ fn emit_diagnostics(path: &str, source: &str, diagnostics: &[Diagnostic]) -> Res
     Ok(())
 }
 
-async fn analyze(path: PathBuf, lint: bool) -> Result<Vec<AnalysisResult>> {
+/// The format of reported diagnostics.
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum DiagnosticFormat {
+    /// The diagnostics are rendered for display in a terminal.
+    #[default]
+    Human,
+    /// The diagnostics are output as a JSON array.
+    Json,
+    /// The diagnostics are output as a SARIF log.
+    Sarif,
+}
+
+/// Represents the diagnostics reported for a source file.
+struct FileDiagnostics<'a> {
+    /// The path of the source file, as displayed to the user.
+    path: Cow<'a, str>,
+    /// The source of the file.
+    source: Cow<'a, str>,
+    /// The diagnostics reported for the file.
+    diagnostics: Cow<'a, [Diagnostic]>,
+}
+
+/// Reports the diagnostics of the given files in the given format.
+///
+/// The JSON and SARIF formats print a single document for all of the files,
+/// even if there are no diagnostics.
+fn report_diagnostics(format: DiagnosticFormat, files: &[FileDiagnostics<'_>]) -> Result<()> {
+    match format {
+        DiagnosticFormat::Human => {
+            for file in files {
+                if !file.diagnostics.is_empty() {
+                    emit_diagnostics(&file.path, &file.source, &file.diagnostics)?;
+                }
+            }
+        }
+        DiagnosticFormat::Json => {
+            let mut diagnostics = Vec::new();
+            for file in files {
+                let lines = SourceLines::new(&file.source);
+                for diagnostic in file.diagnostics.iter() {
+                    let mut value = serde_json::to_value(diagnostic.located(&lines))?;
+                    value["file"] = file.path.as_ref().into();
+                    diagnostics.push(value);
+                }
+            }
+
+            println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+        }
+        DiagnosticFormat::Sarif => {
+            let tool = SarifTool {
+                name: "wdl",
+                version: env!("CARGO_PKG_VERSION"),
+                information_uri: env!("CARGO_PKG_REPOSITORY"),
+            };
+            let files: Vec<_> = files
+                .iter()
+                .map(|f| SarifFile {
+                    path: &f.path,
+                    source: &f.source,
+                    diagnostics: &f.diagnostics,
+                })
+                .collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&sarif_log(tool, &files))?
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// The minimum severity of diagnostics that causes a command to fail.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DenyLevel {
+    /// Fail if any errors are reported.
+    Errors,
+    /// Fail if any errors or warnings are reported.
+    Warnings,
+    /// Fail if any diagnostics are reported.
+    Notes,
+}
+
+impl DenyLevel {
+    /// Determines if diagnostics of the given severity are denied.
+    fn denies(self, severity: Severity) -> bool {
+        match self {
+            Self::Errors => severity == Severity::Error,
+            Self::Warnings => severity != Severity::Note,
+            Self::Notes => true,
+        }
+    }
+}
+
+/// Represents a summary of the diagnostics reported for source files.
+#[derive(Default)]
+struct Summary {
+    /// The number of files that were checked.
+    files: usize,
+    /// The number of errors reported.
+    errors: usize,
+    /// The number of warnings reported.
+    warnings: usize,
+    /// The number of notes reported.
+    notes: usize,
+    /// The number of diagnostics suppressed by a lint baseline.
+    baselined: usize,
+}
+
+impl Summary {
+    /// Adds the diagnostics reported for a file to the summary.
+    fn add(&mut self, diagnostics: &[Diagnostic]) {
+        self.files += 1;
+        for diagnostic in diagnostics {
+            match diagnostic.severity() {
+                Severity::Error => self.errors += 1,
+                Severity::Warning => self.warnings += 1,
+                Severity::Note => self.notes += 1,
+            }
+        }
+    }
+
+    /// Prints the summary to stderr and fails if any diagnostics of a denied
+    /// severity were reported.
+    fn finish(&self, deny: DenyLevel) -> Result<()> {
+        /// Formats a count of things with the given singular name.
+        fn count(count: usize, name: &str) -> String {
+            format!("{count} {name}{s}", s = if count == 1 { "" } else { "s" })
+        }
+
+        eprintln!(
+            "checked {files}: {errors}, {warnings}, {notes}{baselined}",
+            files = count(self.files, "file"),
+            errors = count(self.errors, "error"),
+            warnings = count(self.warnings, "warning"),
+            notes = count(self.notes, "note"),
+            baselined = if self.baselined > 0 {
+                format!(
+                    " ({count} suppressed by the baseline)",
+                    count = self.baselined
+                )
+            } else {
+                String::new()
+            }
+        );
+
+        let denied = [
+            (Severity::Error, self.errors),
+            (Severity::Warning, self.warnings),
+            (Severity::Note, self.notes),
+        ]
+        .into_iter()
+        .filter(|(severity, _)| deny.denies(*severity))
+        .map(|(_, count)| count)
+        .sum::<usize>();
+
+        if denied > 0 {
+            bail!(
+                "aborting due to previous {denied} diagnostic{s}",
+                s = if denied == 1 { "" } else { "s" }
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The source files to operate on.
+#[derive(Args)]
+pub struct SourceArgs {
+    /// The paths to the source WDL files or directories.
+    ///
+    /// Directories are searched recursively for `.wdl` files; files ignored
+    /// by `.gitignore` and hidden files are skipped.
+    #[clap(value_name = "PATH", required = true)]
+    pub paths: Vec<PathBuf>,
+
+    /// Excludes files in searched directories that match the given glob.
+    ///
+    /// Globs use `.gitignore` syntax and may be specified multiple times.
+    #[clap(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+}
+
+impl SourceArgs {
+    /// Finds the source files to operate on.
+    ///
+    /// Files given explicitly are always included; the files found in
+    /// directories are sorted by path.
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for path in &self.paths {
+            if path.as_os_str() == "-" || !path.is_dir() {
+                files.push(path.clone());
+                continue;
+            }
+
+            let mut overrides = OverrideBuilder::new(path);
+            for glob in &self.exclude {
+                overrides
+                    .add(&format!("!{glob}"))
+                    .with_context(|| format!("invalid exclude glob `{glob}`"))?;
+            }
+
+            let walker = WalkBuilder::new(path)
+                .require_git(false)
+                .overrides(overrides.build().context("invalid exclude globs")?)
+                .build();
+
+            let start = files.len();
+            for entry in walker {
+                let entry = entry.with_context(|| {
+                    format!("failed to read directory `{path}`", path = path.display())
+                })?;
+
+                if entry.file_type().is_some_and(|t| t.is_file())
+                    && entry.path().extension().is_some_and(|e| e == "wdl")
+                {
+                    files.push(entry.into_path());
+                }
+            }
+
+            files[start..].sort();
+        }
+
+        let mut seen = HashSet::new();
+        files.retain(|f| seen.insert(f.clone()));
+        Ok(files)
+    }
+}
+
+/// Loads the lint configuration for the document at the given path.
+///
+/// If a configuration file is not given, the configuration is discovered by
+/// walking up from the document's directory (or the current directory when
+/// reading from stdin); the default configuration is used if none is found.
+fn lint_config(path: &Path, config: Option<&Path>) -> Result<LintConfig> {
+    if let Some(config) = config {
+        return LintConfig::load(config);
+    }
+
+    let start = if path.as_os_str() == "-" {
+        std::env::current_dir().context("failed to determine the current directory")?
+    } else {
+        path.to_path_buf()
+    };
+
+    Ok(LintConfig::discover(&start)?.unwrap_or_default())
+}
+
+/// Analyzes the given source files with a single analyzer and reports the
+/// diagnostics of every analyzed document in the given format.
+///
+/// If linting, the lint configuration of each document is either loaded from
+/// the given configuration file or discovered from the document's directory.
+///
+/// Returns the analysis results and a summary of the reported diagnostics.
+async fn analyze(
+    paths: Vec<PathBuf>,
+    lint: bool,
+    config: Option<&Path>,
+    format: DiagnosticFormat,
+) -> Result<(Vec<AnalysisResult>, Summary)> {
+    let config = config.map(LintConfig::load).transpose()?;
+
     let bar = ProgressBar::new(0);
     bar.set_style(
         ProgressStyle::with_template("[{elapsed_precise}] {bar:40.cyan/blue} {msg} {pos}/{len}")
             .unwrap(),
     );
 
-    let analyzer = Analyzer::new_with_validator(
-        move |bar: ProgressBar, kind, completed, total| async move {
-            if completed == 0 {
-                bar.set_length(total.try_into().unwrap());
-                bar.set_message(format!("{kind}"));
-            }
-            bar.set_position(completed.try_into().unwrap());
-        },
-        move || {
-            let mut validator = Validator::default();
-            if lint {
-                validator.add_visitor(LintVisitor::default());
-            }
-            validator
-        },
-    );
+    let analyzer = Analyzer::new(move |bar: ProgressBar, kind, completed, total| async move {
+        if completed == 0 {
+            bar.set_length(total.try_into().unwrap());
+            bar.set_message(format!("{kind}"));
+        }
+        bar.set_position(completed.try_into().unwrap());
+    });
 
-    analyzer.add_documents(vec![path]).await?;
+    analyzer.add_documents(paths).await?;
     let results = analyzer
         .analyze(bar.clone())
         .await
         .context("failed to analyze documents")?;
 
     let cwd = std::env::current_dir().ok();
+    let mut files = Vec::new();
+    let mut summary = Summary::default();
     for result in &results {
         let path = result.uri().to_file_path().ok();
 
@@ -95,25 +381,189 @@ async fn analyze(path: PathBuf, lint: bool) -> Result<Vec<AnalysisResult>> {
             (Some(cwd), Some(path)) => path.strip_prefix(cwd).unwrap_or(path).to_string_lossy(),
         };
 
+        let source = result
+            .parse_result()
+            .root()
+            .map(|n| SyntaxNode::new_root(n.clone()).text().to_string())
+            .unwrap_or(String::new());
+
         let diagnostics: Cow<'_, [Diagnostic]> = match result.parse_result().error() {
             Some(e) => vec![Diagnostic::error(format!("failed to read `{path}`: {e:#}"))].into(),
+            None if lint => {
+                // Documents that are not local files are linted with the
+                // configuration of the current directory
+                let document_path = result
+                    .uri()
+                    .to_file_path()
+                    .unwrap_or_else(|_| PathBuf::from("-"));
+                let discovered;
+                let config = match &config {
+                    Some(config) => config,
+                    None => {
+                        discovered = lint_config(&document_path, None)?;
+                        &discovered
+                    }
+                };
+
+                let mut diagnostics = result.diagnostics().to_vec();
+                let (document, parse_diagnostics) = Document::parse(&source);
+                if parse_diagnostics.is_empty() {
+                    let mut validator = Validator::empty();
+                    validator.add_visitor(LintVisitor::with_config(config, &document_path));
+                    diagnostics.extend(validator.validate(&document).err().unwrap_or_default());
+                    diagnostics.sort();
+                }
+
+                diagnostics.into()
+            }
             None => result.diagnostics().into(),
         };
 
+        summary.add(&diagnostics);
         if !diagnostics.is_empty() {
-            emit_diagnostics(
-                &path,
-                &result
-                    .parse_result()
-                    .root()
-                    .map(|n| SyntaxNode::new_root(n.clone()).text().to_string())
-                    .unwrap_or(String::new()),
-                &diagnostics,
-            )?;
+            files.push(FileDiagnostics {
+                path: path.into_owned().into(),
+                source: source.into(),
+                diagnostics,
+            });
         }
     }
 
-    Ok(results)
+    report_diagnostics(format, &files)?;
+    Ok((results, summary))
+}
+
+/// The number of unchanged lines to show around changes in a diff.
+const DIFF_CONTEXT: usize = 3;
+
+/// Prints a unified diff between the given old and new source.
+///
+/// Nothing is printed if the sources are the same.
+fn print_diff(path: &str, old: &str, new: &str) {
+    if old == new {
+        return;
+    }
+
+    let old_lines: Vec<_> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<_> = new.split_inclusive('\n').collect();
+    let lines = diff::slice(&old_lines, &new_lines);
+
+    // Calculate the line numbers at the start of each diff line
+    let mut numbers = Vec::with_capacity(lines.len() + 1);
+    let (mut old_line, mut new_line) = (0, 0);
+    for line in &lines {
+        numbers.push((old_line, new_line));
+        match line {
+            diff::Result::Left(_) => old_line += 1,
+            diff::Result::Right(_) => new_line += 1,
+            diff::Result::Both(..) => {
+                old_line += 1;
+                new_line += 1;
+            }
+        }
+    }
+
+    numbers.push((old_line, new_line));
+
+    // Group the changes into hunks with surrounding context
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if matches!(line, diff::Result::Both(..)) {
+            continue;
+        }
+
+        let start = i.saturating_sub(DIFF_CONTEXT);
+        let end = (i + DIFF_CONTEXT + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some((_, prev)) if start <= *prev => *prev = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    println!("{}", format!("--- {path}").bold());
+    println!("{}", format!("+++ {path}").bold());
+    for (start, end) in hunks {
+        let (old_start, new_start) = numbers[start];
+        let (old_end, new_end) = numbers[end];
+        println!(
+            "{}",
+            format!(
+                "@@ -{old},{old_count} +{new},{new_count} @@",
+                old = old_start + 1,
+                old_count = old_end - old_start,
+                new = new_start + 1,
+                new_count = new_end - new_start
+            )
+            .cyan()
+        );
+
+        for line in &lines[start..end] {
+            let (prefix, text) = match line {
+                diff::Result::Left(text) => ('-', *text),
+                diff::Result::Right(text) => ('+', *text),
+                diff::Result::Both(text, _) => (' ', *text),
+            };
+
+            let line = format!("{prefix}{text}", text = text.trim_end_matches(['\r', '\n']));
+            match prefix {
+                '-' => println!("{}", line.red()),
+                '+' => println!("{}", line.green()),
+                _ => println!("{line}"),
+            }
+
+            if !text.ends_with('\n') {
+                println!("\\ No newline at end of file");
+            }
+        }
+    }
+}
+
+/// Reads a JSON object of inputs from the given path.
+fn read_inputs(path: &Path) -> Result<serde_json::Map<String, serde_json::Value>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read inputs file `{path}`", path = path.display()))?;
+    match serde_json::from_str(&contents).with_context(|| {
+        format!(
+            "failed to parse inputs file `{path}`",
+            path = path.display()
+        )
+    })? {
+        serde_json::Value::Object(object) => Ok(object),
+        _ => bail!(
+            "inputs file `{path}` does not contain a JSON object",
+            path = path.display()
+        ),
+    }
+}
+
+/// Finds the analysis result for the given path.
+///
+/// Returns an error if the document could not be read or if any analyzed
+/// document has an error.
+fn find_result<'a>(results: &'a [AnalysisResult], path: &Path) -> Result<&'a AnalysisResult> {
+    let uri = path_to_uri(path).context("failed to convert path to a URI")?;
+    let result = results
+        .iter()
+        .find(|r| **r.uri() == uri)
+        .context("failed to find the analysis result for the document")?;
+
+    if let Some(e) = result.parse_result().error() {
+        bail!("failed to read `{path}`: {e:#}", path = path.display());
+    }
+
+    let count = results
+        .iter()
+        .flat_map(|r| r.diagnostics())
+        .filter(|d| d.severity() == Severity::Error)
+        .count();
+    if count > 0 {
+        bail!(
+            "aborting due to previous {count} error{s}",
+            s = if count == 1 { "" } else { "s" }
+        );
+    }
+
+    Ok(result)
 }
 
 /// Reads source from the given path.
@@ -155,49 +605,525 @@ impl ParseCommand {
     }
 }
 
-/// Checks a WDL source file for errors.
+/// Checks WDL source files for errors.
 #[derive(Args)]
 #[clap(disable_version_flag = true)]
 pub struct CheckCommand {
-    /// The path to the source WDL file.
-    #[clap(value_name = "PATH")]
-    pub path: PathBuf,
+    #[clap(flatten)]
+    pub sources: SourceArgs,
+
+    /// The format of reported diagnostics.
+    #[clap(long, value_enum, default_value_t)]
+    pub format: DiagnosticFormat,
+
+    /// The minimum severity of diagnostics that causes the command to fail.
+    #[clap(long, value_enum, value_name = "LEVEL", default_value = "errors")]
+    pub deny: DenyLevel,
 }
 
 impl CheckCommand {
     async fn exec(self) -> Result<()> {
-        analyze(self.path, false).await?;
-        Ok(())
+        let (_, summary) = analyze(self.sources.files()?, false, None, self.format).await?;
+        summary.finish(self.deny)
     }
 }
 
-/// Runs lint rules against a WDL source file.
+/// Gets the name of a source file as recorded in a lint baseline.
+///
+/// The name is the path of the file relative to the directory containing the
+/// baseline, if possible, so that the baseline does not depend on the current
+/// directory.
+fn baseline_file(baseline: &Path, path: &Path) -> String {
+    let dir = match baseline.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let name = match (fs::canonicalize(dir), fs::canonicalize(path)) {
+        (Ok(dir), Ok(path)) => path
+            .strip_prefix(&dir)
+            .map(Path::to_path_buf)
+            .unwrap_or(path),
+        _ => path.to_path_buf(),
+    };
+
+    name.to_string_lossy().replace('\\', "/")
+}
+
+/// Represents the result of linting a source file.
+struct Linted {
+    /// The path of the source file.
+    path: PathBuf,
+    /// The source of the file before any fixes were applied.
+    original: String,
+    /// The source of the file after any fixes were applied.
+    source: String,
+    /// The number of diagnostics that were fixed.
+    fixed: usize,
+    /// Whether or not the source parsed without diagnostics.
+    parsed: bool,
+    /// The diagnostics of the (fixed) source.
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Runs lint rules against WDL source files.
+///
+/// The files are linted in parallel; the lint configuration of each file is
+/// discovered from the file's directory.
 #[derive(Args)]
 #[clap(disable_version_flag = true)]
 pub struct LintCommand {
+    #[clap(flatten)]
+    pub sources: SourceArgs,
+
+    /// Applies the fixes of lint diagnostics to the files in place.
+    ///
+    /// Any remaining diagnostics are reported after the fixes are applied.
+    #[clap(long, conflicts_with = "diff")]
+    pub fix: bool,
+
+    /// Prints a diff of the fixes of lint diagnostics without modifying the
+    /// files.
+    #[clap(long)]
+    pub diff: bool,
+
+    /// The path to the lint configuration file to use.
+    ///
+    /// Defaults to the first `.wdl-lint.toml` or `wdl.toml` file found by
+    /// walking up from the directory of each source file.
+    #[clap(long, value_name = "CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// The format of reported diagnostics.
+    #[clap(long, value_enum, default_value_t, conflicts_with = "diff")]
+    pub format: DiagnosticFormat,
+
+    /// The minimum severity of diagnostics that causes the command to fail.
+    #[clap(long, value_enum, value_name = "LEVEL", default_value = "notes")]
+    pub deny: DenyLevel,
+
+    /// Reports only the lint diagnostics that are not recorded in the given
+    /// baseline file.
+    ///
+    /// Recorded diagnostics that are no longer reported are flagged so that
+    /// the baseline may be updated.
+    #[clap(long, value_name = "BASELINE", conflicts_with_all = ["diff", "write_baseline"])]
+    pub baseline: Option<PathBuf>,
+
+    /// Records the current lint diagnostics in the given baseline file.
+    ///
+    /// The recorded diagnostics are not reported.
+    #[clap(long, value_name = "BASELINE", conflicts_with = "diff")]
+    pub write_baseline: Option<PathBuf>,
+}
+
+impl LintCommand {
+    /// Lints a single source file with the given configuration.
+    ///
+    /// If a configuration is not given, it is discovered for the file.
+    fn lint(&self, path: &Path, config: Option<&LintConfig>) -> Result<Linted> {
+        let discovered;
+        let config = match config {
+            Some(config) => config,
+            None => {
+                discovered = lint_config(path, None)?;
+                &discovered
+            }
+        };
+
+        let original = read_source(path)?;
+        let mut linted = Linted {
+            path: path.to_path_buf(),
+            source: original.clone(),
+            original,
+            fixed: 0,
+            parsed: false,
+            diagnostics: Vec::new(),
+        };
+
+        if self.fix || self.diff {
+            match wdl::lint::fix(&linted.source, LintVisitor::with_config(config, path)) {
+                Ok(fixed) => {
+                    linted.source = fixed.source;
+                    linted.fixed = fixed.fixed;
+                    linted.parsed = true;
+                    linted.diagnostics = fixed.diagnostics;
+                }
+                Err(diagnostics) => linted.diagnostics = diagnostics,
+            }
+
+            return Ok(linted);
+        }
+
+        let (document, diagnostics) = Document::parse(&linted.source);
+        if !diagnostics.is_empty() {
+            linted.diagnostics = diagnostics;
+            return Ok(linted);
+        }
+
+        let mut validator = Validator::default();
+        validator.add_visitor(LintVisitor::with_config(config, path));
+        linted.parsed = true;
+        linted.diagnostics = validator.validate(&document).err().unwrap_or_default();
+        Ok(linted)
+    }
+
+    async fn exec(self) -> Result<()> {
+        let files = self.sources.files()?;
+        if self.fix && files.iter().any(|f| f.as_os_str() == "-") {
+            bail!("cannot fix stdin in place");
+        }
+
+        let config = self.config.as_deref().map(LintConfig::load).transpose()?;
+
+        let results = files
+            .par_iter()
+            .map(|path| self.lint(path, config.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut baseline = match (&self.baseline, &self.write_baseline) {
+            (Some(path), _) => Some((path, Baseline::load(path)?)),
+            (None, Some(path)) => {
+                let mut baseline = Baseline::default();
+                for linted in &results {
+                    baseline.add(
+                        &baseline_file(path, &linted.path),
+                        &linted.source,
+                        &linted.diagnostics,
+                    );
+                }
+
+                baseline.save(path)?;
+
+                let count = baseline.entries().iter().map(|e| e.count()).sum::<usize>();
+                eprintln!(
+                    "recorded {count} lint diagnostic{s} in `{path}`",
+                    s = if count == 1 { "" } else { "s" },
+                    path = path.display()
+                );
+                Some((path, baseline))
+            }
+            (None, None) => None,
+        };
+
+        let mut reported = Vec::new();
+        let mut summary = Summary::default();
+        for linted in &results {
+            let path = linted.path.to_string_lossy();
+
+            // When printing diffs, only diagnostics of files that failed to
+            // parse are reported
+            if self.diff {
+                if linted.parsed {
+                    print_diff(&path, &linted.original, &linted.source);
+                    continue;
+                }
+            } else if self.fix && linted.fixed > 0 {
+                fs::write(&linted.path, &linted.source)
+                    .with_context(|| format!("failed to write source file `{path}`"))?;
+
+                eprintln!(
+                    "fixed {count} diagnostic{s} in `{path}`",
+                    count = linted.fixed,
+                    s = if linted.fixed == 1 { "" } else { "s" },
+                );
+            }
+
+            let diagnostics: Cow<'_, [Diagnostic]> = match &mut baseline {
+                Some((baseline_path, baseline)) => {
+                    let diagnostics = baseline.suppress(
+                        &baseline_file(baseline_path, &linted.path),
+                        &linted.source,
+                        linted.diagnostics.clone(),
+                    );
+                    summary.baselined += linted.diagnostics.len() - diagnostics.len();
+                    diagnostics.into()
+                }
+                None => linted.diagnostics.as_slice().into(),
+            };
+
+            summary.add(&diagnostics);
+            reported.push(FileDiagnostics {
+                path,
+                source: linted.source.as_str().into(),
+                diagnostics,
+            });
+        }
+
+        report_diagnostics(self.format, &reported)?;
+
+        if let Some((path, baseline)) = &baseline {
+            let mut fixed = 0;
+            for (entry, count) in baseline.fixed() {
+                eprintln!(
+                    "{warning}: {count} `{rule}` diagnostic{s} recorded in the baseline for \
+                     `{file}` {verb} no longer reported: {message}",
+                    warning = if std::io::stderr().is_terminal() {
+                        "warning".yellow().bold()
+                    } else {
+                        "warning".normal()
+                    },
+                    rule = entry.rule(),
+                    s = if count == 1 { "" } else { "s" },
+                    file = entry.file(),
+                    verb = if count == 1 { "is" } else { "are" },
+                    message = entry.message(),
+                );
+                fixed += count;
+            }
+
+            if fixed > 0 {
+                eprintln!(
+                    "update the baseline with `--write-baseline {path}` to remove the fixed \
+                     diagnostics",
+                    path = path.display()
+                );
+            }
+        }
+
+        if self.diff {
+            let failed = results.iter().filter(|l| !l.parsed).count();
+            if failed > 0 {
+                bail!(
+                    "failed to fix {failed} file{s}",
+                    s = if failed == 1 { "" } else { "s" }
+                );
+            }
+
+            return Ok(());
+        }
+
+        summary.finish(self.deny)
+    }
+}
+
+/// Analyzes WDL source files.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct AnalyzeCommand {
+    #[clap(flatten)]
+    pub sources: SourceArgs,
+
+    /// Whether or not to run lints as part of analysis.
+    #[clap(long)]
+    pub lint: bool,
+
+    /// The path to the lint configuration file to use.
+    ///
+    /// Defaults to the first `.wdl-lint.toml` or `wdl.toml` file found by
+    /// walking up from the directory of each analyzed document.
+    #[clap(long, value_name = "CONFIG", requires = "lint")]
+    pub config: Option<PathBuf>,
+
+    /// The format of reported diagnostics.
+    #[clap(long, value_enum, default_value_t)]
+    pub format: DiagnosticFormat,
+
+    /// The minimum severity of diagnostics that causes the command to fail.
+    #[clap(long, value_enum, value_name = "LEVEL", default_value = "errors")]
+    pub deny: DenyLevel,
+}
+
+impl AnalyzeCommand {
+    async fn exec(self) -> Result<()> {
+        let (results, summary) = analyze(
+            self.sources.files()?,
+            self.lint,
+            self.config.as_deref(),
+            self.format,
+        )
+        .await?;
+
+        // The analysis results are only printed with human readable output so
+        // that JSON and SARIF output remain valid documents
+        if self.format == DiagnosticFormat::Human {
+            println!("{:#?}", results);
+        }
+
+        summary.finish(self.deny)
+    }
+}
+
+/// Runs a workflow or task in a WDL source file.
+///
+/// The outputs of the workflow or task are printed as a JSON object.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct RunCommand {
     /// The path to the source WDL file.
     #[clap(value_name = "PATH")]
     pub path: PathBuf,
+
+    /// The name of the task to run.
+    ///
+    /// Defaults to the workflow in the document or, if the document does not
+    /// contain a workflow, the only task in the document.
+    #[clap(long, value_name = "NAME")]
+    pub task: Option<String>,
+
+    /// The path to a JSON file containing the inputs.
+    #[clap(short, long, value_name = "INPUTS")]
+    pub inputs: Option<PathBuf>,
+
+    /// The directory in which to run the workflow or task.
+    #[clap(short, long, value_name = "DIR", default_value = "out")]
+    pub output: PathBuf,
+
+    /// The maximum number of task commands to execute concurrently.
+    ///
+    /// Defaults to the number of available CPUs.
+    #[clap(long, value_name = "N")]
+    pub max_concurrency: Option<NonZeroUsize>,
 }
 
-impl LintCommand {
+impl RunCommand {
     async fn exec(self) -> Result<()> {
-        let source = read_source(&self.path)?;
-        let (document, diagnostics) = Document::parse(&source);
-        if !diagnostics.is_empty() {
-            emit_diagnostics(&self.path.to_string_lossy(), &source, &diagnostics)?;
+        let (results, _) = analyze(
+            vec![self.path.clone()],
+            false,
+            None,
+            DiagnosticFormat::Human,
+        )
+        .await?;
+        let result = find_result(&results, &self.path)?;
 
-            bail!(
-                "aborting due to previous {count} diagnostic{s}",
-                count = diagnostics.len(),
-                s = if diagnostics.len() == 1 { "" } else { "s" }
-            );
+        let document = result
+            .parse_result()
+            .document()
+            .expect("should have a document")
+            .ast()
+            .into_v1()
+            .context("the document is not a supported WDL version")?;
+
+        let workflow = document.items().find_map(|i| match i {
+            DocumentItem::Workflow(w) => Some(w),
+            _ => None,
+        });
+        let mut tasks = document.items().filter_map(|i| match i {
+            DocumentItem::Task(t) => Some(t),
+            _ => None,
+        });
+
+        let task = match (&self.task, &workflow) {
+            (Some(name), _) => {
+                Some(tasks.find(|t| t.name().as_str() == name).with_context(|| {
+                    format!("the document does not contain a task named `{name}`")
+                })?)
+            }
+            (None, Some(_)) => None,
+            (None, None) => {
+                let task = tasks
+                    .next()
+                    .context("the document does not contain a workflow or task")?;
+                if tasks.next().is_some() {
+                    bail!(
+                        "the document contains more than one task: use `--task` to specify the \
+                         task to run"
+                    );
+                }
+                Some(task)
+            }
+        };
+
+        let mut engine = Engine::new(LocalTaskExecutionBackend::new());
+        if let Some(max) = self.max_concurrency {
+            engine = engine.with_max_concurrency(max);
         }
+        let object = match &self.inputs {
+            Some(path) => read_inputs(path)?,
+            None => Default::default(),
+        };
+        let (name, outputs) = match task {
+            Some(task) => {
+                let name = task.name();
+                let inputs = TaskInputs::parse_json(engine.types_mut(), name.as_str(), object)?;
+                let outputs = TaskEvaluator::new(&mut engine)
+                    .evaluate(result.scope(), &task, &inputs, &self.output)
+                    .await
+                    .map(|t| t.outputs().clone());
+                (name, outputs)
+            }
+            None => {
+                let name = workflow.expect("should have a workflow").name();
+                let inputs = WorkflowInputs::parse_json(engine.types_mut(), name.as_str(), object)?;
+                let outputs = WorkflowEvaluator::new(&mut engine)
+                    .evaluate(&results, result, &inputs, &self.output)
+                    .await
+                    .map(|w| w.outputs().clone());
+                (name, outputs)
+            }
+        };
 
-        let mut validator = Validator::default();
-        validator.add_visitor(LintVisitor::default());
-        if let Err(diagnostics) = validator.validate(&document) {
-            emit_diagnostics(&self.path.to_string_lossy(), &source, &diagnostics)?;
+        match outputs {
+            Ok(outputs) => {
+                let outputs = outputs
+                    .iter()
+                    .map(|(k, v)| Ok((format!("{name}.{k}", name = name.as_str()), v.to_json()?)))
+                    .collect::<Result<serde_json::Map<_, _>>>()?;
+                println!(
+                    "{outputs}",
+                    outputs = serde_json::to_string_pretty(&outputs)?
+                );
+                Ok(())
+            }
+            Err(EvaluationError::Source(diagnostic)) => {
+                emit_diagnostics(
+                    &self.path.to_string_lossy(),
+                    &document.syntax().text().to_string(),
+                    &[diagnostic],
+                )?;
+                bail!("aborting due to evaluation error");
+            }
+            Err(EvaluationError::Other(e)) => Err(e),
+        }
+    }
+}
+
+/// Validates an inputs JSON file against a workflow or task in a WDL source
+/// file.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct ValidateInputsCommand {
+    /// The path to the source WDL file.
+    #[clap(value_name = "PATH")]
+    pub path: PathBuf,
+
+    /// The path to the JSON file containing the inputs.
+    #[clap(value_name = "INPUTS")]
+    pub inputs: PathBuf,
+
+    /// The name of the task to validate the inputs against.
+    ///
+    /// Defaults to the workflow in the document.
+    #[clap(long, value_name = "NAME")]
+    pub task: Option<String>,
+}
+
+impl ValidateInputsCommand {
+    async fn exec(self) -> Result<()> {
+        let (results, _) = analyze(
+            vec![self.path.clone()],
+            false,
+            None,
+            DiagnosticFormat::Human,
+        )
+        .await?;
+        let result = find_result(&results, &self.path)?;
+        let inputs = read_inputs(&self.inputs)?;
+
+        if let Err(diagnostics) = validate_inputs(result.scope(), self.task.as_deref(), &inputs) {
+            // Input diagnostics have no source location, so only print the messages
+            for diagnostic in &diagnostics {
+                eprintln!(
+                    "{error}: {msg}",
+                    error = if std::io::stderr().is_terminal() {
+                        "error".red().bold()
+                    } else {
+                        "error".normal()
+                    },
+                    msg = diagnostic.message()
+                );
+            }
 
             bail!(
                 "aborting due to previous {count} diagnostic{s}",
@@ -206,27 +1132,173 @@ impl LintCommand {
             );
         }
 
+        println!("inputs are valid");
         Ok(())
     }
 }
 
-/// Analyzes a WDL source file.
+/// The format of an inputs template.
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum TemplateFormat {
+    /// The template is output as JSON.
+    #[default]
+    Json,
+    /// The template is output as YAML.
+    Yaml,
+}
+
+/// Generates a template of the inputs to a workflow or task in a WDL source
+/// file.
+///
+/// Each input in the template describes the input's type, whether or not it is
+/// optional, its default expression, and its description from
+/// `parameter_meta`.
 #[derive(Args)]
 #[clap(disable_version_flag = true)]
-pub struct AnalyzeCommand {
+pub struct InputsCommand {
     /// The path to the source WDL file.
     #[clap(value_name = "PATH")]
     pub path: PathBuf,
 
-    /// Whether or not to run lints as part of analysis.
+    /// The name of the task to generate the template for.
+    ///
+    /// Defaults to the workflow in the document.
+    #[clap(long, value_name = "NAME")]
+    pub task: Option<String>,
+
+    /// The format of the template.
+    #[clap(long, value_enum, default_value_t)]
+    pub format: TemplateFormat,
+}
+
+impl InputsCommand {
+    async fn exec(self) -> Result<()> {
+        let (results, _) = analyze(
+            vec![self.path.clone()],
+            false,
+            None,
+            DiagnosticFormat::Human,
+        )
+        .await?;
+        let result = find_result(&results, &self.path)?;
+        let template = inputs_template(&results, result, self.task.as_deref())?;
+
+        match self.format {
+            TemplateFormat::Json => {
+                println!(
+                    "{template}",
+                    template = serde_json::to_string_pretty(&template)?
+                )
+            }
+            TemplateFormat::Yaml => {
+                print!(
+                    "{template}",
+                    template = serde_yaml_ng::to_string(&template)?
+                )
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats WDL source files.
+///
+/// By default, the formatted source is printed to stdout.
+#[derive(Args)]
+#[clap(disable_version_flag = true)]
+pub struct FormatCommand {
+    /// The paths to the source WDL files.
+    #[clap(value_name = "PATH", required = true)]
+    pub paths: Vec<PathBuf>,
+
+    /// Checks if the files are formatted without modifying them.
+    ///
+    /// Exits with a non-zero status if any file is not formatted.
+    #[clap(long, conflicts_with = "in_place")]
+    pub check: bool,
+
+    /// Formats the files in place.
     #[clap(long)]
-    pub lint: bool,
+    pub in_place: bool,
+
+    /// The number of spaces to use for each level of indentation.
+    #[clap(long, value_name = "N", default_value_t = wdl::format::DEFAULT_INDENT_SPACES)]
+    pub indent: usize,
+
+    /// Indents with tabs instead of spaces.
+    #[clap(long, conflicts_with = "indent")]
+    pub tabs: bool,
+
+    /// The maximum line width of the formatted source.
+    #[clap(long, value_name = "N", default_value_t = wdl::format::DEFAULT_MAX_LINE_WIDTH)]
+    pub max_line_width: usize,
 }
 
-impl AnalyzeCommand {
+impl FormatCommand {
     async fn exec(self) -> Result<()> {
-        let results = analyze(self.path, self.lint).await?;
-        println!("{:#?}", results);
+        let indent = if self.tabs {
+            Indent::Tabs
+        } else {
+            Indent::Spaces(self.indent)
+        };
+
+        let formatter = Formatter::new(
+            FormatConfig::default()
+                .with_indent(indent)
+                .with_max_line_width(self.max_line_width),
+        );
+
+        let mut errors = 0;
+        let mut unformatted = 0;
+        for path in &self.paths {
+            if self.in_place && path.as_os_str() == "-" {
+                bail!("cannot format stdin in place");
+            }
+
+            let source = read_source(path)?;
+            let formatted = match formatter.format_source(&source) {
+                Ok(formatted) => formatted,
+                Err(diagnostics) => {
+                    emit_diagnostics(&path.to_string_lossy(), &source, &diagnostics)?;
+                    errors += 1;
+                    continue;
+                }
+            };
+
+            if self.check {
+                if formatted != source {
+                    println!("`{path}` is not formatted", path = path.display());
+                    unformatted += 1;
+                }
+            } else if self.in_place {
+                if formatted != source {
+                    fs::write(path, formatted).with_context(|| {
+                        format!(
+                            "failed to write source file `{path}`",
+                            path = path.display()
+                        )
+                    })?;
+                }
+            } else {
+                print!("{formatted}");
+            }
+        }
+
+        if errors > 0 {
+            bail!(
+                "failed to format {errors} file{s}",
+                s = if errors == 1 { "" } else { "s" }
+            );
+        }
+
+        if unformatted > 0 {
+            bail!(
+                "{unformatted} file{s} not formatted",
+                s = if unformatted == 1 { " is" } else { "s are" }
+            );
+        }
+
         Ok(())
     }
 }
@@ -252,6 +1324,10 @@ enum App {
     Check(CheckCommand),
     Lint(LintCommand),
     Analyze(AnalyzeCommand),
+    Run(RunCommand),
+    ValidateInputs(ValidateInputsCommand),
+    Inputs(InputsCommand),
+    Fmt(FormatCommand),
 }
 
 #[tokio::main]
@@ -266,6 +1342,10 @@ async fn main() -> Result<()> {
         App::Check(cmd) => cmd.exec().await,
         App::Lint(cmd) => cmd.exec().await,
         App::Analyze(cmd) => cmd.exec().await,
+        App::Run(cmd) => cmd.exec().await,
+        App::ValidateInputs(cmd) => cmd.exec().await,
+        App::Inputs(cmd) => cmd.exec().await,
+        App::Fmt(cmd) => cmd.exec().await,
     } {
         eprintln!(
             "{error}: {e:?}",
@@ -280,3 +1360,72 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn source_files() {
+        let dir = TempDir::new().expect("failed to create temporary directory");
+        let root = dir.path();
+        fs::create_dir_all(root.join("nested/skipped")).unwrap();
+        fs::write(root.join(".gitignore"), "ignored.wdl\n").unwrap();
+        for file in [
+            "b.wdl",
+            "a.wdl",
+            "ignored.wdl",
+            "not-wdl.txt",
+            "nested/c.wdl",
+            "nested/skipped/d.wdl",
+        ] {
+            fs::write(root.join(file), "version 1.1\n").unwrap();
+        }
+
+        let args = SourceArgs {
+            paths: vec![
+                root.join("b.wdl"),
+                root.to_path_buf(),
+                root.join("nested/c.wdl"),
+            ],
+            exclude: vec!["skipped/".into()],
+        };
+
+        assert_eq!(
+            args.files().expect("should find files"),
+            [
+                root.join("b.wdl"),
+                root.join("a.wdl"),
+                root.join("nested/c.wdl"),
+            ]
+        );
+    }
+
+    #[test]
+    fn deny_levels() {
+        let diagnostics = [
+            Diagnostic::error("error"),
+            Diagnostic::warning("warning"),
+            Diagnostic::note("note"),
+        ];
+
+        for (diagnostic, denied) in diagnostics.into_iter().zip([
+            [true, true, true],
+            [false, true, true],
+            [false, false, true],
+        ]) {
+            let mut summary = Summary::default();
+            summary.add(&[diagnostic]);
+            for (deny, denied) in [DenyLevel::Errors, DenyLevel::Warnings, DenyLevel::Notes]
+                .into_iter()
+                .zip(denied)
+            {
+                assert_eq!(summary.finish(deny).is_err(), denied);
+            }
+        }
+
+        assert!(Summary::default().finish(DenyLevel::Notes).is_ok());
+    }
+}
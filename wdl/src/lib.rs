@@ -78,6 +78,12 @@ pub use wdl_analysis as analysis;
 #[cfg(feature = "ast")]
 #[doc(inline)]
 pub use wdl_ast as ast;
+#[cfg(feature = "engine")]
+#[doc(inline)]
+pub use wdl_engine as engine;
+#[cfg(feature = "format")]
+#[doc(inline)]
+pub use wdl_format as format;
 #[cfg(feature = "grammar")]
 #[doc(inline)]
 pub use wdl_grammar as grammar;